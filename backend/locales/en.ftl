@@ -0,0 +1,115 @@
+# API response messages (English)
+# Keep the message ids in sync with zh-CN.ftl.
+
+## Success
+
+post-created = Post created
+post-updated = Post updated
+post-deleted = Post deleted
+comment-created = Comment created
+comment-updated = Comment updated
+comment-deleted = Comment deleted
+favorite-added = Post added to favorites
+favorite-removed = Post removed from favorites
+user-registered = Registration successful
+login-succeeded = Logged in
+logout-succeeded = Logged out
+profile-updated = Profile updated
+user-created = User created
+user-updated = User updated
+user-deleted = User deleted
+users-deleted = Deleted { $count } user(s)
+media-uploaded = File uploaded
+media-deleted = Media file deleted
+
+## Not found
+
+post-not-found = Post { $id } not found
+post-not-found-or-deleted = Post { $id } not found or already deleted
+comment-not-found = Comment { $id } not found
+comment-not-found-or-deleted = Comment { $id } not found or already deleted
+comment-post-not-found = The post of this comment (ID: { $id }) was not found
+user-not-found = User not found
+user-not-found-or-deleted = User not found or already deleted
+user-id-not-found = User { $id } not found
+media-not-found = Media file not found
+media-id-not-found = Media file { $id } not found
+route-not-found = API endpoint { $path } does not exist
+page-not-found = Page { $path } does not exist
+file-not-found = File { $path } does not exist
+highlight-theme-not-found = Highlight theme { $name } does not exist
+sitemap-not-found = Sitemap { $name } does not exist
+api-docs-bundle-not-configured = The interactive API docs script is not configured (site.api_docs_bundle)
+
+## Authentication and permissions
+
+auth-header-missing = Missing or invalid Authorization header
+auth-token-invalid = Invalid authentication token
+auth-token-expired = The JWT has expired
+auth-invalid-user-id = Invalid user ID in the JWT
+auth-invalid-credentials = Incorrect username or password
+metrics-token-required = A valid token is required to access metrics
+forbidden-update-post = You are not allowed to update this post
+forbidden-delete-post = You are not allowed to delete this post
+forbidden-update-comment = You are not allowed to update this comment
+forbidden-delete-comment = You are not allowed to delete this comment
+forbidden-delete-media = You are not allowed to delete this media file
+forbidden-view-user = You are not allowed to view this user
+forbidden-create-user = You are not allowed to create users
+forbidden-update-user = You are not allowed to update users
+forbidden-delete-user = You are not allowed to delete users
+forbidden-batch-delete-users = You are not allowed to delete users in batch
+forbidden-audit-log = You are not allowed to view the audit log
+
+## Invalid requests
+
+username-taken = Username is already taken
+email-taken = Email is already registered
+role-invalid = Invalid role. Must be 'user' or 'admin'.
+post-unsupported-format = Unsupported content format: { $format }. Use markdown or html
+feed-unsupported-mode = Unsupported feed mode: { $mode }
+audit-unsupported-format = Unsupported export format: { $format }
+pagination-invalid-cursor = Invalid pagination cursor
+media-invalid-multipart = Unable to parse the upload: { $error }
+media-single-file = Only one file can be uploaded at a time
+media-file-too-large = File exceeds the { $max } byte limit
+media-field-too-large = Form field is too large
+media-missing-file = Missing file field
+media-empty-file = The uploaded file is empty
+media-quota-exceeded = Storage quota exceeded: { $used } of { $quota } bytes used
+media-unsupported-type = Only JPEG, PNG, GIF and WebP images are supported
+media-corrupted-image = The image file is corrupted
+media-invalid-image = Unable to decode the image: { $error }
+storage-invalid-key = Invalid storage key: { $key }
+internal-error = Internal server error, please try again later
+
+## Validation
+
+validation-failed = Validation failed: { $details }
+validation-username-length = Username must be 3 to 20 characters long
+validation-email = Invalid email address
+validation-password-length = Password must be at least 6 characters long
+validation-password-required = Password is required
+validation-login-required = Username or email is required
+validation-role = Role must be 'user' or 'admin'
+validation-display-name-length = Display name must not exceed 50 characters
+validation-avatar-url = Avatar must be a site path or an http(s) URL
+validation-title-length = Title must be 1 to 255 characters long
+validation-content-required = Post content is required
+validation-category-length = Category must be 1 to 50 characters long
+validation-summary-length = Summary must not exceed 500 characters
+validation-post-id = Invalid post ID
+validation-comment-length = Comment must be 1 to 1000 characters long
+validation-query-unknown = Unknown query parameter { $field }; allowed: { $allowed }
+validation-query-invalid = Invalid value for { $field }: { $error }
+validation-sort-cursor = Cursor pagination only supports sort=newest
+validation-include-unknown = Cannot include { $value }; supported: { $allowed }
+# Used when a validation rule has no message of its own
+validation-default-length = Invalid length
+validation-default-range = Value out of range
+validation-default-email = Invalid email address
+validation-default-url = Invalid URL
+validation-default-required = This field is required
+validation-default-regex = Invalid format
+validation-default-must_match = Values do not match
+validation-default = Invalid value
@@ -0,0 +1,116 @@
+# API 响应消息 (简体中文，默认语言)
+# 语法为 Fluent 的子集：`id = 文本`，缩进的行接续上一条消息，`{ $变量 }` 为占位符。
+# 新增消息时请同时更新 en.ftl。
+
+## 操作成功
+
+post-created = 文章创建成功
+post-updated = 文章更新成功
+post-deleted = 文章删除成功
+comment-created = 评论创建成功
+comment-updated = 评论更新成功
+comment-deleted = 评论删除成功
+favorite-added = 文章收藏成功
+favorite-removed = 文章已取消收藏
+user-registered = 用户注册成功
+login-succeeded = 登录成功
+logout-succeeded = 登出成功
+profile-updated = 个人信息更新成功
+user-created = 用户创建成功
+user-updated = 用户信息更新成功
+user-deleted = 用户删除成功
+users-deleted = 成功删除 { $count } 个用户
+media-uploaded = 文件上传成功
+media-deleted = 媒体文件删除成功
+
+## 资源不存在
+
+post-not-found = ID为 { $id } 的文章未找到
+post-not-found-or-deleted = ID为 { $id } 的文章未找到或已被删除
+comment-not-found = ID为 { $id } 的评论未找到
+comment-not-found-or-deleted = ID为 { $id } 的评论未找到或已被删除
+comment-post-not-found = 评论所属文章 (ID: { $id }) 未找到
+user-not-found = 用户未找到
+user-not-found-or-deleted = 用户未找到或已被删除
+user-id-not-found = ID为 { $id } 的用户未找到
+media-not-found = 媒体文件未找到
+media-id-not-found = ID为 { $id } 的媒体文件未找到
+route-not-found = 接口 { $path } 不存在
+page-not-found = 页面 { $path } 不存在
+file-not-found = 文件 { $path } 不存在
+highlight-theme-not-found = 高亮主题 { $name } 不存在
+sitemap-not-found = 站点地图 { $name } 不存在
+api-docs-bundle-not-configured = 未配置交互式 API 文档脚本 (site.api_docs_bundle)
+
+## 认证与权限
+
+auth-header-missing = 缺少或无效的Authorization头
+auth-token-invalid = 认证令牌无效
+auth-token-expired = JWT令牌已过期
+auth-invalid-user-id = JWT令牌中的用户ID无效
+auth-invalid-credentials = 用户名或密码错误
+metrics-token-required = 访问指标需要有效的令牌
+forbidden-update-post = 无权限更新此文章
+forbidden-delete-post = 无权限删除此文章
+forbidden-update-comment = 无权限更新此评论
+forbidden-delete-comment = 无权限删除此评论
+forbidden-delete-media = 您无权删除此媒体文件
+forbidden-view-user = 无权限访问指定用户信息
+forbidden-create-user = 无权限创建用户
+forbidden-update-user = 无权限更新用户信息
+forbidden-delete-user = 无权限删除用户
+forbidden-batch-delete-users = 无权限批量删除用户
+forbidden-audit-log = 无权限查看审计日志
+
+## 请求无效
+
+username-taken = 用户名已存在
+email-taken = 邮箱已存在
+role-invalid = 指定的角色无效。必须是 'user' 或 'admin'。
+post-unsupported-format = 不支持的内容格式: { $format }，可选值为 markdown 或 html
+feed-unsupported-mode = 不支持的订阅源模式: { $mode }
+audit-unsupported-format = 不支持的导出格式: { $format }
+pagination-invalid-cursor = 无效的分页游标
+media-invalid-multipart = 无法解析上传内容: { $error }
+media-single-file = 一次只能上传一个文件
+media-file-too-large = 文件大小超过上限 { $max } 字节
+media-field-too-large = 表单字段过大
+media-missing-file = 缺少 file 字段
+media-empty-file = 上传的文件为空
+media-quota-exceeded = 超出存储配额：已使用 { $used } 字节，配额 { $quota } 字节
+media-unsupported-type = 仅支持 JPEG、PNG、GIF 和 WebP 图片
+media-corrupted-image = 图片文件已损坏
+media-invalid-image = 无法解析图片: { $error }
+storage-invalid-key = 无效的存储键: { $key }
+internal-error = 服务器内部错误，请稍后重试
+
+## 数据验证
+
+validation-failed = 数据验证失败: { $details }
+validation-username-length = 用户名长度必须在3到20个字符之间
+validation-email = 邮箱格式无效
+validation-password-length = 密码长度至少为6个字符
+validation-password-required = 密码不能为空
+validation-login-required = 用户名或邮箱不能为空
+validation-role = 角色必须是 'user' 或 'admin'
+validation-display-name-length = 显示名称不能超过50个字符
+validation-avatar-url = 头像地址必须是站内路径或 http(s) 地址
+validation-title-length = 标题长度必须在1到255个字符之间
+validation-content-required = 文章内容不能为空
+validation-category-length = 分类长度必须在1到50个字符之间
+validation-summary-length = 摘要长度不能超过500个字符
+validation-post-id = 文章ID无效
+validation-comment-length = 评论内容长度必须在1到1000个字符之间
+validation-query-unknown = 未知的查询参数 { $field }，可用的参数: { $allowed }
+validation-query-invalid = { $field } 的值无效: { $error }
+validation-sort-cursor = 游标分页只支持 sort=newest
+validation-include-unknown = 不支持展开 { $value }，可用的值: { $allowed }
+# 验证规则没有指定消息时使用
+validation-default-length = 长度不符合要求
+validation-default-range = 取值超出范围
+validation-default-email = 邮箱格式无效
+validation-default-url = URL 格式无效
+validation-default-required = 不能为空
+validation-default-regex = 格式无效
+validation-default-must_match = 两次输入不一致
+validation-default = 值无效
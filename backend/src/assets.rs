@@ -0,0 +1,214 @@
+// src/assets.rs
+//! 前端静态资源。
+//! 从配置的目录或编译时内嵌的资源中读取前端构建产物，支持预压缩的 `.br` / `.gz` 文件、
+//! 带哈希文件名资源的长期缓存，以及单页应用 (SPA) 的 `index.html` 回退。
+
+use std::borrow::Cow;
+use std::path::{Component, Path, PathBuf};
+
+/// 单页应用的入口文件。
+pub const INDEX_FILE: &str = "index.html";
+
+/// 带哈希文件名的资源（内容变化时文件名随之变化）可以永久缓存。
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+/// 入口文件必须每次校验，保证部署后客户端能拿到新的资源引用。
+const INDEX_CACHE_CONTROL: &str = "no-cache";
+/// 其他静态文件（favicon 等）短期缓存。
+const DEFAULT_CACHE_CONTROL: &str = "public, max-age=3600";
+
+#[cfg(feature = "embed-frontend")]
+#[derive(rust_embed::RustEmbed)]
+#[folder = "../frontend/dist"]
+#[allow_missing = true]
+struct EmbeddedAssets;
+
+/// 静态资源的来源。
+#[derive(Debug, Clone)]
+pub enum StaticAssets {
+    /// 运行时从目录读取
+    Directory(PathBuf),
+    /// 编译时内嵌进二进制
+    #[cfg(feature = "embed-frontend")]
+    Embedded,
+}
+
+/// 预压缩文件的编码。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Brotli,
+    Gzip,
+}
+
+impl ContentEncoding {
+    /// `Content-Encoding` 响应头的值。
+    pub fn header_value(self) -> &'static str {
+        match self {
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Gzip => "gzip",
+        }
+    }
+
+    /// 预压缩文件的扩展名。
+    fn extension(self) -> &'static str {
+        match self {
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Gzip => "gz",
+        }
+    }
+}
+
+/// 解析后的静态资源。
+#[derive(Debug)]
+pub struct Asset {
+    pub body: Cow<'static, [u8]>,
+    pub content_type: String,
+    pub content_encoding: Option<ContentEncoding>,
+    pub cache_control: &'static str,
+}
+
+/// 将请求路径转换为资源的相对路径。
+///
+/// 去掉开头的 `/`，以 `/` 结尾时补上 `index.html`；包含 `..`、反斜杠等可能越出资源目录的路径返回 `None`。
+pub fn sanitize_path(request_path: &str) -> Option<String> {
+    let decoded = percent_encoding::percent_decode_str(request_path).decode_utf8().ok()?;
+    let trimmed = decoded.trim_start_matches('/');
+    if trimmed.contains('\\') || trimmed.contains('\0') {
+        return None;
+    }
+    if !Path::new(trimmed).components().all(|c| matches!(c, Component::Normal(_))) {
+        return None;
+    }
+
+    if trimmed.is_empty() || trimmed.ends_with('/') {
+        Some(format!("{}{}", trimmed, INDEX_FILE))
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// 判断文件是否为构建工具生成的带内容哈希的资源，例如 `assets/index-B3xk9_aZ.js`。
+///
+/// Vite 只在 `assets/` 目录下输出带哈希的文件名；为避免把 `my-long-filename.png` 之类的普通文件
+/// 误判为可永久缓存，这里同时要求文件位于 `assets/` 目录中。
+pub fn is_hashed_asset(path: &str) -> bool {
+    let Some((dir, file_name)) = path.rsplit_once('/') else {
+        return false;
+    };
+    if dir.rsplit('/').next() != Some("assets") {
+        return false;
+    }
+
+    let stem = file_name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(file_name);
+    stem.rsplit_once(['-', '.'])
+        .map(|(_, hash)| {
+            hash.len() >= 8 && hash.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+        .unwrap_or(false)
+}
+
+/// 根据资源路径选择 `Cache-Control`。
+pub fn cache_control_for(path: &str) -> &'static str {
+    if path.rsplit('/').next() == Some(INDEX_FILE) {
+        INDEX_CACHE_CONTROL
+    } else if is_hashed_asset(path) {
+        IMMUTABLE_CACHE_CONTROL
+    } else {
+        DEFAULT_CACHE_CONTROL
+    }
+}
+
+/// 按 `Accept-Encoding` 请求头返回客户端可接受的预压缩编码，优先 Brotli。
+pub fn accepted_encodings(accept_encoding: Option<&str>) -> Vec<ContentEncoding> {
+    let Some(accept_encoding) = accept_encoding else {
+        return Vec::new();
+    };
+
+    let accepts = |name: &str| {
+        accept_encoding.split(',').any(|item| {
+            let mut parts = item.trim().split(';');
+            let coding = parts.next().unwrap_or("").trim();
+            let rejected = parts.any(|param| {
+                param.trim().strip_prefix("q=")
+                    .and_then(|q| q.trim().parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+            coding.eq_ignore_ascii_case(name) && !rejected
+        })
+    };
+
+    [ContentEncoding::Brotli, ContentEncoding::Gzip]
+        .into_iter()
+        .filter(|encoding| accepts(encoding.header_value()))
+        .collect()
+}
+
+impl StaticAssets {
+    /// 根据配置选择静态资源来源。
+    ///
+    /// 配置了目录时优先使用目录；否则在启用 `embed-frontend` 特性时使用内嵌资源。
+    pub fn from_config(static_dir: Option<&Path>) -> Option<Self> {
+        if let Some(dir) = static_dir {
+            return Some(StaticAssets::Directory(dir.to_path_buf()));
+        }
+        #[cfg(feature = "embed-frontend")]
+        {
+            Some(StaticAssets::Embedded)
+        }
+        #[cfg(not(feature = "embed-frontend"))]
+        {
+            None
+        }
+    }
+
+    /// 读取指定相对路径的文件，不存在时返回 `None`。
+    async fn read(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+        match self {
+            StaticAssets::Directory(dir) => {
+                let full_path = dir.join(path);
+                if !full_path.is_file() {
+                    return None;
+                }
+                tokio::fs::read(full_path).await.ok().map(Cow::Owned)
+            }
+            #[cfg(feature = "embed-frontend")]
+            StaticAssets::Embedded => EmbeddedAssets::get(path).map(|file| file.data),
+        }
+    }
+
+    /// 读取文件，优先使用客户端可接受的预压缩版本。
+    async fn read_negotiated(&self, path: &str, encodings: &[ContentEncoding]) -> Option<Asset> {
+        let content_type = mime_guess::from_path(path).first_or_octet_stream().to_string();
+        let cache_control = cache_control_for(path);
+
+        for &encoding in encodings {
+            if let Some(body) = self.read(&format!("{}.{}", path, encoding.extension())).await {
+                return Some(Asset { body, content_type, content_encoding: Some(encoding), cache_control });
+            }
+        }
+        let body = self.read(path).await?;
+        Some(Asset { body, content_type, content_encoding: None, cache_control })
+    }
+
+    /// 解析请求路径对应的资源。
+    ///
+    /// 文件不存在且路径最后一段不带扩展名时（前端路由），回退到 `index.html`；
+    /// 带扩展名的缺失文件返回 `None`，避免把 HTML 当作脚本或图片返回。
+    ///
+    /// # 参数
+    /// * `request_path` - 请求的 URI 路径。
+    /// * `accept_encoding` - 请求头 `Accept-Encoding` 的值。
+    pub async fn resolve(&self, request_path: &str, accept_encoding: Option<&str>) -> Option<Asset> {
+        let path = sanitize_path(request_path)?;
+        let encodings = accepted_encodings(accept_encoding);
+
+        if let Some(asset) = self.read_negotiated(&path, &encodings).await {
+            return Some(asset);
+        }
+
+        let last_segment = path.rsplit('/').next().unwrap_or(&path);
+        if last_segment.contains('.') {
+            return None;
+        }
+        self.read_negotiated(INDEX_FILE, &encodings).await
+    }
+}
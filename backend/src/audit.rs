@@ -0,0 +1,117 @@
+// src/audit.rs
+//! 审计日志：管理员操作与认证事件的记录内容。
+//!
+//! 每条记录包含操作者、动作、目标、变更前后的字段差异、客户端 IP 和请求 ID，
+//! 由 `database::audit::AuditRepository` 追加写入 `audit_log` 表（只追加，不提供修改和删除）。
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::handler::auth::Claims;
+use crate::logging::REDACTED;
+
+// ======================== 动作名称 ========================
+
+pub const AUTH_LOGIN: &str = "auth.login";
+pub const AUTH_LOGIN_FAILED: &str = "auth.login_failed";
+pub const AUTH_LOGOUT: &str = "auth.logout";
+pub const USER_REGISTER: &str = "user.register";
+pub const USER_UPDATE_PROFILE: &str = "user.update_profile"; // 用户修改自己的资料 (包括密码)
+pub const USER_CREATE: &str = "user.create";
+pub const USER_UPDATE: &str = "user.update";
+pub const USER_DELETE: &str = "user.delete";
+pub const POST_UPDATE: &str = "post.update";   // 管理员修改他人的文章
+pub const POST_DELETE: &str = "post.delete";   // 管理员删除他人的文章
+pub const COMMENT_UPDATE: &str = "comment.update";
+pub const COMMENT_DELETE: &str = "comment.delete";
+pub const MEDIA_DELETE: &str = "media.delete";
+
+/// 差异中值需要隐藏的字段（只记录发生了变化）。
+const SENSITIVE_FIELDS: [&str; 1] = ["password_hash"];
+
+/// 一条待写入的审计记录。
+#[derive(Debug, Clone, Default)]
+pub struct AuditEvent {
+    pub actor_id: Option<i32>,
+    pub actor_name: Option<String>,
+    pub action: &'static str,
+    pub target_type: Option<&'static str>,
+    pub target_id: Option<String>,
+    pub changes: Option<Value>,
+}
+
+impl AuditEvent {
+    pub fn new(action: &'static str) -> Self {
+        Self { action, ..Self::default() }
+    }
+
+    /// 以 JWT 中的用户作为操作者。
+    pub fn by(self, claims: &Claims) -> Self {
+        let actor_id = claims.sub.parse().ok();
+        self.by_user(actor_id, &claims.username)
+    }
+
+    /// 以指定用户作为操作者；登录失败等场景用户可能不存在，此时 `actor_id` 为 `None`。
+    pub fn by_user(mut self, actor_id: Option<i32>, actor_name: &str) -> Self {
+        self.actor_id = actor_id;
+        self.actor_name = Some(actor_name.to_string());
+        self
+    }
+
+    pub fn target(mut self, target_type: &'static str, target_id: impl ToString) -> Self {
+        self.target_type = Some(target_type);
+        self.target_id = Some(target_id.to_string());
+        self
+    }
+
+    /// 记录变更差异，没有字段变化时不记录。
+    pub fn changes(mut self, changes: Value) -> Self {
+        self.changes = changes.as_object().is_some_and(|m| !m.is_empty()).then_some(changes);
+        self
+    }
+}
+
+/// 计算变更前后两个快照的字段差异。
+///
+/// # 参数
+/// * `before` - 变更前的记录，创建时为 `None`。
+/// * `after` - 变更后的记录，删除时为 `None`。
+///
+/// # 返回
+/// `{"字段": {"before": 旧值, "after": 新值}}`，只包含值不同的顶层字段；
+/// 敏感字段 (例如 `password_hash`) 的值替换为 `[REDACTED]`。
+pub fn diff<T: Serialize>(before: Option<&T>, after: Option<&T>) -> Value {
+    let before = snapshot(before);
+    let after = snapshot(after);
+
+    let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    let mut changes = Map::new();
+    for field in fields {
+        let old = before.get(field).unwrap_or(&Value::Null);
+        let new = after.get(field).unwrap_or(&Value::Null);
+        if old == new {
+            continue;
+        }
+        let (old, new) = if SENSITIVE_FIELDS.contains(&field.as_str()) {
+            let hide = |v: &Value| if v.is_null() { Value::Null } else { Value::from(REDACTED) };
+            (hide(old), hide(new))
+        } else {
+            (old.clone(), new.clone())
+        };
+        let mut change = Map::new();
+        change.insert("before".into(), old);
+        change.insert("after".into(), new);
+        changes.insert(field.clone(), Value::Object(change));
+    }
+    Value::Object(changes)
+}
+
+fn snapshot<T: Serialize>(record: Option<&T>) -> Map<String, Value> {
+    match record.map(serde_json::to_value) {
+        Some(Ok(Value::Object(map))) => map,
+        _ => Map::new(),
+    }
+}
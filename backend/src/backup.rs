@@ -0,0 +1,106 @@
+// src/backup.rs
+//! 数据导出与导入（`blogctl export` / `blogctl import`）。
+//! 导出为单个 JSON 文档，包含用户（含密码哈希）、文章、评论、收藏和媒体记录，
+//! 可以在不同数据库之间迁移数据（例如从 SQLite 迁移到 PostgreSQL）。
+//! 媒体文件本身不在导出内容中，需要单独备份存储后端。
+
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryOrder, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::database::{comment, favorite, media, post, user};
+use crate::error::CustomError;
+
+/// 当前的导出格式版本。
+pub const BACKUP_VERSION: u32 = 1;
+
+/// 每条 INSERT 语句写入的最大行数，避免超出数据库的参数个数限制。
+const INSERT_CHUNK_SIZE: usize = 100;
+
+/// 导出的全部数据。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backup {
+    pub version: u32,
+    pub exported_at: NaiveDateTime,
+    pub users: Vec<user::Model>,
+    pub posts: Vec<post::Model>,
+    pub comments: Vec<comment::Model>,
+    pub favorites: Vec<favorite::Model>,
+    pub media: Vec<media::Model>,
+}
+
+/// 导出数据库中的全部数据，各表按主键排序。
+pub async fn export(db: &DatabaseConnection) -> Result<Backup, CustomError> {
+    Ok(Backup {
+        version: BACKUP_VERSION,
+        exported_at: Utc::now().naive_utc(),
+        users: user::Entity::find().order_by_asc(user::Column::Id).all(db).await?,
+        posts: post::Entity::find().order_by_asc(post::Column::Id).all(db).await?,
+        comments: comment::Entity::find().order_by_asc(comment::Column::Id).all(db).await?,
+        favorites: favorite::Entity::find()
+            .order_by_asc(favorite::Column::UserId)
+            .order_by_asc(favorite::Column::PostId)
+            .all(db)
+            .await?,
+        media: media::Entity::find().order_by_asc(media::Column::Id).all(db).await?,
+    })
+}
+
+/// 将导出的数据导入到空数据库中（保留原有 ID），整个导入在一个事务中完成。
+///
+/// # 返回
+/// 版本不兼容或目标数据库已有用户时返回 `BadRequest`。
+pub async fn import(db: &DatabaseConnection, backup: Backup) -> Result<(), CustomError> {
+    if backup.version != BACKUP_VERSION {
+        return Err(CustomError::BadRequest(format!(
+            "不支持的导出格式版本 {}，当前版本为 {}",
+            backup.version, BACKUP_VERSION
+        )));
+    }
+    if user::Entity::find().count(db).await? > 0 {
+        return Err(CustomError::BadRequest("目标数据库不为空，只能导入到新建的数据库".to_string()));
+    }
+
+    let txn = db.begin().await?;
+    insert_all::<user::ActiveModel, _>(&txn, backup.users).await?;
+    // 旧版本导出的文章没有写入时统计的字段，导入时补齐
+    let posts = backup.posts.into_iter().map(post::fill_text_stats).collect();
+    insert_all::<post::ActiveModel, _>(&txn, posts).await?;
+    // 父评论的 ID 总是小于子评论，按 ID 顺序插入即可满足外键约束
+    let mut comments = backup.comments;
+    comments.sort_by_key(|c| c.id);
+    insert_all::<comment::ActiveModel, _>(&txn, comments).await?;
+    insert_all::<favorite::ActiveModel, _>(&txn, backup.favorites).await?;
+    insert_all::<media::ActiveModel, _>(&txn, backup.media).await?;
+
+    // PostgreSQL 显式写入 ID 时不会推进自增序列，需要手动调整
+    if txn.get_database_backend() == DbBackend::Postgres {
+        for table in ["users", "posts", "comments", "media"] {
+            let sql = format!(
+                "SELECT setval(pg_get_serial_sequence('{0}', 'id'), COALESCE((SELECT MAX(id) FROM {0}), 0) + 1, false)",
+                table
+            );
+            txn.execute_unprepared(&sql).await?;
+        }
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+/// 分批插入一个表的全部记录。
+async fn insert_all<A, C>(db: &C, models: Vec<<A::Entity as EntityTrait>::Model>) -> Result<(), CustomError>
+where
+    A: ActiveModelTrait + Send,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+    C: ConnectionTrait,
+{
+    for chunk in models.chunks(INSERT_CHUNK_SIZE) {
+        // 导出的记录需要按原样写入，全部字段标记为 Set
+        let rows = chunk.iter().cloned().map(|m| m.into_active_model().reset_all());
+        <A::Entity as EntityTrait>::insert_many(rows).exec_without_returning(db).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,372 @@
+//! 博客运维命令行工具 `blogctl`。
+//! 与服务端读取相同的配置（配置文件、`.env` 和环境变量），直接调用各个 Repository 完成
+//! 用户管理、数据库迁移、文章发布状态调整以及数据导出 / 导入等运维操作。
+//!
+//! 示例：
+//! ```text
+//! blogctl user create --username admin --email admin@example.com --role admin
+//! blogctl db status
+//! blogctl export --output backup.json
+//! ```
+
+use std::io::{IsTerminal, Read, Write};
+
+use anyhow::{anyhow, bail, Result};
+use chrono::DateTime;
+use clap::{Args, Parser, Subcommand};
+use sea_orm::DatabaseConnection;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use validator::Validate;
+
+use axum_blog_engine::audit::{self, diff, AuditEvent};
+use axum_blog_engine::backup;
+use axum_blog_engine::bootstrap::{connect_database, load_dotenv};
+use axum_blog_engine::database::audit::AuditRepository;
+use axum_blog_engine::database::post::PostRepository;
+use axum_blog_engine::database::user::{Model as User, UserRepository};
+use axum_blog_engine::handler::idl::{CreateUserByAdminRequest, UpdatePostRequest, UpdateUserRequest};
+use axum_blog_engine::logging::{self, RedactingMakeWriter};
+use axum_blog_engine::migration::Migrator;
+use axum_blog_engine::settings::{LogFormat, Overrides, Settings};
+use axum_blog_engine::Config;
+
+#[derive(Parser)]
+#[command(name = "blogctl", version, about = "Axum Blog Engine 运维命令行工具")]
+struct Cli {
+    #[command(flatten)]
+    overrides: Overrides,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 用户管理
+    #[command(subcommand)]
+    User(UserCommand),
+    /// 数据库迁移
+    #[command(subcommand)]
+    Db(DbCommand),
+    /// 文章管理
+    #[command(subcommand)]
+    Post(PostCommand),
+    /// 搜索索引
+    #[command(subcommand)]
+    Search(SearchCommand),
+    /// 导出全部数据为 JSON
+    Export {
+        /// 输出文件，默认写到标准输出
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// 从 JSON 导出文件导入数据（目标数据库必须为空）
+    Import {
+        /// 导出文件，`-` 表示从标准输入读取
+        file: std::path::PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum UserCommand {
+    /// 创建用户
+    Create {
+        #[arg(long)]
+        username: String,
+        #[arg(long)]
+        email: String,
+        /// 不指定时交互式输入
+        #[arg(long)]
+        password: Option<String>,
+        /// user 或 admin
+        #[arg(long, default_value = "user")]
+        role: String,
+    },
+    /// 重置用户密码
+    ResetPassword {
+        /// 用户名或邮箱
+        user: String,
+        /// 不指定时交互式输入
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// 修改用户角色
+    SetRole {
+        /// 用户名或邮箱
+        user: String,
+        /// user 或 admin
+        role: String,
+    },
+    /// 分页列出用户
+    List(PageArgs),
+}
+
+#[derive(Args)]
+struct PageArgs {
+    #[arg(long, default_value_t = 1)]
+    page: u64,
+    #[arg(long, default_value_t = 20)]
+    per_page: u64,
+}
+
+#[derive(Subcommand)]
+enum DbCommand {
+    /// 执行尚未应用的迁移
+    Migrate {
+        /// 最多执行的迁移数量，默认全部执行
+        #[arg(long)]
+        steps: Option<usize>,
+    },
+    /// 回滚最近的迁移
+    Rollback {
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
+    },
+    /// 查看迁移状态
+    Status,
+}
+
+#[derive(Subcommand)]
+enum PostCommand {
+    /// 发布文章
+    Publish { id: i32 },
+    /// 撤回文章为草稿
+    Unpublish { id: i32 },
+    /// 删除文章及其评论和收藏
+    Delete { id: i32 },
+}
+
+#[derive(Subcommand)]
+enum SearchCommand {
+    /// 重新生成全部文章的渲染结果、目录、字数和摘要
+    Reindex,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    load_dotenv()?;
+    let cli = Cli::parse();
+
+    // 命令行工具默认只输出错误日志，需要排查问题时可以使用 --log-level 或 RUST_LOG
+    let log_level = cli.overrides.log_level.clone()
+        .or_else(|| std::env::var("RUST_LOG").ok())
+        .unwrap_or_else(|| "error".into());
+    tracing_subscriber::registry()
+        .with(logging::format_layer(
+            LogFormat::Text,
+            std::io::stderr().is_terminal(),
+            RedactingMakeWriter::new(std::io::stderr),
+        ))
+        .with(tracing_subscriber::EnvFilter::new(log_level))
+        .init();
+
+    let settings = Settings::load(&cli.overrides)?;
+
+    // 只在需要时连接数据库
+    let connect = || connect_database(&settings.database);
+    match cli.command {
+        Command::User(cmd) => run_user_command(connect().await?, &settings.app_config()?, cmd).await,
+        Command::Db(cmd) => run_db_command(&connect().await?, cmd).await,
+        Command::Post(cmd) => run_post_command(connect().await?, cmd).await,
+        // 搜索直接查询 posts 表，重建时按当前规则重新生成各篇文章的派生列
+        Command::Search(SearchCommand::Reindex) => {
+            let count = PostRepository::new(connect().await?).reindex_all().await?;
+            println!("已重新生成 {} 篇文章的渲染结果、目录、字数和摘要", count);
+            Ok(())
+        }
+        Command::Export { output } => {
+            let db = connect().await?;
+            let data = backup::export(&db).await?;
+            let json = serde_json::to_string_pretty(&data)?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, json)
+                        .map_err(|e| anyhow!("无法写入 {}: {}", path.display(), e))?;
+                    eprintln!(
+                        "已导出 {} 个用户、{} 篇文章、{} 条评论、{} 条收藏、{} 个媒体文件记录到 {}",
+                        data.users.len(), data.posts.len(), data.comments.len(),
+                        data.favorites.len(), data.media.len(), path.display()
+                    );
+                }
+                None => {
+                    let mut stdout = std::io::stdout().lock();
+                    stdout.write_all(json.as_bytes())?;
+                    stdout.write_all(b"\n")?;
+                }
+            }
+            Ok(())
+        }
+        Command::Import { file } => {
+            let db = connect().await?;
+            let mut json = String::new();
+            if file.as_os_str() == "-" {
+                std::io::stdin().read_to_string(&mut json)?;
+            } else {
+                json = std::fs::read_to_string(&file)
+                    .map_err(|e| anyhow!("无法读取 {}: {}", file.display(), e))?;
+            }
+            let data: backup::Backup = serde_json::from_str(&json)
+                .map_err(|e| anyhow!("导出文件格式无效: {}", e))?;
+            let (users, posts) = (data.users.len(), data.posts.len());
+            backup::import(&db, data).await?;
+            println!("导入完成：{} 个用户、{} 篇文章", users, posts);
+            Ok(())
+        }
+    }
+}
+
+async fn run_user_command(db: DatabaseConnection, config: &Config, cmd: UserCommand) -> Result<()> {
+    let audit_log = AuditRepository::new(db.clone());
+    let repo = UserRepository::new(db);
+    match cmd {
+        UserCommand::Create { username, email, password, role } => {
+            let password = match password {
+                Some(p) => p,
+                None => prompt_password()?,
+            };
+            let req = CreateUserByAdminRequest { username, email, password, role };
+            req.validate()?;
+            if repo.get_user_by_username_or_email(&req.username).await?.is_some()
+                || repo.get_user_by_username_or_email(&req.email).await?.is_some()
+            {
+                bail!("用户名或邮箱已被使用");
+            }
+            let hash = bcrypt::hash(&req.password, config.bcrypt_cost)?;
+            let user = repo.create_user_by_admin(req, hash).await?;
+            audit_log.record(cli_event(audit::USER_CREATE).target("user", user.id)
+                .changes(diff(None, Some(&user))), None, None).await?;
+            println!("已创建用户 {} (id: {}, 角色: {})", user.username, user.id, user.role);
+        }
+        UserCommand::ResetPassword { user, password } => {
+            let target = find_user(&repo, &user).await?;
+            let password = match password {
+                Some(p) => p,
+                None => prompt_password()?,
+            };
+            let req = UpdateUserRequest { username: None, email: None, password: Some(password), role: None };
+            req.validate()?;
+            let hash = bcrypt::hash(req.password.as_deref().unwrap_or_default(), config.bcrypt_cost)?;
+            let updated = repo.update_user_by_admin(target.id, req, Some(hash)).await?;
+            audit_log.record(cli_event(audit::USER_UPDATE).target("user", target.id)
+                .changes(diff(Some(&target), Some(&updated))), None, None).await?;
+            println!("已重置用户 {} 的密码", target.username);
+        }
+        UserCommand::SetRole { user, role } => {
+            let target = find_user(&repo, &user).await?;
+            let req = UpdateUserRequest { username: None, email: None, password: None, role: Some(role) };
+            req.validate()?;
+            let updated = repo.update_user_by_admin(target.id, req, None).await?;
+            audit_log.record(cli_event(audit::USER_UPDATE).target("user", target.id)
+                .changes(diff(Some(&target), Some(&updated))), None, None).await?;
+            println!("用户 {} 的角色已设置为 {}", updated.username, updated.role);
+        }
+        UserCommand::List(PageArgs { page, per_page }) => {
+            if page == 0 || per_page == 0 {
+                bail!("page 和 per-page 必须大于 0");
+            }
+            let (users, total_pages, page, total) = repo.get_all_users(page, per_page).await?;
+            println!("{:<6} {:<20} {:<32} {:<6} 创建时间", "ID", "用户名", "邮箱", "角色");
+            for u in users {
+                println!("{:<6} {:<20} {:<32} {:<6} {}", u.id, u.username, u.email, u.role, u.created_at.format("%Y-%m-%d %H:%M:%S"));
+            }
+            println!("第 {}/{} 页，共 {} 个用户", page, total_pages.max(1), total);
+        }
+    }
+    Ok(())
+}
+
+async fn run_db_command(db: &DatabaseConnection, cmd: DbCommand) -> Result<()> {
+    match cmd {
+        DbCommand::Migrate { steps } => {
+            let executed = Migrator::up(db, steps).await?;
+            if executed.is_empty() {
+                println!("没有需要执行的迁移");
+            }
+            for name in executed {
+                println!("已执行 {}", name);
+            }
+        }
+        DbCommand::Rollback { steps } => {
+            let reverted = Migrator::down(db, Some(steps)).await?;
+            if reverted.is_empty() {
+                println!("没有可以回滚的迁移");
+            }
+            for name in reverted {
+                println!("已回滚 {}", name);
+            }
+        }
+        DbCommand::Status => {
+            for status in Migrator::status(db).await? {
+                match status.applied_at.and_then(|t| DateTime::from_timestamp(t, 0)) {
+                    Some(at) => println!("[x] {}  ({})", status.name, at.format("%Y-%m-%d %H:%M:%S UTC")),
+                    None => println!("[ ] {}", status.name),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_post_command(db: DatabaseConnection, cmd: PostCommand) -> Result<()> {
+    let audit_log = AuditRepository::new(db.clone());
+    let repo = PostRepository::new(db);
+    let set_published = |id: i32, is_published: bool| {
+        let (repo, audit_log) = (&repo, &audit_log);
+        async move {
+            let before = repo.get_post_by_id(id).await?;
+            let req = UpdatePostRequest {
+                title: None,
+                content_markdown: None,
+                category: None,
+                is_published: Some(is_published),
+                cover_url: None,
+                excerpt: None,
+            };
+            let post = repo.update_post(id, req).await?;
+            audit_log.record(cli_event(audit::POST_UPDATE).target("post", id)
+                .changes(diff(before.as_ref(), Some(&post))), None, None).await?;
+            anyhow::Ok(post)
+        }
+    };
+    match cmd {
+        PostCommand::Publish { id } => {
+            let post = set_published(id, true).await?;
+            println!("已发布文章 {} 《{}》", post.id, post.title);
+        }
+        PostCommand::Unpublish { id } => {
+            let post = set_published(id, false).await?;
+            println!("文章 {} 《{}》已撤回为草稿", post.id, post.title);
+        }
+        PostCommand::Delete { id } => {
+            let post = repo.get_post_by_id(id).await?;
+            repo.delete_post(id).await?;
+            audit_log.record(cli_event(audit::POST_DELETE).target("post", id)
+                .changes(diff(post.as_ref(), None)), None, None).await?;
+            println!("已删除文章 {}", id);
+        }
+    }
+    Ok(())
+}
+
+/// 按用户名或邮箱查找用户，不存在时报错。
+async fn find_user(repo: &UserRepository, user: &str) -> Result<User> {
+    repo.get_user_by_username_or_email(user)
+        .await?
+        .ok_or_else(|| anyhow!("用户 {} 不存在", user))
+}
+
+/// 以命令行工具为操作者的审计记录，操作者名称为 `blogctl:<系统用户名>`。
+fn cli_event(action: &'static str) -> AuditEvent {
+    let os_user = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or_else(|_| "unknown".into());
+    AuditEvent::new(action).by_user(None, &format!("blogctl:{}", os_user))
+}
+
+/// 交互式输入两次密码（不回显）。
+fn prompt_password() -> Result<String> {
+    let password = rpassword::prompt_password("密码: ")?;
+    let confirm = rpassword::prompt_password("确认密码: ")?;
+    if password != confirm {
+        bail!("两次输入的密码不一致");
+    }
+    Ok(password)
+}
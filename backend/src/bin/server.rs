@@ -2,100 +2,110 @@
 //! 负责应用程序的初始化、路由聚合和服务器启动。
 
 use axum::Router;
+use clap::Parser;
 use tokio::net::TcpListener;
-use std::{net::SocketAddr, sync::Arc, env, time::Duration};
-use tracing::{debug, info, error, warn};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use sea_orm::{DatabaseConnection, ConnectOptions};
+use std::{future::IntoFuture, sync::Arc, env, net::SocketAddr, time::Duration};
+use tracing::{debug, info, warn};
 use anyhow::Result; // 用于 main 函数的错误处理
-use tower_http::cors::{CorsLayer, Any};
-use axum::http::Method;
+use tower_http::cors::{AllowOrigin, CorsLayer, Any};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use opentelemetry::trace::TracerProvider as _;
+use axum::http::{HeaderValue, Method};
 
-// 从 axum_blog_engine 库导入 AppState 和 Config
-use axum_blog_engine::{AppState, Config};
+// 从 axum_blog_engine 库导入 AppState、分层配置和共用的启动流程
+use axum_blog_engine::AppState;
+use axum_blog_engine::bootstrap::{build_storage, connect_database, load_dotenv};
+use axum_blog_engine::settings::{Overrides, Settings};
 
 // 导入数据库 Repositories
 use axum_blog_engine::database::user::UserRepository;
 use axum_blog_engine::database::post::PostRepository;
 use axum_blog_engine::database::comment::CommentRepository;
 use axum_blog_engine::database::favorite::FavoriteRepository;
+use axum_blog_engine::database::media::MediaRepository;
+use axum_blog_engine::database::audit::AuditRepository;
 
 // 导入路由配置函数
-use axum_blog_engine::handler::user::user_routes;
-use axum_blog_engine::handler::post::post_routes;
-use axum_blog_engine::handler::comment::comment_routes_for_post as post_comments_routes; // 为 /posts/:id/comments 导入一个别名
-use axum_blog_engine::handler::comment::comment_routes;
-use axum_blog_engine::handler::favorite::favorite_routes;
+use axum_blog_engine::handler::openapi::api_routes;
+use axum_blog_engine::handler::feed::feed_routes;
+use axum_blog_engine::handler::sitemap::sitemap_routes;
+use axum_blog_engine::handler::page::page_routes;
+use axum_blog_engine::handler::assets::serve_frontend;
+use axum_blog_engine::handler::media::media_file_routes;
+use axum_blog_engine::handler::health::health_routes;
+use axum_blog_engine::handler::metrics::{metrics_routes, tag_matched_route, track_metrics};
+use axum_blog_engine::handler::request_id::scope_request_id;
+use axum_blog_engine::handler::problem::negotiate_error_format;
+use axum_blog_engine::handler::locale::negotiate_locale;
+use axum_blog_engine::lifecycle::{close_database, shutdown_signal, BackgroundTasks};
+use axum_blog_engine::metrics::Metrics;
+use axum_blog_engine::telemetry::{init_tracer_provider, request_span};
+use axum_blog_engine::logging;
+use axum_blog_engine::migration::Migrator;
+use axum_blog_engine::theme::Theme;
+use axum_blog_engine::assets::StaticAssets;
+
+/// 博客服务端，配置优先级：命令行参数 > 环境变量 > 配置文件 > 默认值
+#[derive(Parser)]
+#[command(name = "server", version)]
+struct Cli {
+    #[command(flatten)]
+    overrides: Overrides,
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // 1. 初始化日志系统
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "axum_blog_engine=debug,tower_http=debug,sea_orm=debug".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
-
-    info!("应用程序启动中...");
-
-    // 2. 加载环境变量
-    let env_path = std::path::Path::new("./.env");
-    match dotenv::from_path(env_path) {
-        Ok(_) => info!("已成功加载 .env 文件"),
-        Err(e) => error!("无法加载 .env 文件: {}", e), // Non-fatal, as defaults might be used
-    };
+    // 1. 加载配置 (.env、配置文件、环境变量与命令行参数)，校验失败时拒绝启动
+    let dotenv_loaded = load_dotenv()?;
+    let cli = Cli::parse();
+    let settings = Settings::load(&cli.overrides)?;
 
-    let db_url = env::var("DATABASE_URL")
-        .unwrap_or_else(|_| {
-            info!("未找到 DATABASE_URL 环境变量，使用默认值：sqlite:blogdb.db");
-            "sqlite:blogdb.db?mode=rwc".into() // 确保 SQLite URL 格式正确，rwc表示读写创建
-        });
-    
-    let jwt_secret = env::var("JWT_SECRET")
-        .unwrap_or_else(|_| {
-            warn!("未找到 JWT_SECRET 环境变量，使用默认值：default_jwt_secret。生产环境请务必设置！");
-            "default_jwt_secret".into() // 生产环境请务必使用强密钥
-        });
-    
-    let bcrypt_cost_str = env::var("BCRYPT_COST")
-        .unwrap_or_else(|_| "12".into());
-    let bcrypt_cost = bcrypt_cost_str.parse::<u32>()
-        .unwrap_or_else(|_| {
-            warn!("无法解析 BCRYPT_COST 环境变量，使用默认值 12。");
-            12
-        });
+    // 2. 初始化日志与追踪系统 (配置了 OTLP 地址时同时导出 span)
+    // 日志可以是文本或 JSON 格式，输出到标准输出或轮转文件，写出前统一脱敏
+    let tracer_provider = init_tracer_provider(&settings.tracing)?;
+    logging::init(&settings.log, tracer_provider.tracer("axum_blog_engine"))?;
 
-    debug!("数据库 URL: {}", db_url);
+    info!("应用程序启动中... (运行模式: {:?})", settings.environment);
+    if dotenv_loaded {
+        info!("已成功加载 .env 文件");
+    }
+    if let Some(dir) = &settings.log.dir {
+        info!("日志写入目录 {} (格式: {:?}, 轮转: {:?})", dir.display(), settings.log.format, settings.log.rotation);
+    }
+    for warning in &settings.warnings {
+        warn!("{}", warning);
+    }
+    if let Some(endpoint) = &settings.tracing.otlp_endpoint {
+        info!("追踪数据将通过 OTLP 导出到 {}", endpoint);
+    }
     debug!("当前工作目录: {:?}", env::current_dir().unwrap_or_default());
 
     // 3. 初始化数据库连接
-    let mut connect_options = ConnectOptions::new(db_url.clone());
-    connect_options
-        .max_connections(10)
-        .min_connections(5)
-        .connect_timeout(Duration::from_secs(8))
-        .idle_timeout(Duration::from_secs(8))
-        .sqlx_logging(true) // 开启 SQLX 日志
-        .sqlx_logging_level(tracing::log::LevelFilter::Info); // 设置日志级别
-
-    let db_connection: DatabaseConnection = sea_orm::Database::connect(connect_options)
-        .await
-        .map_err(|e| anyhow::anyhow!("数据库连接失败: {}", e))?;
-    
-    info!("数据库连接成功");
+    let mut db_connection = connect_database(&settings.database).await?;
+    // 语句耗时回调必须在连接被复制到各个 Repository 之前注册
+    let metrics = Arc::new(Metrics::new());
+    metrics.instrument_database(&mut db_connection);
 
-    // 运行数据库迁移 (如果使用 SeaORM 的迁移工具)
-    // 假设你有一个独立的 SeaORM 迁移项目或通过 SeaORM CLI 管理迁移
-    // 如果你还没有设置，可以暂时注释掉这行。
-    // 例如：`sea_orm_migration::Migrator::run(&db_connection, Config::default()).await?;`
+    if settings.database.auto_migrate {
+        let executed = Migrator::up(&db_connection, None).await
+            .map_err(|e| anyhow::anyhow!("数据库迁移失败: {}", e))?;
+        info!("数据库迁移完成，本次执行 {} 个迁移", executed.len());
+    } else {
+        let pending = Migrator::status(&db_connection).await
+            .map_err(|e| anyhow::anyhow!("读取迁移状态失败: {}", e))?
+            .iter()
+            .filter(|s| s.applied_at.is_none())
+            .count();
+        if pending > 0 {
+            warn!("有 {} 个数据库迁移尚未执行，请运行 `blogctl db migrate`", pending);
+        }
+    }
 
     // 4. 初始化应用状态
-    let app_config = Arc::new(Config {
-        jwt_secret,
-        bcrypt_cost,
-    });
+    let app_config = Arc::new(settings.app_config()?);
+
+    let theme = Theme::load(app_config.theme_dir.as_deref())
+        .map_err(|e| anyhow::anyhow!("主题加载失败: {}", e))?;
 
     let app_state = AppState {
         db: db_connection.clone(),
@@ -104,13 +114,26 @@ async fn main() -> Result<()> {
         post_repo: PostRepository::new(db_connection.clone()),
         comment_repo: CommentRepository::new(db_connection.clone()),
         favorite_repo: FavoriteRepository::new(db_connection.clone()),
+        theme: Arc::new(theme),
+        assets: StaticAssets::from_config(app_config.static_dir.as_deref()).map(Arc::new),
+        media_repo: MediaRepository::new(db_connection.clone()),
+        storage: build_storage(&settings.media.dir, &settings.media.storage)?,
+        tasks: BackgroundTasks::new(),
+        metrics,
+        audit_repo: AuditRepository::new(db_connection.clone()),
     };
+    let tasks = app_state.tasks.clone();
+
+    match &app_state.assets {
+        Some(assets) => info!("前端静态资源来源: {:?}", assets),
+        None => info!("未配置前端静态资源，仅提供 API 和服务端渲染页面"),
+    }
 
     info!("应用状态初始化完成");
 
     // 5. 构建 Axum 路由 (遵循 RESTful 风格)
     let cors = CorsLayer::new()
-        .allow_origin(Any)
+        .allow_origin(allowed_origins(&settings.server.cors_origins))
         .allow_methods([
             Method::GET,
             Method::POST,
@@ -120,38 +143,114 @@ async fn main() -> Result<()> {
         ])
         .allow_headers(Any);
 
-    let app = Router::new()
+    let mut app = Router::new()
         // API 路由前缀
-        .nest("/api", Router::new()
-            // 用户相关路由
-            .nest("/user", user_routes())
-            // 帖子相关路由
-            .nest("/post", post_routes())
-            // 文章评论路由 (嵌套在文章路由下)
-            .nest("/post/:post_id/comments", post_comments_routes())
-            // 独立的评论资源路由 (例如 POST /comments, GET /comments/:id)
-            .nest("/comment", comment_routes())
-            // 收藏相关路由
-            .nest("/post_fav", favorite_routes()) // 你的 API 路径是 /post_fav
-        )
+        .nest("/api", api_routes().into_router())
+        // 存活与就绪检查
+        .merge(health_routes())
+        // 订阅源 (RSS / Atom / JSON Feed)，挂载在站点根路径
+        .merge(feed_routes())
+        // 站点地图与 robots.txt
+        .merge(sitemap_routes())
+        // 服务端渲染的公开页面 (文章、分类、作者)
+        .merge(page_routes())
+        // 已上传的媒体文件
+        .merge(media_file_routes());
+    // Prometheus 指标：未配置独立管理端口时挂载在主端口上
+    if settings.metrics.enabled && settings.metrics.listen.is_none() {
+        app = app.merge(metrics_routes());
+    }
+    let app = app
+        // 记录匹配到的路由模板，作为请求指标的标签
+        .route_layer(axum::middleware::from_fn(tag_matched_route))
+        // 其余请求交给前端静态资源 (SPA 回退到 index.html)
+        .fallback(serve_frontend)
         // 添加 CORS 中间件
         .layer(cors)
-        // 可以添加其他全局中间件，例如 TraceLayer 用于请求日志
-        .layer(tower_http::trace::TraceLayer::new_for_http())
-        .with_state(app_state); // 注入应用程序状态
+        // 请求数量与耗时指标
+        .layer(axum::middleware::from_fn_with_state(app_state.clone(), track_metrics))
+        // 错误响应体中带上请求 ID
+        .layer(axum::middleware::from_fn(scope_request_id))
+        // 按 Accept 头选择错误响应格式 (JSON / application/problem+json)
+        .layer(axum::middleware::from_fn(negotiate_error_format))
+        // 按 lang 参数、Cookie 和 Accept-Language 选择响应消息的语言
+        .layer(axum::middleware::from_fn(negotiate_locale))
+        // 请求日志，每个请求一个根 span (携带 request_id 与上游 traceparent)
+        .layer(tower_http::trace::TraceLayer::new_for_http().make_span_with(request_span))
+        // 在响应中回传 X-Request-Id
+        .layer(PropagateRequestIdLayer::x_request_id())
+        // 沿用客户端传入的 X-Request-Id，没有时生成 UUID
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+        .with_state(app_state.clone()); // 注入应用程序状态
 
     debug!("路由配置完成");
 
     // 6. 启动服务器
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3000)); // 监听所有网络接口的 3000 端口
+    let addr = settings.server.listen;
     let listener = TcpListener::bind(addr).await
         .map_err(|e| anyhow::anyhow!("无法绑定监听地址 {}: {}", addr, e))?;
     
     info!("服务器正在监听 {}", addr);
-    axum::serve(listener, app)
-        .await
-        .map_err(|e| anyhow::anyhow!("服务器启动失败: {}", e))?;
+
+    // 独立的指标端口 (例如只在内网可访问的管理端口)
+    if let (true, Some(metrics_addr)) = (settings.metrics.enabled, settings.metrics.listen) {
+        let metrics_listener = TcpListener::bind(metrics_addr).await
+            .map_err(|e| anyhow::anyhow!("无法绑定指标监听地址 {}: {}", metrics_addr, e))?;
+        info!("指标端点正在监听 {}/metrics", metrics_addr);
+        let metrics_app = metrics_routes().with_state(app_state);
+        let metrics_shutdown = tasks.shutdown_token();
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(metrics_listener, metrics_app)
+                .with_graceful_shutdown(metrics_shutdown.cancelled_owned())
+                .await
+            {
+                warn!("指标服务运行失败: {}", e);
+            }
+        });
+    }
+
+    // 收到 SIGTERM / SIGINT 后停止接受新连接，在超时时间内等待进行中的请求完成
+    let shutdown_timeout = Duration::from_secs(settings.server.shutdown_timeout_secs);
+    tokio::spawn(shutdown_signal(tasks.clone()));
+    let shutdown = tasks.shutdown_token();
+    // 携带连接对端地址，供审计日志记录客户端 IP
+    let server = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(shutdown.clone().cancelled_owned());
+    let mut server = std::pin::pin!(server.into_future());
+    let stopped = tokio::select! {
+        result = &mut server => {
+            result.map_err(|e| anyhow::anyhow!("服务器运行失败: {}", e))?;
+            true
+        }
+        _ = shutdown.cancelled() => false,
+    };
+
+    // 进行中的请求与后台任务 (例如图片变体生成) 共用同一个停机截止时间
+    let deadline = tokio::time::Instant::now() + shutdown_timeout;
+    if !stopped {
+        match tokio::time::timeout_at(deadline, server).await {
+            Ok(result) => result.map_err(|e| anyhow::anyhow!("服务器运行失败: {}", e))?,
+            Err(_) => warn!("等待进行中的请求超时 ({:?})，强制关闭", shutdown_timeout),
+        }
+    }
+    if !tasks.wait_until(deadline).await {
+        warn!("部分后台任务未能在停机超时前完成");
+    }
+    close_database(db_connection).await;
+
+    // 导出尚未发送的 span (导出器使用阻塞的 HTTP 客户端)
+    if let Err(e) = tokio::task::spawn_blocking(move || tracer_provider.shutdown()).await? {
+        warn!("关闭追踪导出器失败: {}", e);
+    }
 
     info!("服务器已关闭");
     Ok(())
 }
+
+/// 根据配置的来源列表构建 CORS 允许规则，`["*"]` 表示任意来源（已在配置校验中检查格式）。
+fn allowed_origins(origins: &[String]) -> AllowOrigin {
+    if origins.iter().any(|o| o == "*") {
+        return Any.into();
+    }
+    AllowOrigin::list(origins.iter().filter_map(|o| HeaderValue::from_str(o).ok()))
+}
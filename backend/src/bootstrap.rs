@@ -0,0 +1,85 @@
+// src/bootstrap.rs
+//! 服务端 (`server`) 与命令行工具 (`blogctl`) 共用的启动流程：
+//! 加载 `.env`、连接数据库、创建媒体存储后端。配置的合并与校验见 `settings` 模块。
+
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use sea_orm::{ConnectOptions, ConnectionTrait, DatabaseConnection, DbBackend};
+use tracing::{debug, info};
+
+use crate::settings::{DatabaseSettings, StorageKind, StorageSettings};
+use crate::storage::sigv4::Credentials;
+use crate::storage::{LocalStorage, S3Config, S3Storage, StorageBackend};
+
+/// 加载当前目录下的 `.env` 文件，文件不存在时跳过。
+///
+/// # 返回
+/// 是否加载了 `.env` 文件；文件存在但格式错误时返回错误。
+pub fn load_dotenv() -> Result<bool> {
+    match dotenv::from_path(std::path::Path::new("./.env")) {
+        Ok(_) => Ok(true),
+        Err(dotenv::Error::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(anyhow::anyhow!("无法加载 .env 文件: {}", e)),
+    }
+}
+
+/// 按配置的连接池参数连接数据库；SQLite 会切换到 WAL 日志模式。
+pub async fn connect_database(settings: &DatabaseSettings) -> Result<DatabaseConnection> {
+    debug!("数据库 URL: {}", settings.url);
+
+    let mut connect_options = ConnectOptions::new(settings.url.clone());
+    connect_options
+        .max_connections(settings.max_connections)
+        .min_connections(settings.min_connections)
+        .connect_timeout(Duration::from_secs(settings.connect_timeout_secs))
+        .idle_timeout(Duration::from_secs(settings.idle_timeout_secs))
+        .sqlx_logging(true) // 开启 SQLX 日志
+        .sqlx_logging_level(tracing::log::LevelFilter::Debug) // 完整的 SQL 只在 debug 级别输出
+        .sqlx_slow_statements_logging_settings(tracing::log::LevelFilter::Warn, Duration::from_secs(1)); // 慢查询以 warn 级别输出
+
+    let db_connection: DatabaseConnection = sea_orm::Database::connect(connect_options)
+        .await
+        .map_err(|e| anyhow::anyhow!("数据库连接失败: {}", e))?;
+
+    info!("数据库连接成功");
+
+    if db_connection.get_database_backend() == DbBackend::Sqlite {
+        // 提高并发性能，尤其是在高写入负载下
+        db_connection.execute_unprepared("PRAGMA journal_mode = WAL;").await
+            .map_err(|e| anyhow::anyhow!("设置 SQLite 日志模式失败: {}", e))?;
+    }
+    Ok(db_connection)
+}
+
+/// 根据 `[media.storage]` 配置创建媒体存储后端：`local` (默认) 或 `s3`。
+///
+/// # 参数
+/// * `media_dir` - 本地存储后端的目录。
+/// * `settings` - 已校验的存储后端配置。
+pub fn build_storage(media_dir: &str, settings: &StorageSettings) -> Result<Arc<dyn StorageBackend>> {
+    match settings.backend {
+        StorageKind::Local => {
+            info!("媒体文件存储目录: {}", media_dir);
+            Ok(Arc::new(LocalStorage::new(media_dir)))
+        }
+        StorageKind::S3 => {
+            let config = S3Config {
+                endpoint: settings.endpoint(),
+                credentials: Credentials {
+                    access_key_id: settings.access_key_id.clone(),
+                    secret_access_key: settings.secret_access_key.clone(),
+                    region: settings.region.clone(),
+                },
+                bucket: settings.bucket.clone(),
+                prefix: settings.prefix.clone(),
+                path_style: settings.path_style,
+                presign_expires_secs: settings.presign_expires_secs,
+                multipart_threshold: settings.multipart_threshold,
+                multipart_part_size: settings.multipart_part_size,
+            };
+            info!("媒体文件存储于对象存储: {} (bucket: {})", config.endpoint, config.bucket);
+            Ok(Arc::new(S3Storage::new(config).map_err(|e| anyhow::anyhow!("对象存储配置无效: {}", e))?))
+        }
+    }
+}
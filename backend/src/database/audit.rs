@@ -0,0 +1,174 @@
+// src/database/audit.rs
+//! 审计日志数据库仓库和实体定义。
+
+// --- SeaORM 实体定义 ---
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveModelBehavior, QueryOrder};
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+/// `audit_log` 表的实体定义。
+///
+/// 不与 `users` 建立外键：用户被删除后，相关记录仍需保留。
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize)]
+#[sea_orm(table_name = "audit_log")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub actor_id: Option<i32>,
+    pub actor_name: Option<String>, // 操作时的用户名 (登录失败时为输入的用户名或邮箱)
+    pub action: String,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub changes: Option<String>, // 变更前后的字段差异 (JSON)
+    pub ip: Option<String>,
+    pub request_id: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// `audit_log` 没有关联实体。
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+/// 定义 `audit_log` 实体的 `ActiveModel`。
+impl ActiveModelBehavior for ActiveModel {}
+
+// --- AuditRepository (数据库操作) ---
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect,
+    Select, Set,
+};
+use chrono::Utc;
+
+// 导入自定义错误类型
+use crate::error::CustomError;
+use crate::audit::AuditEvent;
+use tracing::instrument;
+
+/// 查询审计日志的过滤条件，各项均为可选。
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub actor_id: Option<i32>,
+    pub action: Option<String>,
+    pub from: Option<NaiveDateTime>, // 包含
+    pub to: Option<NaiveDateTime>,   // 不包含
+}
+
+/// `AuditRepository` 提供与 `audit_log` 表交互的方法。
+/// 审计日志只追加，因此只提供写入和查询。
+#[derive(Clone)]
+pub struct AuditRepository {
+    db: DatabaseConnection,
+}
+
+impl AuditRepository {
+    /// 创建一个新的 `AuditRepository` 实例。
+    pub fn new(db: DatabaseConnection) -> Self {
+        AuditRepository { db }
+    }
+
+    /// 追加一条审计记录。
+    ///
+    /// # 参数
+    /// * `event` - 操作者、动作、目标和变更差异。
+    /// * `ip` - 客户端 IP。
+    /// * `request_id` - 请求 ID，与日志和追踪中的 `request_id` 相同。
+    ///
+    /// # 返回
+    /// 包含写入的审计记录 `Model` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, event), fields(action = event.action))]
+    pub async fn record(
+        &self,
+        event: AuditEvent,
+        ip: Option<String>,
+        request_id: Option<String>,
+    ) -> Result<Model, CustomError> {
+        let active_model = ActiveModel {
+            actor_id: Set(event.actor_id),
+            actor_name: Set(event.actor_name),
+            action: Set(event.action.to_string()),
+            target_type: Set(event.target_type.map(String::from)),
+            target_id: Set(event.target_id),
+            changes: Set(event.changes.map(|c| c.to_string())),
+            ip: Set(ip),
+            request_id: Set(request_id),
+            created_at: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        };
+        let entry = active_model.insert(&self.db).await?;
+        Ok(entry)
+    }
+
+    /// 分页查询审计日志，按时间倒序。
+    ///
+    /// # 参数
+    /// * `filter` - 过滤条件。
+    /// * `page` - 当前页码（1-索引）。
+    /// * `page_size` - 每页的记录数量。
+    ///
+    /// # 返回
+    /// 包含 `(Vec<Model>, total_pages, current_page, total_entries)` 元组或 `CustomError` 的 `Result`。
+    #[instrument(skip(self))]
+    pub async fn get_paginated_entries(
+        &self,
+        filter: &AuditFilter,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(Vec<Model>, u64, u64, u64), CustomError> {
+        let paginator = filtered(filter).paginate(&self.db, page_size);
+
+        let total_entries = paginator.num_items().await?;
+        let total_pages = paginator.num_pages().await?;
+        let entries = paginator.fetch_page(page.saturating_sub(1)).await?;
+
+        Ok((entries, total_pages, page, total_entries))
+    }
+
+    /// 按时间倒序分批查询符合条件的审计日志，用于流式导出 CSV。
+    ///
+    /// 以上一批最后一条记录的 `(created_at, id)` 作为游标，导出期间新写入的记录不会导致重复或遗漏。
+    ///
+    /// # 参数
+    /// * `filter` - 过滤条件。
+    /// * `after` - 上一批最后一条记录的 `(created_at, id)`，第一批为 `None`。
+    /// * `limit` - 每批的记录数量。
+    ///
+    /// # 返回
+    /// 包含本批记录的 `Result`，没有更多记录时为空。
+    #[instrument(skip(self))]
+    pub async fn get_entries_after(
+        &self,
+        filter: &AuditFilter,
+        after: Option<(NaiveDateTime, i64)>,
+        limit: u64,
+    ) -> Result<Vec<Model>, CustomError> {
+        let mut query = filtered(filter);
+        if let Some((created_at, id)) = after {
+            query = query.filter(
+                Condition::any()
+                    .add(Column::CreatedAt.lt(created_at))
+                    .add(Condition::all().add(Column::CreatedAt.eq(created_at)).add(Column::Id.lt(id))),
+            );
+        }
+        let entries = query.limit(limit).all(&self.db).await?;
+        Ok(entries)
+    }
+}
+
+fn filtered(filter: &AuditFilter) -> Select<Entity> {
+    let mut query = Entity::find();
+    if let Some(actor_id) = filter.actor_id {
+        query = query.filter(Column::ActorId.eq(actor_id));
+    }
+    if let Some(action) = &filter.action {
+        query = query.filter(Column::Action.eq(action.as_str()));
+    }
+    if let Some(from) = filter.from {
+        query = query.filter(Column::CreatedAt.gte(from));
+    }
+    if let Some(to) = filter.to {
+        query = query.filter(Column::CreatedAt.lt(to));
+    }
+    // 同一秒内的记录按 ID 保持写入顺序
+    query.order_by_desc(Column::CreatedAt).order_by_desc(Column::Id)
+}
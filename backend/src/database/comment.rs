@@ -5,12 +5,13 @@
 use sea_orm::entity::prelude::*;
 use sea_orm::{ActiveModelBehavior, QueryOrder, IntoActiveModel};
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize}; // 用于 blogctl 导出 / 导入
 
 /// `comments` 表的实体定义。
 ///
 /// 此结构体直接映射到数据库中 `comments` 表的列。
 /// `created_at` 字段使用 `chrono::NaiveDateTime` 类型。
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "comments")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -83,16 +84,21 @@ impl ActiveModelBehavior for ActiveModel {}
 // --- CommentRepository (数据库操作) ---
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
-    PaginatorTrait, QuerySelect
+    PaginatorTrait
 };
 use chrono::Utc;
+use std::collections::HashMap;
 
 // 导入 DTOs
 use crate::handler::idl::{
-    CreateCommentRequest, UpdateCommentRequest, CommentInfo
+    AuthorInfo, CreateCommentRequest, UpdateCommentRequest
 };
+use crate::database::user::get_authors_by_ids;
 // 导入自定义错误类型
-use crate::error::CustomError;
+use crate::error::{CustomError, ErrorCode};
+use crate::i18n::t_args;
+use crate::pagination::{keyset_page, keyset_select, Cursor, CursorPage};
+use tracing::instrument;
 
 
 /// `CommentRepository` 提供与 `comments` 表交互的方法。
@@ -116,6 +122,7 @@ impl CommentRepository {
     ///
     /// # 返回
     /// 包含创建的评论 `Model` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, req))]
     pub async fn create_comment(
         &self,
         req: CreateCommentRequest,
@@ -143,6 +150,7 @@ impl CommentRepository {
     ///
     /// # 返回
     /// 包含 `Option<Model>` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self))]
     pub async fn get_comment_by_id(&self, comment_id: i32) -> Result<Option<Model>, CustomError> {
         let comment = Entity::find_by_id(comment_id).one(&self.db).await?;
         Ok(comment)
@@ -156,6 +164,7 @@ impl CommentRepository {
     ///
     /// # 返回
     /// 包含更新后的评论 `Model` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, req))]
     pub async fn update_comment(
         &self,
         comment_id: i32,
@@ -164,7 +173,7 @@ impl CommentRepository {
         let comment = Entity::find_by_id(comment_id).one(&self.db).await?;
         let mut comment: ActiveModel = match comment {
             Some(c) => c.into_active_model(),
-            None => return Err(CustomError::NotFound(format!("ID为 {} 的评论未找到", comment_id))),
+            None => return Err(CustomError::coded(ErrorCode::CommentNotFound, t_args("comment-not-found", &[("id", &comment_id)]))),
         };
 
         comment.content = Set(req.content);
@@ -181,10 +190,11 @@ impl CommentRepository {
     ///
     /// # 返回
     /// 表示成功或失败的 `Result`。
+    #[instrument(skip(self))]
     pub async fn delete_comment(&self, comment_id: i32) -> Result<(), CustomError> {
         let result = Entity::delete_by_id(comment_id).exec(&self.db).await?;
         if result.rows_affected == 0 {
-            return Err(CustomError::NotFound(format!("ID为 {} 的评论未找到或已被删除", comment_id)));
+            return Err(CustomError::coded(ErrorCode::CommentNotFound, t_args("comment-not-found-or-deleted", &[("id", &comment_id)])));
         }
         Ok(())
     }
@@ -198,6 +208,7 @@ impl CommentRepository {
     ///
     /// # 返回
     /// 包含 `(Vec<Model>, total_pages, current_page, total_comments)` 元组或 `CustomError` 的 `Result`。
+    #[instrument(skip(self))]
     pub async fn get_paginated_comments_by_post_id(
         &self,
         post_id: i32,
@@ -216,4 +227,46 @@ impl CommentRepository {
 
         Ok((comments, total_pages, page, total_comments))
     }
+
+    /// 按游标检索指定文章的评论，按 `(created_at, id)` 倒序。
+    ///
+    /// # 参数
+    /// * `post_id` - 要获取评论的文章 ID。
+    /// * `cursor` - 上一次返回的游标，第一页为 `None`。
+    /// * `limit` - 每页数量。
+    #[instrument(skip(self))]
+    pub async fn get_comments_by_cursor(
+        &self,
+        post_id: i32,
+        cursor: Option<&Cursor>,
+        limit: u64,
+    ) -> Result<CursorPage<Model>, CustomError> {
+        let select = Entity::find().filter(Column::PostId.eq(post_id));
+        let rows = keyset_select(select, Column::CreatedAt, Column::Id, cursor, limit)
+            .all(&self.db)
+            .await?;
+        Ok(keyset_page(rows, limit, cursor, |comment| (comment.created_at, comment.id)))
+    }
+
+    /// 批量查询评论者的公开信息。
+    ///
+    /// # 参数
+    /// * `user_ids` - 一页评论的评论者 ID，可以有重复。
+    ///
+    /// # 返回
+    /// 用户 ID 到 `AuthorInfo` 的映射。
+    #[instrument(skip(self))]
+    pub async fn get_authors(&self, user_ids: &[i32]) -> Result<HashMap<i32, AuthorInfo>, CustomError> {
+        get_authors_by_ids(&self.db, user_ids).await
+    }
+
+    /// 统计指定文章的评论数量。
+    #[instrument(skip(self))]
+    pub async fn count_comments_by_post_id(&self, post_id: i32) -> Result<u64, CustomError> {
+        let count = Entity::find()
+            .filter(Column::PostId.eq(post_id))
+            .count(&self.db)
+            .await?;
+        Ok(count)
+    }
 }
@@ -5,14 +5,15 @@
 use sea_orm::entity::prelude::*;
 use sea_orm::{QueryOrder, ActiveModelBehavior, IntoActiveModel};
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize}; // 用于 blogctl 导出 / 导入
 // 导入文章实体
-use crate::database::post::{Entity as PostEntity, Model as PostModel, Column as PostColumn};
+use crate::database::post::{Entity as PostEntity, Column as PostColumn, SummaryRow, convert_summary_row_to_post_summary};
 
 /// `favorites` 表的实体定义。
 ///
 /// 此结构体直接映射到数据库中 `favorites` 表的列。
 /// 注意：`user_id` 和 `post_id` 组成联合主键。
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "favorites")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -64,14 +65,17 @@ impl ActiveModelBehavior for ActiveModel {}
 // --- FavoriteRepository (数据库操作) ---
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
-    PaginatorTrait, QuerySelect, RelationTrait
+    PaginatorTrait, RelationTrait
 };
 use chrono::Utc;
+use std::collections::HashMap;
 
-// 导入 DTOs (如果需要，例如 PostInfo)
-use crate::handler::idl::PostInfo;
+// 导入 DTOs
+use crate::handler::idl::PostSummary;
 // 导入自定义错误类型
 use crate::error::CustomError;
+use tracing::instrument;
+use crate::pagination::{keyset_page, keyset_select, Cursor, CursorPage};
 // PostModel 和 PostEntity 已在文件开头导入
 
 
@@ -97,6 +101,7 @@ impl FavoriteRepository {
     ///
     /// # 返回
     /// `Result<bool, CustomError>`，`true` 表示已收藏，`false` 表示已取消收藏。
+    #[instrument(skip(self))]
     pub async fn toggle_favorite(
         &self,
         user_id: i32,
@@ -120,7 +125,6 @@ impl FavoriteRepository {
                 user_id: Set(user_id),
                 post_id: Set(post_id),
                 created_at: Set(now),
-                ..Default::default()
             };
             active_model.insert(&self.db).await?;
             Ok(true) // 已收藏
@@ -135,6 +139,7 @@ impl FavoriteRepository {
     ///
     /// # 返回
     /// `Result<bool, CustomError>`，`true` 表示已收藏，`false` 表示未收藏。
+    #[instrument(skip(self))]
     pub async fn is_favorited(&self, user_id: i32, post_id: i32) -> Result<bool, CustomError> {
         let count = Entity::find()
             .filter(Column::UserId.eq(user_id))
@@ -153,13 +158,14 @@ impl FavoriteRepository {
     /// * `page_size` - 每页的文章数量。
     ///
     /// # 返回
-    /// 包含 `(Vec<PostInfo>, total_pages, current_page, total_favorites)` 元组或 `CustomError` 的 `Result`。
+    /// 包含 `(Vec<PostSummary>, total_pages, current_page, total_favorites)` 元组或 `CustomError` 的 `Result`。
+    #[instrument(skip(self))]
     pub async fn get_paginated_user_favorites(
         &self,
         user_id: i32,
         page: u64,
         page_size: u64,
-    ) -> Result<(Vec<PostInfo>, u64, u64, u64), CustomError> {
+    ) -> Result<(Vec<PostSummary>, u64, u64, u64), CustomError> {
         // 首先获取用户收藏的文章ID列表
         let favorite_records = Entity::find()
             .filter(Column::UserId.eq(user_id))
@@ -170,38 +176,66 @@ impl FavoriteRepository {
         let total_pages = favorite_records.num_pages().await?;
 
         let favorites = favorite_records.fetch_page(page.saturating_sub(1)).await?;
-        
+        let posts_info = self.favorited_posts(&favorites).await?;
+
+        Ok((posts_info, total_pages, page, total_favorites))
+    }
+
+    /// 按游标检索用户收藏的文章，按收藏时间倒序。
+    /// 游标以收藏记录的 `(created_at, post_id)` 为排序键。
+    ///
+    /// # 参数
+    /// * `user_id` - 要获取收藏列表的用户 ID。
+    /// * `cursor` - 上一次返回的游标，第一页为 `None`。
+    /// * `limit` - 每页数量。
+    #[instrument(skip(self))]
+    pub async fn get_user_favorites_by_cursor(
+        &self,
+        user_id: i32,
+        cursor: Option<&Cursor>,
+        limit: u64,
+    ) -> Result<CursorPage<PostSummary>, CustomError> {
+        let select = Entity::find().filter(Column::UserId.eq(user_id));
+        let rows = keyset_select(select, Column::CreatedAt, Column::PostId, cursor, limit)
+            .all(&self.db)
+            .await?;
+        let page = keyset_page(rows, limit, cursor, |favorite| (favorite.created_at, favorite.post_id));
+        let posts = self.favorited_posts(&page.items).await?;
+        Ok(CursorPage { items: posts, next_cursor: page.next_cursor, prev_cursor: page.prev_cursor })
+    }
+
+    /// 统计用户收藏的文章数量。
+    #[instrument(skip(self))]
+    pub async fn count_user_favorites(&self, user_id: i32) -> Result<u64, CustomError> {
+        let count = Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .count(&self.db)
+            .await?;
+        Ok(count)
+    }
+
+    /// 查出收藏记录对应的文章摘要，保持收藏记录的顺序。
+    async fn favorited_posts(&self, favorites: &[Model]) -> Result<Vec<PostSummary>, CustomError> {
         // 提取文章ID
         let post_ids: Vec<i32> = favorites.iter().map(|f| f.post_id).collect();
-        
-        // 根据文章ID获取文章详情
-        let favorited_posts_models: Vec<PostModel> = if post_ids.is_empty() {
-            Vec::new()
-        } else {
-            PostEntity::find()
-                .filter(PostColumn::Id.is_in(post_ids))
-                .all(&self.db)
-                .await?
-        };
+        if post_ids.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        let posts_info: Vec<PostInfo> = favorited_posts_models
+        // 根据文章ID获取文章详情
+        let mut posts: HashMap<i32, SummaryRow> = PostEntity::find()
+            .filter(PostColumn::Id.is_in(post_ids.clone()))
+            .into_partial_model::<SummaryRow>()
+            .all(&self.db)
+            .await?
             .into_iter()
-            .map(|post_model| PostInfo {
-                id: post_model.id,
-                title: post_model.title,
-                content_markdown: post_model.content_markdown,
-                category: post_model.category,
-                author_id: post_model.author_id,
-                is_published: post_model.is_published == 1,
-                view_count: post_model.view_count,
-                cover_url: post_model.cover_url,
-                created_at: post_model.created_at, // 这是文章的创建时间
-                updated_at: post_model.updated_at, // 这是文章的更新时间
-                // 注意：这里没有收藏记录的 `created_at`。如果需要，需要额外处理，
-                // 例如通过 `select_as_pair` 来获取 Favorite 的 created_at
-            })
+            .map(|post| (post.id, post))
             .collect();
 
-        Ok((posts_info, total_pages, page, total_favorites))
+        Ok(post_ids
+            .into_iter()
+            .filter_map(|id| posts.remove(&id))
+            .map(convert_summary_row_to_post_summary)
+            .collect())
     }
 }
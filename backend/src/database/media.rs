@@ -0,0 +1,265 @@
+// src/database/media.rs
+//! 媒体文件数据库仓库和实体定义。
+
+// --- SeaORM 实体定义 ---
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveModelBehavior, QueryOrder, QuerySelect};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize}; // 用于 blogctl 导出 / 导入
+
+/// `media` 表的实体定义。
+///
+/// 只记录文件的元数据，文件内容由 `StorageBackend` 按 `storage_key` 保存。
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "media")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub owner_id: i32,
+    #[sea_orm(unique)]
+    pub storage_key: String,
+    pub original_name: Option<String>,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub checksum: String,     // 文件内容的 SHA-256 (十六进制)
+    pub width: Option<i32>,   // 图片宽度，非图片为 None
+    pub height: Option<i32>,  // 图片高度，非图片为 None
+    pub variants: Option<String>, // 自动生成的各尺寸版本 (JSON)，None 表示尚未处理
+    pub created_at: NaiveDateTime,
+}
+
+/// 定义 `media` 实体的关系。
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::OwnerId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Owner, // 媒体文件属于上传它的用户
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Owner.def()
+    }
+}
+
+/// 定义 `media` 实体的 `ActiveModel`。
+impl ActiveModelBehavior for ActiveModel {}
+
+// --- MediaRepository (数据库操作) ---
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, Set, TransactionTrait};
+use sea_orm::sea_query::Expr;
+use chrono::Utc;
+
+// 导入自定义错误类型
+use crate::error::{CustomError, ErrorCode};
+use tracing::instrument;
+use crate::media::MediaVariant;
+use crate::i18n::t_args;
+
+/// 解析媒体记录中的变体列表。
+///
+/// # 返回
+/// 尚未处理时返回 `None`；处理完成（包括无需生成变体）时返回 `Some`。
+pub fn media_variants(model: &Model) -> Option<Vec<MediaVariant>> {
+    model.variants
+        .as_deref()
+        .map(|json| serde_json::from_str(json).unwrap_or_default())
+}
+
+/// 统计用户已使用的存储空间（原文件与各尺寸版本之和）。
+async fn used_bytes<C: ConnectionTrait>(conn: &C, owner_id: i32) -> Result<i64, CustomError> {
+    let rows: Vec<(i64, Option<String>)> = Entity::find()
+        .select_only()
+        .columns([Column::SizeBytes, Column::Variants])
+        .filter(Column::OwnerId.eq(owner_id))
+        .into_tuple()
+        .all(conn)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(size_bytes, variants)| {
+            let variants: Vec<MediaVariant> = variants
+                .as_deref()
+                .map(|json| serde_json::from_str(json).unwrap_or_default())
+                .unwrap_or_default();
+            size_bytes + variants.iter().map(|v| v.size_bytes as i64).sum::<i64>()
+        })
+        .sum())
+}
+
+/// 新建媒体记录所需的信息。
+#[derive(Debug, Clone)]
+pub struct NewMedia {
+    pub owner_id: i32,
+    pub storage_key: String,
+    pub original_name: Option<String>,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub checksum: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+}
+
+/// 根据新建信息构造待插入的 `ActiveModel`。
+fn new_active_model(media: NewMedia) -> ActiveModel {
+    ActiveModel {
+        owner_id: Set(media.owner_id),
+        storage_key: Set(media.storage_key),
+        original_name: Set(media.original_name),
+        mime_type: Set(media.mime_type),
+        size_bytes: Set(media.size_bytes),
+        checksum: Set(media.checksum),
+        width: Set(media.width),
+        height: Set(media.height),
+        created_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
+    }
+}
+
+/// `MediaRepository` 提供与 `media` 表交互的方法。
+#[derive(Clone)]
+pub struct MediaRepository {
+    db: DatabaseConnection,
+}
+
+impl MediaRepository {
+    /// 创建一个新的 `MediaRepository` 实例。
+    pub fn new(db: DatabaseConnection) -> Self {
+        MediaRepository { db }
+    }
+
+    /// 记录一个已上传的媒体文件。
+    ///
+    /// # 参数
+    /// * `media` - 媒体文件的元数据。
+    ///
+    /// # 返回
+    /// 包含创建的媒体 `Model` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, media))]
+    pub async fn create_media(&self, media: NewMedia) -> Result<Model, CustomError> {
+        let media = new_active_model(media).insert(&self.db).await?;
+        Ok(media)
+    }
+
+    /// 根据 ID 检索媒体记录。
+    #[instrument(skip(self))]
+    pub async fn get_media_by_id(&self, media_id: i32) -> Result<Option<Model>, CustomError> {
+        let media = Entity::find_by_id(media_id).one(&self.db).await?;
+        Ok(media)
+    }
+
+    /// 根据存储键检索媒体记录。
+    #[instrument(skip(self))]
+    pub async fn get_media_by_key(&self, storage_key: &str) -> Result<Option<Model>, CustomError> {
+        let media = Entity::find()
+            .filter(Column::StorageKey.eq(storage_key))
+            .one(&self.db)
+            .await?;
+        Ok(media)
+    }
+
+    /// 列出用户上传的全部媒体文件，按上传时间倒序。
+    #[instrument(skip(self))]
+    pub async fn get_media_by_owner(&self, owner_id: i32) -> Result<Vec<Model>, CustomError> {
+        let media = Entity::find()
+            .filter(Column::OwnerId.eq(owner_id))
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .all(&self.db)
+            .await?;
+        Ok(media)
+    }
+
+    /// 统计用户已使用的存储空间（字节），用于配额检查。
+    ///
+    /// 包括原文件和后台生成的各尺寸版本。
+    ///
+    /// # 参数
+    /// * `owner_id` - 用户 ID。
+    ///
+    /// # 返回
+    /// 包含已使用字节数或 `CustomError` 的 `Result`；没有上传过文件时为 0。
+    #[instrument(skip(self))]
+    pub async fn get_used_bytes(&self, owner_id: i32) -> Result<i64, CustomError> {
+        used_bytes(&self.db, owner_id).await
+    }
+
+    /// 在配额内记录一个已上传的媒体文件。
+    ///
+    /// 插入记录和统计用量在同一个事务中完成，并锁定用户行，
+    /// 同一用户的并发上传依次检查，不会合计超出配额。
+    ///
+    /// # 参数
+    /// * `media` - 媒体文件的元数据。
+    /// * `quota_bytes` - 用户的存储配额（字节）。
+    ///
+    /// # 返回
+    /// 包含创建的媒体 `Model` 的 `Result`；超出配额时返回 `MediaQuotaExceeded`，记录不会写入。
+    #[instrument(skip(self, media))]
+    pub async fn create_media_within_quota(&self, media: NewMedia, quota_bytes: u64) -> Result<Model, CustomError> {
+        let txn = self.db.begin().await?;
+        let owner_id = media.owner_id;
+        let size_bytes = media.size_bytes;
+        // 先插入再加锁：SQLite 不支持行锁，由插入语句取得写锁，使后续统计与其他写事务串行
+        let created = new_active_model(media).insert(&txn).await?;
+        super::user::Entity::find_by_id(owner_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?;
+
+        let used = used_bytes(&txn, owner_id).await?;
+        if used as u64 > quota_bytes {
+            // 丢弃事务即回滚刚插入的记录
+            return Err(CustomError::coded(ErrorCode::MediaQuotaExceeded, t_args("media-quota-exceeded", &[
+                ("used", &(used - size_bytes)),
+                ("quota", &quota_bytes),
+            ])));
+        }
+        txn.commit().await?;
+        Ok(created)
+    }
+
+    /// 记录后台生成的变体。
+    ///
+    /// # 参数
+    /// * `media_id` - 媒体 ID。
+    /// * `variants` - 已写入存储后端的变体列表。
+    ///
+    /// # 返回
+    /// 表示操作成功或失败的 `Result`，记录已被删除时返回 `MediaNotFound`。
+    #[instrument(skip(self, variants))]
+    pub async fn set_media_variants(&self, media_id: i32, variants: &[MediaVariant]) -> Result<(), CustomError> {
+        let json = serde_json::to_string(variants)
+            .map_err(|e| CustomError::InternalServerError(format!("序列化媒体变体失败: {}", e)))?;
+        let result = Entity::update_many()
+            .col_expr(Column::Variants, Expr::value(json))
+            .filter(Column::Id.eq(media_id))
+            .exec(&self.db)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(CustomError::coded(ErrorCode::MediaNotFound, t_args("media-id-not-found", &[("id", &media_id)])));
+        }
+        Ok(())
+    }
+
+    /// 删除媒体记录。
+    ///
+    /// # 参数
+    /// * `media_id` - 要删除的媒体 ID。
+    ///
+    /// # 返回
+    /// 表示操作成功或失败的 `Result`，记录不存在时返回 `MediaNotFound`。
+    #[instrument(skip(self))]
+    pub async fn delete_media(&self, media_id: i32) -> Result<(), CustomError> {
+        let result = Entity::delete_by_id(media_id).exec(&self.db).await?;
+        if result.rows_affected == 0 {
+            return Err(CustomError::coded(ErrorCode::MediaNotFound, t_args("media-id-not-found", &[("id", &media_id)])));
+        }
+        Ok(())
+    }
+}
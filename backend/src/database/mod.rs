@@ -1,4 +1,5 @@
-// 声明 orm 模块，通常包含数据库连接初始化逻辑
+// 声明 orm 模块，通常包含数据库连接初始化逻辑（目前连接由 server.rs 创建，暂未使用）
+#[allow(dead_code)]
 mod orm;
 
 // 声明并公开 user 模块，包含 UserRepository 和用户实体定义
@@ -12,3 +13,9 @@ pub mod comment;
 
 // 声明并公开 favorite 模块，包含 FavoriteRepository 和收藏实体定义
 pub mod favorite;
+
+// 声明并公开 media 模块，包含 MediaRepository 和媒体文件实体定义
+pub mod media;
+
+// 声明并公开 audit 模块，包含 AuditRepository 和审计日志实体定义
+pub mod audit;
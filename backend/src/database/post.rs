@@ -4,6 +4,7 @@
 // --- SeaORM 实体定义 ---
 use sea_orm::entity::prelude::*;
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize}; // 用于 blogctl 导出 / 导入
 // 引入 IntoActiveModel trait，以便使用 Model::into_active_model() 方法
 use sea_orm::{IntoActiveModel, ActiveModelBehavior};
 
@@ -11,7 +12,7 @@ use sea_orm::{IntoActiveModel, ActiveModelBehavior};
 ///
 /// 此结构体直接映射到数据库中 `posts` 表的列。
 /// `created_at` 和 `updated_at` 字段使用 `chrono::NaiveDateTime` 类型。
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "posts")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -20,9 +21,19 @@ pub struct Model {
     pub content_markdown: String,
     pub category: String,
     pub author_id: i32,
-    pub is_published: i32, // 数据库中是 INTEGER (0/1), Rust 中会转换为 bool
+    pub is_published: bool,
     pub view_count: i32,
     pub cover_url: Option<String>, // 可选字段
+    pub excerpt: Option<String>,   // 作者自定义摘要，为空时列表中使用自动摘要
+    pub content_html: Option<String>, // 渲染并清洗后的 HTML 缓存
+    pub content_hash: Option<String>, // 生成 content_html 时 Markdown 内容的哈希
+    pub toc_json: Option<String>,     // 根据标题生成的目录 (JSON)，与 content_html 一同缓存
+    #[serde(default)]
+    pub word_count: i32,                // 写入时统计的字数
+    #[serde(default)]
+    pub reading_time_minutes: i32,      // 写入时估算的阅读时间（分钟）
+    #[serde(default)]
+    pub auto_excerpt: Option<String>,   // 写入时根据正文生成的自动摘要
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
@@ -73,16 +84,112 @@ impl ActiveModelBehavior for ActiveModel {}
 // --- PostRepository (数据库操作) ---
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
-    PaginatorTrait, QuerySelect, RelationTrait, QueryOrder
+    PaginatorTrait, QueryOrder, QuerySelect
 };
+use sea_orm::sea_query::{Alias, Expr, Func, Order, Query, SimpleExpr};
+use std::collections::HashMap;
 use chrono::Utc;
 
 // 导入 DTOs
 use crate::handler::idl::{
-    CreatePostRequest, UpdatePostRequest, PostListRequest, PostInfo
+    AuthorInfo, CreatePostRequest, UpdatePostRequest, PostListRequest, PostSort, PostSummary
 };
+use crate::database::{comment, favorite, user};
 // 导入自定义错误类型
-use crate::error::CustomError;
+use crate::error::{CustomError, ErrorCode};
+use tracing::instrument;
+// 导入 Markdown 渲染工具
+use crate::render::{content_hash, render_markdown, RenderedMarkdown, TocEntry};
+use crate::render::summary::{auto_excerpt, text_stats, EXCERPT_MAX_CHARS};
+use crate::i18n::t_args;
+use crate::pagination::{keyset_page, keyset_select, Cursor, CursorPage};
+
+/// 获取文章摘要：作者填写了摘要时直接使用，否则使用写入时生成的自动摘要。
+pub fn post_excerpt(model: &Model) -> String {
+    match (&model.excerpt, &model.auto_excerpt) {
+        (Some(excerpt), _) if !excerpt.trim().is_empty() => excerpt.clone(),
+        (_, Some(auto)) => auto.clone(),
+        // 不经过 Repository 直接写入的数据没有自动摘要
+        _ => auto_excerpt(&model.content_markdown, EXCERPT_MAX_CHARS),
+    }
+}
+
+/// 为缺少写入时统计结果的文章（例如旧版本导出的数据）补齐字数、阅读时间和自动摘要。
+pub fn fill_text_stats(mut model: Model) -> Model {
+    if model.auto_excerpt.is_none() {
+        let stats = text_stats(&model.content_markdown);
+        model.word_count = stats.word_count as i32;
+        model.reading_time_minutes = stats.reading_time_minutes as i32;
+        model.auto_excerpt = Some(auto_excerpt(&model.content_markdown, EXCERPT_MAX_CHARS));
+    }
+    model
+}
+
+/// 列表查询读取的文章列：不包含正文和渲染缓存。
+#[derive(Debug, Clone, DerivePartialModel, sea_orm::FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+pub struct SummaryRow {
+    pub id: i32,
+    pub title: String,
+    pub category: String,
+    pub author_id: i32,
+    pub is_published: bool,
+    pub view_count: i32,
+    pub cover_url: Option<String>,
+    pub excerpt: Option<String>,
+    pub auto_excerpt: Option<String>,
+    pub word_count: i32,
+    pub reading_time_minutes: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// 将列表查询的 `SummaryRow` 转换为 `PostSummary` DTO。
+pub fn convert_summary_row_to_post_summary(row: SummaryRow) -> PostSummary {
+    let excerpt = row.excerpt.filter(|e| !e.trim().is_empty()).or(row.auto_excerpt).unwrap_or_default();
+
+    PostSummary {
+        id: row.id,
+        title: row.title,
+        excerpt,
+        category: row.category,
+        author_id: row.author_id,
+        is_published: row.is_published,
+        view_count: row.view_count,
+        cover_url: row.cover_url,
+        word_count: row.word_count.max(0) as u32,
+        reading_time_minutes: row.reading_time_minutes.max(0) as u32,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+        author: None,         // 由 `include=author` 展开
+        comment_count: None,  // 由 `include=counts` 展开
+        favorite_count: None,
+    }
+}
+
+/// 一篇文章的评论数和收藏数。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PostCounts {
+    pub comment_count: u64,
+    pub favorite_count: u64,
+}
+
+/// 将目录序列化为 JSON 字符串以便存入 `toc_json` 列。
+fn toc_to_json(toc: &[TocEntry]) -> String {
+    serde_json::to_string(toc).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// 根据 Markdown 计算写入时缓存的派生列：渲染后的 HTML、内容哈希、目录、字数、阅读时间和自动摘要。
+fn set_derived_content(post: &mut ActiveModel, content_markdown: &str) {
+    let rendered = render_markdown(content_markdown);
+    post.content_html = Set(Some(rendered.html));
+    post.content_hash = Set(Some(content_hash(content_markdown)));
+    post.toc_json = Set(Some(toc_to_json(&rendered.toc)));
+    let stats = text_stats(content_markdown);
+    post.word_count = Set(stats.word_count as i32);
+    post.reading_time_minutes = Set(stats.reading_time_minutes as i32);
+    post.auto_excerpt = Set(Some(auto_excerpt(content_markdown, EXCERPT_MAX_CHARS)));
+}
 
 
 /// `PostRepository` 提供与 `posts` 表交互的方法。
@@ -106,27 +213,28 @@ impl PostRepository {
     ///
     /// # 返回
     /// 包含创建的文章 `Model` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, req))]
     pub async fn create_post(
         &self,
         req: CreatePostRequest,
         author_id: i32,
     ) -> Result<Model, CustomError> {
         let now = Utc::now().naive_utc();
-        // 将 Option<bool> 转换为 i32 (0 或 1)
-        let is_published_int = if req.is_published.unwrap_or(false) { 1 } else { 0 };
-
-        let active_model = ActiveModel {
+        let mut active_model = ActiveModel {
             title: Set(req.title),
-            content_markdown: Set(req.content_markdown),
             category: Set(req.category),
             author_id: Set(author_id),
-            is_published: Set(is_published_int),
+            is_published: Set(req.is_published.unwrap_or(false)),
             view_count: Set(0), // 新文章初始浏览量为0
             cover_url: Set(req.cover_url), // Option<String> 直接 Set
+            excerpt: Set(req.excerpt.filter(|e| !e.trim().is_empty())), // 空摘要视为未填写
             created_at: Set(now),
             updated_at: Set(now),
             ..Default::default()
         };
+        // 写入时渲染 HTML 并记录内容哈希，读取时无需重复渲染
+        set_derived_content(&mut active_model, &req.content_markdown);
+        active_model.content_markdown = Set(req.content_markdown);
 
         let post = active_model.insert(&self.db).await?;
         Ok(post)
@@ -139,6 +247,7 @@ impl PostRepository {
     ///
     /// # 返回
     /// 包含 `Option<Model>` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self))]
     pub async fn get_post_by_id(&self, post_id: i32) -> Result<Option<Model>, CustomError> {
         let post = Entity::find_by_id(post_id).one(&self.db).await?;
         Ok(post)
@@ -152,28 +261,40 @@ impl PostRepository {
     ///
     /// # 返回
     /// 包含更新后的文章 `Model` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, req))]
     pub async fn update_post(
         &self,
         post_id: i32,
         req: UpdatePostRequest,
     ) -> Result<Model, CustomError> {
         let post = Entity::find_by_id(post_id).one(&self.db).await?;
-        let mut post: ActiveModel = match post {
-            Some(p) => p.into_active_model(),
-            None => return Err(CustomError::NotFound(format!("ID为 {} 的文章未找到", post_id))),
+        let (mut post, old_hash, content_markdown) = match post {
+            Some(p) => {
+                // 缓存缺失（旧数据）时视为过期，借本次更新补齐
+                let old_hash = p.content_html.as_ref()
+                    .and(p.toc_json.as_ref())
+                    .and(p.auto_excerpt.as_ref())
+                    .and(p.content_hash.clone());
+                let content_markdown = p.content_markdown.clone();
+                (p.into_active_model(), old_hash, content_markdown)
+            }
+            None => return Err(CustomError::coded(ErrorCode::PostNotFound, t_args("post-not-found", &[("id", &post_id)]))),
         };
 
         if let Some(title) = req.title {
             post.title = Set(title);
         }
-        if let Some(content_markdown) = req.content_markdown {
-            post.content_markdown = Set(content_markdown);
+        let content_markdown = req.content_markdown.unwrap_or(content_markdown);
+        // 仅当内容实际发生变化或缓存过期时才重新渲染
+        if old_hash.as_deref() != Some(content_hash(&content_markdown).as_str()) {
+            set_derived_content(&mut post, &content_markdown);
         }
+        post.content_markdown = Set(content_markdown);
         if let Some(category) = req.category {
             post.category = Set(category);
         }
         if let Some(is_published) = req.is_published {
-            post.is_published = Set(if is_published { 1 } else { 0 });
+            post.is_published = Set(is_published);
         }
         // 对于 Option<String> 类型的 cover_url，如果 req.cover_url 是 Some(value)，则更新为 Some(value)；
         // 如果 req.cover_url 是 None，则表示不更新该字段，保持数据库现有值。
@@ -183,6 +304,10 @@ impl PostRepository {
             post.cover_url = Set(Some(cover_url_val));
         }
         // 如果 req.cover_url 为 None，则不更新 cover_url 字段，保持数据库现有值。
+        // 摘要：空字符串表示清除自定义摘要，列表中恢复使用自动摘要
+        if let Some(excerpt) = req.excerpt {
+            post.excerpt = Set(Some(excerpt).filter(|e| !e.trim().is_empty()));
+        }
 
         post.updated_at = Set(Utc::now().naive_utc());
 
@@ -197,10 +322,11 @@ impl PostRepository {
     ///
     /// # 返回
     /// 表示成功或失败的 `Result`。
+    #[instrument(skip(self))]
     pub async fn delete_post(&self, post_id: i32) -> Result<(), CustomError> {
         let result = Entity::delete_by_id(post_id).exec(&self.db).await?;
         if result.rows_affected == 0 {
-            return Err(CustomError::NotFound(format!("ID为 {} 的文章未找到或已被删除", post_id)));
+            return Err(CustomError::coded(ErrorCode::PostNotFound, t_args("post-not-found-or-deleted", &[("id", &post_id)])));
         }
         Ok(())
     }
@@ -212,6 +338,7 @@ impl PostRepository {
     ///
     /// # 返回
     /// 表示成功删除行数的 `Result`。
+    #[instrument(skip(self))]
     pub async fn delete_posts_by_ids(&self, post_ids: Vec<i32>) -> Result<u64, CustomError> {
         let result = Entity::delete_many()
             .filter(Column::Id.is_in(post_ids))
@@ -223,71 +350,288 @@ impl PostRepository {
     /// 检索文章分页列表（带筛选和搜索功能）。
     ///
     /// # 参数
-    /// * `req` - `PostListRequest`，包含分页、分类和搜索查询参数。
+    /// * `req` - `PostListRequest`，包含分页、排序、过滤和搜索查询参数。
     ///
     /// # 返回
-    /// 包含 `(Vec<PostInfo>, total_pages, current_page, total_posts)` 元组或 `CustomError` 的 `Result`。
+    /// 包含 `(Vec<PostSummary>, total_pages, current_page, total_posts)` 元组或 `CustomError` 的 `Result`。
+    #[instrument(skip(self))]
     pub async fn get_paginated_posts(
         &self,
         req: PostListRequest,
-    ) -> Result<(Vec<PostInfo>, u64, u64, u64), CustomError> {
+    ) -> Result<(Vec<PostSummary>, u64, u64, u64), CustomError> {
         // req.page 和 req.limit 已经在 DTO 中处理了默认值，所以这里直接 unwrap
         let page = req.page.unwrap_or(1);
         let page_size = req.limit.unwrap_or(10);
 
-        let mut select = Entity::find();
+        let paginator = sorted(filtered(&req), req.sort.unwrap_or_default())
+            .into_partial_model::<SummaryRow>()
+            .paginate(&self.db, page_size);
 
-        // 根据分类过滤
-        if let Some(category) = req.category {
-            select = select.filter(Column::Category.eq(category));
+        let total_posts = paginator.num_items().await?;
+        let total_pages = paginator.num_pages().await?;
+
+        // `fetch_page` 接收 0-indexed 的页码，所以需要 `saturating_sub(1)`
+        let posts = paginator.fetch_page(page.saturating_sub(1)).await?
+            .into_iter()
+            .map(convert_summary_row_to_post_summary)
+            .collect();
+
+        Ok((posts, total_pages, page, total_posts))
+    }
+
+    /// 按游标检索文章列表，过滤条件与 `get_paginated_posts` 相同，按 `(created_at, id)` 倒序。
+    ///
+    /// # 参数
+    /// * `req` - 过滤条件，其中的分页参数不使用。
+    /// * `cursor` - 上一次返回的游标，第一页为 `None`。
+    /// * `limit` - 每页数量。
+    ///
+    /// # 返回
+    /// 一页 `PostSummary` 及前后页的游标。
+    #[instrument(skip(self))]
+    pub async fn get_posts_by_cursor(
+        &self,
+        req: &PostListRequest,
+        cursor: Option<&Cursor>,
+        limit: u64,
+    ) -> Result<CursorPage<PostSummary>, CustomError> {
+        let rows = keyset_select(filtered(req), Column::CreatedAt, Column::Id, cursor, limit)
+            .into_partial_model::<SummaryRow>()
+            .all(&self.db)
+            .await?;
+        Ok(keyset_page(rows, limit, cursor, |post| (post.created_at, post.id))
+            .map(convert_summary_row_to_post_summary))
+    }
+
+    /// 批量查询文章作者的公开信息。
+    ///
+    /// # 参数
+    /// * `author_ids` - 一页文章的作者 ID，可以有重复。
+    ///
+    /// # 返回
+    /// 作者 ID 到 `AuthorInfo` 的映射。
+    #[instrument(skip(self))]
+    pub async fn get_authors(&self, author_ids: &[i32]) -> Result<HashMap<i32, AuthorInfo>, CustomError> {
+        user::get_authors_by_ids(&self.db, author_ids).await
+    }
+
+    /// 批量统计文章的评论数和收藏数，评论和收藏各用一条 `GROUP BY` 查询。
+    ///
+    /// # 参数
+    /// * `post_ids` - 文章 ID 列表。
+    ///
+    /// # 返回
+    /// 文章 ID 到 `PostCounts` 的映射，没有评论和收藏的文章不在其中。
+    #[instrument(skip(self))]
+    pub async fn get_post_counts(&self, post_ids: &[i32]) -> Result<HashMap<i32, PostCounts>, CustomError> {
+        let mut counts: HashMap<i32, PostCounts> = HashMap::new();
+        if post_ids.is_empty() {
+            return Ok(counts);
         }
 
-        // 根据搜索查询过滤
-        if let Some(query) = req.query {
-            // 使用 LIKE 进行模糊匹配，可以同时搜索标题和内容
-            select = select.filter(
-                Column::Title.like(format!("%{}%", query))
-                    .or(Column::ContentMarkdown.like(format!("%{}%", query)))
-            );
+        let comments: Vec<(i32, i64)> = comment::Entity::find()
+            .select_only()
+            .column(comment::Column::PostId)
+            .column_as(comment::Column::Id.count(), "count")
+            .filter(comment::Column::PostId.is_in(post_ids.to_vec()))
+            .group_by(comment::Column::PostId)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        for (post_id, count) in comments {
+            counts.entry(post_id).or_default().comment_count = count as u64;
         }
 
-        // 根据作者ID过滤
-        if let Some(author_id) = req.author_id {
-            select = select.filter(Column::AuthorId.eq(author_id));
+        let favorites: Vec<(i32, i64)> = favorite::Entity::find()
+            .select_only()
+            .column(favorite::Column::PostId)
+            .column_as(favorite::Column::UserId.count(), "count")
+            .filter(favorite::Column::PostId.is_in(post_ids.to_vec()))
+            .group_by(favorite::Column::PostId)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        for (post_id, count) in favorites {
+            counts.entry(post_id).or_default().favorite_count = count as u64;
         }
+        Ok(counts)
+    }
+
+    /// 统计符合过滤条件的文章数量。
+    #[instrument(skip(self))]
+    pub async fn count_posts(&self, req: &PostListRequest) -> Result<u64, CustomError> {
+        Ok(filtered(req).count(&self.db).await?)
+    }
 
-        // 仅获取已发布的文章 (如果指定)
-        // req.published_only 默认值在 DTO 中已经处理，这里直接 unwrap
-        if req.published_only.unwrap_or(true) { 
-            select = select.filter(Column::IsPublished.eq(1));
+    /// 获取最新发布的文章（用于订阅源输出）。
+    ///
+    /// 只返回已发布的文章，按创建时间倒序排列。
+    ///
+    /// # 参数
+    /// * `category` - 可选，只返回该分类下的文章。
+    /// * `author_id` - 可选，只返回该作者的文章。
+    /// * `limit` - 最多返回的文章数量。
+    ///
+    /// # 返回
+    /// 包含文章 `Vec<Model>` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self))]
+    pub async fn get_recent_published_posts(
+        &self,
+        category: Option<String>,
+        author_id: Option<i32>,
+        limit: u64,
+    ) -> Result<Vec<Model>, CustomError> {
+        let mut select = Entity::find().filter(Column::IsPublished.eq(true));
+
+        if let Some(category) = category {
+            select = select.filter(Column::Category.eq(category));
+        }
+        if let Some(author_id) = author_id {
+            select = select.filter(Column::AuthorId.eq(author_id));
         }
 
-        // 按创建时间倒序排序
-        select = select.order_by_desc(Column::CreatedAt);
+        let posts = select
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await?;
+        Ok(posts)
+    }
 
-        let paginator = select.paginate(&self.db, page_size);
+    /// 统计已发布文章的数量（用于站点地图分页）。
+    #[instrument(skip(self))]
+    pub async fn count_published_posts(&self) -> Result<u64, CustomError> {
+        let count = Entity::find()
+            .filter(Column::IsPublished.eq(true))
+            .count(&self.db)
+            .await?;
+        Ok(count)
+    }
 
-        let total_posts = paginator.num_items().await?;
-        let total_pages = paginator.num_pages().await?;
+    /// 获取已发布文章的 ID 和最后更新时间（用于站点地图）。
+    ///
+    /// 只查询站点地图需要的两列，按 ID 升序排列以保证分页稳定。
+    ///
+    /// # 参数
+    /// * `offset` - 跳过的文章数量。
+    /// * `limit` - 最多返回的文章数量。
+    ///
+    /// # 返回
+    /// 包含 `(post_id, updated_at)` 列表或 `CustomError` 的 `Result`。
+    #[instrument(skip(self))]
+    pub async fn get_published_post_lastmods(
+        &self,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<(i32, NaiveDateTime)>, CustomError> {
+        let rows = Entity::find()
+            .select_only()
+            .column(Column::Id)
+            .column(Column::UpdatedAt)
+            .filter(Column::IsPublished.eq(true))
+            .order_by_asc(Column::Id)
+            .offset(offset)
+            .limit(limit)
+            .into_tuple::<(i32, NaiveDateTime)>()
+            .all(&self.db)
+            .await?;
+        Ok(rows)
+    }
 
-        // `fetch_page` 接收 0-indexed 的页码，所以需要 `saturating_sub(1)`
-        let posts = paginator.fetch_page(page.saturating_sub(1)).await?
-            .into_iter()
-            .map(|post_model| PostInfo {
-                id: post_model.id,
-                title: post_model.title,
-                content_markdown: post_model.content_markdown,
-                category: post_model.category,
-                author_id: post_model.author_id,
-                is_published: post_model.is_published == 1, // 数据库中 1/0 转换为 bool
-                view_count: post_model.view_count,
-                cover_url: post_model.cover_url,
-                created_at: post_model.created_at,
-                updated_at: post_model.updated_at,
-            })
-            .collect();
+    /// 获取包含已发布文章的分类，以及每个分类下文章的最后更新时间。
+    ///
+    /// # 返回
+    /// 包含 `(category, lastmod)` 列表或 `CustomError` 的 `Result`，按分类名排序。
+    #[instrument(skip(self))]
+    pub async fn get_published_category_lastmods(&self) -> Result<Vec<(String, NaiveDateTime)>, CustomError> {
+        let rows = Entity::find()
+            .select_only()
+            .column(Column::Category)
+            .column_as(Expr::col(Column::UpdatedAt).max(), "lastmod")
+            .filter(Column::IsPublished.eq(true))
+            .group_by(Column::Category)
+            .order_by_asc(Column::Category)
+            .into_tuple::<(String, NaiveDateTime)>()
+            .all(&self.db)
+            .await?;
+        Ok(rows)
+    }
 
-        Ok((posts, total_pages, page, total_posts))
+    /// 获取发布过文章的作者，以及每位作者文章的最后更新时间。
+    ///
+    /// # 返回
+    /// 包含 `(author_id, lastmod)` 列表或 `CustomError` 的 `Result`，按作者 ID 排序。
+    #[instrument(skip(self))]
+    pub async fn get_published_author_lastmods(&self) -> Result<Vec<(i32, NaiveDateTime)>, CustomError> {
+        let rows = Entity::find()
+            .select_only()
+            .column(Column::AuthorId)
+            .column_as(Expr::col(Column::UpdatedAt).max(), "lastmod")
+            .filter(Column::IsPublished.eq(true))
+            .group_by(Column::AuthorId)
+            .order_by_asc(Column::AuthorId)
+            .into_tuple::<(i32, NaiveDateTime)>()
+            .all(&self.db)
+            .await?;
+        Ok(rows)
+    }
+
+    /// 获取文章渲染后的 HTML 和目录。
+    ///
+    /// 优先使用写入时缓存的 `content_html` / `toc_json`；如果缓存缺失（旧数据）或内容哈希不匹配
+    /// （内容被直接修改），则在内存中重新渲染。读取路径不回写数据库，缓存在下次创建或更新时刷新。
+    ///
+    /// # 参数
+    /// * `post` - 文章 `Model`。
+    ///
+    /// # 返回
+    /// 包含 `RenderedMarkdown` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, post), fields(post_id = post.id))]
+    pub async fn get_rendered_content(&self, post: &Model) -> Result<RenderedMarkdown, CustomError> {
+        let hash = content_hash(&post.content_markdown);
+        if let (Some(html), Some(cached_hash), Some(toc_json)) =
+            (&post.content_html, &post.content_hash, &post.toc_json)
+        {
+            if *cached_hash == hash {
+                if let Ok(toc) = serde_json::from_str::<Vec<TocEntry>>(toc_json) {
+                    return Ok(RenderedMarkdown { html: html.clone(), toc });
+                }
+            }
+        }
+
+        Ok(render_markdown(&post.content_markdown))
+    }
+
+    /// 按当前的渲染规则重新生成全部文章的派生列（HTML、内容哈希、目录、字数、阅读时间和自动摘要）。
+    /// 渲染或摘要规则变化后由 `blogctl search reindex` 调用，不修改文章的更新时间。
+    ///
+    /// # 返回
+    /// 包含处理的文章数量或 `CustomError` 的 `Result`。
+    #[instrument(skip(self))]
+    pub async fn reindex_all(&self) -> Result<u64, CustomError> {
+        const BATCH_SIZE: u64 = 200;
+        let mut last_id = 0;
+        let mut count = 0;
+        loop {
+            let posts = Entity::find()
+                .filter(Column::Id.gt(last_id))
+                .order_by_asc(Column::Id)
+                .limit(BATCH_SIZE)
+                .all(&self.db)
+                .await?;
+            let Some(last) = posts.last() else { break };
+            last_id = last.id;
+
+            for post in posts {
+                let content_markdown = post.content_markdown.clone();
+                let mut active_model = post.into_active_model();
+                set_derived_content(&mut active_model, &content_markdown);
+                active_model.update(&self.db).await?;
+                count += 1;
+            }
+        }
+        Ok(count)
     }
 
     /// 增加文章的浏览量。
@@ -297,6 +641,7 @@ impl PostRepository {
     ///
     /// # 返回
     /// 表示操作成功或失败的 `Result`。
+    #[instrument(skip(self))]
     pub async fn increment_view_count(&self, post_id: i32) -> Result<(), CustomError> {
         let post = Entity::find_by_id(post_id).one(&self.db).await?;
         if let Some(model) = post {
@@ -306,7 +651,81 @@ impl PostRepository {
             active_model.update(&self.db).await?;
             Ok(())
         } else {
-            Err(CustomError::NotFound(format!("ID为 {} 的文章未找到", post_id)))
+            Err(CustomError::coded(ErrorCode::PostNotFound, t_args("post-not-found", &[("id", &post_id)])))
+        }
+    }
+}
+
+/// 文章列表的过滤条件：分类、搜索关键词、作者和发布状态。
+fn filtered(req: &PostListRequest) -> Select<Entity> {
+    let mut select = Entity::find();
+
+    // 根据分类过滤，逗号分隔的多个分类之间是"或"的关系
+    if let Some(category) = &req.category {
+        let categories: Vec<&str> = category.split(',').map(str::trim).filter(|c| !c.is_empty()).collect();
+        if !categories.is_empty() {
+            select = select.filter(Column::Category.is_in(categories));
         }
     }
+
+    // 按创建时间范围过滤
+    if let Some(from) = req.from {
+        select = select.filter(Column::CreatedAt.gte(from.naive_utc()));
+    }
+    if let Some(to) = req.to {
+        select = select.filter(Column::CreatedAt.lt(to.naive_utc()));
+    }
+
+    // 按是否有封面过滤，空字符串视为没有封面
+    match req.has_cover {
+        Some(true) => select = select.filter(Column::CoverUrl.is_not_null().and(Column::CoverUrl.ne(""))),
+        Some(false) => select = select.filter(Column::CoverUrl.is_null().or(Column::CoverUrl.eq(""))),
+        None => {}
+    }
+
+    // 根据搜索查询过滤
+    if let Some(query) = &req.query {
+        // 使用 LIKE 进行模糊匹配，可以同时搜索标题和内容
+        // 两边都转为小写，使各数据库下都不区分大小写 (PostgreSQL 的 LIKE 区分大小写)
+        let pattern = format!("%{}%", query.to_lowercase());
+        select = select.filter(
+            Expr::expr(Func::lower(Expr::col(Column::Title))).like(pattern.clone())
+                .or(Expr::expr(Func::lower(Expr::col(Column::ContentMarkdown))).like(pattern))
+        );
+    }
+
+    // 根据作者ID过滤
+    if let Some(author_id) = req.author_id {
+        select = select.filter(Column::AuthorId.eq(author_id));
+    }
+
+    // 仅获取已发布的文章 (如果指定)
+    // req.published_only 默认值在 DTO 中已经处理，这里直接 unwrap
+    if req.published_only.unwrap_or(true) {
+        select = select.filter(Column::IsPublished.eq(true));
+    }
+
+    select
+}
+
+/// 按 `sort` 参数排序，用于 page / limit 分页；排序值相同时按创建时间和 ID 倒序，保证翻页稳定。
+fn sorted(select: Select<Entity>, sort: PostSort) -> Select<Entity> {
+    // 收藏数 / 评论数用关联子查询统计，不需要 GROUP BY
+    let count_of = |table: &str| -> SimpleExpr {
+        let subquery = Query::select()
+            .expr(Expr::col(Alias::new("post_id")).count())
+            .from(Alias::new(table))
+            .and_where(Expr::col((Alias::new(table), Alias::new("post_id"))).equals((Entity, Column::Id)))
+            .to_owned();
+        SimpleExpr::SubQuery(None, Box::new(subquery.into_sub_query_statement()))
+    };
+    let select = match sort {
+        PostSort::Newest => select,
+        PostSort::Oldest => return select.order_by_asc(Column::CreatedAt).order_by_asc(Column::Id),
+        PostSort::MostViewed => select.order_by_desc(Column::ViewCount),
+        PostSort::MostFavorited => select.order_by(count_of("favorites"), Order::Desc),
+        PostSort::MostCommented => select.order_by(count_of("comments"), Order::Desc),
+        PostSort::RecentlyUpdated => select.order_by_desc(Column::UpdatedAt),
+    };
+    select.order_by_desc(Column::CreatedAt).order_by_desc(Column::Id)
 }
@@ -4,6 +4,7 @@
 // --- SeaORM 实体定义 ---
 use sea_orm::entity::prelude::*;
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize}; // 用于 blogctl 导出 / 导入
 // 引入 IntoActiveModel trait，以便使用 Model::into_active_model() 方法
 use sea_orm::IntoActiveModel;
 // 引入 ActiveModelBehavior trait，虽然通常由 prelude 覆盖，但明确引入有时有帮助
@@ -15,7 +16,7 @@ use sea_orm::ActiveModelBehavior;
 /// 此结构体直接映射到数据库中 `users` 表的列。
 /// `created_at` 和 `updated_at` 字段使用 `chrono::NaiveDateTime` 类型，
 /// 以匹配 SQLite 数据库中日期使用的 `TEXT` 格式，并结合 SeaORM 的 `with-chrono` 特性。
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "users")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -26,6 +27,8 @@ pub struct Model {
     pub email: String,
     pub password_hash: String, // 存储哈希后的密码
     pub role: String, // 'user' 或 'admin'
+    pub display_name: Option<String>, // 显示名称，未设置时显示用户名
+    pub avatar_url: Option<String>,   // 头像地址
     pub created_at: NaiveDateTime, // 数据库默认自动填充
     pub updated_at: NaiveDateTime, // 数据库默认自动填充
 }
@@ -73,8 +76,10 @@ impl ActiveModelBehavior for ActiveModel {}
 
 
 // --- UserRepository (数据库操作) ---
+use std::collections::HashMap;
+
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
     PaginatorTrait, QuerySelect
 };
 // 导入 `anyhow::Result` 已经足够，不需要单独的 `bail`，可以直接用 `?`
@@ -83,10 +88,12 @@ use chrono::Utc;
 
 // 从 handler 模块导入 DTO
 use crate::handler::idl::{
-    CreateUserByAdminRequest, RegisterRequest, UpdateMyProfileRequest, UpdateUserRequest, UserInfo,
+    AuthorInfo, CreateUserByAdminRequest, RegisterRequest, UpdateMyProfileRequest, UpdateUserRequest, UserInfo,
 };
 // 导入自定义错误类型
-use crate::error::CustomError;
+use crate::error::{CustomError, ErrorCode};
+use crate::i18n::t;
+use tracing::instrument;
 
 
 /// `UserRepository` 提供与 `users` 表交互的方法。
@@ -112,6 +119,7 @@ impl UserRepository {
     ///
     /// # 返回
     /// 包含创建的用户 `Model` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, req, password_hash), fields(username = %req.username))]
     pub async fn create_user_from_register(
         &self,
         req: RegisterRequest,
@@ -142,6 +150,7 @@ impl UserRepository {
     ///
     /// # 返回
     /// 包含创建的用户 `Model` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, req, password_hash), fields(username = %req.username))]
     pub async fn create_user_by_admin(
         &self,
         req: CreateUserByAdminRequest,
@@ -169,6 +178,7 @@ impl UserRepository {
     ///
     /// # 返回
     /// 包含 `Option<Model>` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self))]
     pub async fn get_user_by_id(&self, user_id: i32) -> Result<Option<Model>, CustomError> { // 使用 CustomError
         let user = Entity::find_by_id(user_id).one(&self.db).await?;
         Ok(user)
@@ -182,6 +192,7 @@ impl UserRepository {
     ///
     /// # 返回
     /// 包含 `Option<Model>` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, username_or_email))]
     pub async fn get_user_by_username_or_email(
         &self,
         username_or_email: &str,
@@ -206,6 +217,7 @@ impl UserRepository {
     ///
     /// # 返回
     /// 包含更新后的用户 `Model` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, req, new_password_hash))]
     pub async fn update_my_profile(
         &self,
         user_id: i32,
@@ -215,7 +227,7 @@ impl UserRepository {
         let user = Entity::find_by_id(user_id).one(&self.db).await?;
         let mut user: ActiveModel = match user {
             Some(u) => u.into_active_model(),
-            None => return Err(CustomError::NotFound("用户未找到".to_string())), // 使用 CustomError::NotFound
+            None => return Err(CustomError::coded(ErrorCode::UserNotFound, t("user-not-found")))
         };
 
         if let Some(username) = req.username {
@@ -227,6 +239,13 @@ impl UserRepository {
         if let Some(password_hash) = new_password_hash {
             user.password_hash = Set(password_hash);
         }
+        // 空字符串表示清除
+        if let Some(display_name) = req.display_name {
+            user.display_name = Set(Some(display_name).filter(|s| !s.trim().is_empty()));
+        }
+        if let Some(avatar_url) = req.avatar_url {
+            user.avatar_url = Set(Some(avatar_url).filter(|s| !s.is_empty()));
+        }
         user.updated_at = Set(Utc::now().naive_utc()); // 更新时间戳
 
         let updated_user = user.update(&self.db).await?;
@@ -242,6 +261,7 @@ impl UserRepository {
     ///
     /// # 返回
     /// 包含更新后的用户 `Model` 或 `CustomError` 的 `Result`。
+    #[instrument(skip(self, req, new_password_hash))]
     pub async fn update_user_by_admin(
         &self,
         user_id: i32,
@@ -251,7 +271,7 @@ impl UserRepository {
         let user = Entity::find_by_id(user_id).one(&self.db).await?;
         let mut user: ActiveModel = match user {
             Some(u) => u.into_active_model(),
-            None => return Err(CustomError::NotFound("用户未找到".to_string())), // 使用 CustomError::NotFound
+            None => return Err(CustomError::coded(ErrorCode::UserNotFound, t("user-not-found")))
         };
 
         if let Some(username) = req.username {
@@ -266,7 +286,7 @@ impl UserRepository {
         if let Some(role) = req.role {
             // 基本角色验证：确保是 'user' 或 'admin'
             if role != "user" && role != "admin" {
-                return Err(CustomError::BadRequest("指定的角色无效。必须是 'user' 或 'admin'。".to_string())); // 使用 CustomError::BadRequest
+                return Err(CustomError::BadRequest(t("role-invalid"))); // 使用 CustomError::BadRequest
             }
             user.role = Set(role);
         }
@@ -283,10 +303,11 @@ impl UserRepository {
     ///
     /// # 返回
     /// 表示成功或失败的 `Result`。
+    #[instrument(skip(self))]
     pub async fn delete_user(&self, user_id: i32) -> Result<(), CustomError> { // 使用 CustomError
         let result = Entity::delete_by_id(user_id).exec(&self.db).await?;
         if result.rows_affected == 0 {
-            return Err(CustomError::NotFound("用户未找到或已被删除".to_string())); // 使用 CustomError::NotFound
+            return Err(CustomError::coded(ErrorCode::UserNotFound, t("user-not-found-or-deleted")));
         }
         Ok(())
     }
@@ -298,6 +319,7 @@ impl UserRepository {
     ///
     /// # 返回
     /// 表示成功删除行数的 `Result`。
+    #[instrument(skip(self))]
     pub async fn delete_users_by_ids(&self, user_ids: Vec<i32>) -> Result<u64, CustomError> { // 使用 CustomError
         let result = Entity::delete_many()
             .filter(Column::Id.is_in(user_ids))
@@ -306,6 +328,25 @@ impl UserRepository {
         Ok(result.rows_affected)
     }
 
+    /// 根据 ID 列表批量检索用户。
+    ///
+    /// # 参数
+    /// * `user_ids` - 要检索的用户 ID 列表。
+    ///
+    /// # 返回
+    /// 包含找到的用户 `Vec<Model>` 或 `CustomError` 的 `Result`（不存在的 ID 会被忽略）。
+    #[instrument(skip(self))]
+    pub async fn get_users_by_ids(&self, user_ids: Vec<i32>) -> Result<Vec<Model>, CustomError> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let users = Entity::find()
+            .filter(Column::Id.is_in(user_ids))
+            .all(&self.db)
+            .await?;
+        Ok(users)
+    }
+
     /// 检索用户分页列表。（通常仅限管理员访问）
     ///
     /// # 参数
@@ -314,6 +355,7 @@ impl UserRepository {
     ///
     /// # 返回
     /// 包含 `(Vec<UserInfo>, total_pages, current_page, total_users)` 元组或 `CustomError` 的 `Result`。
+    #[instrument(skip(self))]
     pub async fn get_all_users(
         &self,
         page: u64,
@@ -333,6 +375,8 @@ impl UserRepository {
                 username: user_model.username,
                 email: user_model.email,
                 role: user_model.role,
+                display_name: user_model.display_name,
+                avatar_url: user_model.avatar_url,
                 created_at: user_model.created_at,
             })
             .collect();
@@ -340,3 +384,41 @@ impl UserRepository {
         Ok((users, total_pages, page, total_users))
     }
 }
+
+/// 批量查询用户的公开信息，供文章和评论展开作者使用。
+///
+/// 只查询一次 (`id IN (...)`)，并且只读取公开的列。
+///
+/// # 参数
+/// * `db` - 数据库连接。
+/// * `user_ids` - 用户 ID，可以有重复。
+///
+/// # 返回
+/// 用户 ID 到 `AuthorInfo` 的映射，已删除的用户不在其中。
+#[instrument(skip(db))]
+pub async fn get_authors_by_ids(
+    db: &impl ConnectionTrait,
+    user_ids: &[i32],
+) -> Result<HashMap<i32, AuthorInfo>, CustomError> {
+    if user_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let mut ids = user_ids.to_vec();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let rows: Vec<(i32, String, Option<String>, Option<String>)> = Entity::find()
+        .select_only()
+        .columns([Column::Id, Column::Username, Column::DisplayName, Column::AvatarUrl])
+        .filter(Column::Id.is_in(ids))
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, username, display_name, avatar_url)| {
+            let display_name = display_name.unwrap_or_else(|| username.clone());
+            (id, AuthorInfo { id, username, display_name, avatar_url })
+        })
+        .collect())
+}
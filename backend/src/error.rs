@@ -1,16 +1,144 @@
 //! 定义应用程序的自定义错误类型及其 Axum 响应转换。
+//!
+//! 每个错误都带有稳定的错误码 (`ErrorCode`)，客户端应根据 `code` 而不是 `message` 判断错误类型。
+//! 默认返回 `{success: false, code, message}` 形式的 JSON；请求的 `Accept` 头偏好
+//! `application/problem+json` 时返回 RFC 7807 格式（由 `handler::problem` 中间件协商）。
+
+use std::future::Future;
 
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Serialize;
 use serde_json::json;
 use tracing::error; // 引入 error 宏
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::i18n::{t, t_args};
+use crate::telemetry::current_request_id;
 
 /// 定义一个通用结果类型，所有返回 Result 的函数都应使用此类型。
 pub type CustomResult<T> = Result<T, CustomError>;
 
+/// RFC 7807 问题详情的媒体类型。
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// 机器可读的错误码，序列化为 `SCREAMING_SNAKE_CASE` 字符串（例如 `POST_NOT_FOUND`）。
+///
+/// 已发布的错误码不应改名或删除；新增错误场景时在此添加新的错误码。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    BadRequest,
+    ValidationFailed,
+    InvalidCursor,
+    UsernameTaken,
+    EmailTaken,
+    Unauthorized,
+    AuthTokenMissing,
+    AuthTokenInvalid,
+    AuthTokenExpired,
+    AuthInvalidCredentials,
+    Forbidden,
+    NotFound,
+    UserNotFound,
+    PostNotFound,
+    CommentNotFound,
+    MediaNotFound,
+    PayloadTooLarge,
+    MediaQuotaExceeded,
+    UnsupportedMediaType,
+    InternalError,
+    DatabaseError,
+}
+
+impl ErrorCode {
+    /// 全部错误码，用于生成 API 文档。
+    pub const ALL: [ErrorCode; 21] = [
+        ErrorCode::BadRequest,
+        ErrorCode::ValidationFailed,
+        ErrorCode::InvalidCursor,
+        ErrorCode::UsernameTaken,
+        ErrorCode::EmailTaken,
+        ErrorCode::Unauthorized,
+        ErrorCode::AuthTokenMissing,
+        ErrorCode::AuthTokenInvalid,
+        ErrorCode::AuthTokenExpired,
+        ErrorCode::AuthInvalidCredentials,
+        ErrorCode::Forbidden,
+        ErrorCode::NotFound,
+        ErrorCode::UserNotFound,
+        ErrorCode::PostNotFound,
+        ErrorCode::CommentNotFound,
+        ErrorCode::MediaNotFound,
+        ErrorCode::PayloadTooLarge,
+        ErrorCode::MediaQuotaExceeded,
+        ErrorCode::UnsupportedMediaType,
+        ErrorCode::InternalError,
+        ErrorCode::DatabaseError,
+    ];
+
+    /// 错误码的字符串形式，与 JSON 中的 `code` 字段一致。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::BadRequest => "BAD_REQUEST",
+            ErrorCode::ValidationFailed => "VALIDATION_FAILED",
+            ErrorCode::InvalidCursor => "INVALID_CURSOR",
+            ErrorCode::UsernameTaken => "USERNAME_TAKEN",
+            ErrorCode::EmailTaken => "EMAIL_TAKEN",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::AuthTokenMissing => "AUTH_TOKEN_MISSING",
+            ErrorCode::AuthTokenInvalid => "AUTH_TOKEN_INVALID",
+            ErrorCode::AuthTokenExpired => "AUTH_TOKEN_EXPIRED",
+            ErrorCode::AuthInvalidCredentials => "AUTH_INVALID_CREDENTIALS",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::UserNotFound => "USER_NOT_FOUND",
+            ErrorCode::PostNotFound => "POST_NOT_FOUND",
+            ErrorCode::CommentNotFound => "COMMENT_NOT_FOUND",
+            ErrorCode::MediaNotFound => "MEDIA_NOT_FOUND",
+            ErrorCode::PayloadTooLarge => "PAYLOAD_TOO_LARGE",
+            ErrorCode::MediaQuotaExceeded => "MEDIA_QUOTA_EXCEEDED",
+            ErrorCode::UnsupportedMediaType => "UNSUPPORTED_MEDIA_TYPE",
+            ErrorCode::InternalError => "INTERNAL_ERROR",
+            ErrorCode::DatabaseError => "DATABASE_ERROR",
+        }
+    }
+
+    /// 错误码对应的 HTTP 状态码。
+    pub fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::BadRequest
+            | ErrorCode::ValidationFailed
+            | ErrorCode::InvalidCursor
+            | ErrorCode::UsernameTaken
+            | ErrorCode::EmailTaken => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized
+            | ErrorCode::AuthTokenMissing
+            | ErrorCode::AuthTokenInvalid
+            | ErrorCode::AuthTokenExpired
+            | ErrorCode::AuthInvalidCredentials => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound
+            | ErrorCode::UserNotFound
+            | ErrorCode::PostNotFound
+            | ErrorCode::CommentNotFound
+            | ErrorCode::MediaNotFound => StatusCode::NOT_FOUND,
+            ErrorCode::PayloadTooLarge | ErrorCode::MediaQuotaExceeded => StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorCode::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ErrorCode::InternalError | ErrorCode::DatabaseError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// 应用程序的自定义错误枚举。
 /// 使用 `thiserror` 宏简化错误定义和实现 `std::error::Error` trait。
 #[derive(Debug, thiserror::Error)]
@@ -43,17 +171,104 @@ pub enum CustomError {
     #[error("密码处理错误: {0}")]
     BcryptError(String), // 直接存储字符串，因为 bcrypt::BcryptError 不直接实现 Display
 
-    /// JWT 生成、解析或验证失败。
+    /// JWT 解析或验证失败（过期的令牌使用 `ErrorCode::AuthTokenExpired`）。
     #[error("认证令牌错误: {0}")]
     JwtError(String), // 直接存储字符串，因为 jsonwebtoken::errors::Error 不直接实现 Display
 
-    /// 数据验证失败，由 `validator` 库生成。
+    /// 数据验证失败，由 `validator` 库生成；响应中附带逐字段的错误详情。
     #[error("数据验证失败: {0}")]
     ValidationError(#[from] validator::ValidationErrors), // 使用 `#[from]` 实现自动从 `validator::ValidationErrors` 转换
 
-    // 您可以根据需要添加更多具体的错误类型，例如：
-    // #[error("文件上传失败: {0}")]
-    // FileUploadError(String),
+    /// 查询参数验证失败，例如未知的参数名或无法解析的值。
+    /// 字段名来自请求本身，不是 `&'static str`，因此不使用 `ValidationErrors`。
+    #[error("查询参数无效: {0:?}")]
+    InvalidQuery(Vec<FieldError>),
+
+    /// 请求体过大，例如上传文件超过大小限制或用户存储配额。
+    #[error("请求体过大: {0}")]
+    PayloadTooLarge(String),
+
+    /// 不支持的媒体类型，例如上传了不允许的文件格式。
+    #[error("不支持的媒体类型: {0}")]
+    UnsupportedMediaType(String),
+
+    /// 带有具体错误码的错误，HTTP 状态码由 `ErrorCode::status` 决定。
+    /// 例如 `CustomError::coded(ErrorCode::PostNotFound, "ID为 1 的文章未找到")`。
+    #[error("{message}")]
+    Coded { code: ErrorCode, message: String },
+}
+
+impl CustomError {
+    /// 创建带有具体错误码的错误。
+    pub fn coded(code: ErrorCode, message: impl Into<String>) -> Self {
+        CustomError::Coded { code, message: message.into() }
+    }
+
+    /// 错误码；没有指定具体错误码的变体使用对应的通用错误码。
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            CustomError::InternalServerError(_) | CustomError::BcryptError(_) => ErrorCode::InternalError,
+            CustomError::BadRequest(_) => ErrorCode::BadRequest,
+            CustomError::Unauthorized(_) => ErrorCode::Unauthorized,
+            CustomError::Forbidden(_) => ErrorCode::Forbidden,
+            CustomError::NotFound(_) => ErrorCode::NotFound,
+            CustomError::DbError(_) => ErrorCode::DatabaseError,
+            CustomError::JwtError(_) => ErrorCode::AuthTokenInvalid,
+            CustomError::ValidationError(_) | CustomError::InvalidQuery(_) => ErrorCode::ValidationFailed,
+            CustomError::PayloadTooLarge(_) => ErrorCode::PayloadTooLarge,
+            CustomError::UnsupportedMediaType(_) => ErrorCode::UnsupportedMediaType,
+            CustomError::Coded { code, .. } => *code,
+        }
+    }
+
+    /// HTTP 状态码。
+    pub fn status(&self) -> StatusCode {
+        self.code().status()
+    }
+
+    /// 逐字段的验证错误，只有验证类错误才有。
+    fn field_errors(&self) -> Option<Vec<FieldError>> {
+        match self {
+            CustomError::ValidationError(e) => Some(field_errors(e)),
+            CustomError::InvalidQuery(errors) => Some(errors.clone()),
+            _ => None,
+        }
+    }
+
+    /// 写入日志的错误信息，包含服务器内部错误的具体原因。
+    fn log_message(&self) -> String {
+        match self {
+            CustomError::InternalServerError(msg)
+            | CustomError::BadRequest(msg)
+            | CustomError::Unauthorized(msg)
+            | CustomError::Forbidden(msg)
+            | CustomError::NotFound(msg)
+            | CustomError::PayloadTooLarge(msg)
+            | CustomError::UnsupportedMediaType(msg)
+            | CustomError::Coded { message: msg, .. } => msg.clone(),
+            CustomError::DbError(e) => format!("数据库操作失败: {}", e),
+            CustomError::BcryptError(e) => format!("密码处理错误: {}", e),
+            CustomError::JwtError(e) => format!("认证令牌错误: {}", e),
+            CustomError::ValidationError(e) => format!("数据验证失败: {}", e),
+            CustomError::InvalidQuery(errors) => {
+                let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+                format!("查询参数无效: {}", fields.join(", "))
+            }
+        }
+    }
+
+    /// 返回给客户端的错误信息（按当前请求的语言）；5xx 错误不暴露数据库、密码哈希等内部细节。
+    fn client_message(&self) -> String {
+        match self {
+            _ if self.status().is_server_error() => t("internal-error"),
+            CustomError::JwtError(_) => t("auth-token-invalid"),
+            CustomError::ValidationError(_) | CustomError::InvalidQuery(_) => {
+                let details: Vec<String> = self.field_errors().unwrap_or_default().into_iter().map(|f| f.message).collect();
+                t_args("validation-failed", &[("details", &details.join("; "))])
+            }
+            _ => self.log_message(),
+        }
+    }
 }
 
 // 为 `bcrypt::BcryptError` 实现 `From<bcrypt::BcryptError>` 到 `CustomError` 的转换
@@ -66,42 +281,178 @@ impl From<bcrypt::BcryptError> for CustomError {
 }
 
 // 为 `jsonwebtoken::errors::Error` 实现 `From<jsonwebtoken::errors::Error>` 到 `CustomError` 的转换
-// 同上，因为 `jsonwebtoken::errors::Error` 不直接实现 `std::fmt::Display`。
+// 令牌过期单独使用 `AUTH_TOKEN_EXPIRED`，便于客户端据此刷新登录状态。
 impl From<jsonwebtoken::errors::Error> for CustomError {
     fn from(err: jsonwebtoken::errors::Error) -> Self {
-        CustomError::JwtError(err.to_string())
+        match err.kind() {
+            jsonwebtoken::errors::ErrorKind::ExpiredSignature => {
+                CustomError::coded(ErrorCode::AuthTokenExpired, t("auth-token-expired"))
+            }
+            _ => CustomError::JwtError(format!("JWT令牌无效: {}", err)),
+        }
+    }
+}
+
+/// 单个字段的验证错误，对应响应中 `errors` 数组的一项。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    /// 字段路径，嵌套结构用 `.` 连接，列表元素用 `[下标]`，例如 `items[0].name`。
+    pub field: String,
+    /// 验证规则，例如 `length`、`email`、`range`。
+    pub code: String,
+    pub message: String,
+}
+
+/// 将 `ValidationErrors` 展开为按字段路径排序的错误列表。
+///
+/// 验证规则的 `message` 是消息目录中的 ID，这里翻译为当前请求的语言。
+pub fn field_errors(errors: &ValidationErrors) -> Vec<FieldError> {
+    let mut fields = Vec::new();
+    collect_field_errors("", errors, &mut fields);
+    fields.sort_by(|a, b| a.field.cmp(&b.field));
+    fields
+}
+
+fn collect_field_errors(prefix: &str, errors: &ValidationErrors, out: &mut Vec<FieldError>) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() { field.to_string() } else { format!("{}.{}", prefix, field) };
+        match kind {
+            ValidationErrorsKind::Field(list) => {
+                out.extend(list.iter().map(|e| FieldError {
+                    field: path.clone(),
+                    code: e.code.to_string(),
+                    message: match &e.message {
+                        Some(message) => t(message),
+                        None => default_validation_message(&e.code),
+                    },
+                }));
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(&path, nested, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(&format!("{}[{}]", path, index), nested, out);
+                }
+            }
+        }
+    }
+}
+
+/// 验证规则没有指定 `message` 时使用的默认提示。
+fn default_validation_message(code: &str) -> String {
+    match code {
+        "length" | "range" | "email" | "url" | "required" | "regex" | "must_match" => {
+            t(&format!("validation-default-{}", code))
+        }
+        _ => t("validation-default"),
     }
 }
 
+/// 错误响应的格式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorFormat {
+    /// `{success: false, code, message, ...}`，与其他接口的 `BaseResponse` 一致。
+    #[default]
+    Json,
+    /// RFC 7807 `application/problem+json`。
+    Problem,
+}
+
+impl ErrorFormat {
+    /// 根据 `Accept` 头选择错误响应格式。
+    ///
+    /// `application/problem+json` 的 q 值大于 0 且不低于 `application/json` 时使用 RFC 7807 格式；
+    /// 没有 `Accept` 头或无法解析时使用默认格式。
+    pub fn from_accept(accept: Option<&HeaderValue>) -> Self {
+        let Some(accept) = accept.and_then(|v| v.to_str().ok()) else {
+            return ErrorFormat::Json;
+        };
+        let mut problem_q = 0.0;
+        let mut json_q = 0.0;
+        for range in accept.split(',') {
+            let mut parts = range.split(';').map(str::trim);
+            let media_type = parts.next().unwrap_or_default().to_ascii_lowercase();
+            let q = parts
+                .filter_map(|p| p.strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            match media_type.as_str() {
+                PROBLEM_JSON => problem_q = q,
+                "application/json" => json_q = q,
+                _ => {}
+            }
+        }
+        if problem_q > 0.0 && problem_q >= json_q {
+            ErrorFormat::Problem
+        } else {
+            ErrorFormat::Json
+        }
+    }
+}
+
+tokio::task_local! {
+    /// 当前请求协商出的错误响应格式。
+    static ERROR_FORMAT: ErrorFormat;
+}
+
+/// 在给定错误响应格式的上下文中运行 `future`。
+pub async fn with_error_format<F: Future>(format: ErrorFormat, future: F) -> F::Output {
+    ERROR_FORMAT.scope(format, future).await
+}
+
+/// 当前请求的错误响应格式；不在请求上下文中时为默认格式。
+fn current_error_format() -> ErrorFormat {
+    ERROR_FORMAT.try_with(|f| *f).unwrap_or_default()
+}
 
 /// 为 `CustomError` 实现 `axum::response::IntoResponse` Trait。
 /// 这使得我们可以直接从 Axum 处理函数中返回 `Result<(), CustomError>`，
 /// Axum 会自动将 `CustomError` 转换为 HTTP 响应。
 impl IntoResponse for CustomError {
     fn into_response(self) -> Response {
-        // 根据 CustomError 的变体，确定 HTTP 状态码和具体的错误信息
-        let (status, message) = match self {
-            CustomError::InternalServerError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-            CustomError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            CustomError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
-            CustomError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
-            CustomError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            CustomError::DbError(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("数据库操作失败: {}", e)),
-            CustomError::BcryptError(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("密码处理错误: {}", e)),
-            CustomError::JwtError(e) => (StatusCode::UNAUTHORIZED, format!("认证令牌错误: {}", e)),
-            CustomError::ValidationError(e) => (StatusCode::BAD_REQUEST, format!("数据验证失败: {}", e)),
-        };
+        let code = self.code();
+        let status = code.status();
+        let message = self.client_message();
 
-        // 记录错误日志，便于调试
-        error!("API 错误: 状态码={}, 消息='{}'", status, message);
+        // 记录错误日志，便于调试 (输出前会经过 logging::redact 脱敏)；内部错误的具体原因只出现在日志中
+        error!(status = status.as_u16(), code = code.as_str(), error = %self.log_message(), "API 错误");
 
-        // 构建 JSON 响应体，遵循 BaseResponse 格式
-        let body = Json(json!({
-            "success": false,
-            "message": message,
-        }));
+        let errors = self.field_errors();
+        // 在请求上下文中时附带请求 ID，便于与日志对应
+        let request_id = current_request_id().filter(|id| !id.is_empty());
 
-        // 返回 HTTP 响应
-        (status, body).into_response()
+        match current_error_format() {
+            ErrorFormat::Json => {
+                // 遵循 BaseResponse 格式
+                let mut body = json!({
+                    "success": false,
+                    "code": code,
+                    "message": message,
+                });
+                if let Some(errors) = errors {
+                    body["errors"] = json!(errors);
+                }
+                if let Some(request_id) = request_id {
+                    body["request_id"] = request_id.into();
+                }
+                (status, Json(body)).into_response()
+            }
+            ErrorFormat::Problem => {
+                // 没有为错误码定义单独的说明文档，按 RFC 7807 使用 about:blank，title 为状态码短语
+                let mut body = json!({
+                    "type": "about:blank",
+                    "title": status.canonical_reason().unwrap_or_default(),
+                    "status": status.as_u16(),
+                    "detail": message,
+                    "code": code,
+                });
+                if let Some(errors) = errors {
+                    body["errors"] = json!(errors);
+                }
+                if let Some(request_id) = request_id {
+                    body["request_id"] = request_id.into();
+                }
+                (status, [(header::CONTENT_TYPE, PROBLEM_JSON)], body.to_string()).into_response()
+            }
+        }
     }
 }
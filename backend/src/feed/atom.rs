@@ -0,0 +1,60 @@
+// src/feed/atom.rs
+//! Atom 1.0 订阅源生成。
+
+use atom_syndication::{Category, Content, Entry, Feed, Link, Person, Text};
+
+use super::{to_utc, FeedChannel, FeedItem};
+
+/// 构造一个 Atom 链接。
+fn link(href: &str, rel: &str) -> Link {
+    let mut link = Link::default();
+    link.set_href(href);
+    link.set_rel(rel);
+    link
+}
+
+/// 生成 Atom 1.0 文档。
+///
+/// 摘要写入 `<summary>`；完整模式下正文以 HTML 写入 `<content>`。
+pub fn build_atom(channel: &FeedChannel, items: &[FeedItem]) -> String {
+    let entries: Vec<Entry> = items
+        .iter()
+        .map(|item| {
+            let mut entry = Entry::default();
+            entry.set_id(item.guid());
+            entry.set_title(Text::plain(item.title.clone()));
+            entry.set_updated(to_utc(item.updated).fixed_offset());
+            entry.set_published(Some(to_utc(item.published).fixed_offset()));
+            entry.set_links(vec![link(&item.url, "alternate")]);
+            entry.set_summary(Some(Text::plain(item.summary.clone())));
+
+            let mut author = Person::default();
+            author.set_name(item.author_name.clone());
+            entry.set_authors(vec![author]);
+
+            let mut category = Category::default();
+            category.set_term(item.category.clone());
+            entry.set_categories(vec![category]);
+
+            if let Some(html) = &item.content_html {
+                let mut content = Content::default();
+                content.set_content_type(Some("html".to_string()));
+                content.set_value(Some(html.clone()));
+                entry.set_content(Some(content));
+            }
+            entry
+        })
+        .collect();
+
+    let mut feed = Feed::default();
+    feed.set_id(channel.feed_url.clone());
+    feed.set_title(Text::plain(channel.title.clone()));
+    feed.set_subtitle(Some(Text::plain(channel.description.clone())));
+    feed.set_updated(to_utc(channel.updated).fixed_offset());
+    feed.set_links(vec![
+        link(&channel.home_page_url, "alternate"),
+        link(&channel.feed_url, "self"),
+    ]);
+    feed.set_entries(entries);
+    feed.to_string()
+}
@@ -0,0 +1,71 @@
+// src/feed/json.rs
+//! JSON Feed 1.1 订阅源生成 (https://www.jsonfeed.org/version/1.1/)。
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::{to_utc, FeedChannel, FeedItem};
+
+/// JSON Feed 顶层对象。
+#[derive(Debug, Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: &'a str,
+    description: &'a str,
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+/// JSON Feed 条目。
+#[derive(Debug, Serialize)]
+struct JsonFeedItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    summary: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_html: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_text: Option<&'a str>, // 规范要求 content_html 与 content_text 至少有一个
+    date_published: DateTime<Utc>,
+    date_modified: DateTime<Utc>,
+    authors: Vec<JsonFeedAuthor<'a>>,
+    tags: Vec<&'a str>,
+}
+
+/// JSON Feed 作者。
+#[derive(Debug, Serialize)]
+struct JsonFeedAuthor<'a> {
+    name: &'a str,
+}
+
+/// 生成 JSON Feed 1.1 文档。
+///
+/// 完整模式下正文写入 `content_html`；摘要模式下摘要同时作为 `content_text`。
+pub fn build_json_feed(channel: &FeedChannel, items: &[FeedItem]) -> String {
+    let feed = JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: &channel.title,
+        home_page_url: &channel.home_page_url,
+        feed_url: &channel.feed_url,
+        description: &channel.description,
+        items: items
+            .iter()
+            .map(|item| JsonFeedItem {
+                id: item.guid(),
+                url: &item.url,
+                title: &item.title,
+                summary: &item.summary,
+                content_html: item.content_html.as_deref(),
+                content_text: item.content_html.is_none().then_some(item.summary.as_str()),
+                date_published: to_utc(item.published),
+                date_modified: to_utc(item.updated),
+                authors: vec![JsonFeedAuthor { name: &item.author_name }],
+                tags: vec![&item.category],
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&feed).unwrap_or_default()
+}
@@ -0,0 +1,158 @@
+// src/feed/mod.rs
+//! 订阅源模块。
+//! 将已发布的文章生成为 RSS 2.0、Atom 和 JSON Feed 三种格式，并提供条件请求 (ETag / Last-Modified) 的判断。
+
+pub mod rss;  // RSS 2.0 输出
+pub mod atom; // Atom 1.0 输出
+pub mod json; // JSON Feed 1.1 输出
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sha2::{Digest, Sha256};
+
+/// 单个订阅源最多包含的文章数量。
+pub const FEED_ITEM_LIMIT: u64 = 20;
+
+/// 订阅源格式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+impl FeedFormat {
+    /// 对应格式的 `Content-Type`。
+    pub fn content_type(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+            FeedFormat::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+
+    /// 对应格式的文件名，用于拼接订阅源自身的 URL。
+    pub fn file_name(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "feed.xml",
+            FeedFormat::Atom => "atom.xml",
+            FeedFormat::Json => "feed.json",
+        }
+    }
+}
+
+/// 订阅源内容模式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeedMode {
+    /// 输出完整的渲染后 HTML 正文
+    #[default]
+    Full,
+    /// 只输出摘要
+    Excerpt,
+}
+
+impl FeedMode {
+    /// 从查询参数解析内容模式，未知值返回 `None`。
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "full" => Some(FeedMode::Full),
+            "excerpt" => Some(FeedMode::Excerpt),
+            _ => None,
+        }
+    }
+}
+
+/// 订阅源的频道信息。
+#[derive(Debug, Clone)]
+pub struct FeedChannel {
+    pub title: String,
+    pub description: String,
+    pub home_page_url: String,  // 对应的网页地址
+    pub feed_url: String,       // 订阅源自身的地址
+    pub updated: NaiveDateTime, // 所有条目中最新的 updated_at
+}
+
+/// 订阅源中的一篇文章。
+#[derive(Debug, Clone)]
+pub struct FeedItem {
+    pub id: i32,
+    pub title: String,
+    pub url: String,
+    pub author_name: String,
+    pub category: String,
+    pub summary: String,
+    pub content_html: Option<String>, // 仅在 `FeedMode::Full` 下填充
+    pub published: NaiveDateTime,
+    pub updated: NaiveDateTime,
+}
+
+impl FeedItem {
+    /// 条目的全局唯一标识，文章地址不变时保持稳定。
+    pub fn guid(&self) -> &str {
+        &self.url
+    }
+}
+
+/// 将数据库中的 UTC 时间转换为带时区的时间。
+pub(crate) fn to_utc(time: NaiveDateTime) -> DateTime<Utc> {
+    time.and_utc()
+}
+
+/// 生成指定格式的订阅源文本。
+///
+/// # 参数
+/// * `format` - 订阅源格式。
+/// * `channel` - 频道信息。
+/// * `items` - 文章条目，按发布时间倒序。
+///
+/// # 返回
+/// 订阅源文本。
+pub fn build_feed(format: FeedFormat, channel: &FeedChannel, items: &[FeedItem]) -> String {
+    match format {
+        FeedFormat::Rss => rss::build_rss(channel, items),
+        FeedFormat::Atom => atom::build_atom(channel, items),
+        FeedFormat::Json => json::build_json_feed(channel, items),
+    }
+}
+
+/// 根据订阅源内容计算 ETag（强校验）。
+pub fn compute_etag(body: &str) -> String {
+    let digest = Sha256::digest(body.as_bytes());
+    let hex: String = digest.iter().take(16).map(|b| format!("{:02x}", b)).collect();
+    format!("\"{}\"", hex)
+}
+
+/// 判断客户端缓存是否仍然有效（应返回 304 Not Modified）。
+///
+/// 按 RFC 9110，请求带有 `If-None-Match` 时只比较 ETag，忽略 `If-Modified-Since`。
+///
+/// # 参数
+/// * `if_none_match` - 请求头 `If-None-Match` 的值。
+/// * `if_modified_since` - 请求头 `If-Modified-Since` 的值。
+/// * `etag` - 当前内容的 ETag。
+/// * `last_modified` - 当前内容的最后修改时间。
+pub fn is_not_modified(
+    if_none_match: Option<&str>,
+    if_modified_since: Option<&str>,
+    etag: &str,
+    last_modified: NaiveDateTime,
+) -> bool {
+    if let Some(if_none_match) = if_none_match {
+        return if_none_match
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == "*" || tag == etag);
+    }
+
+    if let Some(since) = if_modified_since.and_then(|v| httpdate::parse_http_date(v).ok()) {
+        // HTTP 日期只精确到秒
+        let since = DateTime::<Utc>::from(since).timestamp();
+        return to_utc(last_modified).timestamp() <= since;
+    }
+
+    false
+}
+
+/// 格式化 `Last-Modified` 响应头使用的 HTTP 日期。
+pub fn http_date(time: NaiveDateTime) -> String {
+    httpdate::fmt_http_date(to_utc(time).into())
+}
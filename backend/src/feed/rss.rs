@@ -0,0 +1,47 @@
+// src/feed/rss.rs
+//! RSS 2.0 订阅源生成。
+
+use ::rss::extension::dublincore::DublinCoreExtension;
+use ::rss::{Category, Channel, Guid, Item};
+
+use super::{to_utc, FeedChannel, FeedItem};
+
+/// 生成 RSS 2.0 文档。
+///
+/// 摘要写入 `<description>`；完整模式下正文写入 `<content:encoded>`，作者写入 `<dc:creator>`。
+pub fn build_rss(channel: &FeedChannel, items: &[FeedItem]) -> String {
+    let items: Vec<Item> = items
+        .iter()
+        .map(|feed_item| {
+            let mut item = Item::default();
+            item.set_title(feed_item.title.clone());
+            item.set_link(feed_item.url.clone());
+            item.set_description(feed_item.summary.clone());
+            item.set_content(feed_item.content_html.clone());
+            item.set_pub_date(to_utc(feed_item.published).to_rfc2822());
+
+            let mut guid = Guid::default();
+            guid.set_value(feed_item.guid());
+            guid.set_permalink(true);
+            item.set_guid(guid);
+
+            let mut category = Category::default();
+            category.set_name(feed_item.category.clone());
+            item.set_categories(vec![category]);
+
+            let mut dublin_core = DublinCoreExtension::default();
+            dublin_core.set_creators(vec![feed_item.author_name.clone()]);
+            item.set_dublin_core_ext(dublin_core);
+            item
+        })
+        .collect();
+
+    let mut rss_channel = Channel::default();
+    rss_channel.set_title(channel.title.clone());
+    rss_channel.set_link(channel.home_page_url.clone());
+    rss_channel.set_description(channel.description.clone());
+    rss_channel.set_last_build_date(to_utc(channel.updated).to_rfc2822());
+    rss_channel.set_generator("Axum Blog Engine".to_string());
+    rss_channel.set_items(items);
+    rss_channel.to_string()
+}
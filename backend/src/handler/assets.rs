@@ -0,0 +1,49 @@
+//! 前端静态资源的 HTTP 请求处理函数。
+//! 作为路由的兜底处理：`/api` 下未匹配的请求返回 JSON 404，其余请求交给前端资源和 SPA 回退。
+
+use axum::{
+    extract::State,
+    http::{header, HeaderValue, Method, Request, StatusCode},
+    response::{IntoResponse, Response},
+    body::Body,
+};
+
+use crate::AppState;
+use crate::error::CustomError;
+use crate::i18n::t_args;
+
+/// 兜底处理函数：提供前端静态资源。
+pub async fn serve_frontend(State(state): State<AppState>, request: Request<Body>) -> Response {
+    let path = request.uri().path().to_string();
+
+    if path == "/api" || path.starts_with("/api/") {
+        return CustomError::NotFound(t_args("route-not-found", &[("path", &path)])).into_response();
+    }
+
+    let Some(assets) = state.assets.as_ref() else {
+        return CustomError::NotFound(t_args("page-not-found", &[("path", &path)])).into_response();
+    };
+    if request.method() != Method::GET && request.method() != Method::HEAD {
+        return StatusCode::METHOD_NOT_ALLOWED.into_response();
+    }
+
+    let accept_encoding = request.headers()
+        .get(header::ACCEPT_ENCODING)
+        .and_then(|v| v.to_str().ok());
+
+    let Some(asset) = assets.resolve(&path, accept_encoding).await else {
+        return CustomError::NotFound(t_args("file-not-found", &[("path", &path)])).into_response();
+    };
+
+    let mut response = Response::new(Body::from(asset.body.into_owned()));
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&asset.content_type) {
+        headers.insert(header::CONTENT_TYPE, value);
+    }
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static(asset.cache_control));
+    headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+    if let Some(encoding) = asset.content_encoding {
+        headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(encoding.header_value()));
+    }
+    response
+}
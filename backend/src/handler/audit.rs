@@ -0,0 +1,224 @@
+//! 审计日志相关的 HTTP 请求处理函数。
+//!
+//! 各处理函数通过 `ClientIp` 提取器取得客户端地址，操作成功后调用 `record_audit` 追加记录；
+//! 管理员可以通过 `GET /api/admin/audit` 按操作者、动作和时间范围查询，或导出为 CSV。
+
+use std::net::SocketAddr;
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, FromRef, FromRequestParts, Query, State},
+    http::{header, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json,
+};
+use futures_util::stream::{self, Stream, StreamExt};
+use tracing::error;
+
+use crate::audit::AuditEvent;
+use crate::database::audit::{AuditFilter, AuditRepository, Model as AuditModel};
+use crate::error::{CustomError, CustomResult};
+use crate::handler::idl::{AuditEntryInfo, AuditLogRequest, AuditLogResponse};
+use crate::handler::user::AuthUser;
+use crate::openapi::{ApiRouter, ApiSchema, Operation};
+use crate::telemetry::current_request_id;
+use crate::AppState;
+use crate::i18n::{t, t_args};
+
+/// CSV 导出时每批从数据库读取的记录数。
+pub const CSV_BATCH_SIZE: u64 = 500;
+
+/// CSV 导出的列，与 `AuditEntryInfo` 的字段一一对应。
+const CSV_COLUMNS: [&str; 10] = [
+    "id", "created_at", "actor_id", "actor_name", "action", "target_type", "target_id", "changes", "ip", "request_id",
+];
+
+/// 客户端 IP 提取器。
+///
+/// 默认取 TCP 连接的对端地址（需要以 `into_make_service_with_connect_info` 启动服务）；
+/// 开启 `server.trust_forwarded_for` 时取 `X-Forwarded-For` 的最后一项，
+/// 即紧邻的反向代理追加的地址，客户端自行伪造的前几项不会被采用。
+pub struct ClientIp(pub Option<String>);
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for ClientIp
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let app_state = AppState::from_ref(state);
+        if app_state.config.trust_forwarded_for {
+            let forwarded = parts.headers
+                .get_all("x-forwarded-for")
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .flat_map(|v| v.split(','))
+                .map(str::trim)
+                .rfind(|v| !v.is_empty());
+            if let Some(ip) = forwarded {
+                return Ok(ClientIp(Some(ip.to_string())));
+            }
+        }
+        let peer = parts.extensions.get::<ConnectInfo<SocketAddr>>().map(|c| c.0.ip().to_string());
+        Ok(ClientIp(peer))
+    }
+}
+
+/// 追加一条审计记录，带上客户端 IP 与当前请求 ID。
+///
+/// 调用时操作本身已经完成，写入失败只记录错误日志，不影响响应。
+pub async fn record_audit(state: &AppState, client: &ClientIp, event: AuditEvent) {
+    let action = event.action;
+    if let Err(e) = state.audit_repo.record(event, client.0.clone(), current_request_id()).await {
+        error!(action, error = %e, "写入审计日志失败");
+    }
+}
+
+/// 将审计记录 `Model` 转换为 `AuditEntryInfo` DTO。
+fn convert_audit_model_to_entry_info(model: AuditModel) -> AuditEntryInfo {
+    AuditEntryInfo {
+        id: model.id,
+        actor_id: model.actor_id,
+        actor_name: model.actor_name,
+        action: model.action,
+        target_type: model.target_type,
+        target_id: model.target_id,
+        changes: model.changes.and_then(|c| serde_json::from_str(&c).ok()),
+        ip: model.ip,
+        request_id: model.request_id,
+        created_at: model.created_at,
+    }
+}
+
+/// 查询审计日志 (GET /api/admin/audit) - 需要管理员权限
+///
+/// 支持按 `actor_id`、`action` 和 `from` / `to` 时间范围过滤并分页；
+/// `format=csv` 时以附件形式导出全部符合条件的记录，分批读取并边读边写出响应。
+pub async fn get_audit_log(
+    AuthUser(claims): AuthUser,
+    State(state): State<AppState>,
+    Query(req): Query<AuditLogRequest>,
+) -> CustomResult<Response> {
+    if claims.role != "admin" {
+        return Err(CustomError::Forbidden(t("forbidden-audit-log")));
+    }
+
+    let filter = AuditFilter {
+        actor_id: req.actor_id,
+        action: req.action.filter(|a| !a.is_empty()),
+        from: req.from.map(|t| t.naive_utc()),
+        to: req.to.map(|t| t.naive_utc()),
+    };
+
+    match req.format.as_deref() {
+        None | Some("json") => {}
+        Some("csv") => {
+            return Ok((
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                    (header::CONTENT_DISPOSITION, "attachment; filename=\"audit_log.csv\""),
+                ],
+                Body::from_stream(audit_csv_stream(state.audit_repo.clone(), filter)),
+            ).into_response());
+        }
+        Some(other) => return Err(CustomError::BadRequest(t_args("audit-unsupported-format", &[("format", &other)]))),
+    }
+
+    let page = req.page.unwrap_or(1);
+    let limit = req.limit.unwrap_or(20).clamp(1, 100);
+    let (entries, total_pages, current_page, total_entries) =
+        state.audit_repo.get_paginated_entries(&filter, page, limit).await?;
+
+    Ok(Json(AuditLogResponse {
+        success: true,
+        entries: entries.into_iter().map(convert_audit_model_to_entry_info).collect(),
+        total_pages,
+        current_page,
+        total_entries,
+    }).into_response())
+}
+
+/// 分批读取审计日志并逐批输出 CSV 文本，不把全部记录载入内存。
+///
+/// 读取失败时记录错误日志并中断响应（关闭连接），客户端不会把部分内容当作完整文件。
+fn audit_csv_stream(
+    repo: AuditRepository,
+    filter: AuditFilter,
+) -> impl Stream<Item = Result<String, CustomError>> + Send {
+    let header = stream::once(async { Ok(csv_line(CSV_COLUMNS)) });
+    // 游标为 None 表示已经读完
+    let rows = stream::try_unfold(Some(None), move |cursor| {
+        let (repo, filter) = (repo.clone(), filter.clone());
+        async move {
+            let Some(after) = cursor else {
+                return Ok(None);
+            };
+            let entries = repo.get_entries_after(&filter, after, CSV_BATCH_SIZE).await.map_err(|e| {
+                error!(error = %e, "导出审计日志失败");
+                e
+            })?;
+            let Some(last) = entries.last() else {
+                return Ok(None);
+            };
+            let next = ((entries.len() as u64) == CSV_BATCH_SIZE).then_some(Some((last.created_at, last.id)));
+            Ok(Some((entries.iter().map(csv_row).collect::<String>(), next)))
+        }
+    });
+    header.chain(rows)
+}
+
+/// 一条审计记录对应的 CSV 行（RFC 4180，含换行）。
+pub fn csv_row(entry: &AuditModel) -> String {
+    csv_line([
+        entry.id.to_string(),
+        entry.created_at.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        entry.actor_id.map(|id| id.to_string()).unwrap_or_default(),
+        entry.actor_name.clone().unwrap_or_default(),
+        entry.action.clone(),
+        entry.target_type.clone().unwrap_or_default(),
+        entry.target_id.clone().unwrap_or_default(),
+        entry.changes.clone().unwrap_or_default(),
+        entry.ip.clone().unwrap_or_default(),
+        entry.request_id.clone().unwrap_or_default(),
+    ])
+}
+
+/// 转义各字段并拼接为一行 CSV（含换行）。
+fn csv_line<S: AsRef<str>>(fields: impl IntoIterator<Item = S>) -> String {
+    let row: Vec<String> = fields.into_iter().map(|f| csv_field(f.as_ref())).collect();
+    format!("{}\r\n", row.join(","))
+}
+
+/// 转义单个 CSV 字段。
+///
+/// 以 `=`、`+`、`-`、`@`、制表符或回车开头的值（例如登录失败时输入的用户名）前加 `'`，
+/// 防止在电子表格软件中被当作公式执行。
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// 审计日志路由，挂载在 `/api/admin` 下
+pub fn audit_routes() -> ApiRouter {
+    ApiRouter::new("admin")
+        .route("/audit", get(get_audit_log), // GET /api/admin/audit
+            Operation::get("查询审计日志")
+                .description("`format=csv` 时以附件形式流式导出全部符合条件的记录。")
+                .admin()
+                .query::<AuditLogRequest>()
+                .json::<AuditLogResponse>()
+                .response(200, "CSV 导出", Some(("text/csv", String::schema))))
+}
@@ -1,4 +1,4 @@
-use crate::error::{CustomError, CustomResult};
+use crate::error::{CustomError, CustomResult, ErrorCode};
 use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
 use axum_extra::{
     extract::TypedHeader,
@@ -7,6 +7,7 @@ use axum_extra::{
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
+use crate::i18n::t;
 
 lazy_static! {
     static ref JWT_SECRET: String = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
@@ -44,7 +45,7 @@ where
         let TypedHeader(Authorization(bearer)) =
             TypedHeader::<Authorization<Bearer>>::from_request_parts(parts, state)
                 .await
-                .map_err(|_| CustomError::Unauthorized("缺少或无效的Authorization头".to_string()))?;
+                .map_err(|_| CustomError::coded(ErrorCode::AuthTokenMissing, t("auth-header-missing")))?;
 
         // Decode the user data
         let token_data = decode::<Claims>(
@@ -52,7 +53,7 @@ where
             &DecodingKey::from_secret(JWT_SECRET.as_bytes()),
             &Validation::default(),
         )
-        .map_err(|e| CustomError::JwtError(format!("JWT令牌无效: {}", e)))?;
+        .map_err(CustomError::from)?;
 
         Ok(token_data.claims)
     }
@@ -0,0 +1,258 @@
+//! 订阅源相关的 HTTP 请求处理函数。
+//! 提供全站、按分类、按作者的 RSS 2.0 / Atom / JSON Feed 输出，并支持条件请求。
+
+use std::collections::{BTreeSet, HashMap};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::DateTime;
+
+use crate::AppState;
+use crate::database::post::post_excerpt;
+use crate::error::{CustomError, CustomResult, ErrorCode};
+use crate::feed::{
+    build_feed, compute_etag, http_date, is_not_modified, FeedChannel, FeedFormat, FeedItem,
+    FeedMode, FEED_ITEM_LIMIT,
+};
+use crate::handler::idl::FeedRequest;
+use crate::i18n::t_args;
+
+/// 订阅源的范围。
+enum FeedScope {
+    Site,             // 全站
+    Category(String), // 指定分类
+    Author(i32),      // 指定作者
+}
+
+/// 生成订阅源响应。
+///
+/// 响应携带 `ETag` 和 `Last-Modified`；客户端缓存仍然有效时返回 `304 Not Modified`。
+async fn feed_response(
+    state: AppState,
+    headers: HeaderMap,
+    format: FeedFormat,
+    scope: FeedScope,
+    req: FeedRequest,
+) -> CustomResult<Response> {
+    let mode = match req.mode.as_deref() {
+        None => FeedMode::default(),
+        Some(value) => FeedMode::parse(value)
+            .ok_or_else(|| CustomError::BadRequest(t_args("feed-unsupported-mode", &[("mode", &value)])))?,
+    };
+    let config = &state.config;
+
+    // 确定频道信息和筛选条件
+    let (title, home_page_url, feed_url, category, author_id) = match scope {
+        FeedScope::Site => (
+            config.site_title.clone(),
+            config.site_url.clone(),
+            format!("{}/{}", config.site_url, format.file_name()),
+            None,
+            None,
+        ),
+        FeedScope::Category(category) => {
+            let page_url = config.category_url(&category);
+            (
+                format!("{} - 分类: {}", config.site_title, category),
+                page_url.clone(),
+                format!("{}/{}", page_url, format.file_name()),
+                Some(category),
+                None,
+            )
+        }
+        FeedScope::Author(author_id) => {
+            let author = state.user_repo.get_user_by_id(author_id).await?
+                .ok_or_else(|| CustomError::coded(ErrorCode::UserNotFound, t_args("user-id-not-found", &[("id", &author_id)])))?;
+            let page_url = config.author_url(author_id);
+            (
+                format!("{} - 作者: {}", config.site_title, author.username),
+                page_url.clone(),
+                format!("{}/{}", page_url, format.file_name()),
+                None,
+                Some(author_id),
+            )
+        }
+    };
+
+    let posts = state.post_repo
+        .get_recent_published_posts(category, author_id, FEED_ITEM_LIMIT)
+        .await?;
+
+    // 批量查询作者，避免逐条查询
+    let author_ids: BTreeSet<i32> = posts.iter().map(|post| post.author_id).collect();
+    let authors: HashMap<i32, String> = state.user_repo
+        .get_users_by_ids(author_ids.into_iter().collect())
+        .await?
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect();
+
+    let mut items = Vec::with_capacity(posts.len());
+    for post in &posts {
+        let content_html = match mode {
+            FeedMode::Full => Some(state.post_repo.get_rendered_content(post).await?.html),
+            FeedMode::Excerpt => None,
+        };
+        items.push(FeedItem {
+            id: post.id,
+            title: post.title.clone(),
+            url: config.post_url(post.id),
+            author_name: authors.get(&post.author_id).cloned().unwrap_or_default(),
+            category: post.category.clone(),
+            summary: post_excerpt(post),
+            content_html,
+            published: post.created_at,
+            updated: post.updated_at,
+        });
+    }
+
+    let channel = FeedChannel {
+        title,
+        description: config.site_description.clone(),
+        home_page_url,
+        feed_url,
+        // 没有文章时使用固定的纪元时间，保证响应可缓存
+        updated: posts.iter()
+            .map(|post| post.updated_at)
+            .max()
+            .unwrap_or(DateTime::UNIX_EPOCH.naive_utc()),
+    };
+
+    let body = build_feed(format, &channel, &items);
+    let etag = compute_etag(&body);
+    let last_modified = http_date(channel.updated);
+
+    let header_str = |name: header::HeaderName| headers.get(name).and_then(|v| v.to_str().ok());
+    let not_modified = is_not_modified(
+        header_str(header::IF_NONE_MATCH),
+        header_str(header::IF_MODIFIED_SINCE),
+        &etag,
+        channel.updated,
+    );
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        (
+            [(header::CONTENT_TYPE, format.content_type())],
+            body,
+        ).into_response()
+    };
+
+    let response_headers = response.headers_mut();
+    response_headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("public, max-age=300"));
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response_headers.insert(header::ETAG, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&last_modified) {
+        response_headers.insert(header::LAST_MODIFIED, value);
+    }
+    Ok(response)
+}
+
+/// 全站 RSS 2.0 订阅源 (GET /feed.xml)
+pub async fn site_rss(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(req): Query<FeedRequest>,
+) -> CustomResult<Response> {
+    feed_response(state, headers, FeedFormat::Rss, FeedScope::Site, req).await
+}
+
+/// 全站 Atom 订阅源 (GET /atom.xml)
+pub async fn site_atom(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(req): Query<FeedRequest>,
+) -> CustomResult<Response> {
+    feed_response(state, headers, FeedFormat::Atom, FeedScope::Site, req).await
+}
+
+/// 全站 JSON Feed 订阅源 (GET /feed.json)
+pub async fn site_json(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(req): Query<FeedRequest>,
+) -> CustomResult<Response> {
+    feed_response(state, headers, FeedFormat::Json, FeedScope::Site, req).await
+}
+
+/// 分类 RSS 2.0 订阅源 (GET /category/:category/feed.xml)
+pub async fn category_rss(
+    State(state): State<AppState>,
+    Path(category): Path<String>,
+    headers: HeaderMap,
+    Query(req): Query<FeedRequest>,
+) -> CustomResult<Response> {
+    feed_response(state, headers, FeedFormat::Rss, FeedScope::Category(category), req).await
+}
+
+/// 分类 Atom 订阅源 (GET /category/:category/atom.xml)
+pub async fn category_atom(
+    State(state): State<AppState>,
+    Path(category): Path<String>,
+    headers: HeaderMap,
+    Query(req): Query<FeedRequest>,
+) -> CustomResult<Response> {
+    feed_response(state, headers, FeedFormat::Atom, FeedScope::Category(category), req).await
+}
+
+/// 分类 JSON Feed 订阅源 (GET /category/:category/feed.json)
+pub async fn category_json(
+    State(state): State<AppState>,
+    Path(category): Path<String>,
+    headers: HeaderMap,
+    Query(req): Query<FeedRequest>,
+) -> CustomResult<Response> {
+    feed_response(state, headers, FeedFormat::Json, FeedScope::Category(category), req).await
+}
+
+/// 作者 RSS 2.0 订阅源 (GET /author/:author_id/feed.xml)
+pub async fn author_rss(
+    State(state): State<AppState>,
+    Path(author_id): Path<i32>,
+    headers: HeaderMap,
+    Query(req): Query<FeedRequest>,
+) -> CustomResult<Response> {
+    feed_response(state, headers, FeedFormat::Rss, FeedScope::Author(author_id), req).await
+}
+
+/// 作者 Atom 订阅源 (GET /author/:author_id/atom.xml)
+pub async fn author_atom(
+    State(state): State<AppState>,
+    Path(author_id): Path<i32>,
+    headers: HeaderMap,
+    Query(req): Query<FeedRequest>,
+) -> CustomResult<Response> {
+    feed_response(state, headers, FeedFormat::Atom, FeedScope::Author(author_id), req).await
+}
+
+/// 作者 JSON Feed 订阅源 (GET /author/:author_id/feed.json)
+pub async fn author_json(
+    State(state): State<AppState>,
+    Path(author_id): Path<i32>,
+    headers: HeaderMap,
+    Query(req): Query<FeedRequest>,
+) -> CustomResult<Response> {
+    feed_response(state, headers, FeedFormat::Json, FeedScope::Author(author_id), req).await
+}
+
+/// 订阅源路由配置函数。
+/// 订阅源面向阅读器，挂载在站点根路径而不是 `/api` 下。
+pub fn feed_routes() -> Router<AppState> {
+    Router::new()
+        .route("/feed.xml", get(site_rss))                                // GET /feed.xml
+        .route("/atom.xml", get(site_atom))                               // GET /atom.xml
+        .route("/feed.json", get(site_json))                              // GET /feed.json
+        .route("/category/:category/feed.xml", get(category_rss))         // GET /category/:category/feed.xml
+        .route("/category/:category/atom.xml", get(category_atom))        // GET /category/:category/atom.xml
+        .route("/category/:category/feed.json", get(category_json))       // GET /category/:category/feed.json
+        .route("/author/:author_id/feed.xml", get(author_rss))            // GET /author/:author_id/feed.xml
+        .route("/author/:author_id/atom.xml", get(author_atom))           // GET /author/:author_id/atom.xml
+        .route("/author/:author_id/feed.json", get(author_json))          // GET /author/:author_id/feed.json
+}
@@ -0,0 +1,78 @@
+//! 健康检查与版本信息的 HTTP 请求处理函数。
+//! `/healthz` 只表示进程存活；`/readyz` 检查数据库连接和迁移状态，供负载均衡判断是否转发流量。
+
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use tracing::warn;
+
+use crate::handler::idl::{HealthResponse, ReadinessResponse, VersionResponse};
+use crate::migration::Migrator;
+use crate::openapi::{ApiRouter, Operation};
+use crate::AppState;
+
+/// 存活检查 (GET /healthz)
+pub async fn healthz() -> Json<HealthResponse> {
+    Json(HealthResponse { status: "ok" })
+}
+
+/// 就绪检查 (GET /readyz)
+/// 数据库可用、没有待执行的迁移且未在停机时返回 200，否则返回 503。
+/// 该接口无需认证，数据库错误只写入日志，响应中不包含连接地址等细节。
+pub async fn readyz(State(state): State<AppState>) -> (StatusCode, Json<ReadinessResponse>) {
+    let shutting_down = state.tasks.is_shutting_down();
+    let (database, pending_migrations) = match state.db.ping().await {
+        Ok(()) => {
+            let pending = match Migrator::status(&state.db).await {
+                Ok(status) => Some(status.iter().filter(|s| s.applied_at.is_none()).count()),
+                Err(e) => {
+                    warn!("就绪检查读取迁移状态失败: {}", e);
+                    None
+                }
+            };
+            ("ok".to_string(), pending)
+        }
+        Err(e) => {
+            warn!("就绪检查连接数据库失败: {}", e);
+            ("unavailable".to_string(), None)
+        }
+    };
+
+    let ready = !shutting_down && database == "ok" && pending_migrations == Some(0);
+    let status = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(ReadinessResponse { ready, database, pending_migrations, shutting_down }))
+}
+
+/// 构建与版本信息 (GET /api/version)
+pub async fn get_version() -> Json<VersionResponse> {
+    let mut database_backends = Vec::new();
+    if cfg!(feature = "sqlite") {
+        database_backends.push("sqlite");
+    }
+    if cfg!(feature = "postgres") {
+        database_backends.push("postgres");
+    }
+    if cfg!(feature = "mysql") {
+        database_backends.push("mysql");
+    }
+
+    Json(VersionResponse {
+        name: env!("CARGO_PKG_NAME"),
+        version: env!("CARGO_PKG_VERSION"),
+        git_commit: env!("BUILD_GIT_COMMIT"),
+        profile: if cfg!(debug_assertions) { "debug" } else { "release" },
+        database_backends,
+    })
+}
+
+/// 健康检查路由，挂载在站点根路径
+pub fn health_routes() -> Router<AppState> {
+    Router::new()
+        .route("/healthz", get(healthz)) // GET /healthz
+        .route("/readyz", get(readyz))   // GET /readyz
+}
+
+/// 版本信息路由，挂载在 /api 下
+pub fn version_routes() -> ApiRouter {
+    ApiRouter::new("meta")
+        .route("/version", get(get_version), // GET /api/version
+            Operation::get("构建与版本信息").json::<VersionResponse>())
+}
@@ -1,5 +1,6 @@
 //! 定义应用程序的请求和响应数据传输对象 (DTOs)。
 //! 这些结构体是客户端与服务器之间数据交换的契约。
+//! 验证规则的 `message` 填写 `locales/*.ftl` 中的消息 ID，返回给客户端时按请求的语言翻译。
 
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -7,98 +8,131 @@ use chrono::NaiveDateTime; // 用于时间戳，与数据库中的 TEXT 格式
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 
+use crate::api_model;
+use crate::render::TocEntry;
+
 // ======================== 用户相关 DTOs ========================
 
-/// 用户注册请求体：POST /users/register
-#[derive(Debug, Serialize, Deserialize, Validate)]
-pub struct RegisterRequest {
-    #[validate(length(min = 3, max = 20, message = "用户名长度必须在3到20个字符之间"))]
-    pub username: String,
-    
-    #[validate(email(message = "邮箱格式无效"))]
-    pub email: String,
-    
-    #[validate(length(min = 6, message = "密码长度至少为6个字符"))]
-    pub password: String, // 原始密码，后端会进行哈希
-}
+api_model! {
+    /// 用户注册请求体：POST /api/user/register
+    #[derive(Debug, Serialize, Deserialize, Validate)]
+    pub struct RegisterRequest {
+        #[validate(length(min = 3, max = 20, message = "validation-username-length"))]
+        pub username: String,
 
-/// 用户注册响应体
-#[derive(Debug, Serialize)]
-pub struct RegisterResponse {
-    pub success: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub token: Option<String>, // 注册成功后直接返回 JWT Token
-    pub user_id: i32,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub message: Option<String>, // 错误或成功消息
-}
+        #[validate(email(message = "validation-email"))]
+        pub email: String,
 
-/// 用户登录请求体：POST /users/login
-#[derive(Debug, Serialize, Deserialize, Validate)]
-pub struct LoginRequest {
-    #[validate(length(min = 1, message = "用户名或邮箱不能为空"))]
-    pub username_or_email: String, // 用户名或邮箱
-    #[validate(length(min = 1, message = "密码不能为空"))]
-    pub password: String,          // 原始密码
-}
+        #[validate(length(min = 6, message = "validation-password-length"))]
+        pub password: String, // 原始密码，后端会进行哈希
+    }
 
-/// 用户登录响应体
-#[derive(Debug, Serialize)]
-pub struct LoginResponse {
-    pub success: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub token: Option<String>, // 登录成功后返回 JWT Token
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub user_info: Option<UserInfo>, // 登录成功后返回用户信息
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub message: Option<String>, // 错误或成功消息
-}
+    /// 用户注册响应体
+    #[derive(Debug, Serialize)]
+    pub struct RegisterResponse {
+        pub success: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub token: Option<String>, // 注册成功后直接返回 JWT Token
+        pub user_id: i32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub message: Option<String>, // 错误或成功消息
+    }
 
-/// 用户信息结构体（用于响应）
-/// 用于返回用户的公共信息，不包含敏感数据如密码哈希。
-#[derive(Debug, Serialize)]
-pub struct UserInfo {
-    pub id: i32,
-    pub username: String,
-    pub email: String,
-    pub role: String,
-    pub created_at: NaiveDateTime, // 使用 NaiveDateTime 以保持类型一致性
-}
+    /// 用户登录请求体：POST /api/user/login
+    #[derive(Debug, Serialize, Deserialize, Validate)]
+    pub struct LoginRequest {
+        #[validate(length(min = 1, message = "validation-login-required"))]
+        pub username_or_email: String, // 用户名或邮箱
+        #[validate(length(min = 1, message = "validation-password-required"))]
+        pub password: String,          // 原始密码
+    }
 
-/// 获取登录用户信息响应体：GET /users/me
-#[derive(Debug, Serialize)]
-pub struct UserInfoResponse {
-    pub success: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub user: Option<UserInfo>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub message: Option<String>,
-}
+    /// 用户登录响应体
+    #[derive(Debug, Serialize)]
+    pub struct LoginResponse {
+        pub success: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub token: Option<String>, // 登录成功后返回 JWT Token
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub user_info: Option<UserInfo>, // 登录成功后返回用户信息
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub message: Option<String>, // 错误或成功消息
+    }
 
-/// 更新用户个人信息请求体：PUT /users/me
-#[derive(Debug, Serialize, Deserialize, Validate)]
-pub struct UpdateMyProfileRequest {
-    #[validate(length(min = 3, max = 20, message = "用户名长度必须在3到20个字符之间"))]
-    pub username: Option<String>, // 可以选择性更新
-    #[validate(email(message = "邮箱格式无效"))]
-    pub email: Option<String>,    // 可以选择性更新
-    #[validate(length(min = 6, message = "密码长度至少为6个字符"))]
-    pub password: Option<String>, // 新密码，后端会进行哈希
+    /// 用户信息结构体（用于响应）
+    /// 用于返回用户的公共信息，不包含敏感数据如密码哈希。
+    #[derive(Debug, Serialize)]
+    pub struct UserInfo {
+        pub id: i32,
+        pub username: String,
+        pub email: String,
+        pub role: String,
+        pub display_name: Option<String>,
+        pub avatar_url: Option<String>,
+        pub created_at: NaiveDateTime, // 使用 NaiveDateTime 以保持类型一致性
+    }
+
+    /// 作者的公开信息，嵌入在文章和评论中 (`include=author`)
+    #[derive(Debug, Clone, Serialize)]
+    pub struct AuthorInfo {
+        pub id: i32,
+        pub username: String,
+        pub display_name: String, // 未设置显示名称时为用户名
+        pub avatar_url: Option<String>,
+    }
+
+    /// 获取登录用户信息响应体：GET /api/user/me
+    #[derive(Debug, Serialize)]
+    pub struct UserInfoResponse {
+        pub success: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub user: Option<UserInfo>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub message: Option<String>,
+    }
+
+    /// 更新用户个人信息请求体：PUT /api/user/me
+    #[derive(Debug, Default, Serialize, Deserialize, Validate)]
+    pub struct UpdateMyProfileRequest {
+        #[validate(length(min = 3, max = 20, message = "validation-username-length"))]
+        pub username: Option<String>, // 可以选择性更新
+        #[validate(email(message = "validation-email"))]
+        pub email: Option<String>,    // 可以选择性更新
+        #[validate(length(min = 6, message = "validation-password-length"))]
+        pub password: Option<String>, // 新密码，后端会进行哈希
+        #[validate(length(max = 50, message = "validation-display-name-length"))]
+        pub display_name: Option<String>, // 空字符串表示清除
+        #[validate(custom = "validate_avatar_url")]
+        pub avatar_url: Option<String>,   // 空字符串表示清除
+    }
+
+    /// 更新用户（管理员）请求体：PUT /api/user/:id
+    /// 管理员可以更新用户的基本信息，包括角色。
+    #[derive(Debug, Serialize, Deserialize, Validate)]
+    pub struct UpdateUserRequest {
+        // user_id 通常通过路径参数传递，这里不需要
+        #[validate(length(min = 3, max = 20, message = "validation-username-length"))]
+        pub username: Option<String>,
+        #[validate(email(message = "validation-email"))]
+        pub email: Option<String>,
+        #[validate(length(min = 6, message = "validation-password-length"))]
+        pub password: Option<String>,
+        #[validate(custom = "validate_role")] // 自定义角色验证
+        pub role: Option<String>, // 管理员可以修改角色
+    }
 }
 
-/// 更新用户（管理员）请求体：PUT /users/:id
-/// 管理员可以更新用户的基本信息，包括角色。
-#[derive(Debug, Serialize, Deserialize, Validate)]
-pub struct UpdateUserRequest {
-    // user_id 通常通过路径参数传递，这里不需要
-    #[validate(length(min = 3, max = 20, message = "用户名长度必须在3到20个字符之间"))]
-    pub username: Option<String>,
-    #[validate(email(message = "邮箱格式无效"))]
-    pub email: Option<String>,
-    #[validate(length(min = 6, message = "密码长度至少为6个字符"))]
-    pub password: Option<String>,
-    #[validate(custom = "validate_role")] // 自定义角色验证
-    pub role: Option<String>, // 管理员可以修改角色
+// 头像地址验证函数：本站路径 (例如上传的媒体文件) 或 http(s) 地址，空字符串表示清除
+fn validate_avatar_url(url: &str) -> Result<(), validator::ValidationError> {
+    let valid = url.is_empty()
+        || (url.len() <= 500 && (url.starts_with('/') || url.starts_with("https://") || url.starts_with("http://")));
+    if valid {
+        Ok(())
+    } else {
+        let mut error = validator::ValidationError::new("avatar_url");
+        error.message = Some("validation-avatar-url".into());
+        Err(error)
+    }
 }
 
 // 自定义角色验证函数
@@ -106,207 +140,386 @@ fn validate_role(role: &str) -> Result<(), validator::ValidationError> {
     if role == "user" || role == "admin" {
         Ok(())
     } else {
-        Err(validator::ValidationError::new("角色必须是 'user' 或 'admin'"))
+        let mut error = validator::ValidationError::new("role");
+        error.message = Some("validation-role".into());
+        Err(error)
     }
 }
 
 
-/// 创建用户（管理员）请求体：POST /users
-/// 管理员创建新用户时使用，必须指定角色。
-#[derive(Debug, Serialize, Deserialize, Validate)]
-pub struct CreateUserByAdminRequest {
-    #[validate(length(min = 3, max = 20, message = "用户名长度必须在3到20个字符之间"))]
-    pub username: String,
-    #[validate(email(message = "邮箱格式无效"))]
-    pub email: String,
-    #[validate(length(min = 6, message = "密码长度至少为6个字符"))]
-    pub password: String,
-    #[validate(custom = "validate_role")]
-    pub role: String, // 管理员创建时指定角色
-}
+api_model! {
+    /// 创建用户（管理员）请求体：POST /api/user
+    /// 管理员创建新用户时使用，必须指定角色。
+    #[derive(Debug, Serialize, Deserialize, Validate)]
+    pub struct CreateUserByAdminRequest {
+        #[validate(length(min = 3, max = 20, message = "validation-username-length"))]
+        pub username: String,
+        #[validate(email(message = "validation-email"))]
+        pub email: String,
+        #[validate(length(min = 6, message = "validation-password-length"))]
+        pub password: String,
+        #[validate(custom = "validate_role")]
+        pub role: String, // 管理员创建时指定角色
+    }
 
-/// 删除用户（管理员）请求体：DELETE /users (批量删除)
-/// 用于批量删除用户，请求体中包含要删除的用户 ID 列表。
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DeleteUserRequest {
-    pub user_ids: Vec<i32>,
+    /// 删除用户（管理员）请求体：DELETE /api/user (批量删除)
+    /// 用于批量删除用户，请求体中包含要删除的用户 ID 列表。
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct DeleteUserRequest {
+        pub user_ids: Vec<i32>,
+    }
 }
 
 
 // ======================== 文章相关 DTOs ========================
 
-/// 文章信息结构体 (用于响应)
-/// 包含文章的元数据和 Markdown 内容，以及作者ID。
-#[derive(Debug, Serialize)]
-pub struct PostInfo {
-    pub id: i32,
-    pub title: String,
-    pub content_markdown: String, // 存储 Markdown 内容
-    pub category: String,         // 文章分类
-    pub author_id: i32,
-    pub is_published: bool,       // 数据库中 0/1 对应 false/true
-    pub view_count: i32,
-    pub cover_url: Option<String>, // 封面 URL 可能是 None
-    pub created_at: NaiveDateTime,
-    pub updated_at: NaiveDateTime,
-    // 可以在这里添加作者的简要信息，例如：
-    // pub author_username: String,
-}
+api_model! {
+    /// 文章信息结构体 (用于响应)
+    /// 包含文章的元数据和 Markdown 内容，以及作者ID。
+    #[derive(Debug, Serialize)]
+    pub struct PostInfo {
+        pub id: i32,
+        pub title: String,
+        pub content_markdown: String, // 存储 Markdown 内容
+        pub category: String,         // 文章分类
+        pub author_id: i32,
+        pub is_published: bool,
+        pub view_count: i32,
+        pub cover_url: Option<String>, // 封面 URL 可能是 None (保留用于编辑，展示请使用 cover)
+        pub cover: Option<CoverImage>, // 封面图片及其响应式版本
+        pub excerpt: Option<String>,   // 作者填写的摘要，未填写时为 None
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub content_html: Option<String>, // 渲染后的 HTML，仅在请求 format=html 时返回
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub toc: Option<Vec<TocEntry>>,   // 根据标题生成的目录，仅在详情接口返回
+        pub created_at: NaiveDateTime,
+        pub updated_at: NaiveDateTime,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub author: Option<AuthorInfo>,  // 仅在 include=author 时返回
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub comment_count: Option<u64>,  // 仅在 include=counts 时返回
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub favorite_count: Option<u64>, // 仅在 include=counts 时返回
+    }
+
+    /// 文章封面图片
+    ///
+    /// `srcset` / `webp_srcset` 可直接用于 `<img srcset>` 和 `<picture><source>`，
+    /// 封面不是本站上传的图片或变体尚未生成时为 None。
+    #[derive(Debug, Serialize)]
+    pub struct CoverImage {
+        pub url: String,                 // 原图地址
+        pub width: Option<i32>,
+        pub height: Option<i32>,
+        pub srcset: Option<String>,      // 原格式各尺寸，例如 "https://.../a-320w.jpg 320w, https://.../a.jpg 1600w"
+        pub webp_srcset: Option<String>, // WebP 各尺寸
+    }
+
+    /// 文章摘要结构体 (用于列表响应)
+    /// 不包含正文，只包含列表展示所需的轻量信息。
+    #[derive(Debug, Serialize)]
+    pub struct PostSummary {
+        pub id: i32,
+        pub title: String,
+        pub excerpt: String,            // 作者填写的摘要，未填写时根据正文自动生成
+        pub category: String,
+        pub author_id: i32,
+        pub is_published: bool,
+        pub view_count: i32,
+        pub cover_url: Option<String>,
+        pub word_count: u32,            // 字数（中日韩文字按字计，其余按词计）
+        pub reading_time_minutes: u32,  // 预计阅读时间（分钟）
+        pub created_at: NaiveDateTime,
+        pub updated_at: NaiveDateTime,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub author: Option<AuthorInfo>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub comment_count: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub favorite_count: Option<u64>,
+    }
+
+    /// 创建文章请求体：POST /api/post
+    #[derive(Debug, Serialize, Deserialize, Validate)]
+    pub struct CreatePostRequest {
+        #[validate(length(min = 1, max = 255, message = "validation-title-length"))]
+        pub title: String,
+        #[validate(length(min = 1, message = "validation-content-required"))]
+        pub content_markdown: String,
+        #[validate(length(min = 1, max = 50, message = "validation-category-length"))]
+        pub category: String,
+        pub is_published: Option<bool>, // 可选，默认为 false (草稿)
+        pub cover_url: Option<String>,
+        #[validate(length(max = 500, message = "validation-summary-length"))]
+        pub excerpt: Option<String>, // 可选，作者自定义摘要，覆盖自动生成的摘要
+    }
+
+    /// 更新文章请求体：PUT /api/post/:id
+    #[derive(Debug, Serialize, Deserialize, Validate)]
+    pub struct UpdatePostRequest {
+        // 文章ID通过路径参数传递
+        #[validate(length(min = 1, max = 255, message = "validation-title-length"))]
+        pub title: Option<String>,
+        #[validate(length(min = 1, message = "validation-content-required"))]
+        pub content_markdown: Option<String>,
+        #[validate(length(min = 1, max = 50, message = "validation-category-length"))]
+        pub category: Option<String>,
+        pub is_published: Option<bool>,
+        pub cover_url: Option<String>,
+        #[validate(length(max = 500, message = "validation-summary-length"))]
+        pub excerpt: Option<String>, // 传入空字符串表示清除自定义摘要，恢复自动生成
+    }
+
+    /// 订阅源查询参数：GET /feed.xml?mode=excerpt
+    #[derive(Debug, Deserialize, Default)]
+    pub struct FeedRequest {
+        pub mode: Option<String>, // "full"（默认，输出完整正文）或 "excerpt"（只输出摘要）
+    }
+
+    /// 公开列表页面的查询参数：GET /category/:category?page=2
+    #[derive(Debug, Deserialize, Default)]
+    pub struct PageListRequest {
+        pub page: Option<u64>,
+    }
+
+    /// 获取文章详情的查询参数：GET /api/post/:id
+    #[derive(Debug, Serialize, Deserialize, Default)]
+    pub struct PostDetailRequest {
+        pub format: Option<String>, // 内容格式："markdown"（默认）或 "html"
+        pub include: Option<String>, // 逗号分隔的展开项：author、counts
+    }
+
+    /// 高亮主题信息 (用于响应)
+    #[derive(Debug, Serialize)]
+    pub struct HighlightThemeInfo {
+        pub slug: String, // 主题标识，用于获取样式表
+        pub name: String, // 主题名称
+    }
+
+    /// 高亮主题列表响应体：GET /api/render/themes
+    #[derive(Debug, Serialize)]
+    pub struct HighlightThemeListResponse {
+        pub success: bool,
+        pub default_theme: String,
+        pub themes: Vec<HighlightThemeInfo>,
+    }
 
-/// 创建文章请求体：POST /posts
-#[derive(Debug, Serialize, Deserialize, Validate)]
-pub struct CreatePostRequest {
-    #[validate(length(min = 1, max = 255, message = "标题长度必须在1到255个字符之间"))]
-    pub title: String,
-    #[validate(length(min = 1, message = "文章内容不能为空"))]
-    pub content_markdown: String,
-    #[validate(length(min = 1, max = 50, message = "分类长度必须在1到50个字符之间"))]
-    pub category: String,
-    pub is_published: Option<bool>, // 可选，默认为 false (草稿)
-    pub cover_url: Option<String>,
+    /// 搜索/分页获取文章列表请求体：GET /api/post
+    ///
+    /// 带 `cursor` 参数时使用游标分页，否则使用 `page` / `limit` 分页 (兼容模式)。
+    /// 只接受这里声明的参数，未知参数会返回验证错误 (见 `handler::query::StrictQuery`)。
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    pub struct PostListRequest {
+        #[serde(default = "default_page")] // 默认页码 1
+        pub page: Option<u64>,
+        #[serde(default = "default_limit")] // 默认每页数量 10
+        pub limit: Option<u64>,
+        pub category: Option<String>, // 按分类过滤，多个分类用逗号分隔
+        pub query: Option<String>,   // 搜索关键词
+        pub published_only: Option<bool>, // 只看已发布的文章，管理员可能查看所有
+        pub author_id: Option<i32>, // 按作者过滤，用于草稿箱功能
+        pub cursor: Option<String>, // 上一次响应中的 next_cursor / prev_cursor，空字符串表示第一页
+        pub include_total: Option<bool>, // 游标分页时是否统计总数 (需要额外的 COUNT 查询)
+        pub sort: Option<PostSort>, // 排序方式，默认 newest；游标分页只支持 newest
+        pub from: Option<chrono::DateTime<chrono::Utc>>, // 创建时间起点 (RFC 3339，包含)
+        pub to: Option<chrono::DateTime<chrono::Utc>>,   // 创建时间终点 (RFC 3339，不包含)
+        pub has_cover: Option<bool>, // true 只看有封面的文章，false 只看没有封面的
+        pub include: Option<String>, // 逗号分隔的展开项：author (作者信息)、counts (评论数和收藏数)
+    }
 }
 
-/// 更新文章请求体：PUT /posts/:id
-#[derive(Debug, Serialize, Deserialize, Validate)]
-pub struct UpdatePostRequest {
-    // 文章ID通过路径参数传递
-    #[validate(length(min = 1, max = 255, message = "标题长度必须在1到255个字符之间"))]
-    pub title: Option<String>,
-    #[validate(length(min = 1, message = "文章内容不能为空"))]
-    pub content_markdown: Option<String>,
-    #[validate(length(min = 1, max = 50, message = "分类长度必须在1到50个字符之间"))]
-    pub category: Option<String>,
-    pub is_published: Option<bool>,
-    pub cover_url: Option<String>,
+/// 文章列表的排序方式，查询参数中使用 snake_case，例如 `sort=most_viewed`。
+///
+/// 除 `oldest` 外都是倒序，相同时按创建时间倒序。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostSort {
+    /// 最新创建
+    #[default]
+    Newest,
+    /// 最早创建
+    Oldest,
+    /// 浏览量最多
+    MostViewed,
+    /// 收藏最多
+    MostFavorited,
+    /// 评论最多
+    MostCommented,
+    /// 最近更新
+    RecentlyUpdated,
 }
 
-/// 搜索/分页获取文章列表请求体：GET /posts
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PostListRequest {
-    #[serde(default = "default_page")] // 默认页码 1
-    pub page: Option<u64>,
-    #[serde(default = "default_limit")] // 默认每页数量 10
-    pub limit: Option<u64>,
-    pub category: Option<String>, // 按分类过滤
-    pub query: Option<String>,   // 搜索关键词
-    pub published_only: Option<bool>, // 只看已发布的文章，管理员可能查看所有
-    pub author_id: Option<i32>, // 按作者过滤，用于草稿箱功能
+impl PostSort {
+    pub const ALL: [PostSort; 6] = [
+        PostSort::Newest,
+        PostSort::Oldest,
+        PostSort::MostViewed,
+        PostSort::MostFavorited,
+        PostSort::MostCommented,
+        PostSort::RecentlyUpdated,
+    ];
+
+    /// 查询参数中的取值。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PostSort::Newest => "newest",
+            PostSort::Oldest => "oldest",
+            PostSort::MostViewed => "most_viewed",
+            PostSort::MostFavorited => "most_favorited",
+            PostSort::MostCommented => "most_commented",
+            PostSort::RecentlyUpdated => "recently_updated",
+        }
+    }
 }
 
 // 辅助函数：提供默认值
 fn default_page() -> Option<u64> { Some(1) }
 fn default_limit() -> Option<u64> { Some(10) }
 
-/// 文章列表响应体
-#[derive(Debug, Serialize)]
-pub struct PostListResponse {
-    pub success: bool,
-    pub posts: Vec<PostInfo>,
-    pub total_pages: u64,
-    pub current_page: u64,
-    pub total_posts: u64,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub message: Option<String>,
+api_model! {
+    /// 文章列表响应体
+    ///
+    /// 页码和总页数只在兼容模式下返回；总数在兼容模式下总是返回，游标分页时需要 `include_total=true`。
+    #[derive(Debug, Serialize)]
+    pub struct PostListResponse {
+        pub success: bool,
+        pub posts: Vec<PostSummary>, // 列表只返回摘要，正文请通过详情接口获取
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub total_pages: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub current_page: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub total_posts: Option<u64>,
+        pub next_cursor: Option<String>, // 没有更多数据时为 null
+        pub prev_cursor: Option<String>, // 第一页时为 null
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub message: Option<String>,
+    }
 }
 
 
 // ======================== 帖子收藏相关 DTOs ========================
 
-/// 切换收藏状态请求体：POST /post_fav
-#[derive(Debug, Serialize, Deserialize, Validate)]
-pub struct ToggleFavoriteRequest {
-    #[validate(range(min = 1, message = "文章ID无效"))]
-    pub post_id: i32,
-}
+api_model! {
+    /// 切换收藏状态请求体：POST /api/post_fav
+    #[derive(Debug, Serialize, Deserialize, Validate)]
+    pub struct ToggleFavoriteRequest {
+        #[validate(range(min = 1, message = "validation-post-id"))]
+        pub post_id: i32,
+    }
 
-/// 帖子收藏列表响应体：GET /post_fav/my/list
-#[derive(Debug, Serialize)]
-pub struct FavoriteListResponse {
-    pub success: bool,
-    pub favorites: Vec<PostInfo>, // 返回收藏的文章信息
-    pub total_pages: u64,
-    pub current_page: u64,
-    pub total_favorites: u64,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub message: Option<String>,
+    /// 帖子收藏列表响应体：GET /api/post_fav/my/list
+    #[derive(Debug, Serialize)]
+    pub struct FavoriteListResponse {
+        pub success: bool,
+        pub favorites: Vec<PostSummary>, // 返回收藏的文章摘要，按收藏时间倒序
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub total_pages: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub current_page: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub total_favorites: Option<u64>,
+        pub next_cursor: Option<String>,
+        pub prev_cursor: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub message: Option<String>,
+    }
 }
 
 
 // ======================== 评论相关 DTOs ========================
 
-/// 评论信息结构体 (用于响应)
-#[derive(Debug, Serialize)]
-pub struct CommentInfo {
-    pub id: i32,
-    pub content: String,
-    pub post_id: i32,
-    pub user_id: i32,
-    pub parent_id: Option<i32>, // 父评论 ID，可选
-    pub created_at: NaiveDateTime,
-    // 可以在这里添加用户信息，例如：
-    // pub username: String,
-}
+api_model! {
+    /// 评论信息结构体 (用于响应)
+    #[derive(Debug, Serialize)]
+    pub struct CommentInfo {
+        pub id: i32,
+        pub content: String,
+        pub post_id: i32,
+        pub user_id: i32,
+        pub parent_id: Option<i32>, // 父评论 ID，可选
+        pub created_at: NaiveDateTime,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub author: Option<AuthorInfo>, // 评论者信息，仅在 include=author 时返回
+    }
 
-/// 创建评论请求体：POST /comments
-#[derive(Debug, Serialize, Deserialize, Validate)]
-pub struct CreateCommentRequest {
-    #[validate(length(min = 1, max = 1000, message = "评论内容长度必须在1到1000个字符之间"))]
-    pub content: String,
-    #[validate(range(min = 1, message = "文章ID无效"))]
-    pub post_id: i32,            // 评论的文章 ID
-    pub parent_id: Option<i32>,  // 父评论 ID，可选，用于嵌套评论
-}
+    /// 创建评论请求体：POST /api/comment
+    #[derive(Debug, Serialize, Deserialize, Validate)]
+    pub struct CreateCommentRequest {
+        #[validate(length(min = 1, max = 1000, message = "validation-comment-length"))]
+        pub content: String,
+        #[validate(range(min = 1, message = "validation-post-id"))]
+        pub post_id: i32,            // 评论的文章 ID
+        pub parent_id: Option<i32>,  // 父评论 ID，可选，用于嵌套评论
+    }
 
-/// 更新评论请求体：PUT /comments/:id
-#[derive(Debug, Serialize, Deserialize, Validate)]
-pub struct UpdateCommentRequest {
-    #[validate(length(min = 1, max = 1000, message = "评论内容长度必须在1到1000个字符之间"))]
-    pub content: String,
-    // 评论 ID 通过路径参数传递
-}
+    /// 更新评论请求体：PUT /api/comment/:id
+    #[derive(Debug, Serialize, Deserialize, Validate)]
+    pub struct UpdateCommentRequest {
+        #[validate(length(min = 1, max = 1000, message = "validation-comment-length"))]
+        pub content: String,
+        // 评论 ID 通过路径参数传递
+    }
 
-/// 评论列表请求体：GET /posts/:post_id/comments
-/// 必须指定文章 ID 来获取评论列表。
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CommentListRequest {
-    // post_id 通常通过 Path 提取器获得，这里可以移除或作为可选字段
-    // pub post_id: i32, // 必须指定文章ID来获取评论
-    #[serde(default = "default_page")]
-    pub page: Option<u64>,
-    #[serde(default = "default_limit")]
-    pub limit: Option<u64>,
-}
+    /// 获取评论详情的查询参数：GET /api/comment/:id
+    #[derive(Debug, Serialize, Deserialize, Default)]
+    pub struct CommentDetailRequest {
+        pub include: Option<String>, // 展开项，只支持 author
+    }
+
+    /// 评论列表请求体：GET /api/post/:post_id/comments
+    /// 文章 ID 来自路径；分页方式与 `PostListRequest` 相同。
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct CommentListRequest {
+        // post_id 通常通过 Path 提取器获得，这里可以移除或作为可选字段
+        // pub post_id: i32, // 必须指定文章ID来获取评论
+        #[serde(default = "default_page")]
+        pub page: Option<u64>,
+        #[serde(default = "default_limit")]
+        pub limit: Option<u64>,
+        pub cursor: Option<String>,
+        pub include_total: Option<bool>,
+        pub include: Option<String>, // 展开项，评论只支持 author
+    }
 
-/// 评论列表响应体
-#[derive(Debug, Serialize)]
-pub struct CommentListResponse {
-    pub success: bool,
-    pub comments: Vec<CommentInfo>,
-    pub total_pages: u64,
-    pub current_page: u64,
-    pub total_comments: u64,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub message: Option<String>,
+    /// 评论列表响应体
+    #[derive(Debug, Serialize)]
+    pub struct CommentListResponse {
+        pub success: bool,
+        pub comments: Vec<CommentInfo>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub total_pages: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub current_page: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub total_comments: Option<u64>,
+        pub next_cursor: Option<String>,
+        pub prev_cursor: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub message: Option<String>,
+    }
 }
 
 
 // ======================== 通用响应 DTOs ========================
 
-/// 基本响应结构体，用于表示操作成功或失败，可带可选消息。
-#[derive(Debug, Serialize)]
-pub struct BaseResponse {
-    pub success: bool,
-    #[serde(skip_serializing_if = "Option::is_none")] // 如果为 None，则不序列化该字段
-    pub message: Option<String>,
-}
+api_model! {
+    /// 基本响应结构体，用于表示操作成功或失败，可带可选消息。
+    #[derive(Debug, Serialize)]
+    pub struct BaseResponse {
+        pub success: bool,
+        #[serde(skip_serializing_if = "Option::is_none")] // 如果为 None，则不序列化该字段
+        pub message: Option<String>,
+    }
 
-/// 带 ID 的基本响应结构体，通常用于创建资源后返回新资源的 ID。
-#[derive(Debug, Serialize)]
-pub struct IdResponse {
-    pub success: bool,
-    pub id: i32,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub message: Option<String>,
+    /// 带 ID 的基本响应结构体，通常用于创建资源后返回新资源的 ID。
+    #[derive(Debug, Serialize)]
+    pub struct IdResponse {
+        pub success: bool,
+        pub id: i32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub message: Option<String>,
+    }
 }
 
 // 为 BaseResponse 实现 IntoResponse trait
@@ -322,3 +535,121 @@ impl IntoResponse for IdResponse {
         Json(self).into_response()
     }
 }
+
+// ======================== 媒体文件相关 ========================
+
+api_model! {
+    /// 媒体文件信息 (用于响应)
+    #[derive(Debug, Serialize)]
+    pub struct MediaInfo {
+        pub id: i32,
+        pub url: String,                   // 公开访问地址
+        pub storage_key: String,
+        pub original_name: Option<String>,
+        pub mime_type: String,
+        pub size_bytes: i64,
+        pub checksum: String,              // SHA-256 (十六进制)
+        pub width: Option<i32>,
+        pub height: Option<i32>,
+        pub variants: Option<Vec<MediaVariantInfo>>, // 自动生成的变体，None 表示仍在后台处理
+        pub created_at: NaiveDateTime,
+    }
+
+    /// 媒体文件变体信息
+    #[derive(Debug, Serialize)]
+    pub struct MediaVariantInfo {
+        pub url: String,
+        pub width: u32,
+        pub height: u32,
+        pub mime_type: String,
+        pub size_bytes: u64,
+    }
+
+    /// 上传媒体文件响应体
+    #[derive(Debug, Serialize)]
+    pub struct MediaUploadResponse {
+        pub success: bool,
+        pub message: Option<String>,
+        pub media: MediaInfo,
+    }
+
+    /// 当前用户的媒体文件列表响应体
+    #[derive(Debug, Serialize)]
+    pub struct MediaListResponse {
+        pub success: bool,
+        pub media: Vec<MediaInfo>,
+        pub used_bytes: i64,   // 已使用的存储空间
+        pub quota_bytes: u64,  // 存储配额
+    }
+
+    /// 存活检查响应体：GET /healthz
+    #[derive(Debug, Serialize)]
+    pub struct HealthResponse {
+        pub status: &'static str, // 固定为 "ok"
+    }
+
+    /// 就绪检查响应体：GET /readyz
+    #[derive(Debug, Serialize)]
+    pub struct ReadinessResponse {
+        pub ready: bool,
+        pub database: String,                  // "ok" 或 "unavailable" (错误详情只写入日志)
+        pub pending_migrations: Option<usize>, // 尚未执行的迁移数量，无法读取时为 None
+        pub shutting_down: bool,               // 正在停机时不再接收新流量
+    }
+
+    /// 版本信息响应体：GET /api/version
+    #[derive(Debug, Serialize)]
+    pub struct VersionResponse {
+        pub name: &'static str,
+        pub version: &'static str,
+        pub git_commit: &'static str,             // 构建时的 Git 提交，未知时为 "unknown"
+        pub profile: &'static str,                // debug 或 release
+        pub database_backends: Vec<&'static str>, // 编译进来的数据库驱动
+    }
+}
+
+
+// ======================== 审计日志相关 ========================
+
+// 审计日志查询请求 (GET /api/admin/audit)
+api_model! {
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct AuditLogRequest {
+        pub actor_id: Option<i32>,  // 按操作者过滤
+        pub action: Option<String>, // 按动作过滤，例如 user.update
+        pub from: Option<chrono::DateTime<chrono::Utc>>, // 起始时间 (RFC 3339，包含)
+        pub to: Option<chrono::DateTime<chrono::Utc>>,   // 结束时间 (RFC 3339，不包含)
+        pub page: Option<u64>,
+        pub limit: Option<u64>,
+        pub format: Option<String>, // "csv" 时导出全部符合条件的记录
+    }
+}
+
+// 审计记录 (用于响应)
+api_model! {
+    #[derive(Debug, Serialize)]
+    pub struct AuditEntryInfo {
+        pub id: i64,
+        pub actor_id: Option<i32>,
+        pub actor_name: Option<String>,
+        pub action: String,
+        pub target_type: Option<String>,
+        pub target_id: Option<String>,
+        pub changes: Option<serde_json::Value>, // 变更前后的字段差异
+        pub ip: Option<String>,
+        pub request_id: Option<String>,
+        pub created_at: NaiveDateTime,
+    }
+}
+
+// 审计日志列表响应
+api_model! {
+    #[derive(Debug, Serialize)]
+    pub struct AuditLogResponse {
+        pub success: bool,
+        pub entries: Vec<AuditEntryInfo>,
+        pub total_pages: u64,
+        pub current_page: u64,
+        pub total_entries: u64,
+    }
+}
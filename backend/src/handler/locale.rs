@@ -0,0 +1,47 @@
+//! 响应消息语言协商中间件。
+//!
+//! 依次使用查询参数 `lang`、Cookie `lang`（前端保存的用户偏好）和 `Accept-Language` 头，
+//! 都没有或不受支持时使用默认语言（简体中文）。
+
+use axum::{extract::Request, http::header, middleware::Next, response::Response};
+
+use crate::i18n::{with_locale, Locale};
+
+/// 查询参数和 Cookie 中保存语言偏好的名称。
+pub const LANG_PARAM: &str = "lang";
+
+/// 在协商出的语言的上下文中运行后续处理 (`Router::layer`)。
+pub async fn negotiate_locale(request: Request, next: Next) -> Response {
+    let locale = request_locale(&request).unwrap_or_default();
+    with_locale(locale, next.run(request)).await
+}
+
+/// 按优先级从请求中读取语言偏好。
+pub fn request_locale(request: &Request) -> Option<Locale> {
+    let from_query = request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(name, _)| *name == LANG_PARAM)
+            .and_then(|(_, value)| Locale::parse(value))
+    });
+    let from_cookie = || {
+        request
+            .headers()
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(';'))
+            .filter_map(|pair| pair.trim().split_once('='))
+            .find(|(name, _)| *name == LANG_PARAM)
+            .and_then(|(_, value)| Locale::parse(value))
+    };
+    let from_header = || {
+        request
+            .headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(Locale::from_accept_language)
+    };
+    from_query.or_else(from_cookie).or_else(from_header)
+}
@@ -0,0 +1,407 @@
+//! 媒体文件相关的 HTTP 请求处理函数。
+//! 提供上传 (multipart)、列出、删除接口，以及支持 `Range` 请求的公开文件访问。
+
+use axum::{
+    extract::{DefaultBodyLimit, Multipart, Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{AppState, Config};
+use crate::audit::{self, diff, AuditEvent};
+use crate::database::media::{media_variants, Model as MediaModel, NewMedia};
+use crate::error::{CustomError, CustomResult, ErrorCode};
+use crate::handler::audit::{record_audit, ClientIp};
+use crate::handler::idl::{
+    BaseResponse, CoverImage, MediaInfo, MediaListResponse, MediaUploadResponse, MediaVariantInfo,
+};
+use crate::handler::user::AuthUser;
+use crate::openapi::{ApiRouter, ApiSchema, Components, Operation};
+use crate::media::{image_dimensions, process_variants, sniff_media_type, strip_metadata, MediaVariant};
+use crate::storage::{parse_range_header, validate_key};
+use crate::i18n::{t, t_args};
+
+/// 非文件字段允许的最大长度（字节）。
+const MAX_TEXT_FIELD_BYTES: usize = 1024;
+
+/// 将媒体记录转换为响应 DTO。
+fn convert_media_model_to_media_info(config: &Config, media: MediaModel) -> MediaInfo {
+    let variants = media_variants(&media).map(|variants| {
+        variants
+            .into_iter()
+            .map(|v| MediaVariantInfo {
+                url: config.media_url(&v.storage_key),
+                width: v.width,
+                height: v.height,
+                mime_type: v.mime_type,
+                size_bytes: v.size_bytes,
+            })
+            .collect()
+    });
+    MediaInfo {
+        id: media.id,
+        url: config.media_url(&media.storage_key),
+        storage_key: media.storage_key,
+        original_name: media.original_name,
+        mime_type: media.mime_type,
+        size_bytes: media.size_bytes,
+        checksum: media.checksum,
+        width: media.width,
+        height: media.height,
+        variants,
+        created_at: media.created_at,
+    }
+}
+
+/// 生成 `srcset` 属性值，例如 `"https://.../a-320w.jpg 320w, https://.../a.jpg 1600w"`。
+fn build_srcset<'a>(config: &Config, entries: impl Iterator<Item = (&'a str, u32)>) -> Option<String> {
+    let parts: Vec<String> = entries
+        .map(|(key, width)| format!("{} {}w", config.media_url(key), width))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// 根据封面地址和对应的媒体记录构建封面信息。
+///
+/// # 参数
+/// * `config` - 应用配置。
+/// * `cover_url` - 文章的封面地址。
+/// * `media` - 封面为本站上传的图片时，对应的媒体记录。
+pub fn build_cover_image(config: &Config, cover_url: &str, media: Option<&MediaModel>) -> CoverImage {
+    let Some(media) = media else {
+        return CoverImage { url: cover_url.to_string(), width: None, height: None, srcset: None, webp_srcset: None };
+    };
+
+    let variants = media_variants(media).unwrap_or_default();
+    let mut same_format: Vec<&MediaVariant> = variants.iter().filter(|v| v.mime_type == media.mime_type).collect();
+    let mut webp: Vec<&MediaVariant> = variants.iter().filter(|v| v.mime_type == "image/webp").collect();
+    same_format.sort_by_key(|v| v.width);
+    webp.sort_by_key(|v| v.width);
+
+    // 原图本身也是 srcset 中最大的一项
+    let original = media.width.map(|width| (media.storage_key.as_str(), width as u32));
+    let srcset = if same_format.is_empty() {
+        None
+    } else {
+        build_srcset(config, same_format.iter().map(|v| (v.storage_key.as_str(), v.width)).chain(original))
+    };
+    let webp_srcset = if media.mime_type == "image/webp" {
+        None
+    } else {
+        build_srcset(config, webp.iter().map(|v| (v.storage_key.as_str(), v.width)))
+    };
+
+    CoverImage {
+        url: config.media_url(&media.storage_key),
+        width: media.width,
+        height: media.height,
+        srcset,
+        webp_srcset,
+    }
+}
+
+/// 解析文章的封面：本站上传的图片附带尺寸和响应式版本，外部地址原样返回。
+pub async fn resolve_cover(state: &AppState, cover_url: Option<&str>) -> CustomResult<Option<CoverImage>> {
+    let Some(cover_url) = cover_url.filter(|url| !url.is_empty()) else {
+        return Ok(None);
+    };
+    let media = match state.config.media_key_from_url(cover_url) {
+        Some(key) => state.media_repo.get_media_by_key(key).await?,
+        None => None,
+    };
+    Ok(Some(build_cover_image(&state.config, cover_url, media.as_ref())))
+}
+
+/// 将 multipart 解析错误转换为请求错误。
+fn multipart_error(e: axum::extract::multipart::MultipartError) -> CustomError {
+    CustomError::BadRequest(t_args("media-invalid-multipart", &[("error", &e)]))
+}
+
+/// 上传媒体文件 (POST /api/media)
+/// 需要用户认证。请求体为 `multipart/form-data`，文件放在 `file` 字段中。
+///
+/// 文件边读取边检查大小，超过单文件上限或用户配额时返回 413；
+/// 文件类型根据文件头识别，不在允许列表中时返回 415。
+/// 保存前清除 EXIF / GPS 等元数据，保存后在后台生成各尺寸和 WebP 版本。
+pub async fn upload_media(
+    AuthUser(claims): AuthUser,
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> CustomResult<(StatusCode, Json<MediaUploadResponse>)> {
+    let user_id = claims.sub.parse::<i32>()
+        .map_err(|_| CustomError::Unauthorized(t("auth-invalid-user-id")))?;
+
+    let max_bytes = state.config.upload_max_bytes;
+    let mut upload: Option<(Option<String>, Vec<u8>)> = None;
+
+    while let Some(mut field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() == Some("file") {
+            if upload.is_some() {
+                return Err(CustomError::BadRequest(t("media-single-file")));
+            }
+            let original_name = field.file_name().map(|name| name.to_string());
+            let mut data = Vec::new();
+            while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+                if (data.len() + chunk.len()) as u64 > max_bytes {
+                    return Err(CustomError::PayloadTooLarge(t_args("media-file-too-large", &[("max", &max_bytes)])));
+                }
+                data.extend_from_slice(&chunk);
+            }
+            upload = Some((original_name, data));
+        } else {
+            // 其他字段不使用，但同样限制大小，避免借此绕过上传上限
+            let mut size = 0;
+            while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+                size += chunk.len();
+                if size > MAX_TEXT_FIELD_BYTES {
+                    return Err(CustomError::PayloadTooLarge(t("media-field-too-large")));
+                }
+            }
+        }
+    }
+
+    let (original_name, data) = upload
+        .ok_or_else(|| CustomError::BadRequest(t("media-missing-file")))?;
+    if data.is_empty() {
+        return Err(CustomError::BadRequest(t("media-empty-file")));
+    }
+
+    // 提前拒绝明显超出配额的上传，避免无谓地写入存储；最终以写入记录时的检查为准
+    let used_bytes = state.media_repo.get_used_bytes(user_id).await?;
+    if used_bytes as u64 + data.len() as u64 > state.config.media_quota_bytes {
+        return Err(CustomError::coded(ErrorCode::MediaQuotaExceeded, t_args("media-quota-exceeded", &[
+            ("used", &used_bytes),
+            ("quota", &state.config.media_quota_bytes),
+        ])));
+    }
+
+    let media_type = sniff_media_type(&data).ok_or_else(|| {
+        CustomError::UnsupportedMediaType(t("media-unsupported-type"))
+    })?;
+    // 图片解码与重新编码是 CPU 密集型操作，放到阻塞线程池中执行
+    let (data, width, height, checksum) = tokio::task::spawn_blocking(move || {
+        let data = strip_metadata(&data, media_type)?;
+        let (width, height) = match image_dimensions(&data) {
+            Some((width, height)) => (Some(width as i32), Some(height as i32)),
+            None => (None, None),
+        };
+        let checksum: String = Sha256::digest(&data).iter().map(|b| format!("{:02x}", b)).collect();
+        Ok::<_, CustomError>((data, width, height, checksum))
+    })
+    .await
+    .map_err(|e| CustomError::InternalServerError(format!("图片处理任务异常退出: {}", e)))??;
+    let storage_key = format!("{}.{}", uuid::Uuid::new_v4(), media_type.extension);
+
+    state.storage.put(&storage_key, &data, media_type.mime_type).await?;
+
+    let new_media = NewMedia {
+        owner_id: user_id,
+        storage_key: storage_key.clone(),
+        original_name,
+        mime_type: media_type.mime_type.to_string(),
+        size_bytes: data.len() as i64,
+        checksum,
+        width,
+        height,
+    };
+    let media = match state.media_repo.create_media_within_quota(new_media, state.config.media_quota_bytes).await {
+        Ok(media) => media,
+        Err(e) => {
+            // 超出配额或数据库写入失败时清理已保存的文件，避免产生孤儿文件
+            if let Err(cleanup) = state.storage.delete(&storage_key).await {
+                tracing::warn!("清理上传文件 {} 失败: {}", storage_key, cleanup);
+            }
+            return Err(e);
+        }
+    };
+
+    // 在后台生成响应式版本，不阻塞上传响应
+    let (storage, media_repo, media_id) = (state.storage.clone(), state.media_repo.clone(), media.id);
+    let (widths, shutdown) = (state.config.image_variant_widths.clone(), state.tasks.shutdown_token());
+    state.tasks.spawn(async move {
+        if let Err(e) = process_variants(storage, media_repo, media_id, storage_key, data, media_type, widths, shutdown).await {
+            tracing::warn!("媒体 {} 的变体处理失败: {}", media_id, e);
+        }
+    });
+
+    Ok((
+        StatusCode::CREATED,
+        Json(MediaUploadResponse {
+            success: true,
+            message: Some(t("media-uploaded")),
+            media: convert_media_model_to_media_info(&state.config, media),
+        }),
+    ))
+}
+
+/// 获取当前用户的媒体文件列表及存储用量 (GET /api/media)
+/// 需要用户认证。
+pub async fn get_my_media(
+    AuthUser(claims): AuthUser,
+    State(state): State<AppState>,
+) -> CustomResult<Json<MediaListResponse>> {
+    let user_id = claims.sub.parse::<i32>()
+        .map_err(|_| CustomError::Unauthorized(t("auth-invalid-user-id")))?;
+
+    let media = state.media_repo.get_media_by_owner(user_id).await?;
+    let used_bytes = state.media_repo.get_used_bytes(user_id).await?;
+
+    Ok(Json(MediaListResponse {
+        success: true,
+        media: media
+            .into_iter()
+            .map(|m| convert_media_model_to_media_info(&state.config, m))
+            .collect(),
+        used_bytes,
+        quota_bytes: state.config.media_quota_bytes,
+    }))
+}
+
+/// 删除媒体文件 (DELETE /api/media/:media_id)
+/// 需要用户认证，只有上传者或管理员可以删除。
+pub async fn delete_media(
+    AuthUser(claims): AuthUser,
+    State(state): State<AppState>,
+    Path(media_id): Path<i32>,
+    client: ClientIp,
+) -> CustomResult<Json<BaseResponse>> {
+    let user_id = claims.sub.parse::<i32>()
+        .map_err(|_| CustomError::Unauthorized(t("auth-invalid-user-id")))?;
+
+    let media = state.media_repo.get_media_by_id(media_id).await?
+        .ok_or_else(|| CustomError::coded(ErrorCode::MediaNotFound, t_args("media-id-not-found", &[("id", &media_id)])))?;
+
+    if media.owner_id != user_id && claims.role != "admin" {
+        return Err(CustomError::Forbidden(t("forbidden-delete-media")));
+    }
+
+    // 先删记录再删文件：文件删除失败只会留下孤儿文件，不会出现指向不存在文件的记录
+    state.media_repo.delete_media(media_id).await?;
+    for variant in media_variants(&media).unwrap_or_default() {
+        state.storage.delete(&variant.storage_key).await?;
+    }
+    state.storage.delete(&media.storage_key).await?;
+    // 管理员删除他人的媒体文件时记录审计日志
+    if media.owner_id != user_id {
+        record_audit(&state, &client, AuditEvent::new(audit::MEDIA_DELETE)
+            .by(&claims)
+            .target("media", media_id)
+            .changes(diff(Some(&media), None))).await;
+    }
+
+    Ok(Json(BaseResponse {
+        success: true,
+        message: Some(t("media-deleted")),
+    }))
+}
+
+/// 公开访问媒体文件 (GET /media/*key)
+///
+/// 支持单段 `Range` 请求（返回 206），范围无法满足时返回 416。
+/// 存储键包含随机 UUID，内容不会变化，因此使用长期缓存。
+/// 存储后端提供预签名地址时返回 307 重定向。
+pub async fn serve_media(
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+    headers: HeaderMap,
+) -> CustomResult<Response> {
+    validate_key(&key).map_err(|_| CustomError::coded(ErrorCode::MediaNotFound, t("media-not-found")))?;
+
+    // 对象存储支持预签名地址时，让客户端直接从存储服务下载 (Range 请求由存储服务处理)
+    if let Some(url) = state.storage.presigned_url(&key).await? {
+        return Ok((
+            StatusCode::TEMPORARY_REDIRECT,
+            [(header::LOCATION, url), (header::CACHE_CONTROL, "no-store".to_string())],
+        )
+            .into_response());
+    }
+
+    let total = state.storage.size(&key).await?
+        .ok_or_else(|| CustomError::coded(ErrorCode::MediaNotFound, t("media-not-found")))?;
+
+    let range = match headers.get(header::RANGE).and_then(|v| v.to_str().ok()) {
+        Some(value) => match parse_range_header(value, total) {
+            Ok(range) => range,
+            Err(_) => {
+                return Ok((
+                    StatusCode::RANGE_NOT_SATISFIABLE,
+                    [(header::CONTENT_RANGE, format!("bytes */{}", total))],
+                )
+                    .into_response());
+            }
+        },
+        None => None,
+    };
+
+    let body = state.storage.get_stream(&key, range).await?
+        .ok_or_else(|| CustomError::coded(ErrorCode::MediaNotFound, t("media-not-found")))?;
+
+    let content_type = mime_guess::from_path(&key).first_or_octet_stream();
+    let mut response = body.into_response();
+    let response_headers = response.headers_mut();
+    response_headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_str(content_type.as_ref())
+            .unwrap_or_else(|_| HeaderValue::from_static("application/octet-stream")),
+    );
+    response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=31536000, immutable"),
+    );
+    response_headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+
+    if let Some(range) = range {
+        *response.status_mut() = StatusCode::PARTIAL_CONTENT;
+        if let Ok(value) = HeaderValue::from_str(&format!("bytes {}-{}/{}", range.start, range.end, total)) {
+            response.headers_mut().insert(header::CONTENT_RANGE, value);
+        }
+    }
+    Ok(response)
+}
+
+/// 上传接口的 `multipart/form-data` 请求体。
+fn upload_form_schema(_: &mut Components) -> Value {
+    json!({
+        "type": "object",
+        "properties": { "file": { "type": "string", "contentMediaType": "application/octet-stream" } },
+        "required": ["file"],
+    })
+}
+
+/// 媒体文件管理路由配置函数，挂载在 `/api/media` 下。
+pub fn media_routes() -> ApiRouter {
+    ApiRouter::new("media")
+        // POST /api/media - 上传文件 (大小由处理函数按配置逐块检查，因此关闭默认的请求体限制)
+        // GET /api/media - 当前用户的媒体文件及用量
+        .route(
+            "/",
+            get(get_my_media).post(upload_media).layer(DefaultBodyLimit::disable()),
+            [
+                Operation::get("获取我的媒体文件及存储用量").auth().json::<MediaListResponse>(),
+                Operation::post("上传媒体文件")
+                    .description("超过单文件上限或用户配额时返回 413，文件类型不在允许列表中时返回 415。")
+                    .auth()
+                    .request_body("multipart/form-data", upload_form_schema)
+                    .response(201, "已上传", Some(("application/json", MediaUploadResponse::schema))),
+            ],
+        )
+        // DELETE /api/media/:media_id - 删除媒体文件
+        .route("/:media_id", delete(delete_media),
+            Operation::delete("删除媒体文件")
+                .description("只有上传者或管理员可以删除。")
+                .auth()
+                .path_param::<i32>("media_id", "媒体文件 ID")
+                .json::<BaseResponse>())
+}
+
+/// 媒体文件公开访问路由，挂载在站点根路径。
+pub fn media_file_routes() -> Router<AppState> {
+    Router::new()
+        // GET /media/*key - 读取文件内容，支持 Range 请求
+        .route("/media/*key", get(serve_media))
+}
@@ -0,0 +1,87 @@
+//! Prometheus 指标的 HTTP 请求处理函数与请求计时中间件。
+//!
+//! 计时中间件 `track_metrics` 作为最外层的 `layer` 包裹整个路由，
+//! `tag_matched_route` 作为 `route_layer` 把匹配到的路由模板带回响应，
+//! 这样标签使用 `/api/post/:id` 这样的模板而不是实际路径；未匹配的请求统一记为 `<unmatched>`。
+
+use std::time::Instant;
+
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+
+use crate::error::{CustomError, CustomResult};
+use crate::metrics::UNMATCHED_ROUTE;
+use crate::AppState;
+use crate::i18n::t;
+
+/// 响应扩展中携带的路由模板。
+#[derive(Clone)]
+struct MatchedRoute(String);
+
+/// 记录请求数量与耗时的中间件，需要放在所有路由之外 (`Router::layer`)。
+pub async fn track_metrics(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let started = Instant::now();
+    let response = next.run(request).await;
+
+    let route = response
+        .extensions()
+        .get::<MatchedRoute>()
+        .map(|r| r.0.as_str())
+        .unwrap_or(UNMATCHED_ROUTE);
+    state.metrics.observe_http(
+        method.as_str(),
+        route,
+        response.status().as_u16(),
+        started.elapsed().as_secs_f64(),
+    );
+    response
+}
+
+/// 把匹配到的路由模板放进响应扩展，供 `track_metrics` 读取 (`Router::route_layer`)。
+pub async fn tag_matched_route(request: Request, next: Next) -> Response {
+    let route = request.extensions().get::<MatchedPath>().map(|p| p.as_str().to_string());
+    let mut response = next.run(request).await;
+    if let Some(route) = route {
+        response.extensions_mut().insert(MatchedRoute(route));
+    }
+    response
+}
+
+/// 输出 Prometheus 文本格式的指标 (GET /metrics)
+/// 配置了 `metrics.token` 时需要携带 `Authorization: Bearer <token>`，令牌按常数时间比较。
+pub async fn get_metrics(State(state): State<AppState>, headers: HeaderMap) -> CustomResult<Response> {
+    if let Some(token) = &state.config.metrics_token {
+        let provided = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        // 比较摘要而不是原文，耗时与令牌内容和长度都无关
+        let matches = provided
+            .is_some_and(|provided| bool::from(Sha256::digest(provided).ct_eq(&Sha256::digest(token))));
+        if !matches {
+            return Err(CustomError::Unauthorized(t("metrics-token-required")));
+        }
+    }
+
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        state.metrics.render(&state.db),
+    ).into_response())
+}
+
+/// 指标路由，挂载在站点根路径或独立的管理端口上
+pub fn metrics_routes() -> Router<AppState> {
+    Router::new()
+        .route("/metrics", get(get_metrics)) // GET /metrics
+}
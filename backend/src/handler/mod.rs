@@ -7,3 +7,17 @@ pub mod user; // 用户相关的 HTTP 请求处理函数
 pub mod post; // 文章相关的 HTTP 请求处理函数
 pub mod comment; // 评论相关的 HTTP 请求处理函数
 pub mod favorite; // 收藏相关的 HTTP 请求处理函数
+pub mod render; // 渲染相关的 HTTP 请求处理函数（高亮主题）
+pub mod feed; // 订阅源 (RSS / Atom / JSON Feed) 的 HTTP 请求处理函数
+pub mod sitemap; // 站点地图和 robots.txt 的 HTTP 请求处理函数
+pub mod page; // 服务端渲染的公开页面 (文章、分类、作者)
+pub mod assets; // 前端静态资源与 SPA 回退
+pub mod media; // 媒体文件上传、管理与公开访问
+pub mod health; // 存活 / 就绪检查与版本信息
+pub mod metrics; // Prometheus 指标输出与请求计时中间件
+pub mod request_id; // 把 X-Request-Id 带到错误响应体中的中间件
+pub mod audit; // 审计日志的写入辅助与管理员查询 / 导出
+pub mod problem; // 按 Accept 头选择错误响应格式 (JSON / problem+json) 的中间件
+pub mod locale; // 按 lang 参数、Cookie 和 Accept-Language 选择响应消息语言的中间件
+pub mod openapi; // 汇总 /api 路由并提供 OpenAPI 文档与交互式文档页面
+pub mod query; // 按 DTO 字段白名单解析查询参数、拒绝未知参数的提取器
@@ -0,0 +1,132 @@
+//! API 文档的 HTTP 请求处理函数。
+//! 汇总 `/api` 下的全部路由，提供由路由表生成的 OpenAPI 文档 (`/api/openapi.json`)
+//! 和交互式文档页面 (`/api/docs`)。
+
+use axum::{
+    extract::State,
+    http::header,
+    response::{Html, IntoResponse, Response},
+    routing::get,
+    Json,
+};
+use lazy_static::lazy_static;
+use serde_json::Value;
+
+use crate::handler::audit::audit_routes;
+use crate::handler::comment::{comment_routes, comment_routes_for_post};
+use crate::handler::favorite::favorite_routes;
+use crate::handler::health::version_routes;
+use crate::handler::media::media_routes;
+use crate::handler::post::post_routes;
+use crate::handler::render::render_routes;
+use crate::handler::user::user_routes;
+use crate::error::{CustomError, CustomResult};
+use crate::i18n::t;
+use crate::openapi::{ApiRouter, ApiSchema, Operation};
+use crate::AppState;
+
+lazy_static! {
+    /// 路由表在运行期间不会变化，文档只生成一次。
+    static ref OPENAPI_DOCUMENT: Value = api_routes().openapi("/api");
+}
+
+/// 交互式文档页面，使用 Scalar 渲染 `/api/openapi.json`。
+/// 脚本由本站提供 (`site.api_docs_bundle`)，页面不加载第三方 CDN 上的代码。
+const DOCS_HTML: &str = r#"<!doctype html>
+<html>
+<head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <title>Axum Blog Engine API</title>
+</head>
+<body>
+    <script id="api-reference" data-url="/api/openapi.json"></script>
+    <script src="/api/docs/scalar.js"></script>
+</body>
+</html>
+"#;
+
+/// 未配置 Scalar 脚本时的文档页面。
+const DOCS_UNAVAILABLE_HTML: &str = r#"<!doctype html>
+<html>
+<head>
+    <meta charset="utf-8" />
+    <title>Axum Blog Engine API</title>
+</head>
+<body>
+    <p>Interactive docs are not configured (site.api_docs_bundle). See <a href="/api/openapi.json">/api/openapi.json</a>.</p>
+</body>
+</html>
+"#;
+
+/// OpenAPI 3.1 文档 (GET /api/openapi.json)
+pub async fn get_openapi_document() -> impl IntoResponse {
+    Json(OPENAPI_DOCUMENT.clone())
+}
+
+/// 交互式 API 文档 (GET /api/docs)
+pub async fn get_api_docs(State(state): State<AppState>) -> Html<&'static str> {
+    if state.config.api_docs_bundle.is_some() {
+        Html(DOCS_HTML)
+    } else {
+        Html(DOCS_UNAVAILABLE_HTML)
+    }
+}
+
+/// 文档页面使用的 Scalar 脚本 (GET /api/docs/scalar.js)
+/// 从 `site.api_docs_bundle` 指向的本地文件读取，未配置时返回 404。
+pub async fn get_api_docs_bundle(State(state): State<AppState>) -> CustomResult<Response> {
+    let path = state.config.api_docs_bundle.as_ref()
+        .ok_or_else(|| CustomError::NotFound(t("api-docs-bundle-not-configured")))?;
+    let script = tokio::fs::read(path).await.map_err(|e| {
+        CustomError::InternalServerError(format!("读取 API 文档脚本 {} 失败: {}", path.display(), e))
+    })?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/javascript; charset=utf-8"),
+            // 升级脚本时替换文件即可生效
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        script,
+    ).into_response())
+}
+
+/// `/api` 下的全部路由，在 `src/bin/server.rs` 中挂载到 `/api`。
+///
+/// 新增的 API 路由需要在这里登记，文档会自动包含它们。
+pub fn api_routes() -> ApiRouter {
+    ApiRouter::new("api")
+        // 用户相关路由
+        .nest("/user", user_routes())
+        // 帖子相关路由
+        .nest("/post", post_routes())
+        // 文章评论路由 (嵌套在文章路由下)
+        .nest("/post/:post_id/comments", comment_routes_for_post())
+        // 独立的评论资源路由 (例如 POST /api/comment, GET /api/comment/:id)
+        .nest("/comment", comment_routes())
+        // 收藏相关路由
+        .nest("/post_fav", favorite_routes())
+        // 渲染相关路由 (代码高亮主题)
+        .nest("/render", render_routes())
+        // 媒体文件上传与管理
+        .nest("/media", media_routes())
+        // 管理后台 (审计日志查询与导出)
+        .nest("/admin", audit_routes())
+        // 构建与版本信息
+        .merge(version_routes())
+        // API 文档
+        .merge(docs_routes())
+}
+
+/// API 文档路由，挂载在 `/api` 下
+fn docs_routes() -> ApiRouter {
+    ApiRouter::new("meta")
+        .route("/openapi.json", get(get_openapi_document), // GET /api/openapi.json
+            Operation::get("OpenAPI 文档").json::<Value>())
+        .route("/docs", get(get_api_docs), // GET /api/docs
+            Operation::get("交互式 API 文档").response(200, "HTML 页面", Some(("text/html", String::schema))))
+        .route("/docs/scalar.js", get(get_api_docs_bundle), // GET /api/docs/scalar.js
+            Operation::get("交互式文档使用的 Scalar 脚本")
+                .description("从 `site.api_docs_bundle` 读取，未配置时返回 404。")
+                .response(200, "JavaScript", Some(("text/javascript", String::schema))))
+}
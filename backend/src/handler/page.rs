@@ -0,0 +1,175 @@
+//! 服务端渲染的公开页面处理函数。
+//! 为文章、分类和作者输出带有 OpenGraph / Twitter Card / JSON-LD 元数据的 HTML 页面，
+//! 便于搜索引擎收录和聊天软件生成链接预览；评论、收藏等交互仍由前端应用完成。
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use serde_json::json;
+use tracing::warn;
+
+use crate::AppState;
+use crate::database::post::post_excerpt;
+use crate::error::CustomResult;
+use crate::handler::idl::{PageListRequest, PostListRequest};
+use crate::handler::media::resolve_cover;
+use crate::handler::post::convert_post_model_to_post_info;
+use crate::render::highlight::default_theme_slug;
+use crate::seo::{list_page_meta, post_page_meta, PageMeta};
+
+/// 列表页面每页显示的文章数量。
+const PAGE_SIZE: u64 = 10;
+
+/// 站点信息，所有模板共用。
+fn site_context(state: &AppState) -> serde_json::Value {
+    json!({
+        "title": state.config.site_title,
+        "description": state.config.site_description,
+        "url": state.config.site_url,
+    })
+}
+
+/// 渲染 404 页面。
+fn not_found_page(state: &AppState, message: String) -> CustomResult<Response> {
+    let meta = list_page_meta(
+        &state.config,
+        "页面不存在",
+        &message,
+        format!("{}/", state.config.site_url),
+        format!("{}/feed.xml", state.config.site_url),
+    );
+    let html = state.theme.render("not_found.html", json!({
+        "site": site_context(state),
+        "meta": meta,
+        "message": message,
+        "highlight_theme": default_theme_slug(),
+    }))?;
+    Ok((StatusCode::NOT_FOUND, Html(html)).into_response())
+}
+
+/// 文章页面 (GET /post/:post_id)
+///
+/// 只展示已发布的文章，草稿返回 404。
+pub async fn post_page(
+    State(state): State<AppState>,
+    Path(post_id): Path<i32>,
+) -> CustomResult<Response> {
+    let post_model = match state.post_repo.get_post_by_id(post_id).await? {
+        Some(post) if post.is_published => post,
+        _ => return not_found_page(&state, format!("ID为 {} 的文章未找到", post_id)),
+    };
+
+    let rendered = state.post_repo.get_rendered_content(&post_model).await?;
+    if let Err(e) = state.post_repo.increment_view_count(post_id).await {
+        warn!("增加文章 {} 浏览量失败: {}", post_id, e);
+    }
+
+    let author_name = state.user_repo.get_user_by_id(post_model.author_id).await?
+        .map(|user| user.username)
+        .unwrap_or_default();
+    let description = post_excerpt(&post_model);
+    let reading_time_minutes = post_model.reading_time_minutes;
+
+    let mut post_info = convert_post_model_to_post_info(post_model);
+    post_info.content_html = Some(rendered.html);
+    post_info.toc = Some(rendered.toc);
+    post_info.cover = resolve_cover(&state, post_info.cover_url.as_deref()).await?;
+
+    let meta = post_page_meta(&state.config, &post_info, &description, &author_name);
+    let html = state.theme.render("post.html", json!({
+        "site": site_context(&state),
+        "meta": meta,
+        "post": post_info,
+        "author_name": author_name,
+        "category_url": state.config.category_url(&post_info.category),
+        "published_display": post_info.created_at.format("%Y-%m-%d").to_string(),
+        "reading_time_minutes": reading_time_minutes,
+        "highlight_theme": default_theme_slug(),
+    }))?;
+    Ok(Html(html).into_response())
+}
+
+/// 渲染分类页和作者页共用的文章列表。
+async fn list_page(
+    state: &AppState,
+    heading: String,
+    meta: PageMeta,
+    page: u64,
+    category: Option<String>,
+    author_id: Option<i32>,
+) -> CustomResult<Response> {
+    let (posts, total_pages, page, _) = state.post_repo.get_paginated_posts(PostListRequest {
+        page: Some(page),
+        limit: Some(PAGE_SIZE),
+        category,
+        query: None,
+        published_only: Some(true),
+        author_id,
+        ..Default::default()
+    }).await?;
+
+    let page_url = |page: u64| format!("{}?page={}", meta.canonical_url, page);
+    let html = state.theme.render("list.html", json!({
+        "site": site_context(state),
+        "heading": heading,
+        "posts": posts,
+        "prev_url": (page > 1).then(|| page_url(page - 1)),
+        "next_url": (page < total_pages).then(|| page_url(page + 1)),
+        "meta": meta,
+        "highlight_theme": default_theme_slug(),
+    }))?;
+    Ok(Html(html).into_response())
+}
+
+/// 分类页面 (GET /category/:category)
+pub async fn category_page(
+    State(state): State<AppState>,
+    Path(category): Path<String>,
+    Query(req): Query<PageListRequest>,
+) -> CustomResult<Response> {
+    let canonical_url = state.config.category_url(&category);
+    let meta = list_page_meta(
+        &state.config,
+        &format!("分类: {}", category),
+        &format!("{} 分类下的文章", category),
+        canonical_url.clone(),
+        format!("{}/feed.xml", canonical_url),
+    );
+    let heading = format!("分类: {}", category);
+    list_page(&state, heading, meta, req.page.unwrap_or(1).max(1), Some(category), None).await
+}
+
+/// 作者页面 (GET /author/:author_id)
+pub async fn author_page(
+    State(state): State<AppState>,
+    Path(author_id): Path<i32>,
+    Query(req): Query<PageListRequest>,
+) -> CustomResult<Response> {
+    let Some(author) = state.user_repo.get_user_by_id(author_id).await? else {
+        return not_found_page(&state, format!("ID为 {} 的用户未找到", author_id));
+    };
+
+    let canonical_url = state.config.author_url(author_id);
+    let meta = list_page_meta(
+        &state.config,
+        &format!("作者: {}", author.username),
+        &format!("{} 发布的文章", author.username),
+        canonical_url.clone(),
+        format!("{}/feed.xml", canonical_url),
+    );
+    let heading = format!("作者: {}", author.username);
+    list_page(&state, heading, meta, req.page.unwrap_or(1).max(1), None, Some(author_id)).await
+}
+
+/// 公开页面路由配置函数。
+/// 挂载在站点根路径；前端应用使用 hash 路由，不会与这些路径冲突。
+pub fn page_routes() -> Router<AppState> {
+    Router::new()
+        .route("/post/:post_id", get(post_page))             // GET /post/:post_id
+        .route("/category/:category", get(category_page))    // GET /category/:category
+        .route("/author/:author_id", get(author_page))       // GET /author/:author_id
+}
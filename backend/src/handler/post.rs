@@ -2,31 +2,34 @@
 //! 遵循 RESTful API 设计风格。
 
 use axum::{
-    extract::{Path, State, Json, Query},
-    http::StatusCode,
-    response::{IntoResponse, Response},
-    routing::{post, get, put, delete},
-    Router,
+    extract::{OriginalUri, Path, State, Json, Query},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::get,
 };
-use serde_json::json;
 use validator::Validate;
-use tracing::{debug, info, error, warn};
+use tracing::warn;
 
 // 导入 crate 根目录下的 AppState
 use crate::AppState;
-// 导入数据库模块的 PostRepository 和文章实体模型
-use crate::database::post::{PostRepository, Model as PostModel};
-// 导入用户实体模型，用于权限检查时获取作者信息
-use crate::database::user::Model as UserModel;
+// 导入数据库模块的文章实体模型
+use crate::database::post::Model as PostModel;
 // 导入 IDL 中定义的请求和响应 DTOs
 use crate::handler::idl::{
-    CreatePostRequest, UpdatePostRequest, PostListRequest, PostInfo,
-    PostListResponse, BaseResponse, IdResponse,
+    CreatePostRequest, UpdatePostRequest, PostListRequest, PostDetailRequest, PostInfo,
+    PostListResponse, PostSort, PostSummary, BaseResponse, IdResponse,
 };
 // 导入自定义错误类型和通用 Result
-use crate::error::{CustomError, CustomResult};
+use crate::error::{CustomError, CustomResult, ErrorCode, FieldError};
 // 导入认证提取器
 use crate::handler::user::AuthUser; // 从 user 模块引入 AuthUser
+use crate::handler::media::resolve_cover;
+use crate::handler::query::{Include, StrictQuery};
+use crate::openapi::{ApiRouter, Operation};
+use crate::pagination::{cursor_limit, link_headers, Cursor};
+use crate::audit::{self, diff, AuditEvent};
+use crate::handler::audit::{record_audit, ClientIp};
+use crate::i18n::{t, t_args};
 
 
 /// 将文章 `Model` 转换为 `PostInfo` DTO。
@@ -38,18 +41,51 @@ pub fn convert_post_model_to_post_info(model: PostModel) -> PostInfo {
         content_markdown: model.content_markdown,
         category: model.category,
         author_id: model.author_id,
-        is_published: model.is_published == 1, // 数据库中 1/0 转换为 bool
+        is_published: model.is_published,
         view_count: model.view_count,
         cover_url: model.cover_url,
+        cover: None,        // 需要查询媒体表，由 `resolve_cover` 填充
+        excerpt: model.excerpt,
+        content_html: None, // 仅在详情接口请求 HTML 时填充
+        toc: None,          // 仅在详情接口填充
         created_at: model.created_at,
         updated_at: model.updated_at,
+        author: None,         // 以下字段按 `include` 参数填充
+        comment_count: None,
+        favorite_count: None,
     }
 }
 
+/// 文章接口支持的 `include` 展开项。
+pub const POST_INCLUDES: [&str; 2] = [Include::AUTHOR, Include::COUNTS];
+
+/// 按 `include` 参数为一页文章摘要填充作者信息和计数。
+///
+/// 整页只查询一次作者、一次评论数和一次收藏数，不会逐篇查询。
+pub async fn expand_post_summaries(state: &AppState, posts: &mut [PostSummary], include: Include) -> CustomResult<()> {
+    if include.author {
+        let author_ids: Vec<i32> = posts.iter().map(|p| p.author_id).collect();
+        let authors = state.post_repo.get_authors(&author_ids).await?;
+        for post in posts.iter_mut() {
+            post.author = authors.get(&post.author_id).cloned();
+        }
+    }
+    if include.counts {
+        let post_ids: Vec<i32> = posts.iter().map(|p| p.id).collect();
+        let counts = state.post_repo.get_post_counts(&post_ids).await?;
+        for post in posts.iter_mut() {
+            let counts = counts.get(&post.id).copied().unwrap_or_default();
+            post.comment_count = Some(counts.comment_count);
+            post.favorite_count = Some(counts.favorite_count);
+        }
+    }
+    Ok(())
+}
+
 
 // ======================== 文章相关 API 处理函数 (RESTful 风格) ========================
 
-/// 创建文章 (POST /posts)
+/// 创建文章 (POST /api/post)
 /// 需要用户认证。
 pub async fn create_post(
     AuthUser(claims): AuthUser, // 认证用户，获取作者ID
@@ -59,27 +95,44 @@ pub async fn create_post(
     req.validate()?; // 验证请求数据
 
     let author_id = claims.sub.parse::<i32>()
-        .map_err(|_| CustomError::Unauthorized("JWT令牌中的用户ID无效".to_string()))?;
+        .map_err(|_| CustomError::Unauthorized(t("auth-invalid-user-id")))?;
 
     let created_post = state.post_repo.create_post(req, author_id).await?;
+    if created_post.is_published {
+        state.metrics.post_published();
+    }
 
     Ok(Json(IdResponse {
         success: true,
         id: created_post.id,
-        message: Some("文章创建成功".to_string()),
+        message: Some(t("post-created")),
     }))
 }
 
-/// 获取文章详情 (GET /posts/:id)
+/// 获取文章详情 (GET /api/post/:id)
 ///
 /// 此接口对所有用户开放，无论是否认证。
 /// 同时会增加文章的浏览量。
+/// 响应中包含根据标题生成的目录 `toc`；
+/// 传入 `format=html` 时，额外包含服务端渲染并清洗后的 `content_html`。
 pub async fn get_post_by_id(
     State(state): State<AppState>,
     Path(post_id): Path<i32>,
+    Query(req): Query<PostDetailRequest>,
 ) -> CustomResult<Json<PostInfo>> {
+    let with_html = match req.format.as_deref() {
+        None | Some("markdown") => false,
+        Some("html") => true,
+        Some(other) => {
+            return Err(CustomError::BadRequest(t_args("post-unsupported-format", &[("format", &other)])))
+        }
+    };
+    let include = Include::parse(req.include.as_deref(), &POST_INCLUDES)?;
+
     let post_model = state.post_repo.get_post_by_id(post_id).await?
-        .ok_or(CustomError::NotFound(format!("ID为 {} 的文章未找到", post_id)))?;
+        .ok_or(CustomError::coded(ErrorCode::PostNotFound, t_args("post-not-found", &[("id", &post_id)])))?;
+
+    let rendered = state.post_repo.get_rendered_content(&post_model).await?;
 
     // 增加浏览量（异步，不阻塞响应）
     // 注意：这里没有处理 increment_view_count 可能返回的错误，生产环境可能需要更细致的日志或错误处理
@@ -87,82 +140,115 @@ pub async fn get_post_by_id(
         warn!("增加文章 {} 浏览量失败: {}", post_id, e);
     }
 
-    Ok(Json(convert_post_model_to_post_info(post_model)))
+    let mut post_info = convert_post_model_to_post_info(post_model);
+    post_info.content_html = with_html.then_some(rendered.html);
+    post_info.toc = Some(rendered.toc);
+    post_info.cover = resolve_cover(&state, post_info.cover_url.as_deref()).await?;
+    if include.author {
+        post_info.author = state.post_repo.get_authors(&[post_info.author_id]).await?.remove(&post_info.author_id);
+    }
+    if include.counts {
+        let counts = state.post_repo.get_post_counts(&[post_id]).await?.remove(&post_id).unwrap_or_default();
+        post_info.comment_count = Some(counts.comment_count);
+        post_info.favorite_count = Some(counts.favorite_count);
+    }
+    Ok(Json(post_info))
 }
 
-/// 更新文章 (PUT /posts/:id)
+/// 更新文章 (PUT /api/post/:id)
 /// 只有文章作者或管理员才能更新文章。
 pub async fn update_post(
     AuthUser(claims): AuthUser, // 认证用户，获取操作者信息
     State(state): State<AppState>,
     Path(post_id): Path<i32>, // 要更新的文章 ID
+    client: ClientIp,
     Json(req): Json<UpdatePostRequest>,
 ) -> CustomResult<Json<BaseResponse>> {
     req.validate()?; // 验证请求数据
 
     // 检查文章是否存在
     let existing_post = state.post_repo.get_post_by_id(post_id).await?
-        .ok_or(CustomError::NotFound(format!("ID为 {} 的文章未找到", post_id)))?;
+        .ok_or(CustomError::coded(ErrorCode::PostNotFound, t_args("post-not-found", &[("id", &post_id)])))?;
 
     let operator_id = claims.sub.parse::<i32>()
-        .map_err(|_| CustomError::Unauthorized("JWT令牌中的用户ID无效".to_string()))?;
+        .map_err(|_| CustomError::Unauthorized(t("auth-invalid-user-id")))?;
 
     // 权限检查：只有文章作者或管理员才能更新
     if existing_post.author_id != operator_id && claims.role != "admin" {
         return Err(CustomError::Forbidden(
-            "无权限更新此文章".to_string(),
+            t("forbidden-update-post"),
         ));
     }
 
-    state.post_repo.update_post(post_id, req).await?;
+    let updated_post = state.post_repo.update_post(post_id, req).await?;
+    if updated_post.is_published && !existing_post.is_published {
+        state.metrics.post_published();
+    }
+    // 管理员修改他人的文章时记录审计日志
+    if existing_post.author_id != operator_id {
+        record_audit(&state, &client, AuditEvent::new(audit::POST_UPDATE)
+            .by(&claims)
+            .target("post", post_id)
+            .changes(diff(Some(&existing_post), Some(&updated_post)))).await;
+    }
 
     Ok(Json(BaseResponse {
         success: true,
-        message: Some("文章更新成功".to_string()),
+        message: Some(t("post-updated")),
     }))
 }
 
-/// 删除文章 (DELETE /posts/:id)
+/// 删除文章 (DELETE /api/post/:id)
 /// 只有文章作者或管理员才能删除文章。
 pub async fn delete_post(
     AuthUser(claims): AuthUser, // 认证用户，获取操作者信息
     State(state): State<AppState>,
     Path(post_id): Path<i32>, // 要删除的文章 ID
+    client: ClientIp,
 ) -> CustomResult<impl IntoResponse> { // 返回 CustomResult<impl IntoResponse> 以便自定义响应
     // 检查文章是否存在
     let existing_post = state.post_repo.get_post_by_id(post_id).await?
-        .ok_or(CustomError::NotFound(format!("ID为 {} 的文章未找到", post_id)))?;
+        .ok_or(CustomError::coded(ErrorCode::PostNotFound, t_args("post-not-found", &[("id", &post_id)])))?;
 
     let operator_id = claims.sub.parse::<i32>()
-        .map_err(|_| CustomError::Unauthorized("JWT令牌中的用户ID无效".to_string()))?;
+        .map_err(|_| CustomError::Unauthorized(t("auth-invalid-user-id")))?;
 
     // 权限检查：只有文章作者或管理员才能删除
     if existing_post.author_id != operator_id && claims.role != "admin" {
         return Err(CustomError::Forbidden(
-            "无权限删除此文章".to_string(),
+            t("forbidden-delete-post"),
         ));
     }
 
     state.post_repo.delete_post(post_id).await?;
+    if existing_post.author_id != operator_id {
+        record_audit(&state, &client, AuditEvent::new(audit::POST_DELETE)
+            .by(&claims)
+            .target("post", post_id)
+            .changes(diff(Some(&existing_post), None))).await;
+    }
 
     Ok((
         StatusCode::NO_CONTENT, // 204 No Content，表示成功但无返回体
         Json(BaseResponse {
             success: true,
-            message: Some("文章删除成功".to_string()),
+            message: Some(t("post-deleted")),
         }),
     ))
 }
 
-/// 获取文章列表和搜索 (GET /posts)
+/// 获取文章列表和搜索 (GET /api/post)
 ///
 /// 此接口对所有用户开放，无论是否认证。
-/// 支持分页、按分类过滤和全文搜索。
+/// 支持分页、排序、按分类 / 创建时间 / 封面过滤和全文搜索。
+/// 列表只返回 `PostSummary`（摘要、字数、阅读时间等），正文请通过详情接口获取。
+/// 带 `cursor` 参数时使用游标分页，并通过 `Link` 响应头给出前后页地址。
 pub async fn get_posts_list_and_search(
     auth_user: Option<AuthUser>, // 允许未认证用户访问，但如果认证了则可以检查权限
     State(state): State<AppState>,
-    Query(mut req): Query<PostListRequest>, // 使用 Query 提取查询参数
-) -> CustomResult<Json<PostListResponse>> {
+    OriginalUri(uri): OriginalUri,
+    StrictQuery(mut req): StrictQuery<PostListRequest>, // 未知的查询参数会被拒绝
+) -> CustomResult<(HeaderMap, Json<PostListResponse>)> {
     // 权限控制逻辑
     match &auth_user {
         None => {
@@ -191,25 +277,87 @@ pub async fn get_posts_list_and_search(
         }
     }
 
-    let (posts_info, total_pages, current_page, total_posts) = 
+    let include = Include::parse(req.include.as_deref(), &POST_INCLUDES)?;
+
+    // 游标分页
+    if let Some(cursor) = req.cursor.take() {
+        // 游标只记录创建时间和 ID，无法按其他字段翻页
+        if req.sort.is_some_and(|sort| sort != PostSort::Newest) {
+            return Err(CustomError::InvalidQuery(vec![FieldError {
+                field: "sort".to_string(),
+                code: "cursor_unsupported".to_string(),
+                message: t("validation-sort-cursor"),
+            }]));
+        }
+        let cursor = Cursor::from_param(&cursor)?;
+        let limit = cursor_limit(req.limit);
+        let mut page = state.post_repo.get_posts_by_cursor(&req, cursor.as_ref(), limit).await?;
+        expand_post_summaries(&state, &mut page.items, include).await?;
+        let total_posts = match req.include_total {
+            Some(true) => Some(state.post_repo.count_posts(&req).await?),
+            _ => None,
+        };
+
+        return Ok((link_headers(&uri, &page), Json(PostListResponse {
+            success: true,
+            posts: page.items,
+            total_pages: None,
+            current_page: None,
+            total_posts,
+            next_cursor: page.next_cursor,
+            prev_cursor: page.prev_cursor,
+            message: None,
+        })));
+    }
+
+    // 兼容模式：page / limit 分页
+    let (mut posts_info, total_pages, current_page, total_posts) = 
         state.post_repo.get_paginated_posts(req).await?;
+    expand_post_summaries(&state, &mut posts_info, include).await?;
 
-    Ok(Json(PostListResponse {
+    Ok((HeaderMap::new(), Json(PostListResponse {
         success: true,
         posts: posts_info,
-        total_pages,
-        current_page,
-        total_posts,
+        total_pages: Some(total_pages),
+        current_page: Some(current_page),
+        total_posts: Some(total_posts),
+        next_cursor: None,
+        prev_cursor: None,
         message: None,
-    }))
+    })))
 }
 
 /// 文章路由配置函数。
 /// 此函数将所有文章相关的路由组合起来，方便在 `src/bin/server.rs` 中集成。
-pub fn post_routes() -> Router<AppState> {
-    Router::new()
-        .route("/", get(get_posts_list_and_search).post(create_post))  // GET /posts (获取文章列表/搜索) 和 POST /posts (创建文章)
-        .route("/:id", get(get_post_by_id).put(update_post).delete(delete_post))  // GET/PUT/DELETE /posts/:id
-        // 注意：/post/edit 被合并到 /posts/:id 的 PUT 请求中，符合 RESTful 风格
-        // /post/search 逻辑也被合并到 /posts 的 GET 请求中，通过 Query 参数实现
+pub fn post_routes() -> ApiRouter {
+    ApiRouter::new("post")
+        // GET /api/post (获取文章列表/搜索) 和 POST /api/post (创建文章)
+        .route("/", get(get_posts_list_and_search).post(create_post), [
+            Operation::get("获取文章列表和搜索")
+                .description("支持分页、排序、按分类 (逗号分隔多个) / 创建时间 / 封面过滤和全文搜索，只返回文章摘要，`include=author,counts` 展开作者信息和计数。未知的查询参数返回 400。带 `cursor` 参数时使用游标分页 (只支持 `sort=newest`)，并返回 `Link` 响应头。")
+                .query::<PostListRequest>()
+                .json::<PostListResponse>(),
+            Operation::post("创建文章").auth().body::<CreatePostRequest>().json::<IdResponse>(),
+        ])
+        // GET/PUT/DELETE /api/post/:id
+        .route("/:id", get(get_post_by_id).put(update_post).delete(delete_post), [
+            Operation::get("获取文章详情")
+                .description("同时增加浏览量；`format=html` 时额外返回渲染后的 `content_html`；`include=author,counts` 展开作者信息和计数。")
+                .path_param::<i32>("id", "文章 ID")
+                .query::<PostDetailRequest>()
+                .json::<PostInfo>(),
+            Operation::put("更新文章")
+                .description("只有文章作者或管理员可以更新。")
+                .auth()
+                .path_param::<i32>("id", "文章 ID")
+                .body::<UpdatePostRequest>()
+                .json::<BaseResponse>(),
+            Operation::delete("删除文章")
+                .description("只有文章作者或管理员可以删除。")
+                .auth()
+                .path_param::<i32>("id", "文章 ID")
+                .response(204, "已删除", None),
+        ])
+        // 注意：/post/edit 被合并到 /api/post/:id 的 PUT 请求中，符合 RESTful 风格
+        // /post/search 逻辑也被合并到 /api/post 的 GET 请求中，通过 Query 参数实现
 }
@@ -0,0 +1,13 @@
+//! 错误响应格式协商中间件。
+//!
+//! 根据请求的 `Accept` 头决定 `CustomError` 输出普通 JSON 还是 RFC 7807 `application/problem+json`。
+
+use axum::{extract::Request, http::header, middleware::Next, response::Response};
+
+use crate::error::{with_error_format, ErrorFormat};
+
+/// 在协商出的错误响应格式的上下文中运行后续处理 (`Router::layer`)。
+pub async fn negotiate_error_format(request: Request, next: Next) -> Response {
+    let format = ErrorFormat::from_accept(request.headers().get(header::ACCEPT));
+    with_error_format(format, next.run(request)).await
+}
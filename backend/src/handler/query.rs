@@ -0,0 +1,127 @@
+//! 严格的查询参数提取器。
+//!
+//! axum 自带的 `Query` 会忽略未声明的参数，拼错的参数名 (例如 `catgory=rust`) 会被静默丢弃，
+//! 返回的却是未过滤的结果。`StrictQuery` 只接受 DTO 中声明的字段 (以及全局的 `lang`)，
+//! 遇到未知参数或无法解析的值时返回 `VALIDATION_FAILED`，并在 `errors` 中逐项指出出错的参数。
+
+use axum::{extract::FromRequestParts, http::request::Parts};
+use serde::de::DeserializeOwned;
+
+use crate::error::{CustomError, FieldError};
+use crate::handler::locale::LANG_PARAM;
+use crate::i18n::t_args;
+use crate::openapi::ApiSchema;
+
+/// 按白名单解析查询参数，允许的参数名来自 `ApiSchema::field_names`。
+pub struct StrictQuery<T>(pub T);
+
+#[axum::async_trait]
+impl<T, S> FromRequestParts<S> for StrictQuery<T>
+where
+    T: DeserializeOwned + ApiSchema,
+    S: Send + Sync,
+{
+    type Rejection = CustomError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parse_query(parts.uri.query().unwrap_or_default()).map(StrictQuery)
+    }
+}
+
+/// 解析查询字符串。
+///
+/// # 参数
+/// * `query` - 不含 `?` 的查询字符串。
+///
+/// # 返回
+/// 解析后的 `T`；参数名不在白名单中或值无法解析时返回 `CustomError::InvalidQuery`。
+pub fn parse_query<T>(query: &str) -> Result<T, CustomError>
+where
+    T: DeserializeOwned + ApiSchema,
+{
+    let invalid = |field: &str, error: &dyn std::fmt::Display| FieldError {
+        field: field.to_string(),
+        code: "invalid".to_string(),
+        message: t_args("validation-query-invalid", &[("field", &field), ("error", error)]),
+    };
+    let pairs: Vec<(String, String)> =
+        serde_urlencoded::from_str(query).map_err(|e| CustomError::InvalidQuery(vec![invalid("query", &e)]))?;
+
+    // 先检查参数名，`lang` 由语言协商中间件读取，所有接口都允许
+    let allowed = T::field_names();
+    let mut errors: Vec<FieldError> = Vec::new();
+    for (name, _) in &pairs {
+        if name == LANG_PARAM || allowed.contains(&name.as_str()) || errors.iter().any(|e| &e.field == name) {
+            continue;
+        }
+        errors.push(FieldError {
+            field: name.clone(),
+            code: "unknown_field".to_string(),
+            message: t_args("validation-query-unknown", &[("field", name), ("allowed", &allowed.join(", "))]),
+        });
+    }
+    if !errors.is_empty() {
+        return Err(CustomError::InvalidQuery(errors));
+    }
+
+    let known: Vec<&(String, String)> = pairs.iter().filter(|(name, _)| name != LANG_PARAM).collect();
+    let encoded = serde_urlencoded::to_string(&known).unwrap_or_default();
+    serde_urlencoded::from_str::<T>(&encoded).map_err(|error| {
+        // serde 的错误不带字段名，逐个参数单独解析找出出错的那一个；
+        // 都能单独解析时 (例如参数重复) 归到整个查询上
+        let errors: Vec<FieldError> = known
+            .iter()
+            .filter_map(|pair| {
+                let single = serde_urlencoded::to_string([pair]).ok()?;
+                let e = serde_urlencoded::from_str::<T>(&single).err()?;
+                Some(invalid(&pair.0, &e))
+            })
+            .collect();
+        if errors.is_empty() {
+            CustomError::InvalidQuery(vec![invalid("query", &error)])
+        } else {
+            CustomError::InvalidQuery(errors)
+        }
+    })
+}
+
+/// `include` 参数：响应中需要展开的关联数据，默认都不展开。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Include {
+    /// 作者 / 评论者的公开信息
+    pub author: bool,
+    /// 文章的评论数和收藏数
+    pub counts: bool,
+}
+
+impl Include {
+    pub const AUTHOR: &'static str = "author";
+    pub const COUNTS: &'static str = "counts";
+
+    /// 解析逗号分隔的 `include` 参数。
+    ///
+    /// # 参数
+    /// * `value` - 参数值，未提供时不展开任何数据。
+    /// * `allowed` - 接口支持的展开项，例如评论只支持 `author`。
+    ///
+    /// # 返回
+    /// 解析结果；包含不支持的展开项时返回 `CustomError::InvalidQuery`。
+    pub fn parse(value: Option<&str>, allowed: &[&str]) -> Result<Include, CustomError> {
+        let mut include = Include::default();
+        for item in value.unwrap_or_default().split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match item {
+                _ if !allowed.contains(&item) => {
+                    return Err(CustomError::InvalidQuery(vec![FieldError {
+                        field: "include".to_string(),
+                        code: "invalid".to_string(),
+                        message: t_args("validation-include-unknown", &[("value", &item), ("allowed", &allowed.join(", "))]),
+                    }]));
+                }
+                Self::AUTHOR => include.author = true,
+                Self::COUNTS => include.counts = true,
+                _ => {}
+            }
+        }
+        Ok(include)
+    }
+}
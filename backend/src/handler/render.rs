@@ -0,0 +1,62 @@
+//! 渲染相关的 HTTP 请求处理函数。
+//! 提供代码高亮主题的列表和样式表，供前端按需选择配色。
+
+use axum::{
+    extract::Path,
+    http::header,
+    response::IntoResponse,
+    routing::get,
+    Json,
+};
+
+use crate::error::{CustomError, CustomResult};
+use crate::handler::idl::{HighlightThemeInfo, HighlightThemeListResponse};
+use crate::render::highlight::{available_themes, default_theme_slug, theme_css};
+use crate::i18n::t_args;
+use crate::openapi::{ApiRouter, ApiSchema, Operation};
+
+/// 获取可用的高亮主题列表 (GET /api/render/themes)
+///
+/// 此接口对所有用户开放。
+pub async fn list_highlight_themes() -> Json<HighlightThemeListResponse> {
+    let themes = available_themes()
+        .into_iter()
+        .map(|(slug, name)| HighlightThemeInfo { slug, name })
+        .collect();
+
+    Json(HighlightThemeListResponse {
+        success: true,
+        default_theme: default_theme_slug(),
+        themes,
+    })
+}
+
+/// 获取指定高亮主题的样式表 (GET /api/render/themes/:slug/css)
+///
+/// 文章中的代码块只包含类名，前端引入此样式表即可切换配色。
+pub async fn get_highlight_theme_css(
+    Path(slug): Path<String>,
+) -> CustomResult<impl IntoResponse> {
+    let css = theme_css(&slug)
+        .ok_or_else(|| CustomError::NotFound(t_args("highlight-theme-not-found", &[("name", &slug)])))?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/css; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=86400"),
+        ],
+        css,
+    ))
+}
+
+/// 渲染路由配置函数。
+/// 此函数将所有渲染相关的路由组合起来，方便在 `src/bin/server.rs` 中集成。
+pub fn render_routes() -> ApiRouter {
+    ApiRouter::new("render")
+        .route("/themes", get(list_highlight_themes),              // GET /api/render/themes
+            Operation::get("获取代码高亮主题列表").json::<HighlightThemeListResponse>())
+        .route("/themes/:slug/css", get(get_highlight_theme_css),  // GET /api/render/themes/:slug/css
+            Operation::get("获取代码高亮主题样式表")
+                .path_param::<String>("slug", "主题标识")
+                .response(200, "CSS 样式表", Some(("text/css", String::schema))))
+}
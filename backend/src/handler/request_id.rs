@@ -0,0 +1,19 @@
+//! 请求 ID 中间件。
+//!
+//! `X-Request-Id` 由 tower-http 的 `SetRequestIdLayer` 生成（或沿用客户端传入的值），
+//! 这里把它放进任务上下文，使 `CustomError` 的 JSON 响应体能带上同一个 ID。
+
+use axum::{extract::Request, middleware::Next, response::Response};
+
+use crate::telemetry::{with_request_id, REQUEST_ID_HEADER};
+
+/// 在当前请求 ID 的上下文中运行后续处理 (`Router::layer`，需位于 `SetRequestIdLayer` 之内)。
+pub async fn scope_request_id(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    with_request_id(request_id, next.run(request)).await
+}
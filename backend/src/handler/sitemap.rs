@@ -0,0 +1,134 @@
+//! 站点地图和 robots.txt 的 HTTP 请求处理函数。
+//! 站点地图只包含已发布的文章，以及有已发布文章的分类和作者页面。
+
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+
+use crate::AppState;
+use crate::error::{CustomError, CustomResult};
+use crate::sitemap::{build_robots_txt, build_sitemap_index, build_urlset, SitemapUrl, SITEMAP_MAX_URLS};
+use crate::i18n::t_args;
+
+/// 以 XML 形式返回站点地图。
+fn xml_response(xml: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "application/xml; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=3600"),
+        ],
+        xml,
+    ).into_response()
+}
+
+/// 首页、分类页和作者页的地址。
+async fn page_urls(state: &AppState) -> CustomResult<Vec<SitemapUrl>> {
+    let config = &state.config;
+    let categories = state.post_repo.get_published_category_lastmods().await?;
+    let authors = state.post_repo.get_published_author_lastmods().await?;
+
+    let mut urls = Vec::with_capacity(1 + categories.len() + authors.len());
+    urls.push(SitemapUrl {
+        loc: format!("{}/", config.site_url),
+        lastmod: categories.iter().map(|(_, lastmod)| *lastmod).max(),
+    });
+    urls.extend(categories.into_iter().map(|(category, lastmod)| SitemapUrl {
+        loc: config.category_url(&category),
+        lastmod: Some(lastmod),
+    }));
+    urls.extend(authors.into_iter().map(|(author_id, lastmod)| SitemapUrl {
+        loc: config.author_url(author_id),
+        lastmod: Some(lastmod),
+    }));
+    Ok(urls)
+}
+
+/// 指定区间内已发布文章的地址。
+async fn post_urls(state: &AppState, offset: u64, limit: u64) -> CustomResult<Vec<SitemapUrl>> {
+    let posts = state.post_repo.get_published_post_lastmods(offset, limit).await?;
+    Ok(posts
+        .into_iter()
+        .map(|(post_id, lastmod)| SitemapUrl {
+            loc: state.config.post_url(post_id),
+            lastmod: Some(lastmod),
+        })
+        .collect())
+}
+
+/// 站点地图入口 (GET /sitemap.xml)
+///
+/// URL 总数不超过 `SITEMAP_MAX_URLS` 时直接返回 `<urlset>`；
+/// 否则返回 `<sitemapindex>`，指向 `/sitemap/pages.xml` 和分页的 `/sitemap/posts-N.xml`。
+pub async fn get_sitemap(State(state): State<AppState>) -> CustomResult<Response> {
+    let mut urls = page_urls(&state).await?;
+    let total_posts = state.post_repo.count_published_posts().await?;
+
+    if urls.len() as u64 + total_posts <= SITEMAP_MAX_URLS {
+        urls.extend(post_urls(&state, 0, total_posts).await?);
+        return Ok(xml_response(build_urlset(&urls)));
+    }
+
+    let site_url = &state.config.site_url;
+    let mut sitemaps = vec![SitemapUrl {
+        loc: format!("{}/sitemap/pages.xml", site_url),
+        lastmod: urls.first().and_then(|home| home.lastmod),
+    }];
+    let post_pages = total_posts.div_ceil(SITEMAP_MAX_URLS);
+    sitemaps.extend((1..=post_pages).map(|page| SitemapUrl {
+        loc: format!("{}/sitemap/posts-{}.xml", site_url, page),
+        lastmod: None,
+    }));
+    Ok(xml_response(build_sitemap_index(&sitemaps)))
+}
+
+/// 分页的子站点地图 (GET /sitemap/pages.xml, GET /sitemap/posts-N.xml)
+pub async fn get_sitemap_part(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> CustomResult<Response> {
+    let not_found = || CustomError::NotFound(t_args("sitemap-not-found", &[("name", &name)]));
+
+    if name == "pages.xml" {
+        let mut urls = page_urls(&state).await?;
+        urls.truncate(SITEMAP_MAX_URLS as usize);
+        return Ok(xml_response(build_urlset(&urls)));
+    }
+
+    let page = name
+        .strip_prefix("posts-")
+        .and_then(|rest| rest.strip_suffix(".xml"))
+        .and_then(|page| page.parse::<u64>().ok())
+        .filter(|page| *page >= 1)
+        .ok_or_else(not_found)?;
+
+    let urls = post_urls(&state, (page - 1) * SITEMAP_MAX_URLS, SITEMAP_MAX_URLS).await?;
+    if urls.is_empty() && page > 1 {
+        return Err(not_found());
+    }
+    Ok(xml_response(build_urlset(&urls)))
+}
+
+/// robots.txt (GET /robots.txt)
+pub async fn get_robots_txt(State(state): State<AppState>) -> impl IntoResponse {
+    let sitemap_url = format!("{}/sitemap.xml", state.config.site_url);
+    (
+        [
+            (header::CONTENT_TYPE, "text/plain; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=86400"),
+        ],
+        build_robots_txt(state.config.robots_txt.as_deref(), &sitemap_url),
+    )
+}
+
+/// 站点地图路由配置函数。
+/// 与订阅源一样挂载在站点根路径。
+pub fn sitemap_routes() -> Router<AppState> {
+    Router::new()
+        .route("/sitemap.xml", get(get_sitemap))              // GET /sitemap.xml
+        .route("/sitemap/:name", get(get_sitemap_part))        // GET /sitemap/pages.xml, /sitemap/posts-N.xml
+        .route("/robots.txt", get(get_robots_txt))             // GET /robots.txt
+}
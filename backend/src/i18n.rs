@@ -0,0 +1,180 @@
+// src/i18n.rs
+//! 国际化：API 响应消息的多语言目录与语言协商。
+//!
+//! 消息目录位于 `locales/<语言>.ftl`，编译时嵌入二进制，使用 Fluent 语法的子集：
+//! `id = 文本`、缩进的续行、`#` 注释以及 `{ $变量 }` 占位符。
+//! 每个请求的语言由 `handler::locale` 中间件协商后放进任务上下文，
+//! 处理函数和 Repository 通过 `t` / `t_args` 取得当前语言的文本。
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
+
+use lazy_static::lazy_static;
+
+/// 支持的语言。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    /// 简体中文，默认语言；不在请求上下文中（例如命令行工具）时也使用它。
+    #[default]
+    ZhCn,
+    En,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::ZhCn, Locale::En];
+
+    /// BCP 47 语言标签。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::En => "en",
+        }
+    }
+
+    /// 按主语言子标签解析语言标签（不区分大小写），例如 `zh`、`zh-Hans-CN`、`en-US`。
+    pub fn parse(tag: &str) -> Option<Locale> {
+        let primary = tag.trim().split(['-', '_']).next().unwrap_or_default();
+        if primary.eq_ignore_ascii_case("zh") {
+            Some(Locale::ZhCn)
+        } else if primary.eq_ignore_ascii_case("en") {
+            Some(Locale::En)
+        } else {
+            None
+        }
+    }
+
+    /// 从 `Accept-Language` 头中选出 q 值最高的受支持语言；q 值相同时取先出现的。
+    pub fn from_accept_language(header: &str) -> Option<Locale> {
+        let mut best: Option<(Locale, f32)> = None;
+        for range in header.split(',') {
+            let mut parts = range.split(';').map(str::trim);
+            let Some(locale) = parts.next().and_then(Locale::parse) else {
+                continue;
+            };
+            let q = parts
+                .filter_map(|p| p.strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if q > 0.0 && best.is_none_or(|(_, best_q)| q > best_q) {
+                best = Some((locale, q));
+            }
+        }
+        best.map(|(locale, _)| locale)
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::ZhCn => include_str!("../locales/zh-CN.ftl"),
+            Locale::En => include_str!("../locales/en.ftl"),
+        }
+    }
+}
+
+lazy_static! {
+    /// 各语言解析后的消息目录：消息 ID -> 文本。
+    static ref CATALOGUES: HashMap<Locale, HashMap<&'static str, String>> = Locale::ALL
+        .iter()
+        .map(|&locale| (locale, parse_catalogue(locale.source())))
+        .collect();
+}
+
+/// 解析 `.ftl` 文件。只支持简单消息，不支持属性、选择器和术语。
+fn parse_catalogue(source: &'static str) -> HashMap<&'static str, String> {
+    let mut messages = HashMap::new();
+    let mut current: Option<(&'static str, String)> = None;
+    for line in source.lines() {
+        if line.starts_with([' ', '\t']) && !line.trim().is_empty() {
+            // 续行：以换行连接到上一条消息
+            if let Some((_, text)) = current.as_mut() {
+                text.push('\n');
+                text.push_str(line.trim());
+            }
+            continue;
+        }
+        if let Some((id, text)) = current.take() {
+            messages.insert(id, text);
+        }
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((id, text)) = line.split_once('=') {
+            current = Some((id.trim(), text.trim().to_string()));
+        }
+    }
+    if let Some((id, text)) = current {
+        messages.insert(id, text);
+    }
+    messages
+}
+
+/// 某个语言目录中的全部消息 ID（已排序），用于检查各语言的目录是否一致。
+pub fn message_ids(locale: Locale) -> Vec<&'static str> {
+    let mut ids: Vec<&'static str> = CATALOGUES[&locale].keys().copied().collect();
+    ids.sort_unstable();
+    ids
+}
+
+tokio::task_local! {
+    /// 当前请求协商出的语言。
+    static LOCALE: Locale;
+}
+
+/// 在给定语言的上下文中运行 `future`。
+pub async fn with_locale<F: Future>(locale: Locale, future: F) -> F::Output {
+    LOCALE.scope(locale, future).await
+}
+
+/// 当前请求的语言；不在请求上下文中时为默认语言。
+pub fn current_locale() -> Locale {
+    LOCALE.try_with(|l| *l).unwrap_or_default()
+}
+
+/// 取得当前语言中不带参数的消息。
+pub fn t(id: &str) -> String {
+    translate(current_locale(), id, &[])
+}
+
+/// 取得当前语言中的消息，并用 `args` 替换 `{ $名称 }` 占位符。
+///
+/// # 示例
+/// `t_args("post-not-found", &[("id", &post_id)])`
+pub fn t_args(id: &str, args: &[(&str, &dyn Display)]) -> String {
+    translate(current_locale(), id, args)
+}
+
+/// 取得指定语言中的消息。
+///
+/// 该语言缺少这条消息时回退到默认语言；默认语言也没有时原样返回 `id`，
+/// 因此也可以直接传入一段未登记的文本。
+pub fn translate(locale: Locale, id: &str, args: &[(&str, &dyn Display)]) -> String {
+    let template = CATALOGUES[&locale]
+        .get(id)
+        .or_else(|| CATALOGUES[&Locale::default()].get(id));
+    match template {
+        Some(template) => format_message(template, args),
+        None => id.to_string(),
+    }
+}
+
+/// 替换占位符；没有提供值的占位符保持原样。
+fn format_message(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let placeable = &rest[..=end];
+        let name = placeable[1..placeable.len() - 1].trim().strip_prefix('$');
+        match name.and_then(|name| args.iter().find(|(key, _)| *key == name)) {
+            Some((_, value)) => output.push_str(&value.to_string()),
+            None => output.push_str(placeable),
+        }
+        rest = &rest[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use validator::Validate;
-use chrono::{NaiveDateTime, Utc}; // 用于时间戳，与数据库中的 TEXT 格式对应
+use chrono::NaiveDateTime; // 用于时间戳，与数据库中的 TEXT 格式对应
 
 // Axum Blog Engine 的核心库。
 // 定义了应用程序的模块结构、共享状态和通用类型。
@@ -11,6 +11,26 @@ use chrono::{NaiveDateTime, Utc}; // 用于时间戳，与数据库中的 TEXT 
 pub mod database; // 包含数据库实体和 Repository 实现
 pub mod handler;  // 包含 HTTP 请求处理器、DTOs 和认证逻辑
 pub mod error;    // 包含自定义错误类型和错误处理
+pub mod render;   // 包含 Markdown 渲染、HTML 清洗和内容哈希
+pub mod feed;     // 包含 RSS / Atom / JSON Feed 订阅源生成
+pub mod sitemap;  // 包含站点地图与 robots.txt 生成
+pub mod theme;    // 包含公开页面的模板主题加载与渲染
+pub mod seo;      // 包含 OpenGraph / Twitter Card / JSON-LD 元数据生成
+pub mod assets;   // 包含前端静态资源的读取、预压缩协商和缓存策略
+pub mod migration; // 版本化的数据库迁移 (up / down / status)
+pub mod storage;  // 包含媒体文件存储后端抽象及本地文件系统实现
+pub mod media;    // 包含上传文件的类型识别与图片信息读取
+pub mod settings; // 分层配置（配置文件、环境变量、命令行参数）的加载与校验
+pub mod bootstrap; // 服务端与命令行工具共用的数据库连接和存储后端创建
+pub mod backup;   // 数据导出与导入 (blogctl export / import)
+pub mod lifecycle; // 优雅停机：信号处理、后台任务跟踪与数据库关闭
+pub mod metrics;  // Prometheus 指标 (HTTP、数据库与业务事件)
+pub mod telemetry; // 分布式追踪：请求 ID、traceparent 传播与 OTLP 导出
+pub mod logging;  // 日志格式、轮转文件输出与敏感字段脱敏
+pub mod audit;    // 审计日志：管理员操作与认证事件的记录内容
+pub mod i18n;     // 国际化：API 响应消息的多语言目录与 Accept-Language 协商
+pub mod openapi;  // 从路由表和 DTO 生成 OpenAPI 3.1 文档
+pub mod pagination; // 列表接口的游标分页与 Link 响应头
 
 
 // ======================== 重新导出通用类型 ========================
@@ -30,18 +50,79 @@ use crate::database::user::UserRepository;
 use crate::database::post::PostRepository;
 use crate::database::comment::CommentRepository;
 use crate::database::favorite::FavoriteRepository;
+use crate::database::media::MediaRepository;
+use crate::database::audit::AuditRepository;
+use crate::storage::StorageBackend;
+use crate::theme::Theme;
+use crate::assets::StaticAssets;
+use crate::lifecycle::BackgroundTasks;
+use crate::metrics::Metrics;
 
 /// 应用程序的共享配置。
-/// 通常用于存储从环境变量读取的密钥、成本因子或其他应用级别的常量。
+/// 由 `settings::Settings::app_config` 从分层配置中构建，存储密钥、成本因子或其他应用级别的常量。
 /// 使用 `#[derive(Clone)]` 允许在整个应用中方便地复制和传递。
 #[derive(Clone)]
 pub struct Config {
     pub jwt_secret: String, // 用于 JWT 签名和验证的密钥
     pub bcrypt_cost: u32,   // Bcrypt 密码哈希的计算成本
+    pub token_ttl_hours: u32, // 签发的 JWT 有效期（小时）
+    pub site_url: String,   // 站点对外访问的根地址（不带结尾斜杠），用于生成订阅源等绝对链接
+    pub site_title: String, // 站点标题
+    pub site_description: String, // 站点描述
+    pub robots_txt: Option<String>, // 自定义 robots.txt 内容，未设置时使用默认规则
+    pub theme_dir: Option<std::path::PathBuf>, // 公开页面的主题目录，未设置时使用内置模板
+    pub static_dir: Option<std::path::PathBuf>, // 前端构建产物目录，未设置时不提供前端（或使用内嵌资源）
+    pub api_docs_bundle: Option<std::path::PathBuf>, // 交互式 API 文档使用的 Scalar 脚本，未设置时文档页面只提供 OpenAPI 链接
+    pub upload_max_bytes: u64,  // 单个上传文件的大小上限（字节）
+    pub media_quota_bytes: u64, // 每个用户的媒体存储配额（字节）
+    pub image_variant_widths: Vec<u32>, // 上传图片后自动生成的宽度
+    pub metrics_token: Option<String>,  // 访问 /metrics 所需的 Bearer 令牌，未设置时不校验
+    pub trust_forwarded_for: bool, // 是否以 X-Forwarded-For 中的地址作为客户端 IP（位于反向代理之后时开启）
     // 您可以在此添加其他全局配置项，例如 API 密钥、外部服务 URL 等。
     // pub external_api_key: String,
 }
 
+impl Config {
+    /// 文章公开页面的绝对地址。
+    pub fn post_url(&self, post_id: i32) -> String {
+        format!("{}/post/{}", self.site_url, post_id)
+    }
+
+    /// 分类公开页面的绝对地址（分类名按 URL 路径段编码）。
+    pub fn category_url(&self, category: &str) -> String {
+        format!(
+            "{}/category/{}",
+            self.site_url,
+            percent_encoding::utf8_percent_encode(category, percent_encoding::NON_ALPHANUMERIC)
+        )
+    }
+
+    /// 媒体文件的公开访问地址。
+    pub fn media_url(&self, storage_key: &str) -> String {
+        format!("{}/media/{}", self.site_url, storage_key)
+    }
+
+    /// 把以 `/` 开头的站内相对地址补全为绝对地址，其他地址原样返回。
+    pub fn absolute_url(&self, url: &str) -> String {
+        if url.starts_with('/') && !url.starts_with("//") {
+            format!("{}{}", self.site_url, url)
+        } else {
+            url.to_string()
+        }
+    }
+
+    /// 从本站媒体地址（绝对地址或 `/media/` 开头的相对地址）中取出存储键，其他地址返回 `None`。
+    pub fn media_key_from_url<'a>(&self, url: &'a str) -> Option<&'a str> {
+        let path = url.strip_prefix(self.site_url.as_str()).unwrap_or(url);
+        path.strip_prefix("/media/").filter(|key| !key.is_empty())
+    }
+
+    /// 作者公开页面的绝对地址。
+    pub fn author_url(&self, author_id: i32) -> String {
+        format!("{}/author/{}", self.site_url, author_id)
+    }
+}
+
 /// 应用程序的共享状态。
 /// 包含了数据库连接和所有 Repository 实例，
 /// 使得各个 Axum 处理函数可以通过 `axum::extract::State` 提取器方便地访问这些资源。
@@ -54,6 +135,13 @@ pub struct AppState {
     pub post_repo: PostRepository,      // 文章数据操作仓库
     pub comment_repo: CommentRepository, // 评论数据操作仓库
     pub favorite_repo: FavoriteRepository, // 收藏数据操作仓库
+    pub theme: Arc<Theme>,              // 公开页面使用的模板主题
+    pub assets: Option<Arc<StaticAssets>>, // 前端静态资源，未配置时不提供前端
+    pub media_repo: MediaRepository,    // 媒体文件数据操作仓库
+    pub storage: Arc<dyn StorageBackend>, // 媒体文件存储后端
+    pub tasks: BackgroundTasks,         // 后台任务，停机时等待其完成
+    pub metrics: Arc<Metrics>,          // Prometheus 指标
+    pub audit_repo: AuditRepository,    // 审计日志操作仓库
 }
 
 // ======================== 其他可能需要的全局导出 ========================
@@ -114,7 +202,7 @@ pub struct UserInfo {
     pub created_at: NaiveDateTime, 
 }
 
-// 获取登录用户信息响应 (GET /user/get/me)
+// 获取登录用户信息响应 (GET /api/user/me)
 #[derive(Debug, Serialize)]
 pub struct UserInfoResponse {
     pub success: bool,
@@ -122,7 +210,7 @@ pub struct UserInfoResponse {
     pub message: Option<String>,
 }
 
-// 更新用户个人信息请求 (PUT /user/update/my)
+// 更新用户个人信息请求 (PUT /api/user/me)
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct UpdateMyProfileRequest {
     #[validate(length(min = 3, max = 20))]
@@ -133,7 +221,7 @@ pub struct UpdateMyProfileRequest {
     pub password: Option<String>, // 新密码，会进行哈希
 }
 
-// 更新用户（管理员）请求 (PUT /user/update/:id)
+// 更新用户（管理员）请求 (PUT /api/user/:id)
 // 注意：管理员可能需要更新用户的角色
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct UpdateUserRequest {
@@ -146,7 +234,7 @@ pub struct UpdateUserRequest {
     pub role: Option<String>, // 管理员可以修改角色
 }
 
-// 创建用户（管理员）请求 (POST /user/create)
+// 创建用户（管理员）请求 (POST /api/user)
 // 与 RegisterRequest 类似，但可能由管理员使用
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct CreateUserByAdminRequest {
@@ -159,7 +247,7 @@ pub struct CreateUserByAdminRequest {
     pub role: String, // 管理员创建时指定角色
 }
 
-// 删除用户（管理员）请求 (DELETE /user/delete/:id)
+// 删除用户（管理员）请求 (DELETE /api/user/:id)
 // 通常不需要请求体，通过路径参数传递ID
 // 这里仅为示例，如果需要批量删除，可以包含ID列表
 #[derive(Debug, Serialize, Deserialize)]
@@ -178,7 +266,7 @@ pub struct PostInfo {
     pub content_markdown: String, // 存储 Markdown 内容
     pub category: String,         // 文章分类
     pub author_id: i32,
-    pub is_published: bool,       // 0/1 对应 false/true
+    pub is_published: bool,
     pub view_count: i32,
     pub cover_url: Option<String>, // 封面URL可能是None
     pub created_at: NaiveDateTime,
@@ -187,7 +275,7 @@ pub struct PostInfo {
     // pub author_username: String, 
 }
 
-// 创建文章请求 (POST /post/create)
+// 创建文章请求 (POST /api/post)
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct CreatePostRequest {
     #[validate(length(min = 1, max = 255))]
@@ -200,7 +288,7 @@ pub struct CreatePostRequest {
     pub cover_url: Option<String>,
 }
 
-// 更新文章请求 (PUT /post/update/:id 或 /post/edit/:id)
+// 更新文章请求 (PUT /api/post/:id)
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct UpdatePostRequest {
     // 文章ID通常通过路径参数传递
@@ -214,7 +302,7 @@ pub struct UpdatePostRequest {
     pub cover_url: Option<String>,
 }
 
-// 搜索/分页获取文章列表请求 (GET /post/search, GET /post/list)
+// 搜索/分页获取文章列表请求 (GET /api/post)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PostListRequest {
     pub page: Option<u64>,       // 页码
@@ -237,13 +325,13 @@ pub struct PostListResponse {
 
 // ======================== 帖子收藏相关 ========================
 
-// 收藏/取消收藏文章请求 (POST /post_fav)
+// 收藏/取消收藏文章请求 (POST /api/post_fav)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ToggleFavoriteRequest {
     pub post_id: i32,
 }
 
-// 帖子收藏列表响应 (GET /post_fav/my/list)
+// 帖子收藏列表响应 (GET /api/post_fav/my/list)
 #[derive(Debug, Serialize)]
 pub struct FavoriteListResponse {
     pub success: bool,
@@ -269,7 +357,7 @@ pub struct CommentInfo {
     // pub username: String,
 }
 
-// 创建评论请求 (POST /comment/create)
+// 创建评论请求 (POST /api/comment)
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct CreateCommentRequest {
     #[validate(length(min = 1, max = 1000))]
@@ -278,7 +366,7 @@ pub struct CreateCommentRequest {
     pub parent_id: Option<i32>,  // 父评论ID，可选
 }
 
-// 更新评论请求 (PUT /comment/update/:id)
+// 更新评论请求 (PUT /api/comment/:id)
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct UpdateCommentRequest {
     #[validate(length(min = 1, max = 1000))]
@@ -286,7 +374,7 @@ pub struct UpdateCommentRequest {
     // 评论ID通过路径参数传递
 }
 
-// 评论列表请求 (GET /comment/list)
+// 评论列表请求 (GET /api/post/:post_id/comments)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CommentListRequest {
     pub post_id: i32, // 必须指定文章ID来获取评论
@@ -0,0 +1,112 @@
+// src/lifecycle.rs
+//! 服务的停机流程：监听 SIGTERM / SIGINT、跟踪后台任务、关闭数据库连接。
+//!
+//! 收到信号后立即通知后台任务停止（`/readyz` 同时开始返回 503），
+//! 服务器停止接受新连接，在同一个超时时间内等待进行中的请求与后台任务完成，
+//! 最后执行 SQLite WAL 检查点并关闭连接池。
+
+use std::future::Future;
+use std::time::Duration;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend};
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tracing::{info, warn};
+
+/// 后台任务（例如图片变体生成）的集合，停机时等待它们结束。
+#[derive(Clone, Default)]
+pub struct BackgroundTasks {
+    tracker: TaskTracker,
+    shutdown: CancellationToken,
+}
+
+impl BackgroundTasks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 在后台运行任务，停机时会等待其完成。
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tracker.spawn(task);
+    }
+
+    /// 停机通知，长时间运行的任务应在其触发后尽快退出。
+    pub fn shutdown_token(&self) -> CancellationToken {
+        self.shutdown.clone()
+    }
+
+    /// 是否已开始停机。
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.is_cancelled()
+    }
+
+    /// 开始停机：通知所有后台任务停止。
+    pub fn begin_shutdown(&self) {
+        self.shutdown.cancel();
+    }
+
+    /// 等待所有后台任务结束。
+    ///
+    /// # 返回
+    /// 超时前全部结束时返回 `true`。
+    pub async fn wait(&self, timeout: Duration) -> bool {
+        self.wait_until(Instant::now() + timeout).await
+    }
+
+    /// 等待所有后台任务结束，最多等到 `deadline`。
+    ///
+    /// # 返回
+    /// 截止时间前全部结束时返回 `true`。
+    pub async fn wait_until(&self, deadline: Instant) -> bool {
+        self.tracker.close();
+        tokio::time::timeout_at(deadline, self.tracker.wait()).await.is_ok()
+    }
+}
+
+/// 等待 SIGINT (Ctrl+C) 或 SIGTERM，收到后调用 `BackgroundTasks::begin_shutdown`。
+pub async fn shutdown_signal(tasks: BackgroundTasks) {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("无法监听 Ctrl+C 信号: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!("无法监听 SIGTERM 信号: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("收到 SIGINT，开始停机"),
+        _ = terminate => info!("收到 SIGTERM，开始停机"),
+    }
+    tasks.begin_shutdown();
+}
+
+/// 关闭数据库连接池；SQLite 会先把 WAL 中的内容写回主数据库文件。
+pub async fn close_database(db: DatabaseConnection) {
+    if db.get_database_backend() == DbBackend::Sqlite {
+        if let Err(e) = db.execute_unprepared("PRAGMA wal_checkpoint(TRUNCATE);").await {
+            warn!("SQLite WAL 检查点失败: {}", e);
+        }
+    }
+    match db.close().await {
+        Ok(()) => info!("数据库连接已关闭"),
+        Err(e) => warn!("关闭数据库连接失败: {}", e),
+    }
+}
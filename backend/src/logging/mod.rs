@@ -0,0 +1,71 @@
+// src/logging/mod.rs
+//! 日志输出：文本或 JSON 格式，写到标准输出或按大小 / 时间轮转的文件。
+//! 无论哪种输出，每条日志都先经过 `redact` 脱敏。
+
+pub mod redact;
+pub mod rotate;
+
+use std::io::{self, IsTerminal};
+use std::sync::Mutex;
+
+use anyhow::Result;
+use opentelemetry_sdk::trace::Tracer;
+use tracing::Subscriber;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+use crate::settings::{LogFormat, LogSettings};
+
+pub use redact::{redact, RedactingMakeWriter, REDACTED};
+pub use rotate::RollingFile;
+
+/// 初始化服务端的全局日志：按配置选择格式与输出位置，并把 span 交给 OpenTelemetry。
+///
+/// # 参数
+/// * `settings` - `[log]` 配置。
+/// * `tracer` - OpenTelemetry 的 tracer，见 `telemetry::init_tracer_provider`。
+pub fn init(settings: &LogSettings, tracer: Tracer) -> Result<()> {
+    let (writer, ansi) = match &settings.dir {
+        Some(dir) => {
+            let file = RollingFile::open(dir, settings)
+                .map_err(|e| anyhow::anyhow!("无法打开日志目录 {}: {}", dir.display(), e))?;
+            (BoxMakeWriter::new(Mutex::new(file)), false)
+        }
+        None => (BoxMakeWriter::new(io::stdout), io::stdout().is_terminal()),
+    };
+
+    tracing_subscriber::registry()
+        .with(format_layer(settings.format, ansi, RedactingMakeWriter::new(writer)))
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .with(EnvFilter::new(&settings.level))
+        .try_init()
+        .map_err(|e| anyhow::anyhow!("日志系统初始化失败: {}", e))
+}
+
+/// 构建文本或 JSON 格式的输出层。
+///
+/// JSON 格式每行一个对象，事件字段展开在顶层，`spans` 中按从外到内的顺序列出所在的 span
+/// （包括请求根 span 的 `request_id` 与 `trace_id`）。
+pub fn format_layer<S, W>(format: LogFormat, ansi: bool, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => tracing_subscriber::fmt::layer()
+            .with_ansi(ansi)
+            .with_writer(writer)
+            .boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_current_span(false)
+            .with_span_list(true)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
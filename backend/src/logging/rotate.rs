@@ -0,0 +1,143 @@
+// src/logging/rotate.rs
+//! 按大小和时间轮转的日志文件。
+//!
+//! 当前日志总是写入 `<目录>/<文件名>`（例如 `logs/backend.log`；文件名没有扩展名时补上 `.log`）。跨过轮转周期（UTC 的整点或零点）
+//! 或即将超过大小上限时，当前文件被重命名为 `backend.20261018-210000.log` 这样带时间戳的文件，
+//! 然后重新创建当前文件，并只保留最近的 `max_files` 个已轮转文件。
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::settings::{LogRotation, LogSettings};
+
+/// 轮转的日志文件，通过 `Mutex<RollingFile>` 作为 `tracing_subscriber` 的输出。
+pub struct RollingFile {
+    dir: PathBuf,
+    stem: String,
+    extension: String,
+    current: PathBuf,
+    rotation: LogRotation,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    size: u64,
+    period: Option<String>,
+}
+
+impl RollingFile {
+    /// 打开（必要时创建）日志目录和当前日志文件，追加写入。
+    ///
+    /// # 参数
+    /// * `dir` - 日志目录。
+    /// * `settings` - `[log]` 配置中的文件名、轮转周期、大小上限和保留数量。
+    pub fn open(dir: &Path, settings: &LogSettings) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let (stem, extension) = match settings.file_name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => (stem.to_string(), extension.to_string()),
+            _ => (settings.file_name.clone(), "log".to_string()),
+        };
+        // 没有扩展名的文件名（如 `backend`）补上 `.log`，当前文件与轮转文件使用同一组文件名和扩展名
+        let current = dir.join(format!("{}.{}", stem, extension));
+        let mut rolling = Self {
+            dir: dir.to_path_buf(),
+            stem,
+            extension,
+            file: open_append(&current)?,
+            current,
+            rotation: settings.rotation,
+            max_bytes: settings.max_file_bytes,
+            max_files: settings.max_files,
+            size: 0,
+            period: None,
+        };
+
+        // 沿用已有文件时，以它的最后修改时间判断所属周期，重启后跨天的旧内容会在第一次写入时被轮转
+        let metadata = rolling.file.metadata()?;
+        rolling.size = metadata.len();
+        let last_write = match metadata.modified() {
+            Ok(modified) if rolling.size > 0 => DateTime::<Utc>::from(modified),
+            _ => Utc::now(),
+        };
+        rolling.period = period_of(rolling.rotation, last_write);
+        Ok(rolling)
+    }
+
+    /// 当前日志文件的路径。
+    pub fn path(&self) -> PathBuf {
+        self.current.clone()
+    }
+
+    /// 把当前文件重命名为带时间戳的文件，重新创建当前文件并清理多余的旧文件。
+    fn rotate(&mut self, now: DateTime<Utc>) -> io::Result<()> {
+        self.file.flush()?;
+        let timestamp = now.format("%Y%m%d-%H%M%S").to_string();
+        let mut target = self.dir.join(format!("{}.{}.{}", self.stem, timestamp, self.extension));
+        // 同一秒内多次按大小轮转时追加序号
+        let mut sequence = 1;
+        while target.exists() {
+            target = self.dir.join(format!("{}.{}-{}.{}", self.stem, timestamp, sequence, self.extension));
+            sequence += 1;
+        }
+        fs::rename(&self.current, &target)?;
+        self.file = open_append(&self.current)?;
+        self.size = 0;
+        self.prune()
+    }
+
+    /// 只保留最近的 `max_files` 个已轮转文件；时间戳格式保证按文件名排序即按时间排序。
+    fn prune(&self) -> io::Result<()> {
+        if self.max_files == 0 {
+            return Ok(());
+        }
+        let prefix = format!("{}.", self.stem);
+        let suffix = format!(".{}", self.extension);
+        let current = self.current.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let mut rotated: Vec<String> = fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter(|name| *name != current && name.starts_with(&prefix) && name.ends_with(&suffix))
+            .collect();
+        rotated.sort();
+        let excess = rotated.len().saturating_sub(self.max_files);
+        for name in &rotated[..excess] {
+            fs::remove_file(self.dir.join(name))?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let now = Utc::now();
+        let period = period_of(self.rotation, now);
+        let period_changed = period != self.period;
+        let too_large = self.max_bytes > 0 && self.size > 0 && self.size + buf.len() as u64 > self.max_bytes;
+        if period_changed || too_large {
+            self.rotate(now)?;
+            self.period = period;
+        }
+
+        self.file.write_all(buf)?;
+        self.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// 时间所属的轮转周期；不按时间轮转时为 `None`。
+fn period_of(rotation: LogRotation, time: DateTime<Utc>) -> Option<String> {
+    match rotation {
+        LogRotation::Never => None,
+        LogRotation::Hourly => Some(time.format("%Y-%m-%d %H").to_string()),
+        LogRotation::Daily => Some(time.format("%Y-%m-%d").to_string()),
+    }
+}
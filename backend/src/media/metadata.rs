@@ -0,0 +1,167 @@
+// src/media/metadata.rs
+//! 图片元数据清除。
+//! 上传的照片常带有 EXIF（含拍摄设备、GPS 坐标等）、XMP 和文本注释，公开前需要去掉。
+//!
+//! 能无损处理时只在容器层面删除元数据块，不重新编码像素；
+//! 图片带有非默认的 EXIF 方向时，删除 EXIF 会导致显示方向错误，此时把方向应用到像素上后重新编码。
+
+use std::io::Cursor;
+
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageReader};
+
+use super::{encode_image, MediaType};
+use crate::error::CustomError;
+use crate::i18n::{t, t_args};
+
+/// 清除图片中的 EXIF / XMP / 文本等元数据。
+///
+/// # 参数
+/// * `data` - 原始文件内容。
+/// * `media_type` - 由 `sniff_media_type` 识别出的类型。
+///
+/// # 返回
+/// 清除元数据后的文件内容；文件结构损坏时返回 `BadRequest`。
+pub fn strip_metadata(data: &[u8], media_type: MediaType) -> Result<Vec<u8>, CustomError> {
+    match media_type.mime_type {
+        // GIF 没有 EXIF，且可能是动图，保持原样
+        "image/gif" => Ok(data.to_vec()),
+        _ => {
+            if let Some(image) = decode_with_orientation(data)? {
+                return encode_image(&image, media_type)
+                    .map_err(|e| CustomError::InternalServerError(format!("图片重新编码失败: {}", e)));
+            }
+            let stripped = match media_type.mime_type {
+                "image/jpeg" => strip_jpeg(data),
+                "image/png" => strip_png(data),
+                "image/webp" => strip_webp(data),
+                _ => Some(data.to_vec()),
+            };
+            stripped.ok_or_else(|| CustomError::BadRequest(t("media-corrupted-image")))
+        }
+    }
+}
+
+/// 图片带有非默认方向时，解码并把方向应用到像素上；方向正常时返回 `None`。
+fn decode_with_orientation(data: &[u8]) -> Result<Option<DynamicImage>, CustomError> {
+    let invalid = |e: image::ImageError| CustomError::BadRequest(t_args("media-invalid-image", &[("error", &e)]));
+
+    let mut decoder = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|e| CustomError::BadRequest(t_args("media-invalid-image", &[("error", &e)])))?
+        .into_decoder()
+        .map_err(invalid)?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    if orientation == Orientation::NoTransforms {
+        return Ok(None);
+    }
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid)?;
+    image.apply_orientation(orientation);
+    Ok(Some(image))
+}
+
+/// 删除 JPEG 的 APP1 (EXIF / XMP)、APP13 (IPTC) 和 COM (注释) 段。
+fn strip_jpeg(data: &[u8]) -> Option<Vec<u8>> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut output = Vec::with_capacity(data.len());
+    output.extend_from_slice(&data[..2]);
+
+    let mut pos = 2;
+    loop {
+        if *data.get(pos)? != 0xFF {
+            return None;
+        }
+        // 跳过填充的 0xFF
+        while *data.get(pos + 1)? == 0xFF {
+            pos += 1;
+        }
+        let marker = data[pos + 1];
+        match marker {
+            // 独立标记，没有长度字段
+            0x01 | 0xD0..=0xD7 => {
+                output.extend_from_slice(&data[pos..pos + 2]);
+                pos += 2;
+            }
+            // SOS 之后是压缩数据，原样保留
+            0xDA => {
+                output.extend_from_slice(&data[pos..]);
+                return Some(output);
+            }
+            _ => {
+                let length = u16::from_be_bytes([*data.get(pos + 2)?, *data.get(pos + 3)?]) as usize;
+                let end = pos + 2 + length;
+                if length < 2 || end > data.len() {
+                    return None;
+                }
+                if !matches!(marker, 0xE1 | 0xED | 0xFE) {
+                    output.extend_from_slice(&data[pos..end]);
+                }
+                pos = end;
+            }
+        }
+    }
+}
+
+/// 删除 PNG 的 eXIf、文本 (tEXt / zTXt / iTXt) 和 tIME 块。
+fn strip_png(data: &[u8]) -> Option<Vec<u8>> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if !data.starts_with(SIGNATURE) {
+        return None;
+    }
+    let mut output = Vec::with_capacity(data.len());
+    output.extend_from_slice(SIGNATURE);
+
+    let mut pos = SIGNATURE.len();
+    while pos < data.len() {
+        let length = u32::from_be_bytes(data.get(pos..pos + 4)?.try_into().ok()?) as usize;
+        let chunk_type = data.get(pos + 4..pos + 8)?;
+        let end = pos.checked_add(12 + length)?; // 长度 + 类型 + 数据 + CRC
+        if end > data.len() {
+            return None;
+        }
+        if !matches!(chunk_type, b"eXIf" | b"tEXt" | b"zTXt" | b"iTXt" | b"tIME") {
+            output.extend_from_slice(&data[pos..end]);
+        }
+        if chunk_type == b"IEND" {
+            return Some(output);
+        }
+        pos = end;
+    }
+    None
+}
+
+/// 删除 WebP 扩展格式中的 EXIF 和 XMP 块，并清除 VP8X 头中对应的标志位。
+fn strip_webp(data: &[u8]) -> Option<Vec<u8>> {
+    if data.len() < 12 || &data[..4] != b"RIFF" || &data[8..12] != b"WEBP" {
+        return None;
+    }
+    let mut output = Vec::with_capacity(data.len());
+    output.extend_from_slice(&data[..12]);
+
+    let mut pos = 12;
+    while pos < data.len() {
+        let fourcc = data.get(pos..pos + 4)?;
+        let size = u32::from_le_bytes(data.get(pos + 4..pos + 8)?.try_into().ok()?) as usize;
+        let end = pos.checked_add(8 + size + (size & 1))?.min(data.len()); // 数据按偶数字节对齐
+        if pos + 8 + size > data.len() {
+            return None;
+        }
+        match fourcc {
+            b"EXIF" | b"XMP " => {}
+            b"VP8X" => {
+                let start = output.len();
+                output.extend_from_slice(&data[pos..end]);
+                // 标志位：0x08 = EXIF，0x04 = XMP
+                *output.get_mut(start + 8)? &= !(0x08 | 0x04);
+            }
+            _ => output.extend_from_slice(&data[pos..end]),
+        }
+        pos = end;
+    }
+
+    let riff_size = u32::try_from(output.len() - 8).ok()?;
+    output[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(output)
+}
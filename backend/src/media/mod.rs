@@ -0,0 +1,97 @@
+// src/media/mod.rs
+//! 媒体文件的类型识别、校验与图片处理。
+//! 上传文件的类型一律根据文件头的魔数判断，不信任客户端提供的 `Content-Type` 和扩展名。
+
+pub mod metadata; // EXIF / XMP 等元数据清除
+pub mod variants; // 响应式尺寸与 WebP 版本的生成
+
+use std::io::Cursor;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, ImageFormat, ImageReader, ImageResult};
+use serde::{Deserialize, Serialize};
+
+pub use metadata::strip_metadata;
+pub use variants::{generate_variants, process_variants, variant_key, DEFAULT_VARIANT_WIDTHS};
+
+/// 允许上传的媒体类型：(MIME 类型, 存储时使用的扩展名)。
+pub const ALLOWED_MEDIA_TYPES: &[(&str, &str)] = &[
+    ("image/jpeg", "jpg"),
+    ("image/png", "png"),
+    ("image/gif", "gif"),
+    ("image/webp", "webp"),
+];
+
+/// 识别出的媒体类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MediaType {
+    pub mime_type: &'static str,
+    pub extension: &'static str,
+}
+
+impl MediaType {
+    /// WebP 格式，用于生成额外的 WebP 版本。
+    pub const WEBP: MediaType = MediaType { mime_type: "image/webp", extension: "webp" };
+}
+
+/// 后台生成的图片变体（缩小后的尺寸或 WebP 版本），以 JSON 形式记录在媒体表中。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaVariant {
+    pub width: u32,
+    pub height: u32,
+    pub mime_type: String,
+    pub storage_key: String,
+    pub size_bytes: u64,
+}
+
+/// 根据文件头魔数识别媒体类型。
+///
+/// # 参数
+/// * `data` - 文件内容（至少包含文件头）。
+///
+/// # 返回
+/// 属于 `ALLOWED_MEDIA_TYPES` 的类型返回 `Some`，否则返回 `None`。
+pub fn sniff_media_type(data: &[u8]) -> Option<MediaType> {
+    let detected = infer::get(data)?;
+    ALLOWED_MEDIA_TYPES
+        .iter()
+        .find(|(mime_type, _)| *mime_type == detected.mime_type())
+        .map(|(mime_type, extension)| MediaType { mime_type, extension })
+}
+
+/// 读取图片尺寸（只解析文件头，不解码像素）。
+///
+/// # 返回
+/// `(width, height)`，无法识别时返回 `None`。
+pub fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+/// 按指定格式编码图片，不写入任何元数据。
+///
+/// JPEG 使用 85 的质量；WebP 只能无损编码。
+pub fn encode_image(image: &DynamicImage, media_type: MediaType) -> ImageResult<Vec<u8>> {
+    let mut data = Vec::new();
+    match media_type.mime_type {
+        "image/jpeg" => {
+            DynamicImage::ImageRgb8(image.to_rgb8())
+                .write_with_encoder(JpegEncoder::new_with_quality(&mut data, 85))?;
+        }
+        "image/webp" => {
+            let image = if image.color().has_alpha() {
+                DynamicImage::ImageRgba8(image.to_rgba8())
+            } else {
+                DynamicImage::ImageRgb8(image.to_rgb8())
+            };
+            image.write_with_encoder(WebPEncoder::new_lossless(&mut data))?;
+        }
+        "image/gif" => image.write_to(&mut Cursor::new(&mut data), ImageFormat::Gif)?,
+        _ => image.write_to(&mut Cursor::new(&mut data), ImageFormat::Png)?,
+    }
+    Ok(data)
+}
@@ -0,0 +1,178 @@
+// src/media/variants.rs
+//! 响应式图片变体的生成。
+//! 上传完成后在后台任务中按配置的宽度生成缩小版本和 WebP 版本，写入存储后端并记录到媒体表。
+
+use std::sync::Arc;
+
+use image::imageops::FilterType;
+use image::GenericImageView;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use super::{encode_image, MediaType, MediaVariant};
+use crate::database::media::MediaRepository;
+use crate::error::CustomError;
+use crate::storage::StorageBackend;
+
+/// 默认生成的宽度（像素）。
+pub const DEFAULT_VARIANT_WIDTHS: &[u32] = &[320, 768, 1280];
+
+/// 编码完成、尚未写入存储的变体。
+#[derive(Debug, Clone)]
+pub struct EncodedVariant {
+    pub width: u32,
+    pub height: u32,
+    pub media_type: MediaType,
+    pub data: Vec<u8>,
+}
+
+/// 变体的存储键：`<原文件名>-<宽度>w.<扩展名>`，例如 `abc-320w.webp`。
+pub fn variant_key(storage_key: &str, width: u32, extension: &str) -> String {
+    let stem = storage_key.rsplit_once('.').map_or(storage_key, |(stem, _)| stem);
+    format!("{}-{}w.{}", stem, width, extension)
+}
+
+/// 生成图片变体。
+///
+/// 对每个小于原图宽度的配置宽度，生成一份与原图同格式的缩小版本；
+/// 同时为每个尺寸（包括原尺寸）生成 WebP 版本。由于只能无损编码 WebP，
+/// WebP 版本仅在比同尺寸的原格式文件更小时保留。
+/// GIF 可能是动图，缩放会丢失帧，因此不生成变体。
+///
+/// # 参数
+/// * `data` - 已清除元数据的原图。
+/// * `media_type` - 原图类型。
+/// * `widths` - 需要生成的宽度。
+///
+/// # 返回
+/// 按宽度升序排列的变体列表。
+pub fn generate_variants(
+    data: &[u8],
+    media_type: MediaType,
+    widths: &[u32],
+) -> image::ImageResult<Vec<EncodedVariant>> {
+    if media_type.mime_type == "image/gif" {
+        return Ok(Vec::new());
+    }
+
+    let image = image::load_from_memory(data)?;
+    let (original_width, original_height) = image.dimensions();
+
+    let mut target_widths: Vec<u32> = widths.iter().copied().filter(|w| *w > 0 && *w < original_width).collect();
+    target_widths.sort_unstable();
+    target_widths.dedup();
+
+    let mut variants = Vec::new();
+    for width in target_widths {
+        let height = ((original_height as u64 * width as u64 + original_width as u64 / 2)
+            / original_width as u64)
+            .max(1) as u32;
+        let resized = image.resize_exact(width, height, FilterType::Lanczos3);
+        let resized_data = encode_image(&resized, media_type)?;
+
+        if media_type != MediaType::WEBP {
+            let webp = encode_image(&resized, MediaType::WEBP)?;
+            if webp.len() < resized_data.len() {
+                variants.push(EncodedVariant { width, height, media_type: MediaType::WEBP, data: webp });
+            }
+        }
+        variants.push(EncodedVariant { width, height, media_type, data: resized_data });
+    }
+
+    // 原尺寸的 WebP 版本
+    if media_type != MediaType::WEBP {
+        let webp = encode_image(&image, MediaType::WEBP)?;
+        if webp.len() < data.len() {
+            variants.push(EncodedVariant {
+                width: original_width,
+                height: original_height,
+                media_type: MediaType::WEBP,
+                data: webp,
+            });
+        }
+    }
+
+    Ok(variants)
+}
+
+/// 后台处理一个已上传的媒体文件：生成变体、写入存储后端并更新媒体记录。
+///
+/// 处理失败时记录为空列表，避免记录一直停留在“处理中”；
+/// 处理期间媒体记录已被删除时，清理刚写入的变体文件。
+///
+/// # 参数
+/// * `storage` - 存储后端。
+/// * `repo` - 媒体仓库。
+/// * `media_id` - 媒体 ID。
+/// * `storage_key` - 原图的存储键。
+/// * `data` - 已清除元数据的原图。
+/// * `media_type` - 原图类型。
+/// * `widths` - 需要生成的宽度。
+/// * `shutdown` - 停机通知，触发后中止处理并清理已写入的变体，媒体记录保持未处理状态。
+///
+/// # 返回
+/// 成功记录的变体列表。
+#[allow(clippy::too_many_arguments)]
+pub async fn process_variants(
+    storage: Arc<dyn StorageBackend>,
+    repo: MediaRepository,
+    media_id: i32,
+    storage_key: String,
+    data: Vec<u8>,
+    media_type: MediaType,
+    widths: Vec<u32>,
+    shutdown: CancellationToken,
+) -> Result<Vec<MediaVariant>, CustomError> {
+    let aborted = || CustomError::InternalServerError(format!("停机时中止媒体 {} 的变体处理", media_id));
+
+    // 图片编解码是 CPU 密集型操作，放到阻塞线程池中执行；停机时不再等待其完成
+    let encoding = tokio::task::spawn_blocking(move || generate_variants(&data, media_type, &widths));
+    let encoded = tokio::select! {
+        result = encoding => result
+            .map_err(|e| CustomError::InternalServerError(format!("图片处理任务异常退出: {}", e)))?,
+        _ = shutdown.cancelled() => return Err(aborted()),
+    };
+
+    let encoded = match encoded {
+        Ok(encoded) => encoded,
+        Err(e) => {
+            warn!("媒体 {} 生成变体失败: {}", media_id, e);
+            Vec::new()
+        }
+    };
+
+    let mut variants = Vec::with_capacity(encoded.len());
+    for variant in encoded {
+        if shutdown.is_cancelled() {
+            delete_variants(storage.as_ref(), &variants).await;
+            return Err(aborted());
+        }
+        let key = variant_key(&storage_key, variant.width, variant.media_type.extension);
+        if let Err(e) = storage.put(&key, &variant.data, variant.media_type.mime_type).await {
+            warn!("写入媒体变体 {} 失败: {}", key, e);
+            continue;
+        }
+        variants.push(MediaVariant {
+            width: variant.width,
+            height: variant.height,
+            mime_type: variant.media_type.mime_type.to_string(),
+            storage_key: key,
+            size_bytes: variant.data.len() as u64,
+        });
+    }
+
+    if let Err(e) = repo.set_media_variants(media_id, &variants).await {
+        delete_variants(storage.as_ref(), &variants).await;
+        return Err(e);
+    }
+    Ok(variants)
+}
+
+/// 清理已写入存储但未记录的变体文件。
+async fn delete_variants(storage: &dyn StorageBackend, variants: &[MediaVariant]) {
+    for variant in variants {
+        if let Err(cleanup) = storage.delete(&variant.storage_key).await {
+            warn!("清理媒体变体 {} 失败: {}", variant.storage_key, cleanup);
+        }
+    }
+}
@@ -0,0 +1,213 @@
+// src/metrics.rs
+//! Prometheus 指标：HTTP 请求、数据库连接池与查询耗时、业务事件计数。
+//!
+//! 所有指标注册在独立的 `Registry` 中（不使用全局默认注册表），
+//! 由 `GET /metrics` 以 Prometheus 文本格式输出。
+
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+use std::sync::Arc;
+
+use sea_orm::DatabaseConnection;
+
+/// 所有指标名称的前缀。
+const NAMESPACE: &str = "blog";
+
+/// 请求与查询耗时直方图的分桶（秒）。
+const HTTP_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+const DB_BUCKETS: &[f64] = &[0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0];
+
+/// 未匹配任何路由的请求（前端静态资源回退、404）使用的路由标签，避免标签数量无限增长。
+pub const UNMATCHED_ROUTE: &str = "<unmatched>";
+
+/// 标准 HTTP 方法以外的请求使用的方法标签，避免客户端构造任意方法名使标签数量无限增长。
+pub const OTHER_METHOD: &str = "OTHER";
+
+/// 应用的全部指标。
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_duration: HistogramVec,
+    db_query_duration: HistogramVec,
+    db_pool_connections: IntGaugeVec,
+    db_pool_max_connections: IntGauge,
+    registrations: IntCounter,
+    logins: IntCounterVec,
+    posts_published: IntCounter,
+    comments_created: IntCounter,
+}
+
+impl Metrics {
+    /// 创建并注册全部指标。
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP 请求总数").namespace(NAMESPACE),
+            &["method", "route", "status"],
+        ).unwrap();
+        let http_duration = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP 请求处理耗时（秒）")
+                .namespace(NAMESPACE)
+                .buckets(HTTP_BUCKETS.to_vec()),
+            &["method", "route", "status"],
+        ).unwrap();
+        let db_query_duration = HistogramVec::new(
+            HistogramOpts::new("db_query_duration_seconds", "数据库语句执行耗时（秒）")
+                .namespace(NAMESPACE)
+                .buckets(DB_BUCKETS.to_vec()),
+            &["operation", "success"],
+        ).unwrap();
+        let db_pool_connections = IntGaugeVec::new(
+            Opts::new("db_pool_connections", "数据库连接池中的连接数").namespace(NAMESPACE),
+            &["state"],
+        ).unwrap();
+        let db_pool_max_connections = IntGauge::with_opts(
+            Opts::new("db_pool_max_connections", "数据库连接池的最大连接数").namespace(NAMESPACE),
+        ).unwrap();
+        let registrations = IntCounter::with_opts(
+            Opts::new("user_registrations_total", "用户注册数").namespace(NAMESPACE),
+        ).unwrap();
+        let logins = IntCounterVec::new(
+            Opts::new("user_logins_total", "登录次数").namespace(NAMESPACE),
+            &["result"],
+        ).unwrap();
+        let posts_published = IntCounter::with_opts(
+            Opts::new("posts_published_total", "发布的文章数").namespace(NAMESPACE),
+        ).unwrap();
+        let comments_created = IntCounter::with_opts(
+            Opts::new("comments_created_total", "创建的评论数").namespace(NAMESPACE),
+        ).unwrap();
+
+        registry.register(Box::new(http_requests.clone())).unwrap();
+        registry.register(Box::new(http_duration.clone())).unwrap();
+        registry.register(Box::new(db_query_duration.clone())).unwrap();
+        registry.register(Box::new(db_pool_connections.clone())).unwrap();
+        registry.register(Box::new(db_pool_max_connections.clone())).unwrap();
+        registry.register(Box::new(registrations.clone())).unwrap();
+        registry.register(Box::new(logins.clone())).unwrap();
+        registry.register(Box::new(posts_published.clone())).unwrap();
+        registry.register(Box::new(comments_created.clone())).unwrap();
+
+        // 预先创建常用的标签组合，使其在第一次发生前就以 0 出现
+        for result in ["success", "failure"] {
+            logins.with_label_values(&[result]);
+        }
+
+        Self {
+            registry,
+            http_requests,
+            http_duration,
+            db_query_duration,
+            db_pool_connections,
+            db_pool_max_connections,
+            registrations,
+            logins,
+            posts_published,
+            comments_created,
+        }
+    }
+
+    /// 为数据库连接注册语句耗时回调；需要在连接被复制到各个 Repository 之前调用。
+    pub fn instrument_database(self: &Arc<Self>, db: &mut DatabaseConnection) {
+        let metrics = self.clone();
+        db.set_metric_callback(move |info| {
+            metrics.observe_db_query(&info.statement.sql, !info.failed, info.elapsed.as_secs_f64());
+        });
+    }
+
+    /// 记录一次 HTTP 请求。
+    ///
+    /// # 参数
+    /// * `method` - 请求方法，非标准方法记为 `OTHER_METHOD`。
+    /// * `route` - 匹配到的路由模板（例如 `/api/post/:id`），未匹配时为 `UNMATCHED_ROUTE`。
+    pub fn observe_http(&self, method: &str, route: &str, status: u16, seconds: f64) {
+        let method = match method {
+            "GET" | "HEAD" | "POST" | "PUT" | "DELETE" | "CONNECT" | "OPTIONS" | "TRACE" | "PATCH" => method,
+            _ => OTHER_METHOD,
+        };
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_duration.with_label_values(&labels).observe(seconds);
+    }
+
+    /// 记录一条数据库语句的执行耗时，按语句类型 (SELECT / INSERT / ...) 分组。
+    pub fn observe_db_query(&self, sql: &str, success: bool, seconds: f64) {
+        let operation = sql
+            .split_whitespace()
+            .next()
+            .map(|word| word.to_ascii_uppercase())
+            .filter(|word| matches!(word.as_str(), "SELECT" | "INSERT" | "UPDATE" | "DELETE"))
+            .unwrap_or_else(|| "OTHER".into());
+        self.db_query_duration
+            .with_label_values(&[operation.as_str(), if success { "true" } else { "false" }])
+            .observe(seconds);
+    }
+
+    /// 新用户注册成功。
+    pub fn user_registered(&self) {
+        self.registrations.inc();
+    }
+
+    /// 登录尝试的结果。
+    pub fn login_attempt(&self, success: bool) {
+        self.logins.with_label_values(&[if success { "success" } else { "failure" }]).inc();
+    }
+
+    /// 文章被发布（创建时直接发布，或由草稿改为发布）。
+    pub fn post_published(&self) {
+        self.posts_published.inc();
+    }
+
+    /// 新评论创建成功。
+    pub fn comment_created(&self) {
+        self.comments_created.inc();
+    }
+
+    /// 读取连接池当前状态并输出全部指标（Prometheus 文本格式）。
+    pub fn render(&self, db: &DatabaseConnection) -> String {
+        if let Some((size, idle, max)) = pool_stats(db) {
+            self.db_pool_connections.with_label_values(&["idle"]).set(idle as i64);
+            self.db_pool_connections.with_label_values(&["in_use"]).set((size as usize).saturating_sub(idle) as i64);
+            self.db_pool_max_connections.set(max as i64);
+        }
+
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("Prometheus 文本编码失败");
+        String::from_utf8(buffer).expect("Prometheus 文本编码结果不是 UTF-8")
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 连接池的 (当前连接数, 空闲连接数, 最大连接数)。
+fn pool_stats(db: &DatabaseConnection) -> Option<(u32, usize, u32)> {
+    match db {
+        #[cfg(feature = "sqlite")]
+        DatabaseConnection::SqlxSqlitePoolConnection(_) => {
+            let pool = db.get_sqlite_connection_pool();
+            Some((pool.size(), pool.num_idle(), pool.options().get_max_connections()))
+        }
+        #[cfg(feature = "postgres")]
+        DatabaseConnection::SqlxPostgresPoolConnection(_) => {
+            let pool = db.get_postgres_connection_pool();
+            Some((pool.size(), pool.num_idle(), pool.options().get_max_connections()))
+        }
+        #[cfg(feature = "mysql")]
+        DatabaseConnection::SqlxMySqlPoolConnection(_) => {
+            let pool = db.get_mysql_connection_pool();
+            Some((pool.size(), pool.num_idle(), pool.options().get_max_connections()))
+        }
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
@@ -0,0 +1,54 @@
+//! 创建用户表。
+
+use axum::async_trait;
+use sea_orm::sea_query::{ColumnDef, Expr, Table};
+use sea_orm::{DatabaseTransaction, DbErr, DeriveIden};
+
+use super::exec;
+
+pub struct Migration;
+
+#[async_trait]
+impl super::Migration for Migration {
+    fn name(&self) -> &'static str {
+        "m20240101_000001_create_users"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        let table = Table::create()
+            .table(Users::Table)
+            .if_not_exists()
+            .col(ColumnDef::new(Users::Id).integer().not_null().auto_increment().primary_key())
+            // 用户名唯一且长度至少为3
+            .col(ColumnDef::new(Users::Username).string().not_null().unique_key()
+                .check(Expr::cust("length(username) >= 3")))
+            // 邮箱唯一且格式校验
+            .col(ColumnDef::new(Users::Email).string().not_null().unique_key()
+                .check(Expr::cust("email LIKE '%@%.%'")))
+            .col(ColumnDef::new(Users::PasswordHash).string().not_null()
+                .check(Expr::cust("length(password_hash) > 0")))
+            // 用户角色，只能是 'user' 或 'admin'
+            .col(ColumnDef::new(Users::Role).string().not_null().default("user")
+                .check(Expr::cust("role IN ('user', 'admin')")))
+            .col(ColumnDef::new(Users::CreatedAt).date_time().not_null().default(Expr::current_timestamp()))
+            .col(ColumnDef::new(Users::UpdatedAt).date_time().not_null().default(Expr::current_timestamp()))
+            .to_owned();
+        exec(db, &table).await
+    }
+
+    async fn down(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        exec(db, &Table::drop().table(Users::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+pub(super) enum Users {
+    Table,
+    Id,
+    Username,
+    Email,
+    PasswordHash,
+    Role,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,64 @@
+//! 创建文章表及其索引。
+
+use axum::async_trait;
+use sea_orm::sea_query::{ColumnDef, Expr, ForeignKey, ForeignKeyAction, Index, Table};
+use sea_orm::{DatabaseTransaction, DbErr, DeriveIden};
+
+use super::exec;
+use super::m20240101_000001_create_users::Users;
+
+pub struct Migration;
+
+#[async_trait]
+impl super::Migration for Migration {
+    fn name(&self) -> &'static str {
+        "m20240101_000002_create_posts"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        let table = Table::create()
+            .table(Posts::Table)
+            .if_not_exists()
+            .col(ColumnDef::new(Posts::Id).integer().not_null().auto_increment().primary_key())
+            .col(ColumnDef::new(Posts::Title).string().not_null())
+            .col(ColumnDef::new(Posts::ContentMarkdown).text().not_null())
+            .col(ColumnDef::new(Posts::Category).string().not_null().default("未分类"))
+            .col(ColumnDef::new(Posts::AuthorId).integer().not_null())
+            .col(ColumnDef::new(Posts::IsPublished).integer().not_null().default(0)) // 0: 草稿, 1: 已发布
+            .col(ColumnDef::new(Posts::ViewCount).integer().not_null().default(0))
+            .col(ColumnDef::new(Posts::CoverUrl).string())
+            .col(ColumnDef::new(Posts::CreatedAt).date_time().not_null().default(Expr::current_timestamp()))
+            .col(ColumnDef::new(Posts::UpdatedAt).date_time().not_null().default(Expr::current_timestamp()))
+            // 作者删除时，其所有文章也删除
+            .foreign_key(ForeignKey::create()
+                .from(Posts::Table, Posts::AuthorId)
+                .to(Users::Table, Users::Id)
+                .on_delete(ForeignKeyAction::Cascade))
+            .to_owned();
+        exec(db, &table).await?;
+
+        exec(db, &Index::create().if_not_exists().name("idx_posts_author")
+            .table(Posts::Table).col(Posts::AuthorId).to_owned()).await?;
+        exec(db, &Index::create().if_not_exists().name("idx_posts_category")
+            .table(Posts::Table).col(Posts::Category).to_owned()).await
+    }
+
+    async fn down(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        exec(db, &Table::drop().table(Posts::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+pub(super) enum Posts {
+    Table,
+    Id,
+    Title,
+    ContentMarkdown,
+    Category,
+    AuthorId,
+    IsPublished,
+    ViewCount,
+    CoverUrl,
+    CreatedAt,
+    UpdatedAt,
+}
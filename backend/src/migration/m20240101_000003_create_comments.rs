@@ -0,0 +1,65 @@
+//! 创建评论表及其索引。
+
+use axum::async_trait;
+use sea_orm::sea_query::{ColumnDef, Expr, ForeignKey, ForeignKeyAction, Index, Table};
+use sea_orm::{DatabaseTransaction, DbErr, DeriveIden};
+
+use super::exec;
+use super::m20240101_000001_create_users::Users;
+use super::m20240101_000002_create_posts::Posts;
+
+pub struct Migration;
+
+#[async_trait]
+impl super::Migration for Migration {
+    fn name(&self) -> &'static str {
+        "m20240101_000003_create_comments"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        let table = Table::create()
+            .table(Comments::Table)
+            .if_not_exists()
+            .col(ColumnDef::new(Comments::Id).integer().not_null().auto_increment().primary_key())
+            .col(ColumnDef::new(Comments::Content).text().not_null())
+            .col(ColumnDef::new(Comments::PostId).integer().not_null())
+            .col(ColumnDef::new(Comments::UserId).integer().not_null())
+            .col(ColumnDef::new(Comments::ParentId).integer()) // NULL 表示顶级评论
+            .col(ColumnDef::new(Comments::CreatedAt).date_time().not_null().default(Expr::current_timestamp()))
+            // 文章、用户、父评论删除时，相关评论也删除
+            .foreign_key(ForeignKey::create()
+                .from(Comments::Table, Comments::PostId)
+                .to(Posts::Table, Posts::Id)
+                .on_delete(ForeignKeyAction::Cascade))
+            .foreign_key(ForeignKey::create()
+                .from(Comments::Table, Comments::UserId)
+                .to(Users::Table, Users::Id)
+                .on_delete(ForeignKeyAction::Cascade))
+            .foreign_key(ForeignKey::create()
+                .from(Comments::Table, Comments::ParentId)
+                .to(Comments::Table, Comments::Id)
+                .on_delete(ForeignKeyAction::Cascade))
+            .to_owned();
+        exec(db, &table).await?;
+
+        exec(db, &Index::create().if_not_exists().name("idx_comments_post")
+            .table(Comments::Table).col(Comments::PostId).to_owned()).await?;
+        exec(db, &Index::create().if_not_exists().name("idx_comments_user")
+            .table(Comments::Table).col(Comments::UserId).to_owned()).await
+    }
+
+    async fn down(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        exec(db, &Table::drop().table(Comments::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Comments {
+    Table,
+    Id,
+    Content,
+    PostId,
+    UserId,
+    ParentId,
+    CreatedAt,
+}
@@ -0,0 +1,54 @@
+//! 创建收藏表及其索引。
+
+use axum::async_trait;
+use sea_orm::sea_query::{ColumnDef, Expr, ForeignKey, ForeignKeyAction, Index, Table};
+use sea_orm::{DatabaseTransaction, DbErr, DeriveIden};
+
+use super::exec;
+use super::m20240101_000001_create_users::Users;
+use super::m20240101_000002_create_posts::Posts;
+
+pub struct Migration;
+
+#[async_trait]
+impl super::Migration for Migration {
+    fn name(&self) -> &'static str {
+        "m20240101_000004_create_favorites"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        let table = Table::create()
+            .table(Favorites::Table)
+            .if_not_exists()
+            .col(ColumnDef::new(Favorites::UserId).integer().not_null())
+            .col(ColumnDef::new(Favorites::PostId).integer().not_null())
+            .col(ColumnDef::new(Favorites::CreatedAt).date_time().not_null().default(Expr::current_timestamp()))
+            // 联合主键，确保一个用户不能重复收藏同一篇文章
+            .primary_key(Index::create().col(Favorites::UserId).col(Favorites::PostId))
+            .foreign_key(ForeignKey::create()
+                .from(Favorites::Table, Favorites::UserId)
+                .to(Users::Table, Users::Id)
+                .on_delete(ForeignKeyAction::Cascade))
+            .foreign_key(ForeignKey::create()
+                .from(Favorites::Table, Favorites::PostId)
+                .to(Posts::Table, Posts::Id)
+                .on_delete(ForeignKeyAction::Cascade))
+            .to_owned();
+        exec(db, &table).await?;
+
+        exec(db, &Index::create().if_not_exists().name("idx_favorites_user")
+            .table(Favorites::Table).col(Favorites::UserId).to_owned()).await
+    }
+
+    async fn down(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        exec(db, &Table::drop().table(Favorites::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Favorites {
+    Table,
+    UserId,
+    PostId,
+    CreatedAt,
+}
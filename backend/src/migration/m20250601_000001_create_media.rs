@@ -0,0 +1,64 @@
+//! 创建媒体文件表及其索引。
+
+use axum::async_trait;
+use sea_orm::sea_query::{ColumnDef, Expr, ForeignKey, ForeignKeyAction, Index, Table};
+use sea_orm::{DatabaseTransaction, DbErr, DeriveIden};
+
+use super::exec;
+use super::m20240101_000001_create_users::Users;
+
+pub struct Migration;
+
+#[async_trait]
+impl super::Migration for Migration {
+    fn name(&self) -> &'static str {
+        "m20250601_000001_create_media"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        let table = Table::create()
+            .table(Media::Table)
+            .if_not_exists()
+            .col(ColumnDef::new(Media::Id).integer().not_null().auto_increment().primary_key())
+            .col(ColumnDef::new(Media::OwnerId).integer().not_null())
+            .col(ColumnDef::new(Media::StorageKey).string().not_null().unique_key()) // 文件在存储后端中的键
+            .col(ColumnDef::new(Media::OriginalName).string())
+            .col(ColumnDef::new(Media::MimeType).string().not_null()) // 根据文件头识别出的类型
+            .col(ColumnDef::new(Media::SizeBytes).big_integer().not_null())
+            .col(ColumnDef::new(Media::Checksum).string().not_null()) // SHA-256 (十六进制)
+            .col(ColumnDef::new(Media::Width).integer())
+            .col(ColumnDef::new(Media::Height).integer())
+            .col(ColumnDef::new(Media::Variants).text()) // 各尺寸 / WebP 版本 (JSON)，NULL 表示尚未处理
+            .col(ColumnDef::new(Media::CreatedAt).date_time().not_null().default(Expr::current_timestamp()))
+            // 用户删除时，其媒体记录也删除
+            .foreign_key(ForeignKey::create()
+                .from(Media::Table, Media::OwnerId)
+                .to(Users::Table, Users::Id)
+                .on_delete(ForeignKeyAction::Cascade))
+            .to_owned();
+        exec(db, &table).await?;
+
+        exec(db, &Index::create().if_not_exists().name("idx_media_owner")
+            .table(Media::Table).col(Media::OwnerId).to_owned()).await
+    }
+
+    async fn down(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        exec(db, &Table::drop().table(Media::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Media {
+    Table,
+    Id,
+    OwnerId,
+    StorageKey,
+    OriginalName,
+    MimeType,
+    SizeBytes,
+    Checksum,
+    Width,
+    Height,
+    Variants,
+    CreatedAt,
+}
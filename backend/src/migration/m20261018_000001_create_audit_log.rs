@@ -0,0 +1,61 @@
+//! 创建审计日志表及其索引。
+
+use axum::async_trait;
+use sea_orm::sea_query::{ColumnDef, Expr, Index, Table};
+use sea_orm::{DatabaseTransaction, DbErr, DeriveIden};
+
+use super::exec;
+
+pub struct Migration;
+
+#[async_trait]
+impl super::Migration for Migration {
+    fn name(&self) -> &'static str {
+        "m20261018_000001_create_audit_log"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        // 不设外键：用户被删除后，其操作记录和针对他的记录都需要保留
+        let table = Table::create()
+            .table(AuditLog::Table)
+            .if_not_exists()
+            .col(ColumnDef::new(AuditLog::Id).big_integer().not_null().auto_increment().primary_key())
+            .col(ColumnDef::new(AuditLog::ActorId).integer()) // 未登录的操作 (例如登录失败) 为 NULL
+            .col(ColumnDef::new(AuditLog::ActorName).string()) // 操作时的用户名快照
+            .col(ColumnDef::new(AuditLog::Action).string().not_null()) // 例如 user.update、auth.login_failed
+            .col(ColumnDef::new(AuditLog::TargetType).string())
+            .col(ColumnDef::new(AuditLog::TargetId).string())
+            .col(ColumnDef::new(AuditLog::Changes).text()) // 变更前后的字段差异 (JSON)
+            .col(ColumnDef::new(AuditLog::Ip).string())
+            .col(ColumnDef::new(AuditLog::RequestId).string())
+            .col(ColumnDef::new(AuditLog::CreatedAt).date_time().not_null().default(Expr::current_timestamp()))
+            .to_owned();
+        exec(db, &table).await?;
+
+        exec(db, &Index::create().if_not_exists().name("idx_audit_log_actor")
+            .table(AuditLog::Table).col(AuditLog::ActorId).to_owned()).await?;
+        exec(db, &Index::create().if_not_exists().name("idx_audit_log_action")
+            .table(AuditLog::Table).col(AuditLog::Action).to_owned()).await?;
+        exec(db, &Index::create().if_not_exists().name("idx_audit_log_created_at")
+            .table(AuditLog::Table).col(AuditLog::CreatedAt).to_owned()).await
+    }
+
+    async fn down(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        exec(db, &Table::drop().table(AuditLog::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditLog {
+    Table,
+    Id,
+    ActorId,
+    ActorName,
+    Action,
+    TargetType,
+    TargetId,
+    Changes,
+    Ip,
+    RequestId,
+    CreatedAt,
+}
@@ -0,0 +1,38 @@
+//! 为用户表添加公开资料字段 (显示名称、头像)。
+
+use axum::async_trait;
+use sea_orm::sea_query::{ColumnDef, Table};
+use sea_orm::{DatabaseTransaction, DbErr, DeriveIden};
+
+use super::exec;
+use super::m20240101_000001_create_users::Users;
+
+pub struct Migration;
+
+#[async_trait]
+impl super::Migration for Migration {
+    fn name(&self) -> &'static str {
+        "m20261018_000002_add_user_profile"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        // SQLite 的 ALTER TABLE 一次只能修改一列
+        exec(db, &Table::alter().table(Users::Table)
+            .add_column(ColumnDef::new(UserProfile::DisplayName).string()) // NULL 时显示用户名
+            .to_owned()).await?;
+        exec(db, &Table::alter().table(Users::Table)
+            .add_column(ColumnDef::new(UserProfile::AvatarUrl).string())
+            .to_owned()).await
+    }
+
+    async fn down(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        exec(db, &Table::alter().table(Users::Table).drop_column(UserProfile::AvatarUrl).to_owned()).await?;
+        exec(db, &Table::alter().table(Users::Table).drop_column(UserProfile::DisplayName).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserProfile {
+    DisplayName,
+    AvatarUrl,
+}
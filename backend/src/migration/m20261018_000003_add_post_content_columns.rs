@@ -0,0 +1,49 @@
+//! 为文章表添加自定义摘要和渲染缓存字段。
+//! 这些列曾直接写在建表迁移和 init.sql 中，已有的表可能已经包含其中一部分，因此逐列检查后再添加。
+
+use axum::async_trait;
+use sea_orm::sea_query::{ColumnDef, Iden, Table};
+use sea_orm::{DatabaseTransaction, DbErr, DeriveIden};
+
+use super::m20240101_000002_create_posts::Posts;
+use super::{exec, has_column};
+
+pub struct Migration;
+
+#[async_trait]
+impl super::Migration for Migration {
+    fn name(&self) -> &'static str {
+        "m20261018_000003_add_post_content_columns"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        let columns = [
+            ColumnDef::new(PostContent::Excerpt).text().to_owned(),      // 作者自定义摘要，为空时自动生成
+            ColumnDef::new(PostContent::ContentHtml).text().to_owned(),  // 渲染并清洗后的 HTML 缓存
+            ColumnDef::new(PostContent::ContentHash).string().to_owned(), // 生成 content_html 时 Markdown 内容的哈希
+            ColumnDef::new(PostContent::TocJson).text().to_owned(),      // 与 content_html 一同缓存的目录 (JSON)
+        ];
+        // SQLite 的 ALTER TABLE 一次只能修改一列
+        for mut column in columns {
+            if !has_column(db, &Posts::Table.to_string(), &column.get_column_name()).await? {
+                exec(db, &Table::alter().table(Posts::Table).add_column(&mut column).to_owned()).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn down(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        for column in [PostContent::TocJson, PostContent::ContentHash, PostContent::ContentHtml, PostContent::Excerpt] {
+            exec(db, &Table::alter().table(Posts::Table).drop_column(column).to_owned()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum PostContent {
+    Excerpt,
+    ContentHtml,
+    ContentHash,
+    TocJson,
+}
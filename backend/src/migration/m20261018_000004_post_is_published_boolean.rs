@@ -0,0 +1,58 @@
+//! 将文章的发布状态从整数 (0/1) 改为布尔类型。
+
+use axum::async_trait;
+use sea_orm::sea_query::{ColumnDef, Table};
+use sea_orm::{ConnectionTrait, DatabaseTransaction, DbBackend, DbErr};
+
+use super::exec;
+use super::m20240101_000002_create_posts::Posts;
+
+pub struct Migration;
+
+#[async_trait]
+impl super::Migration for Migration {
+    fn name(&self) -> &'static str {
+        "m20261018_000004_post_is_published_boolean"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        match db.get_database_backend() {
+            // SQLite 的布尔值本身就以 0/1 整数存储，已有数据无需转换
+            DbBackend::Sqlite => Ok(()),
+            // 整数不能隐式转换为 boolean，需要先去掉整数默认值并显式指定转换方式
+            DbBackend::Postgres => db
+                .execute_unprepared(
+                    "ALTER TABLE posts ALTER COLUMN is_published DROP DEFAULT, \
+                     ALTER COLUMN is_published TYPE BOOLEAN USING is_published <> 0, \
+                     ALTER COLUMN is_published SET DEFAULT FALSE",
+                )
+                .await
+                .map(|_| ()),
+            // MySQL 的 BOOLEAN 即 TINYINT(1)，0/1 原样保留
+            DbBackend::MySql => {
+                exec(db, &Table::alter().table(Posts::Table)
+                    .modify_column(ColumnDef::new(Posts::IsPublished).boolean().not_null().default(false))
+                    .to_owned()).await
+            }
+        }
+    }
+
+    async fn down(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        match db.get_database_backend() {
+            DbBackend::Sqlite => Ok(()),
+            DbBackend::Postgres => db
+                .execute_unprepared(
+                    "ALTER TABLE posts ALTER COLUMN is_published DROP DEFAULT, \
+                     ALTER COLUMN is_published TYPE INTEGER USING CASE WHEN is_published THEN 1 ELSE 0 END, \
+                     ALTER COLUMN is_published SET DEFAULT 0",
+                )
+                .await
+                .map(|_| ()),
+            DbBackend::MySql => {
+                exec(db, &Table::alter().table(Posts::Table)
+                    .modify_column(ColumnDef::new(Posts::IsPublished).integer().not_null().default(0))
+                    .to_owned()).await
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! 为文章表添加写入时计算的字数、阅读时间和自动摘要，并为已有文章补齐。
+//! 列表接口直接读取这些列，不再逐行解析 Markdown 正文。
+
+use axum::async_trait;
+use sea_orm::sea_query::{ColumnDef, Expr, Query, Table};
+use sea_orm::{ConnectionTrait, DatabaseTransaction, DbErr, DeriveIden};
+
+use super::exec;
+use super::m20240101_000002_create_posts::Posts;
+use crate::render::summary::{auto_excerpt, text_stats, EXCERPT_MAX_CHARS};
+
+pub struct Migration;
+
+#[async_trait]
+impl super::Migration for Migration {
+    fn name(&self) -> &'static str {
+        "m20261018_000005_add_post_text_stats"
+    }
+
+    async fn up(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        // SQLite 的 ALTER TABLE 一次只能修改一列
+        exec(db, &Table::alter().table(Posts::Table)
+            .add_column(ColumnDef::new(PostTextStats::WordCount).integer().not_null().default(0))
+            .to_owned()).await?;
+        exec(db, &Table::alter().table(Posts::Table)
+            .add_column(ColumnDef::new(PostTextStats::ReadingTimeMinutes).integer().not_null().default(0))
+            .to_owned()).await?;
+        exec(db, &Table::alter().table(Posts::Table)
+            .add_column(ColumnDef::new(PostTextStats::AutoExcerpt).text())
+            .to_owned()).await?;
+
+        // 为已有文章补齐统计结果
+        let select = Query::select().columns([Posts::Id, Posts::ContentMarkdown]).from(Posts::Table).to_owned();
+        let rows = db.query_all(db.get_database_backend().build(&select)).await?;
+        for row in rows {
+            let id: i32 = row.try_get("", "id")?;
+            let markdown: String = row.try_get("", "content_markdown")?;
+            let stats = text_stats(&markdown);
+            let update = Query::update()
+                .table(Posts::Table)
+                .values([
+                    (PostTextStats::WordCount, stats.word_count.into()),
+                    (PostTextStats::ReadingTimeMinutes, stats.reading_time_minutes.into()),
+                    (PostTextStats::AutoExcerpt, auto_excerpt(&markdown, EXCERPT_MAX_CHARS).into()),
+                ])
+                .and_where(Expr::col(Posts::Id).eq(id))
+                .to_owned();
+            exec(db, &update).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        for column in [PostTextStats::AutoExcerpt, PostTextStats::ReadingTimeMinutes, PostTextStats::WordCount] {
+            exec(db, &Table::alter().table(Posts::Table).drop_column(column).to_owned()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum PostTextStats {
+    WordCount,
+    ReadingTimeMinutes,
+    AutoExcerpt,
+}
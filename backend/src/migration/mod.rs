@@ -0,0 +1,216 @@
+// src/migration/mod.rs
+//! 数据库迁移。
+//! 表结构由一组按版本排序的迁移定义，每个迁移包含 `up` / `down` 两个方向；
+//! 已执行的版本记录在 `seaql_migrations` 表中（与 SeaORM 迁移工具使用同名表）。
+//! 迁移使用 sea-query 构建语句，由连接的后端 (SQLite / PostgreSQL / MySQL) 生成对应的 SQL；
+//! 少数各数据库写法不同的片段 (例如修改列类型) 通过 `get_database_backend` 区分。
+
+mod m20240101_000001_create_users;
+mod m20240101_000002_create_posts;
+mod m20240101_000003_create_comments;
+mod m20240101_000004_create_favorites;
+mod m20250601_000001_create_media;
+mod m20261018_000001_create_audit_log;
+mod m20261018_000002_add_user_profile;
+mod m20261018_000003_add_post_content_columns;
+mod m20261018_000004_post_is_published_boolean;
+mod m20261018_000005_add_post_text_stats;
+
+use std::collections::HashMap;
+
+use axum::async_trait;
+use sea_orm::sea_query::{Alias, ColumnDef, Expr, Query, Table};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr, Statement, StatementBuilder,
+    TransactionTrait,
+};
+
+use crate::error::CustomError;
+
+/// 迁移记录表名。
+const MIGRATION_TABLE: &str = "seaql_migrations";
+
+/// 单个迁移。
+#[async_trait]
+pub trait Migration: Send + Sync {
+    /// 迁移版本，同时决定执行顺序，例如 `m20240101_000001_create_users`。
+    fn name(&self) -> &'static str;
+
+    /// 应用迁移。
+    async fn up(&self, db: &DatabaseTransaction) -> Result<(), DbErr>;
+
+    /// 回滚迁移。
+    async fn down(&self, db: &DatabaseTransaction) -> Result<(), DbErr>;
+}
+
+/// 按顺序列出全部迁移。新增迁移时追加到末尾。
+pub fn migrations() -> Vec<Box<dyn Migration>> {
+    vec![
+        Box::new(m20240101_000001_create_users::Migration),
+        Box::new(m20240101_000002_create_posts::Migration),
+        Box::new(m20240101_000003_create_comments::Migration),
+        Box::new(m20240101_000004_create_favorites::Migration),
+        Box::new(m20250601_000001_create_media::Migration),
+        Box::new(m20261018_000001_create_audit_log::Migration),
+        Box::new(m20261018_000002_add_user_profile::Migration),
+        Box::new(m20261018_000003_add_post_content_columns::Migration),
+        Box::new(m20261018_000004_post_is_published_boolean::Migration),
+        Box::new(m20261018_000005_add_post_text_stats::Migration),
+    ]
+}
+
+/// 执行一条 DDL 语句。
+pub(crate) async fn exec<S: StatementBuilder>(db: &impl ConnectionTrait, statement: &S) -> Result<(), DbErr> {
+    db.execute(db.get_database_backend().build(statement)).await.map(|_| ())
+}
+
+/// 判断表中是否已存在某列。
+/// 通过 init.sql 建立的旧表可能已经包含后续迁移添加的列，添加前先检查，避免重复添加报错。
+pub(crate) async fn has_column(db: &impl ConnectionTrait, table: &str, column: &str) -> Result<bool, DbErr> {
+    let backend = db.get_database_backend();
+    let sql = match backend {
+        DbBackend::Sqlite => "SELECT COUNT(*) AS count FROM pragma_table_info(?) WHERE name = ?",
+        DbBackend::Postgres => {
+            "SELECT COUNT(*) AS count FROM information_schema.columns \
+             WHERE table_schema = current_schema() AND table_name = $1 AND column_name = $2"
+        }
+        DbBackend::MySql => {
+            "SELECT COUNT(*) AS count FROM information_schema.columns \
+             WHERE table_schema = DATABASE() AND table_name = ? AND column_name = ?"
+        }
+    };
+    let row = db
+        .query_one(Statement::from_sql_and_values(backend, sql, [table.into(), column.into()]))
+        .await?;
+    let count: i64 = match row {
+        Some(row) => row.try_get("", "count")?,
+        None => 0,
+    };
+    Ok(count > 0)
+}
+
+/// 迁移状态。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub name: &'static str,
+    pub applied_at: Option<i64>, // 执行时间 (Unix 时间戳，秒)，未执行时为 None
+}
+
+/// 迁移执行器。
+pub struct Migrator;
+
+impl Migrator {
+    /// 执行尚未应用的迁移。
+    ///
+    /// # 参数
+    /// * `db` - 数据库连接。
+    /// * `steps` - 最多执行的迁移数量，`None` 表示全部。
+    ///
+    /// # 返回
+    /// 本次执行的迁移版本列表。
+    pub async fn up(db: &DatabaseConnection, steps: Option<usize>) -> Result<Vec<&'static str>, CustomError> {
+        let applied = Self::applied_versions(db).await?;
+        let pending: Vec<Box<dyn Migration>> = migrations()
+            .into_iter()
+            .filter(|m| !applied.contains_key(m.name()))
+            .take(steps.unwrap_or(usize::MAX))
+            .collect();
+
+        let mut executed = Vec::with_capacity(pending.len());
+        for migration in pending {
+            // 每个迁移在独立事务中执行，失败时不会留下执行了一半的迁移
+            let txn = db.begin().await?;
+            migration.up(&txn).await.map_err(|e| migration_error(migration.name(), "执行", e))?;
+            let insert = Query::insert()
+                .into_table(Alias::new(MIGRATION_TABLE))
+                .columns([Alias::new("version"), Alias::new("applied_at")])
+                .values_panic([migration.name().into(), chrono::Utc::now().timestamp().into()])
+                .to_owned();
+            exec(&txn, &insert).await?;
+            txn.commit().await?;
+            tracing::info!("已执行数据库迁移 {}", migration.name());
+            executed.push(migration.name());
+        }
+        Ok(executed)
+    }
+
+    /// 按执行的相反顺序回滚迁移。
+    ///
+    /// # 参数
+    /// * `db` - 数据库连接。
+    /// * `steps` - 回滚的迁移数量，`None` 表示全部回滚。
+    ///
+    /// # 返回
+    /// 本次回滚的迁移版本列表。
+    pub async fn down(db: &DatabaseConnection, steps: Option<usize>) -> Result<Vec<&'static str>, CustomError> {
+        let applied = Self::applied_versions(db).await?;
+        let to_revert: Vec<Box<dyn Migration>> = migrations()
+            .into_iter()
+            .rev()
+            .filter(|m| applied.contains_key(m.name()))
+            .take(steps.unwrap_or(usize::MAX))
+            .collect();
+
+        let mut reverted = Vec::with_capacity(to_revert.len());
+        for migration in to_revert {
+            let txn = db.begin().await?;
+            migration.down(&txn).await.map_err(|e| migration_error(migration.name(), "回滚", e))?;
+            let delete = Query::delete()
+                .from_table(Alias::new(MIGRATION_TABLE))
+                .and_where(Expr::col(Alias::new("version")).eq(migration.name()))
+                .to_owned();
+            exec(&txn, &delete).await?;
+            txn.commit().await?;
+            tracing::info!("已回滚数据库迁移 {}", migration.name());
+            reverted.push(migration.name());
+        }
+        Ok(reverted)
+    }
+
+    /// 列出全部迁移及其执行状态。
+    pub async fn status(db: &DatabaseConnection) -> Result<Vec<MigrationStatus>, CustomError> {
+        let applied = Self::applied_versions(db).await?;
+        Ok(migrations()
+            .iter()
+            .map(|m| MigrationStatus { name: m.name(), applied_at: applied.get(m.name()).copied() })
+            .collect())
+    }
+
+    /// 读取已执行的迁移版本，必要时创建迁移记录表。
+    ///
+    /// 数据库中存在当前程序不认识的版本时返回错误，避免旧版本程序操作新版本的表结构。
+    async fn applied_versions(db: &DatabaseConnection) -> Result<HashMap<String, i64>, CustomError> {
+        let create = Table::create()
+            .table(Alias::new(MIGRATION_TABLE))
+            .if_not_exists()
+            .col(ColumnDef::new(Alias::new("version")).string().not_null().primary_key())
+            .col(ColumnDef::new(Alias::new("applied_at")).big_integer().not_null())
+            .to_owned();
+        exec(db, &create).await?;
+
+        let select = Query::select()
+            .columns([Alias::new("version"), Alias::new("applied_at")])
+            .from(Alias::new(MIGRATION_TABLE))
+            .to_owned();
+        let rows = db.query_all(db.get_database_backend().build(&select)).await?;
+        let mut applied = HashMap::with_capacity(rows.len());
+        for row in rows {
+            let version: String = row.try_get("", "version")?;
+            let applied_at: i64 = row.try_get("", "applied_at")?;
+            applied.insert(version, applied_at);
+        }
+
+        let known: Vec<&'static str> = migrations().iter().map(|m| m.name()).collect();
+        if let Some(unknown) = applied.keys().find(|v| !known.contains(&v.as_str())) {
+            return Err(CustomError::InternalServerError(format!(
+                "数据库中存在未知的迁移版本 {}，请升级程序后再启动",
+                unknown
+            )));
+        }
+        Ok(applied)
+    }
+}
+
+fn migration_error(name: &str, action: &str, e: DbErr) -> CustomError {
+    CustomError::InternalServerError(format!("{}数据库迁移 {} 失败: {}", action, name, e))
+}
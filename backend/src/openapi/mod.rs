@@ -0,0 +1,419 @@
+// src/openapi/mod.rs
+//! OpenAPI 3.1 文档生成。
+//!
+//! `/api` 下的路由通过 `ApiRouter` 注册，每条路由同时附带 `Operation` 描述；
+//! 请求和响应的结构来自 `handler::idl` 中用 `api_model!` 定义的 DTO。
+//! 文档由 `ApiRouter::openapi` 从同一份路由表生成，由 `handler::openapi` 提供给客户端。
+
+pub mod schema;
+
+use std::collections::BTreeMap;
+
+use axum::http::Method;
+use axum::routing::MethodRouter;
+use axum::Router;
+use serde_json::{json, Map, Value};
+
+pub use schema::{ApiSchema, Components};
+
+use crate::error::ErrorCode;
+use crate::AppState;
+
+/// 生成 schema 的函数，在生成文档时才调用，以便登记组件。
+type SchemaFn = fn(&mut Components) -> Value;
+
+/// 响应或请求体的内容：媒体类型和 schema。
+type Content = (&'static str, SchemaFn);
+
+/// 接口的认证要求。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Public,
+    /// 携带令牌时会使用其中的用户信息，不携带也可以访问
+    OptionalAuth,
+    Auth,
+    Admin,
+}
+
+/// 一个接口 (路径 + 方法) 的描述。
+#[derive(Clone)]
+pub struct Operation {
+    method: Method,
+    path: String,
+    tag: &'static str,
+    summary: &'static str,
+    description: Option<&'static str>,
+    access: Access,
+    path_params: Vec<(&'static str, &'static str, SchemaFn)>,
+    query: Option<SchemaFn>,
+    request_body: Option<Content>,
+    responses: Vec<(u16, &'static str, Option<Content>)>,
+}
+
+impl Operation {
+    fn new(method: Method, summary: &'static str) -> Self {
+        Operation {
+            method,
+            path: String::new(),
+            tag: "",
+            summary,
+            description: None,
+            access: Access::Public,
+            path_params: Vec::new(),
+            query: None,
+            request_body: None,
+            responses: Vec::new(),
+        }
+    }
+
+    pub fn get(summary: &'static str) -> Self {
+        Self::new(Method::GET, summary)
+    }
+
+    pub fn post(summary: &'static str) -> Self {
+        Self::new(Method::POST, summary)
+    }
+
+    pub fn put(summary: &'static str) -> Self {
+        Self::new(Method::PUT, summary)
+    }
+
+    pub fn delete(summary: &'static str) -> Self {
+        Self::new(Method::DELETE, summary)
+    }
+
+    pub fn description(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn access(mut self, access: Access) -> Self {
+        self.access = access;
+        self
+    }
+
+    /// 需要登录。
+    pub fn auth(self) -> Self {
+        self.access(Access::Auth)
+    }
+
+    /// 需要管理员权限。
+    pub fn admin(self) -> Self {
+        self.access(Access::Admin)
+    }
+
+    /// 路径参数的类型和说明；没有声明的路径参数按字符串处理。
+    pub fn path_param<T: ApiSchema>(mut self, name: &'static str, description: &'static str) -> Self {
+        self.path_params.push((name, description, T::schema));
+        self
+    }
+
+    /// 查询参数，`T` 的每个字段对应一个参数。
+    pub fn query<T: ApiSchema>(mut self) -> Self {
+        self.query = Some(T::inline_schema);
+        self
+    }
+
+    /// JSON 请求体。
+    pub fn body<T: ApiSchema>(self) -> Self {
+        self.request_body("application/json", T::schema)
+    }
+
+    /// 其他格式的请求体。
+    pub fn request_body(mut self, content_type: &'static str, schema: SchemaFn) -> Self {
+        self.request_body = Some((content_type, schema));
+        self
+    }
+
+    /// 200 JSON 响应。
+    pub fn json<T: ApiSchema>(self) -> Self {
+        self.response(200, "成功", Some(("application/json", T::schema)))
+    }
+
+    /// 指定状态码、说明和内容的响应。
+    pub fn response(
+        mut self,
+        status: u16,
+        description: &'static str,
+        content: Option<Content>,
+    ) -> Self {
+        self.responses.push((status, description, content));
+        self
+    }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// 完整路径，使用 axum 的写法 (`/post/:id`)。
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn summary(&self) -> &'static str {
+        self.summary
+    }
+
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    /// 生成 OpenAPI 的 Operation Object。
+    fn to_openapi(&self, components: &mut Components) -> Value {
+        let mut parameters = Vec::new();
+        for name in path_param_names(&self.path) {
+            let (description, schema) = match self.path_params.iter().find(|(n, _, _)| *n == name) {
+                Some((_, description, schema)) => (*description, schema(components)),
+                None => ("", String::schema(components)),
+            };
+            parameters.push(json!({
+                "name": name, "in": "path", "required": true, "description": description, "schema": schema,
+            }));
+        }
+        if let Some(query) = self.query {
+            // 查询参数都有默认值或可以省略，因此不标记为必填
+            let schema = query(components);
+            if let Some(properties) = schema["properties"].as_object() {
+                for (name, schema) in properties {
+                    parameters.push(json!({ "name": name, "in": "query", "required": false, "schema": schema }));
+                }
+            }
+        }
+
+        let mut operation = json!({
+            "tags": [self.tag],
+            "summary": self.summary,
+            "operationId": operation_id(&self.method, &self.path),
+            "parameters": parameters,
+        });
+        if let Some(description) = self.description {
+            operation["description"] = description.into();
+        }
+        if let Some((content_type, schema)) = self.request_body {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { content_type: { "schema": schema(components) } },
+            });
+        }
+
+        let mut responses = Map::new();
+        for (status, description, content) in &self.responses {
+            // 同一状态码可以有多种内容格式，第一个说明生效
+            let response = responses
+                .entry(status.to_string())
+                .or_insert_with(|| json!({ "description": description }));
+            if let Some((content_type, schema)) = content {
+                response["content"][*content_type] = json!({ "schema": schema(components) });
+            }
+        }
+        responses.insert("default".to_string(), json!({ "$ref": "#/components/responses/Error" }));
+        operation["responses"] = Value::Object(responses);
+
+        match self.access {
+            Access::Public => {}
+            Access::OptionalAuth => operation["security"] = json!([{}, { "bearerAuth": [] }]),
+            Access::Auth | Access::Admin => operation["security"] = json!([{ "bearerAuth": [] }]),
+        }
+        if self.access == Access::Admin {
+            operation["x-admin-only"] = true.into();
+        }
+        operation
+    }
+}
+
+/// 一条路由的接口描述，可以是单个 `Operation` 或同一路径下多个方法的数组。
+pub trait IntoOperations {
+    fn into_operations(self) -> Vec<Operation>;
+}
+
+impl IntoOperations for Operation {
+    fn into_operations(self) -> Vec<Operation> {
+        vec![self]
+    }
+}
+
+impl<const N: usize> IntoOperations for [Operation; N] {
+    fn into_operations(self) -> Vec<Operation> {
+        self.into()
+    }
+}
+
+/// 带接口描述的路由表。
+///
+/// `route` 与 `axum::Router::route` 相同，但必须同时给出该路由上每个方法的 `Operation`；
+/// `tests/openapi_test.rs` 会检查每条路由实际允许的方法都有对应的描述。
+pub struct ApiRouter {
+    tag: &'static str,
+    router: Router<AppState>,
+    routes: Vec<String>,
+    operations: Vec<Operation>,
+}
+
+impl ApiRouter {
+    /// 创建路由表，`tag` 为文档中的接口分组。
+    pub fn new(tag: &'static str) -> Self {
+        ApiRouter { tag, router: Router::new(), routes: Vec::new(), operations: Vec::new() }
+    }
+
+    pub fn route(mut self, path: &str, method_router: MethodRouter<AppState>, docs: impl IntoOperations) -> Self {
+        self.router = self.router.route(path, method_router);
+        self.routes.push(path.to_string());
+        for mut operation in docs.into_operations() {
+            operation.path = path.to_string();
+            operation.tag = self.tag;
+            self.operations.push(operation);
+        }
+        self
+    }
+
+    /// 将另一个路由表挂载到 `prefix` 下，与 `axum::Router::nest` 相同。
+    pub fn nest(mut self, prefix: &str, other: ApiRouter) -> Self {
+        self.router = self.router.nest(prefix, other.router);
+        let join = |path: &str| if path == "/" { prefix.to_string() } else { format!("{}{}", prefix, path) };
+        self.routes.extend(other.routes.iter().map(|path| join(path)));
+        self.operations.extend(other.operations.into_iter().map(|mut operation| {
+            operation.path = join(&operation.path);
+            operation
+        }));
+        self
+    }
+
+    /// 合并另一个路由表，与 `axum::Router::merge` 相同。
+    pub fn merge(mut self, other: ApiRouter) -> Self {
+        self.router = self.router.merge(other.router);
+        self.routes.extend(other.routes);
+        self.operations.extend(other.operations);
+        self
+    }
+
+    /// 已注册的路由路径 (axum 写法)，按注册顺序，同一路径可能出现多次。
+    pub fn routes(&self) -> &[String] {
+        &self.routes
+    }
+
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    pub fn into_router(self) -> Router<AppState> {
+        self.router
+    }
+
+    /// 生成 OpenAPI 3.1 文档。
+    ///
+    /// # 参数
+    /// * `server_url` - 路由表挂载的位置，例如 `/api`。
+    pub fn openapi(&self, server_url: &str) -> Value {
+        let mut components = Components::new();
+        let mut paths: BTreeMap<String, Map<String, Value>> = BTreeMap::new();
+        for operation in &self.operations {
+            let item = operation.to_openapi(&mut components);
+            paths
+                .entry(openapi_path(&operation.path))
+                .or_default()
+                .insert(operation.method.as_str().to_lowercase(), item);
+        }
+
+        let mut tags: Vec<&str> = Vec::new();
+        for operation in &self.operations {
+            if !tags.contains(&operation.tag) {
+                tags.push(operation.tag);
+            }
+        }
+
+        json!({
+            "openapi": "3.1.0",
+            "info": {
+                "title": "Axum Blog Engine API",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "servers": [{ "url": server_url }],
+            "tags": tags.iter().map(|name| json!({ "name": name })).collect::<Vec<_>>(),
+            "paths": paths,
+            "components": {
+                "schemas": components,
+                "responses": { "Error": error_response() },
+                "securitySchemes": {
+                    "bearerAuth": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" },
+                },
+            },
+        })
+    }
+}
+
+/// 所有接口共用的错误响应，对应 `CustomError` 的 JSON 与 problem+json 两种格式。
+fn error_response() -> Value {
+    let codes: Vec<&str> = ErrorCode::ALL.iter().map(|code| code.as_str()).collect();
+    let field_errors = json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "field": { "type": "string" },
+                "code": { "type": "string" },
+                "message": { "type": "string" },
+            },
+            "required": ["field", "code", "message"],
+        },
+    });
+    json!({
+        "description": "错误，`code` 为稳定的错误码",
+        "content": {
+            "application/json": { "schema": {
+                "type": "object",
+                "properties": {
+                    "success": { "const": false },
+                    "code": { "enum": codes },
+                    "message": { "type": "string" },
+                    "errors": field_errors,
+                    "request_id": { "type": "string" },
+                },
+                "required": ["success", "code", "message"],
+            } },
+            "application/problem+json": { "schema": {
+                "type": "object",
+                "properties": {
+                    "type": { "type": "string" },
+                    "title": { "type": "string" },
+                    "status": { "type": "integer" },
+                    "detail": { "type": "string" },
+                    "code": { "enum": codes },
+                    "errors": field_errors,
+                    "request_id": { "type": "string" },
+                },
+                "required": ["type", "title", "status", "detail", "code"],
+            } },
+        },
+    })
+}
+
+/// 路径中的参数名，`:id` 和 `*key` 形式。
+fn path_param_names(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix(':').or_else(|| segment.strip_prefix('*')))
+        .collect()
+}
+
+/// axum 路径转换为 OpenAPI 路径：`/post/:id` -> `/post/{id}`。
+pub fn openapi_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':').or_else(|| segment.strip_prefix('*')) {
+            Some(name) => format!("{{{}}}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// 由方法和路径生成 operationId，例如 `GET /post/:id` -> `get_post_id`。
+fn operation_id(method: &Method, path: &str) -> String {
+    let mut parts = vec![method.as_str().to_lowercase()];
+    parts.extend(path.split('/').filter(|s| !s.is_empty()).map(|segment| {
+        segment
+            .trim_start_matches([':', '*'])
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>()
+    }));
+    parts.join("_")
+}
@@ -0,0 +1,176 @@
+//! 数据结构的 JSON Schema（OpenAPI 3.1 使用 JSON Schema 2020-12）。
+//!
+//! `handler::idl` 中的 DTO 通过 `api_model!` 定义，字段列表与结构体定义出自同一处，
+//! 不会与代码脱节；其他类型在这里手动实现 `ApiSchema`。
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde_json::{json, Map, Value};
+
+use crate::handler::idl::PostSort;
+use crate::render::TocEntry;
+
+/// `components.schemas`：组件名 -> schema。
+pub type Components = BTreeMap<&'static str, Value>;
+
+/// 能够描述自身 JSON 结构的类型。
+pub trait ApiSchema {
+    /// 组件名。命名类型登记到 `components.schemas` 中，其他地方通过 `$ref` 引用。
+    fn schema_name() -> Option<&'static str> {
+        None
+    }
+
+    /// 完整的 schema（不使用 `$ref`）。
+    fn inline_schema(components: &mut Components) -> Value;
+
+    /// 结构体的字段名，`handler::query::StrictQuery` 以此作为允许的查询参数。
+    fn field_names() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// 作为结构体字段时是否可以省略（`Option<T>`）。
+    fn optional() -> bool {
+        false
+    }
+
+    /// 引用处使用的 schema：命名类型返回 `$ref`，其余返回完整的 schema。
+    fn schema(components: &mut Components) -> Value {
+        let Some(name) = Self::schema_name() else {
+            return Self::inline_schema(components);
+        };
+        if !components.contains_key(name) {
+            // 先占位，避免自引用的类型（例如嵌套的目录条目）无限递归
+            components.insert(name, Value::Null);
+            let schema = Self::inline_schema(components);
+            components.insert(name, schema);
+        }
+        json!({ "$ref": format!("#/components/schemas/{}", name) })
+    }
+}
+
+/// 由字段列表生成对象 schema，非 `Option` 的字段列为必填。
+pub fn object_schema(properties: Vec<(&'static str, Value, bool)>) -> Value {
+    let mut map = Map::new();
+    let mut required = Vec::new();
+    for (name, schema, optional) in properties {
+        if !optional {
+            required.push(name);
+        }
+        map.insert(name.to_string(), schema);
+    }
+    json!({ "type": "object", "properties": map, "required": required })
+}
+
+/// 定义 DTO 结构体，同时为它实现 `ApiSchema`。
+///
+/// 结构体与字段上的属性（`derive`、`serde`、`validate`、文档注释等）原样保留。
+#[macro_export]
+macro_rules! api_model {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[$field_meta:meta])* $field_vis:vis $field:ident : $ty:ty ),* $(,)?
+        }
+    )*) => {$(
+        $(#[$meta])*
+        $vis struct $name {
+            $( $(#[$field_meta])* $field_vis $field : $ty ),*
+        }
+
+        impl $crate::openapi::ApiSchema for $name {
+            fn schema_name() -> Option<&'static str> {
+                Some(stringify!($name))
+            }
+
+            fn field_names() -> &'static [&'static str] {
+                &[$(stringify!($field)),*]
+            }
+
+            fn inline_schema(components: &mut $crate::openapi::Components) -> serde_json::Value {
+                $crate::openapi::schema::object_schema(vec![$((
+                    stringify!($field),
+                    <$ty as $crate::openapi::ApiSchema>::schema(components),
+                    <$ty as $crate::openapi::ApiSchema>::optional(),
+                )),*])
+            }
+        }
+    )*};
+}
+
+macro_rules! primitive_schema {
+    ($($ty:ty => $schema:tt),* $(,)?) => {$(
+        impl ApiSchema for $ty {
+            fn inline_schema(_: &mut Components) -> Value {
+                json!($schema)
+            }
+        }
+    )*};
+}
+
+primitive_schema! {
+    bool => { "type": "boolean" },
+    String => { "type": "string" },
+    &str => { "type": "string" },
+    u8 => { "type": "integer", "minimum": 0, "maximum": 255 },
+    u32 => { "type": "integer", "format": "int32", "minimum": 0 },
+    u64 => { "type": "integer", "format": "int64", "minimum": 0 },
+    usize => { "type": "integer", "format": "int64", "minimum": 0 },
+    i32 => { "type": "integer", "format": "int32" },
+    i64 => { "type": "integer", "format": "int64" },
+    f64 => { "type": "number", "format": "double" },
+    // 数据库中的时间不带时区，统一为 UTC，例如 2024-01-01T08:00:00
+    NaiveDateTime => { "type": "string", "description": "UTC 时间 (不带时区后缀)" },
+    DateTime<Utc> => { "type": "string", "format": "date-time" },
+    Value => {},
+}
+
+impl<T: ApiSchema> ApiSchema for Option<T> {
+    fn inline_schema(components: &mut Components) -> Value {
+        let schema = T::schema(components);
+        match schema.get("type").and_then(Value::as_str) {
+            Some(ty) => {
+                let mut schema = schema.clone();
+                schema["type"] = json!([ty, "null"]);
+                schema
+            }
+            None => json!({ "anyOf": [schema, { "type": "null" }] }),
+        }
+    }
+
+    fn optional() -> bool {
+        true
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Vec<T> {
+    fn inline_schema(components: &mut Components) -> Value {
+        json!({ "type": "array", "items": T::schema(components) })
+    }
+}
+
+impl ApiSchema for TocEntry {
+    fn schema_name() -> Option<&'static str> {
+        Some("TocEntry")
+    }
+
+    fn inline_schema(components: &mut Components) -> Value {
+        object_schema(vec![
+            ("level", u8::schema(components), false),
+            ("id", String::schema(components), false),
+            ("title", String::schema(components), false),
+            ("children", Vec::<TocEntry>::schema(components), true), // 为空时省略
+        ])
+    }
+}
+
+impl ApiSchema for PostSort {
+    fn schema_name() -> Option<&'static str> {
+        Some("PostSort")
+    }
+
+    fn inline_schema(_: &mut Components) -> Value {
+        let values: Vec<&str> = PostSort::ALL.iter().map(|sort| sort.as_str()).collect();
+        json!({ "type": "string", "enum": values, "default": PostSort::default().as_str() })
+    }
+}
@@ -0,0 +1,234 @@
+// src/pagination.rs
+//! 列表接口的游标分页 (keyset pagination)。
+//!
+//! 列表按 `(created_at, id)` 倒序排列，游标记录一页边界上那一行的排序键，
+//! 下一页从该行之后继续查询，不需要 `OFFSET` 和 `COUNT`，新增数据时也不会跳过或重复。
+//! 游标对客户端不透明，编码为 URL 安全的 Base64，可以原样放进查询参数。
+//!
+//! 旧的 `page` / `limit` 分页保留为兼容模式，请求中带 `cursor` 参数 (可以为空) 时才使用游标分页。
+
+use axum::http::{HeaderMap, HeaderValue, Uri, header};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, NaiveDateTime};
+use sea_orm::sea_query::{Condition, Expr, IntoColumnRef};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Select};
+
+use crate::error::{CustomError, ErrorCode};
+use crate::i18n::t;
+
+/// 游标分页每页的最大数量。
+pub const MAX_LIMIT: u64 = 100;
+
+/// 游标相对于边界行的方向。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// 边界行之后 (更早创建) 的数据，即下一页
+    After,
+    /// 边界行之前 (更晚创建) 的数据，即上一页
+    Before,
+}
+
+/// 解码后的分页游标。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub direction: Direction,
+    pub created_at: NaiveDateTime,
+    pub id: i32,
+}
+
+impl Cursor {
+    /// 指向 `(created_at, id)` 之后的数据。
+    pub fn after(created_at: NaiveDateTime, id: i32) -> Self {
+        Cursor { direction: Direction::After, created_at, id }
+    }
+
+    /// 指向 `(created_at, id)` 之前的数据。
+    pub fn before(created_at: NaiveDateTime, id: i32) -> Self {
+        Cursor { direction: Direction::Before, created_at, id }
+    }
+
+    /// 编码为不透明的字符串。时间保留到纳秒，保证与数据库中的值精确比较。
+    pub fn encode(&self) -> String {
+        let direction = match self.direction {
+            Direction::After => 'a',
+            Direction::Before => 'b',
+        };
+        let time = self.created_at.and_utc();
+        let raw = format!("{}:{}.{}:{}", direction, time.timestamp(), time.timestamp_subsec_nanos(), self.id);
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    /// 解码客户端传回的游标；格式不正确时返回 `INVALID_CURSOR` 错误。
+    pub fn decode(value: &str) -> Result<Cursor, CustomError> {
+        Self::parse(value).ok_or_else(|| CustomError::coded(ErrorCode::InvalidCursor, t("pagination-invalid-cursor")))
+    }
+
+    /// 解码请求中的 `cursor` 参数，空字符串表示从第一页开始。
+    pub fn from_param(value: &str) -> Result<Option<Cursor>, CustomError> {
+        if value.is_empty() {
+            return Ok(None);
+        }
+        Self::decode(value).map(Some)
+    }
+
+    fn parse(value: &str) -> Option<Cursor> {
+        let raw = String::from_utf8(URL_SAFE_NO_PAD.decode(value).ok()?).ok()?;
+        let mut parts = raw.split(':');
+        let direction = match parts.next()? {
+            "a" => Direction::After,
+            "b" => Direction::Before,
+            _ => return None,
+        };
+        let (secs, nanos) = parts.next()?.split_once('.')?;
+        let created_at = DateTime::from_timestamp(secs.parse().ok()?, nanos.parse().ok()?)?.naive_utc();
+        let id = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Cursor { direction, created_at, id })
+    }
+}
+
+/// 游标分页的一页结果。
+#[derive(Debug, Clone)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    /// 下一页的游标，没有更多数据时为 `None`
+    pub next_cursor: Option<String>,
+    /// 上一页的游标，已经是第一页时为 `None`
+    pub prev_cursor: Option<String>,
+}
+
+impl<T> CursorPage<T> {
+    /// 转换每一项，游标保持不变。
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> CursorPage<U> {
+        CursorPage {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+            prev_cursor: self.prev_cursor,
+        }
+    }
+}
+
+/// 为查询加上游标条件和排序，并多取一行用于判断是否还有更多数据。
+///
+/// # 参数
+/// * `select` - 已经加好过滤条件的查询，不要再加排序。
+/// * `created_at` / `id` - 排序键对应的列。
+/// * `cursor` - 客户端传回的游标，第一页为 `None`。
+/// * `limit` - 每页数量。
+pub fn keyset_select<E, C>(
+    select: Select<E>,
+    created_at: C,
+    id: C,
+    cursor: Option<&Cursor>,
+    limit: u64,
+) -> Select<E>
+where
+    E: EntityTrait,
+    C: ColumnTrait + IntoColumnRef + Copy,
+{
+    let select = match cursor {
+        None => select,
+        // (created_at, id) < (游标时间, 游标 ID)，展开成各数据库都支持的形式
+        Some(cursor) if cursor.direction == Direction::After => select.filter(
+            Condition::any()
+                .add(Expr::col(created_at).lt(cursor.created_at))
+                .add(Expr::col(created_at).eq(cursor.created_at).and(Expr::col(id).lt(cursor.id))),
+        ),
+        Some(cursor) => select.filter(
+            Condition::any()
+                .add(Expr::col(created_at).gt(cursor.created_at))
+                .add(Expr::col(created_at).eq(cursor.created_at).and(Expr::col(id).gt(cursor.id))),
+        ),
+    };
+    // 向前翻页时按正序取紧挨着游标的数据，`keyset_page` 再把顺序翻转回来
+    let select = match cursor.map(|c| c.direction) {
+        Some(Direction::Before) => select.order_by_asc(created_at).order_by_asc(id),
+        _ => select.order_by_desc(created_at).order_by_desc(id),
+    };
+    select.limit(limit + 1)
+}
+
+/// 把 `keyset_select` 查出的行整理成一页，并生成前后页的游标。
+///
+/// # 参数
+/// * `rows` - 查询结果，最多 `limit + 1` 行。
+/// * `limit` - 每页数量。
+/// * `cursor` - 本次请求使用的游标。
+/// * `key` - 取出一行的排序键 `(created_at, id)`。
+pub fn keyset_page<T>(
+    mut rows: Vec<T>,
+    limit: u64,
+    cursor: Option<&Cursor>,
+    key: impl Fn(&T) -> (NaiveDateTime, i32),
+) -> CursorPage<T> {
+    let has_more = rows.len() as u64 > limit;
+    rows.truncate(limit as usize);
+    let direction = cursor.map(|c| c.direction);
+    if direction == Some(Direction::Before) {
+        rows.reverse();
+    }
+
+    // 向后翻页时多取到的一行说明后面还有数据；向前翻页时游标本身就在后面
+    let has_next = match direction {
+        Some(Direction::Before) => true,
+        _ => has_more,
+    };
+    // 带游标向后翻页说明前面还有数据；向前翻页时看多取的那一行
+    let has_prev = match direction {
+        None => false,
+        Some(Direction::After) => true,
+        Some(Direction::Before) => has_more,
+    };
+
+    let next_cursor = rows.last().filter(|_| has_next).map(|row| {
+        let (created_at, id) = key(row);
+        Cursor::after(created_at, id).encode()
+    });
+    let prev_cursor = rows.first().filter(|_| has_prev).map(|row| {
+        let (created_at, id) = key(row);
+        Cursor::before(created_at, id).encode()
+    });
+    CursorPage { items: rows, next_cursor, prev_cursor }
+}
+
+/// 游标分页请求的每页数量，默认 10，最大 `MAX_LIMIT`。
+pub fn cursor_limit(limit: Option<u64>) -> u64 {
+    limit.unwrap_or(10).clamp(1, MAX_LIMIT)
+}
+
+/// 生成 RFC 8288 `Link` 响应头，指向上一页和下一页。
+///
+/// 链接沿用本次请求的路径和其他查询参数，只替换 `cursor` (并去掉 `page`)。
+pub fn link_headers<T>(uri: &Uri, page: &CursorPage<T>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let base: Vec<&str> = uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| {
+            let name = pair.split('=').next().unwrap_or_default();
+            !pair.is_empty() && name != "cursor" && name != "page"
+        })
+        .collect();
+    let link = |cursor: &str, rel: &str| {
+        let mut query = base.clone();
+        let param = format!("cursor={}", cursor);
+        query.push(&param);
+        format!("<{}?{}>; rel=\"{}\"", uri.path(), query.join("&"), rel)
+    };
+
+    let links: Vec<String> = [(&page.next_cursor, "next"), (&page.prev_cursor, "prev")]
+        .into_iter()
+        .filter_map(|(cursor, rel)| cursor.as_deref().map(|c| link(c, rel)))
+        .collect();
+    if links.is_empty() {
+        return headers;
+    }
+    if let Ok(value) = HeaderValue::from_str(&links.join(", ")) {
+        headers.insert(header::LINK, value);
+    }
+    headers
+}
@@ -0,0 +1,136 @@
+// src/render/highlight.rs
+//! 围栏代码块的服务端语法高亮。
+//! 输出基于 CSS 类名的 HTML（不内联颜色），配色由可选择的主题样式表决定。
+
+use lazy_static::lazy_static;
+use syntect::highlighting::ThemeSet;
+use syntect::html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+/// 高亮输出中所有 CSS 类名的前缀。
+/// 清洗器只放行带此前缀的类名，文章内容无法借用站点自身的样式类。
+pub const HIGHLIGHT_CLASS_PREFIX: &str = "hl-";
+
+/// 高亮代码块外层 `<pre>` 的类名。
+const CODE_BLOCK_CLASS: &str = "hl-code";
+
+/// 默认配色主题（syntect 内置主题名）。
+pub const DEFAULT_THEME: &str = "InspiredGitHub";
+
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: HIGHLIGHT_CLASS_PREFIX };
+
+lazy_static! {
+    static ref SYNTAX_SET: SyntaxSet = SyntaxSet::load_defaults_newlines();
+    static ref THEME_SET: ThemeSet = ThemeSet::load_defaults();
+}
+
+/// 将代码块渲染为带高亮类名的 HTML。
+///
+/// 无法识别的语言按纯文本输出（仍会进行 HTML 转义）。
+///
+/// # 参数
+/// * `code` - 代码块原文。
+/// * `lang` - 围栏上声明的语言标识（如 `rust`、`js`），可能为空。
+///
+/// # 返回
+/// `<pre class="hl-code"><code ...>...</code></pre>` 形式的 HTML 片段。
+pub fn highlight_code_block(code: &str, lang: &str) -> String {
+    let lang = lang.split_whitespace().next().unwrap_or("");
+    let syntax = SYNTAX_SET
+        .find_syntax_by_token(lang)
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
+
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAX_SET, CLASS_STYLE);
+    for line in LinesWithEndings::from(code) {
+        // 默认语法集中的语法都能正常解析，出错时退化为纯文本输出
+        if generator.parse_html_for_line_which_includes_newline(line).is_err() {
+            return plain_code_block(code, lang);
+        }
+    }
+
+    format!(
+        "<pre class=\"{}\"><code{}>{}</code></pre>\n",
+        CODE_BLOCK_CLASS,
+        language_class(lang),
+        generator.finalize()
+    )
+}
+
+/// 不做高亮、仅转义的代码块。
+fn plain_code_block(code: &str, lang: &str) -> String {
+    format!(
+        "<pre class=\"{}\"><code{}>{}</code></pre>\n",
+        CODE_BLOCK_CLASS,
+        language_class(lang),
+        escape_html(code)
+    )
+}
+
+/// 生成 `class="language-xxx"` 属性（语言为空时不输出）。
+fn language_class(lang: &str) -> String {
+    if lang.is_empty() {
+        String::new()
+    } else {
+        format!(" class=\"language-{}\"", escape_html(lang))
+    }
+}
+
+/// 转义 HTML 特殊字符。
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 将主题名转换为 URL 友好的标识，例如 `Solarized (dark)` -> `solarized-dark`。
+fn theme_slug(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// 列出所有可用的高亮主题。
+///
+/// # 返回
+/// `(标识, 主题名)` 列表，按标识排序。
+pub fn available_themes() -> Vec<(String, String)> {
+    let mut themes: Vec<(String, String)> = THEME_SET
+        .themes
+        .keys()
+        .map(|name| (theme_slug(name), name.clone()))
+        .collect();
+    themes.sort();
+    themes
+}
+
+/// 生成指定主题的高亮样式表。
+///
+/// # 参数
+/// * `slug` - 主题标识（见 `available_themes`）。
+///
+/// # 返回
+/// 主题存在时返回 CSS 文本，否则返回 `None`。
+pub fn theme_css(slug: &str) -> Option<String> {
+    THEME_SET
+        .themes
+        .iter()
+        .find(|(name, _)| theme_slug(name) == slug)
+        .and_then(|(_, theme)| css_for_theme_with_class_style(theme, CLASS_STYLE).ok())
+}
+
+/// 默认主题的标识。
+pub fn default_theme_slug() -> String {
+    theme_slug(DEFAULT_THEME)
+}
@@ -0,0 +1,125 @@
+// src/render/markdown.rs
+//! Markdown 到 HTML 的渲染。
+//! 支持 CommonMark 以及 GFM 扩展：表格、任务列表、脚注和删除线；
+//! 另外对围栏代码块做语法高亮、渲染 `$...$` / `$$...$$` 公式，并为标题生成锚点和目录。
+
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+
+use super::highlight::highlight_code_block;
+use super::math::render_math;
+use super::sanitize::sanitize_html;
+use super::toc::{build_toc, AnchorGenerator, TocEntry};
+
+/// 脚注锚点的 id 前缀。
+/// 为脚注 id 加上固定前缀，避免文章内容中的 id 与页面上已有元素（例如前端的 `#app`）冲突。
+pub(crate) const FOOTNOTE_ID_PREFIX: &str = "fn-";
+
+/// Markdown 渲染结果。
+#[derive(Debug, Clone)]
+pub struct RenderedMarkdown {
+    pub html: String,       // 清洗后的 HTML
+    pub toc: Vec<TocEntry>, // 根据标题生成的目录
+}
+
+/// 启用的 Markdown 扩展语法。
+fn markdown_options() -> Options {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_MATH);
+    options
+}
+
+/// 为脚注名称加上前缀。
+fn prefix_footnote(name: CowStr<'_>) -> CowStr<'static> {
+    CowStr::from(format!("{}{}", FOOTNOTE_ID_PREFIX, name))
+}
+
+/// 将 Markdown 渲染为经过清洗的 HTML，并生成目录。
+///
+/// 渲染结果始终经过 `sanitize_html` 白名单清洗，
+/// 因此 Markdown 中内嵌的原始 HTML（如 `<script>`、事件属性）不会原样输出。
+///
+/// # 参数
+/// * `markdown` - 文章的 Markdown 源文本。
+///
+/// # 返回
+/// 包含安全 HTML 和目录的 `RenderedMarkdown`。
+pub fn render_markdown(markdown: &str) -> RenderedMarkdown {
+    let mut events: Vec<Event> = Vec::new();
+    let mut anchors = AnchorGenerator::default();
+    let mut headings: Vec<(u8, String, String)> = Vec::new();
+
+    // 正在收集的代码块：(语言, 代码文本)
+    let mut code_block: Option<(String, String)> = None;
+    // 正在收集的标题：(在 events 中的起始位置, 标题纯文本)
+    let mut heading: Option<(usize, String)> = None;
+
+    for event in Parser::new_ext(markdown, markdown_options()) {
+        // 代码块内部的文本整体交给高亮器处理
+        if let Some((lang, code)) = code_block.as_mut() {
+            match event {
+                Event::Text(text) => code.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => {
+                    events.push(Event::Html(highlight_code_block(code, lang).into()));
+                    code_block = None;
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        if let Some((_, title)) = heading.as_mut() {
+            if let Event::Text(text) | Event::Code(text) | Event::InlineMath(text) = &event {
+                title.push_str(text);
+            }
+        }
+
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => info.to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                code_block = Some((lang, String::new()));
+            }
+            Event::Start(Tag::Heading { .. }) => {
+                heading = Some((events.len(), String::new()));
+                events.push(event);
+            }
+            Event::End(TagEnd::Heading(level)) => {
+                if let Some((start, title)) = heading.take() {
+                    let id = anchors.anchor_for(&title);
+                    if let Event::Start(Tag::Heading { id: heading_id, .. }) = &mut events[start] {
+                        *heading_id = Some(id.clone().into());
+                    }
+                    headings.push((level as u8, id, title.trim().to_string()));
+                }
+                events.push(Event::End(TagEnd::Heading(level)));
+            }
+            Event::InlineMath(latex) => {
+                events.push(Event::InlineHtml(render_math(&latex, false).into()));
+            }
+            Event::DisplayMath(latex) => {
+                events.push(Event::InlineHtml(render_math(&latex, true).into()));
+            }
+            Event::FootnoteReference(name) => {
+                events.push(Event::FootnoteReference(prefix_footnote(name)));
+            }
+            Event::Start(Tag::FootnoteDefinition(name)) => {
+                events.push(Event::Start(Tag::FootnoteDefinition(prefix_footnote(name))));
+            }
+            other => events.push(other),
+        }
+    }
+
+    let mut unsafe_html = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, events.into_iter());
+
+    RenderedMarkdown {
+        html: sanitize_html(&unsafe_html),
+        toc: build_toc(headings),
+    }
+}
@@ -0,0 +1,39 @@
+// src/render/math.rs
+//! `$...$` 与 `$$...$$` 数学公式的服务端渲染。
+//! 使用 KaTeX 输出 MathML，浏览器无需加载额外脚本即可显示公式。
+
+use katex::{Opts, OutputType};
+use tracing::debug;
+
+use super::highlight::escape_html;
+
+/// 将 LaTeX 公式渲染为 MathML。
+///
+/// 公式存在语法错误时不会中断整篇文章的渲染，
+/// 而是输出带 `math-error` 类名的原始公式文本。
+///
+/// # 参数
+/// * `latex` - 公式的 LaTeX 源文本（不含 `$` 定界符）。
+/// * `display` - `true` 表示独立成行的块级公式（`$$...$$`）。
+///
+/// # 返回
+/// MathML HTML 片段。
+pub fn render_math(latex: &str, display: bool) -> String {
+    let opts = Opts::builder()
+        .display_mode(display)
+        .output_type(OutputType::Mathml)
+        .throw_on_error(true)
+        .build();
+
+    let rendered = opts
+        .map_err(|e| e.to_string())
+        .and_then(|opts| katex::render_with_opts(latex, &opts).map_err(|e| e.to_string()));
+
+    match rendered {
+        Ok(mathml) => mathml,
+        Err(e) => {
+            debug!("公式渲染失败: {}", e);
+            format!("<code class=\"math-error\">{}</code>", escape_html(latex))
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// src/render/mod.rs
+//! 文章内容渲染模块。
+//! 负责将文章的 Markdown 源文本渲染为经过安全清洗的 HTML，并计算内容哈希用于缓存校验。
+
+pub mod markdown;  // Markdown (CommonMark + GFM) 到 HTML 的转换
+pub mod sanitize;  // 基于白名单的 HTML 清洗，防御 XSS
+pub mod highlight; // 代码块语法高亮和高亮主题样式表
+pub mod math;      // KaTeX 数学公式渲染
+pub mod toc;       // 标题锚点与目录生成
+pub mod summary;   // 自动摘要、字数与阅读时间统计
+
+use sha2::{Digest, Sha256};
+
+pub use markdown::{render_markdown, RenderedMarkdown};
+pub use toc::TocEntry;
+
+/// 渲染器版本号。
+/// 修改渲染规则（扩展语法、清洗白名单等）时应递增此值，
+/// 这样数据库中按旧规则缓存的 HTML 会因哈希不匹配而被重新渲染。
+pub const RENDERER_VERSION: u32 = 2;
+
+/// 计算 Markdown 内容的哈希值（十六进制 SHA-256）。
+/// 哈希中包含 `RENDERER_VERSION`，渲染规则变化时缓存会自动失效。
+///
+/// # 参数
+/// * `markdown` - 文章的 Markdown 源文本。
+///
+/// # 返回
+/// 64 个字符的十六进制哈希字符串。
+pub fn content_hash(markdown: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(RENDERER_VERSION.to_le_bytes());
+    hasher.update(markdown.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
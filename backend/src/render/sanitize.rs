@@ -0,0 +1,120 @@
+// src/render/sanitize.rs
+//! 渲染结果的 HTML 白名单清洗。
+//! 在 ammonia 默认白名单的基础上，只额外放行渲染器输出所需的标签和属性。
+
+use ammonia::Builder;
+use lazy_static::lazy_static;
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use super::highlight::HIGHLIGHT_CLASS_PREFIX;
+use super::markdown::FOOTNOTE_ID_PREFIX;
+use super::toc::HEADING_ID_PREFIX;
+
+lazy_static! {
+    /// 共享的清洗器实例。构建白名单有一定开销，因此只构建一次。
+    static ref SANITIZER: Builder<'static> = build_sanitizer();
+}
+
+/// KaTeX 输出的 MathML 标签。
+const MATHML_TAGS: &[&str] = &[
+    "math", "semantics", "annotation", "mrow", "mi", "mo", "mn", "ms", "mtext", "mspace",
+    "msup", "msub", "msubsup", "mfrac", "msqrt", "mroot", "mover", "munder", "munderover",
+    "mtable", "mtr", "mtd", "mstyle", "mpadded", "mphantom", "menclose", "merror",
+];
+
+/// MathML 标签允许的属性。
+const MATHML_ATTRIBUTES: &[&str] = &[
+    "xmlns", "display", "encoding", "mathvariant", "mathcolor", "stretchy", "fence",
+    "separator", "lspace", "rspace", "accent", "accentunder", "linethickness", "columnalign",
+    "rowalign", "rowspacing", "columnspacing", "width", "height", "depth", "voffset",
+    "scriptlevel", "displaystyle", "minsize", "maxsize", "movablelimits", "symmetric",
+    "largeop", "notation",
+];
+
+/// `<span>` / `<pre>` 上允许的固定类名（公式容器和公式错误提示）。
+const EXTRA_CLASSES: &[&str] = &["katex", "katex-display", "math-error"];
+
+/// 构建 HTML 清洗器。
+fn build_sanitizer() -> Builder<'static> {
+    let mut builder = Builder::default();
+    builder
+        // 任务列表：只允许禁用状态的复选框
+        .add_tags(["input"])
+        .add_tag_attribute_values("input", "type", ["checkbox"])
+        .add_tag_attribute_values("input", "checked", [""])
+        .add_tag_attribute_values("input", "disabled", [""])
+        // 表格列对齐：只允许 text-align 样式
+        .add_tag_attributes("th", ["style"])
+        .add_tag_attributes("td", ["style"])
+        .filter_style_properties(HashSet::from(["text-align"]))
+        // 脚注：引用与定义之间通过 id 跳转
+        .add_tag_attributes("div", ["id"])
+        .add_allowed_classes("div", ["footnote-definition"])
+        .add_allowed_classes("sup", ["footnote-reference", "footnote-definition-label"])
+        // 标题锚点
+        .add_tag_attributes("h1", ["id"])
+        .add_tag_attributes("h2", ["id"])
+        .add_tag_attributes("h3", ["id"])
+        .add_tag_attributes("h4", ["id"])
+        .add_tag_attributes("h5", ["id"])
+        .add_tag_attributes("h6", ["id"])
+        // 以上 id 和下面的类名都由 attribute_filter 按前缀过滤
+        // 代码高亮和公式
+        .add_tag_attributes("span", ["class"])
+        .add_tag_attributes("pre", ["class"])
+        .add_tag_attributes("code", ["class"])
+        .add_tags(MATHML_TAGS.iter().copied());
+    for tag in MATHML_TAGS {
+        builder.add_tag_attributes(tag, MATHML_ATTRIBUTES.iter().copied());
+    }
+    builder.attribute_filter(filter_attribute);
+    builder
+}
+
+/// 过滤 `id` 和 `class` 属性，只保留渲染器自身生成的值。
+fn filter_attribute<'u>(element: &str, attribute: &str, value: &'u str) -> Option<Cow<'u, str>> {
+    match attribute {
+        "id" => filter_id_attribute(element, value),
+        "class" => filter_class_attribute(element, value),
+        _ => Some(value.into()),
+    }
+}
+
+/// 过滤 `id` 属性，只保留脚注和标题锚点前缀，防止作者手写的 HTML 覆盖或伪造生成的锚点。
+fn filter_id_attribute<'u>(element: &str, value: &'u str) -> Option<Cow<'u, str>> {
+    let prefix = match element {
+        "div" => FOOTNOTE_ID_PREFIX,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => HEADING_ID_PREFIX,
+        _ => return None,
+    };
+    value.starts_with(prefix).then(|| value.into())
+}
+
+/// 过滤 `class` 属性，只保留渲染器自身生成的类名。
+fn filter_class_attribute<'u>(element: &str, value: &'u str) -> Option<Cow<'u, str>> {
+    let allowed: fn(&str) -> bool = match element {
+        "span" | "pre" => |class| class.starts_with(HIGHLIGHT_CLASS_PREFIX) || EXTRA_CLASSES.contains(&class),
+        "code" => |class| class.starts_with("language-") || class == "math-error",
+        // 其他标签的类名由 allowed_classes 管理
+        _ => return Some(value.into()),
+    };
+
+    let classes: Vec<&str> = value.split_whitespace().filter(|class| allowed(class)).collect();
+    if classes.is_empty() {
+        None
+    } else {
+        Some(classes.join(" ").into())
+    }
+}
+
+/// 按白名单清洗 HTML，移除脚本、事件属性和不安全的 URL 协议。
+///
+/// # 参数
+/// * `html` - 待清洗的 HTML 片段。
+///
+/// # 返回
+/// 清洗后的 HTML 字符串。
+pub fn sanitize_html(html: &str) -> String {
+    SANITIZER.clean(html).to_string()
+}
@@ -0,0 +1,124 @@
+// src/render/summary.rs
+//! 文章摘要与阅读统计。
+//! 从 Markdown 中提取纯文本，生成自动摘要，并按中日韩文字与拉丁文字分别统计字数和阅读时间。
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+/// 自动摘要的最大字符数。
+pub const EXCERPT_MAX_CHARS: usize = 200;
+
+/// 中日韩文字的阅读速度（字/分钟）。
+const CJK_CHARS_PER_MINUTE: f64 = 400.0;
+
+/// 拉丁等以空格分词文字的阅读速度（词/分钟）。
+const WORDS_PER_MINUTE: f64 = 250.0;
+
+/// 文章的字数统计结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextStats {
+    pub word_count: u32,           // 字数：每个中日韩字符计 1，其余按单词计
+    pub reading_time_minutes: u32, // 预计阅读时间（分钟），非空文章至少为 1
+}
+
+/// 判断字符是否属于中日韩文字（汉字、假名、谚文）。
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF     // 平假名、片假名
+        | 0x3400..=0x4DBF   // CJK 扩展 A
+        | 0x4E00..=0x9FFF   // CJK 统一汉字
+        | 0xAC00..=0xD7AF   // 谚文音节
+        | 0xF900..=0xFAFF   // CJK 兼容汉字
+        | 0x20000..=0x2EBEF // CJK 扩展 B-F
+    )
+}
+
+/// 从 Markdown 中提取纯文本。
+///
+/// # 参数
+/// * `markdown` - Markdown 源文本。
+/// * `include_code_blocks` - 是否保留代码块内容（统计字数时保留，生成摘要时跳过）。
+fn plain_text(markdown: &str, include_code_blocks: bool) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    let mut in_code_block = false;
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                text.push(' ');
+            }
+            Event::Text(t) | Event::Code(t) if !in_code_block || include_code_blocks => {
+                text.push_str(&t)
+            }
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            Event::End(TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::TableCell) => {
+                text.push(' ')
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+/// 统计字数和预计阅读时间。
+///
+/// 中日韩文字每个字符计为一个字；其他文字按连续的字母数字序列计为一个词。
+/// 两类文字分别按各自的阅读速度折算阅读时间后相加。
+///
+/// # 参数
+/// * `markdown` - 文章的 Markdown 源文本。
+///
+/// # 返回
+/// `TextStats` 统计结果。
+pub fn text_stats(markdown: &str) -> TextStats {
+    let text = plain_text(markdown, true);
+    let mut cjk_chars = 0u32;
+    let mut words = 0u32;
+    let mut in_word = false;
+
+    for c in text.chars() {
+        if is_cjk(c) {
+            cjk_chars += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                words += 1;
+                in_word = true;
+            }
+        } else if c != '\'' && c != '’' {
+            // 撇号视为单词的一部分 (例如 don't)
+            in_word = false;
+        }
+    }
+
+    let minutes = cjk_chars as f64 / CJK_CHARS_PER_MINUTE + words as f64 / WORDS_PER_MINUTE;
+    let word_count = cjk_chars + words;
+    TextStats {
+        word_count,
+        reading_time_minutes: if word_count == 0 { 0 } else { (minutes.ceil() as u32).max(1) },
+    }
+}
+
+/// 根据 Markdown 内容生成自动摘要。
+///
+/// 摘要取正文（不含代码块）的前 `max_chars` 个字符，空白折叠为单个空格，截断时以 `…` 结尾。
+///
+/// # 参数
+/// * `markdown` - 文章的 Markdown 源文本。
+/// * `max_chars` - 摘要的最大字符数。
+///
+/// # 返回
+/// 摘要字符串。
+pub fn auto_excerpt(markdown: &str, max_chars: usize) -> String {
+    let text = plain_text(markdown, false);
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if collapsed.chars().count() <= max_chars {
+        return collapsed;
+    }
+    let mut excerpt: String = collapsed.chars().take(max_chars).collect();
+    excerpt.truncate(excerpt.trim_end().len());
+    excerpt.push('…');
+    excerpt
+}
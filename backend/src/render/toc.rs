@@ -0,0 +1,101 @@
+// src/render/toc.rs
+//! 标题锚点与目录生成。
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// 标题锚点 id 的前缀，作用同脚注前缀，避免与页面已有元素的 id 冲突。
+pub const HEADING_ID_PREFIX: &str = "h-";
+
+/// 目录条目。
+/// 条目按标题层级嵌套，`children` 为下一级标题。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TocEntry {
+    pub level: u8,     // 标题级别 1-6
+    pub id: String,    // 标题锚点 id，可直接用于 `#id` 跳转
+    pub title: String, // 标题纯文本
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TocEntry>,
+}
+
+/// 为文章内的标题生成稳定且唯一的锚点 id。
+///
+/// 相同的标题文本总是生成相同的 id；同一篇文章中重复的标题依次追加 `-1`、`-2` 后缀，
+/// 并跳过已被其他标题占用的 id（例如标题 "A-1" 已生成 `h-a-1`）。
+#[derive(Default)]
+pub struct AnchorGenerator {
+    counters: HashMap<String, usize>, // 每个 slug 下一个待尝试的后缀
+    issued: HashSet<String>,          // 已分配的 id
+}
+
+impl AnchorGenerator {
+    /// 根据标题文本生成锚点 id。
+    pub fn anchor_for(&mut self, title: &str) -> String {
+        let base = slugify(title);
+        let count = self.counters.entry(base.clone()).or_insert(0);
+        loop {
+            let id = if *count == 0 {
+                format!("{}{}", HEADING_ID_PREFIX, base)
+            } else {
+                format!("{}{}-{}", HEADING_ID_PREFIX, base, count)
+            };
+            *count += 1;
+            if self.issued.insert(id.clone()) {
+                return id;
+            }
+        }
+    }
+}
+
+/// 将标题文本转换为 slug：保留字母数字（包括中文等非 ASCII 文字），
+/// 空白和连字符折叠为单个 `-`，其余标点移除。
+fn slugify(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+    for c in title.trim().chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-' || c == '_') && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "section".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// 将按出现顺序排列的标题列表组装为嵌套目录。
+///
+/// # 参数
+/// * `headings` - `(级别, id, 标题)` 列表。
+///
+/// # 返回
+/// 顶层目录条目列表。
+pub fn build_toc(headings: Vec<(u8, String, String)>) -> Vec<TocEntry> {
+    let mut roots: Vec<TocEntry> = Vec::new();
+    // 当前路径上尚未闭合的条目，层级严格递增
+    let mut stack: Vec<TocEntry> = Vec::new();
+
+    for (level, id, title) in headings {
+        let entry = TocEntry { level, id, title, children: Vec::new() };
+        while stack.last().is_some_and(|top| top.level >= level) {
+            let finished = stack.pop().unwrap();
+            attach(&mut stack, &mut roots, finished);
+        }
+        stack.push(entry);
+    }
+    while let Some(finished) = stack.pop() {
+        attach(&mut stack, &mut roots, finished);
+    }
+    roots
+}
+
+/// 将已闭合的条目挂到父条目下，没有父条目时作为顶层条目。
+fn attach(stack: &mut [TocEntry], roots: &mut Vec<TocEntry>, entry: TocEntry) {
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(entry),
+        None => roots.push(entry),
+    }
+}
@@ -0,0 +1,122 @@
+// src/seo.rs
+//! 公开页面的 SEO 元数据。
+//! 生成 OpenGraph / Twitter Card 所需的字段以及 JSON-LD `BlogPosting` 结构化数据。
+
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::Config;
+use crate::handler::idl::PostInfo;
+
+/// 页面 `<head>` 中使用的元数据。
+#[derive(Debug, Clone, Serialize)]
+pub struct PageMeta {
+    pub title: String,
+    pub description: String,
+    pub canonical_url: String,
+    pub og_type: &'static str,           // "article" 或 "website"
+    pub twitter_card: &'static str,      // 有封面时为 "summary_large_image"，否则为 "summary"
+    pub image: Option<String>,
+    pub feed_url: Option<String>,        // 对应的 RSS 订阅源
+    pub published_time: Option<String>,  // 以下字段仅文章页面填充
+    pub modified_time: Option<String>,
+    pub author_url: Option<String>,
+    pub section: Option<String>,
+    pub json_ld: Option<String>,         // 已转义、可直接嵌入 <script> 的 JSON-LD
+}
+
+/// 以 ISO 8601 格式输出 UTC 时间。
+pub fn iso8601(time: NaiveDateTime) -> String {
+    time.and_utc().to_rfc3339()
+}
+
+/// 将 JSON 序列化为可以安全嵌入 `<script>` 标签的字符串。
+/// 转义 `<`、`>` 和 `&`，防止内容中的 `</script>` 提前结束脚本块。
+fn script_safe_json(value: &serde_json::Value) -> String {
+    value
+        .to_string()
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+}
+
+/// 生成文章页面的元数据。
+///
+/// # 参数
+/// * `config` - 站点配置，用于生成绝对链接。
+/// * `post` - 文章信息。
+/// * `description` - 页面描述（文章摘要）。
+/// * `author_name` - 作者名。
+pub fn post_page_meta(config: &Config, post: &PostInfo, description: &str, author_name: &str) -> PageMeta {
+    let canonical_url = config.post_url(post.id);
+    let author_url = config.author_url(post.author_id);
+
+    // OpenGraph 和 JSON-LD 要求绝对地址：优先使用本站封面的地址，否则补全封面 URL
+    let image = match &post.cover {
+        Some(cover) => Some(cover.url.clone()),
+        None => post.cover_url.as_deref().map(|url| config.absolute_url(url)),
+    };
+
+    let mut json_ld = json!({
+        "@context": "https://schema.org",
+        "@type": "BlogPosting",
+        "headline": post.title,
+        "description": description,
+        "datePublished": iso8601(post.created_at),
+        "dateModified": iso8601(post.updated_at),
+        "articleSection": post.category,
+        "url": canonical_url,
+        "mainEntityOfPage": { "@type": "WebPage", "@id": canonical_url },
+        "author": { "@type": "Person", "name": author_name, "url": author_url },
+        "publisher": { "@type": "Organization", "name": config.site_title, "url": config.site_url },
+    });
+    if let Some(image) = &image {
+        json_ld["image"] = json!(image);
+    }
+
+    PageMeta {
+        title: format!("{} - {}", post.title, config.site_title),
+        description: description.to_string(),
+        canonical_url,
+        og_type: "article",
+        twitter_card: if image.is_some() { "summary_large_image" } else { "summary" },
+        image,
+        feed_url: Some(format!("{}/feed.xml", config.site_url)),
+        published_time: Some(iso8601(post.created_at)),
+        modified_time: Some(iso8601(post.updated_at)),
+        author_url: Some(author_url),
+        section: Some(post.category.clone()),
+        json_ld: Some(script_safe_json(&json_ld)),
+    }
+}
+
+/// 生成列表页面（分类、作者）的元数据。
+///
+/// # 参数
+/// * `title` - 页面标题（不含站点名）。
+/// * `description` - 页面描述。
+/// * `canonical_url` - 页面的规范地址。
+/// * `feed_url` - 对应的订阅源地址。
+pub fn list_page_meta(
+    config: &Config,
+    title: &str,
+    description: &str,
+    canonical_url: String,
+    feed_url: String,
+) -> PageMeta {
+    PageMeta {
+        title: format!("{} - {}", title, config.site_title),
+        description: description.to_string(),
+        canonical_url,
+        og_type: "website",
+        twitter_card: "summary",
+        image: None,
+        feed_url: Some(feed_url),
+        published_time: None,
+        modified_time: None,
+        author_url: None,
+        section: None,
+        json_ld: None,
+    }
+}
@@ -0,0 +1,667 @@
+// src/settings.rs
+//! 分层配置：内置默认值 < TOML 配置文件 < 环境变量 < 命令行参数。
+//!
+//! 配置文件默认为当前目录下的 `config.toml`（不存在时跳过），也可以通过 `--config`
+//! 或 `CONFIG_FILE` 指定；完整的配置项见 `config.example.toml`。
+//! 加载完成后统一校验，所有问题一次性报告；生产模式 (`environment = "production"`)
+//! 下拒绝使用未设置、默认或过短的 JWT 密钥启动。
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::media::DEFAULT_VARIANT_WIDTHS;
+use crate::storage::s3::{Endpoint, MIN_PART_SIZE};
+use crate::Config;
+
+/// 开发模式下未设置 JWT_SECRET 时使用的密钥，生产模式禁止使用。
+pub const DEV_JWT_SECRET: &str = "default_jwt_secret";
+/// 生产模式下 JWT 密钥的最小长度（字节）。
+pub const MIN_JWT_SECRET_LEN: usize = 32;
+/// 未指定配置文件时尝试读取的默认路径。
+pub const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+/// 运行模式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    #[default]
+    Development,
+    Production,
+}
+
+/// 命令行参数中可覆盖的配置项，`server` 和 `blogctl` 共用。
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct Overrides {
+    /// 配置文件路径，默认读取 CONFIG_FILE 或 ./config.toml
+    #[arg(long = "config", value_name = "FILE", global = true)]
+    pub config_file: Option<PathBuf>,
+    /// 运行模式
+    #[arg(long = "env", value_enum, global = true)]
+    pub environment: Option<Environment>,
+    /// 监听地址，例如 0.0.0.0:3000
+    #[arg(long, global = true)]
+    pub listen: Option<SocketAddr>,
+    /// 数据库连接地址
+    #[arg(long, global = true)]
+    pub database_url: Option<String>,
+    /// 日志过滤规则，例如 info 或 axum_blog_engine=debug
+    #[arg(long, global = true)]
+    pub log_level: Option<String>,
+}
+
+/// 全部配置。各个分区与配置文件中的 `[server]`、`[database]` 等表一一对应。
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub environment: Environment,
+    pub server: ServerSettings,
+    pub database: DatabaseSettings,
+    pub auth: AuthSettings,
+    pub site: SiteSettings,
+    pub media: MediaSettings,
+    pub log: LogSettings,
+    pub metrics: MetricsSettings,
+    pub tracing: TracingSettings,
+    /// 加载过程中产生的警告，由调用方在日志系统初始化后输出
+    #[serde(skip)]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    pub listen: SocketAddr,
+    pub cors_origins: Vec<String>, // 允许跨域访问的来源，["*"] 表示任意来源
+    pub shutdown_timeout_secs: u64, // 停机时等待进行中的请求和后台任务完成的最长时间
+    pub trust_forwarded_for: bool, // 位于反向代理之后时开启，审计日志以 X-Forwarded-For 中代理追加的地址作为客户端 IP
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            listen: SocketAddr::from(([0, 0, 0, 0], 3000)),
+            cors_origins: vec!["*".into()],
+            shutdown_timeout_secs: 30,
+            trust_forwarded_for: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSettings {
+    pub url: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub connect_timeout_secs: u64,
+    pub idle_timeout_secs: u64,
+    pub auto_migrate: bool, // 启动时是否自动执行尚未应用的迁移
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        Self {
+            url: "sqlite:blogdb.db?mode=rwc".into(), // rwc 表示读写，不存在时创建
+            max_connections: 10,
+            min_connections: 5,
+            connect_timeout_secs: 8,
+            idle_timeout_secs: 8,
+            auto_migrate: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthSettings {
+    pub jwt_secret: Option<String>,
+    pub bcrypt_cost: u32,
+    pub token_ttl_hours: u32, // 登录 / 注册后签发的 Token 有效期
+}
+
+impl Default for AuthSettings {
+    fn default() -> Self {
+        Self { jwt_secret: None, bcrypt_cost: 12, token_ttl_hours: 30 * 24 }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SiteSettings {
+    pub url: String,
+    pub title: String,
+    pub description: String,
+    pub robots_txt_path: Option<PathBuf>, // 自定义 robots.txt 文件
+    pub theme_dir: Option<PathBuf>,       // 公开页面的主题目录
+    pub static_dir: Option<PathBuf>,      // 前端构建产物目录 (例如 ../frontend/dist)
+    pub api_docs_bundle: Option<PathBuf>, // 交互式 API 文档使用的 Scalar 脚本 (本地文件)
+}
+
+impl Default for SiteSettings {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:3000".into(),
+            title: "Axum Blog".into(),
+            description: "基于 Axum 的博客".into(),
+            robots_txt_path: None,
+            theme_dir: None,
+            static_dir: None,
+            api_docs_bundle: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MediaSettings {
+    pub dir: String, // 本地存储后端的媒体文件目录
+    pub upload_max_bytes: u64,
+    pub quota_bytes: u64, // 每个用户的媒体存储配额
+    pub image_variant_widths: Vec<u32>,
+    pub storage: StorageSettings,
+}
+
+impl Default for MediaSettings {
+    fn default() -> Self {
+        Self {
+            dir: "./media".into(),
+            upload_max_bytes: 10 * 1024 * 1024,
+            quota_bytes: 100 * 1024 * 1024,
+            image_variant_widths: DEFAULT_VARIANT_WIDTHS.to_vec(),
+            storage: StorageSettings::default(),
+        }
+    }
+}
+
+/// 媒体文件的存储后端。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageKind {
+    #[default]
+    Local, // 保存在 media.dir 目录下
+    S3,    // S3 兼容的对象存储 (AWS S3、MinIO 等)
+}
+
+/// `[media.storage]`：媒体存储后端；`backend = "s3"` 时使用其余的对象存储配置。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageSettings {
+    pub backend: StorageKind,
+    pub endpoint: Option<String>, // 未设置时为 https://s3.<region>.amazonaws.com
+    pub region: String,
+    pub bucket: String,
+    pub prefix: String, // 对象键前缀，例如 "media/"
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub path_style: bool,          // MinIO 等需要路径风格地址
+    pub presign_expires_secs: u64, // /media/* 重定向到预签名地址，0 表示由后端代理
+    pub multipart_threshold: usize,
+    pub multipart_part_size: usize,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            backend: StorageKind::Local,
+            endpoint: None,
+            region: "us-east-1".into(),
+            bucket: String::new(),
+            prefix: String::new(),
+            access_key_id: String::new(),
+            secret_access_key: String::new(),
+            path_style: false,
+            presign_expires_secs: 3600,
+            multipart_threshold: 16 * 1024 * 1024,
+            multipart_part_size: 8 * 1024 * 1024,
+        }
+    }
+}
+
+impl StorageSettings {
+    /// 对象存储的服务地址，未配置时按区域使用 AWS S3 的地址。
+    pub fn endpoint(&self) -> String {
+        match &self.endpoint {
+            Some(endpoint) => endpoint.clone(),
+            None => format!("https://s3.{}.amazonaws.com", self.region),
+        }
+    }
+}
+
+/// 日志格式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json, // 每行一个 JSON 对象，便于日志系统采集
+}
+
+/// 日志文件按时间轮转的周期（UTC）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    Never,
+    Hourly,
+    #[default]
+    Daily,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogSettings {
+    pub level: String, // tracing 的过滤规则，与 RUST_LOG 格式相同
+    pub format: LogFormat,
+    pub dir: Option<PathBuf>, // 设置后写入该目录下的轮转文件，否则输出到标准输出
+    pub file_name: String,
+    pub rotation: LogRotation,
+    pub max_file_bytes: u64, // 单个日志文件的大小上限，0 表示不限制
+    pub max_files: usize,    // 保留的已轮转文件数量，0 表示全部保留
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            level: "axum_blog_engine=debug,tower_http=debug,sea_orm=debug".into(),
+            format: LogFormat::Text,
+            dir: None,
+            file_name: "backend.log".into(),
+            rotation: LogRotation::Daily,
+            max_file_bytes: 100 * 1024 * 1024,
+            max_files: 14,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetricsSettings {
+    pub enabled: bool,
+    pub listen: Option<SocketAddr>, // 设置后 /metrics 只在这个独立的管理端口上提供
+    pub token: Option<String>,      // 访问 /metrics 需要的 Bearer 令牌
+}
+
+impl Default for MetricsSettings {
+    fn default() -> Self {
+        Self { enabled: true, listen: None, token: None }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TracingSettings {
+    pub otlp_endpoint: Option<String>, // OTLP/HTTP 收集器地址，例如 http://127.0.0.1:4318，未设置时不导出
+    pub service_name: String,
+    pub sample_ratio: f64, // 没有上游采样决定时的采样比例 (0 ~ 1)
+}
+
+impl Default for TracingSettings {
+    fn default() -> Self {
+        Self { otlp_endpoint: None, service_name: "axum-blog-engine".into(), sample_ratio: 1.0 }
+    }
+}
+
+impl Settings {
+    /// 从配置文件、进程环境变量和命令行参数加载配置并校验。
+    pub fn load(overrides: &Overrides) -> Result<Settings> {
+        let path = match &overrides.config_file {
+            Some(path) => Some(path.clone()),
+            None => std::env::var_os("CONFIG_FILE").map(PathBuf::from),
+        };
+        let file = match path {
+            Some(path) => Some(read_config_file(&path)?),
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => {
+                Some(read_config_file(Path::new(DEFAULT_CONFIG_FILE))?)
+            }
+            None => None,
+        };
+        Self::from_sources(file.as_deref(), |name| std::env::var(name).ok(), overrides)
+    }
+
+    /// 按优先级合并各层配置并校验。
+    ///
+    /// # 参数
+    /// * `file` - 配置文件内容 (TOML)，没有配置文件时为 `None`。
+    /// * `env` - 环境变量读取函数。
+    /// * `overrides` - 命令行参数。
+    pub fn from_sources(
+        file: Option<&str>,
+        env: impl Fn(&str) -> Option<String>,
+        overrides: &Overrides,
+    ) -> Result<Settings> {
+        let mut settings: Settings = match file {
+            Some(text) => toml::from_str(text).map_err(|e| anyhow!("配置文件格式错误: {}", e))?,
+            None => Settings::default(),
+        };
+        let mut errors = Vec::new();
+        settings.apply_env(&env, &mut errors);
+        settings.apply_overrides(overrides);
+        settings.validate(&mut errors);
+
+        if errors.is_empty() {
+            Ok(settings)
+        } else {
+            Err(anyhow!("配置无效:\n  - {}", errors.join("\n  - ")))
+        }
+    }
+
+    /// 环境变量覆盖配置文件；无法解析的值记为错误，不再静默使用默认值。
+    fn apply_env(&mut self, env: &impl Fn(&str) -> Option<String>, errors: &mut Vec<String>) {
+        let var = |name: &str| -> Option<String> { env(name).filter(|v| !v.trim().is_empty()) };
+
+        macro_rules! parsed {
+            ($name:literal, $field:expr) => {
+                if let Some(value) = var($name) {
+                    match value.trim().parse() {
+                        Ok(v) => $field = v,
+                        Err(e) => errors.push(format!("无法解析环境变量 {}={}: {}", $name, value, e)),
+                    }
+                }
+            };
+        }
+
+        if let Some(value) = var("APP_ENV") {
+            match value.as_str() {
+                "development" => self.environment = Environment::Development,
+                "production" => self.environment = Environment::Production,
+                _ => errors.push(format!("APP_ENV 只能是 development 或 production，当前为 {}", value)),
+            }
+        }
+
+        parsed!("LISTEN_ADDR", self.server.listen);
+        if let Some(value) = var("CORS_ORIGINS") {
+            self.server.cors_origins = split_list(&value);
+        }
+        parsed!("SHUTDOWN_TIMEOUT", self.server.shutdown_timeout_secs);
+        if let Some(value) = var("TRUST_FORWARDED_FOR") {
+            self.server.trust_forwarded_for = value != "false" && value != "0";
+        }
+
+        if let Some(value) = var("DATABASE_URL") {
+            self.database.url = value;
+        }
+        parsed!("DB_MAX_CONNECTIONS", self.database.max_connections);
+        parsed!("DB_MIN_CONNECTIONS", self.database.min_connections);
+        parsed!("DB_CONNECT_TIMEOUT", self.database.connect_timeout_secs);
+        parsed!("DB_IDLE_TIMEOUT", self.database.idle_timeout_secs);
+        if let Some(value) = var("AUTO_MIGRATE") {
+            self.database.auto_migrate = value != "false" && value != "0";
+        }
+
+        if let Some(value) = var("JWT_SECRET") {
+            self.auth.jwt_secret = Some(value);
+        }
+        parsed!("BCRYPT_COST", self.auth.bcrypt_cost);
+        parsed!("TOKEN_TTL_HOURS", self.auth.token_ttl_hours);
+
+        if let Some(value) = var("SITE_URL") {
+            self.site.url = value;
+        }
+        if let Some(value) = var("SITE_TITLE") {
+            self.site.title = value;
+        }
+        if let Some(value) = var("SITE_DESCRIPTION") {
+            self.site.description = value;
+        }
+        if let Some(value) = var("ROBOTS_TXT_PATH") {
+            self.site.robots_txt_path = Some(value.into());
+        }
+        if let Some(value) = var("THEME_DIR") {
+            self.site.theme_dir = Some(value.into());
+        }
+        if let Some(value) = var("STATIC_DIR") {
+            self.site.static_dir = Some(value.into());
+        }
+        if let Some(value) = var("API_DOCS_BUNDLE") {
+            self.site.api_docs_bundle = Some(value.into());
+        }
+
+        if let Some(value) = var("MEDIA_DIR") {
+            self.media.dir = value;
+        }
+        parsed!("UPLOAD_MAX_BYTES", self.media.upload_max_bytes);
+        parsed!("MEDIA_QUOTA_BYTES", self.media.quota_bytes);
+        if let Some(value) = var("IMAGE_VARIANT_WIDTHS") {
+            match split_list(&value).iter().map(|w| w.parse::<u32>()).collect::<Result<Vec<_>, _>>() {
+                Ok(widths) => self.media.image_variant_widths = widths,
+                Err(e) => errors.push(format!("无法解析环境变量 IMAGE_VARIANT_WIDTHS={}: {}", value, e)),
+            }
+        }
+        if let Some(value) = var("MEDIA_STORAGE") {
+            match value.as_str() {
+                "local" => self.media.storage.backend = StorageKind::Local,
+                "s3" => self.media.storage.backend = StorageKind::S3,
+                _ => errors.push(format!("MEDIA_STORAGE 只能是 local 或 s3，当前为 {}", value)),
+            }
+        }
+        if let Some(value) = var("S3_ENDPOINT") {
+            self.media.storage.endpoint = Some(value);
+        }
+        if let Some(value) = var("S3_REGION") {
+            self.media.storage.region = value;
+        }
+        if let Some(value) = var("S3_BUCKET") {
+            self.media.storage.bucket = value;
+        }
+        if let Some(value) = var("S3_PREFIX") {
+            self.media.storage.prefix = value;
+        }
+        if let Some(value) = var("S3_ACCESS_KEY_ID") {
+            self.media.storage.access_key_id = value;
+        }
+        if let Some(value) = var("S3_SECRET_ACCESS_KEY") {
+            self.media.storage.secret_access_key = value;
+        }
+        if let Some(value) = var("S3_PATH_STYLE") {
+            self.media.storage.path_style = value == "true" || value == "1";
+        }
+        parsed!("S3_PRESIGN_EXPIRES", self.media.storage.presign_expires_secs);
+        parsed!("S3_MULTIPART_THRESHOLD", self.media.storage.multipart_threshold);
+        parsed!("S3_MULTIPART_PART_SIZE", self.media.storage.multipart_part_size);
+
+        if let Some(value) = var("RUST_LOG") {
+            self.log.level = value;
+        }
+        if let Some(value) = var("LOG_FORMAT") {
+            match value.as_str() {
+                "text" => self.log.format = LogFormat::Text,
+                "json" => self.log.format = LogFormat::Json,
+                _ => errors.push(format!("LOG_FORMAT 只能是 text 或 json，当前为 {}", value)),
+            }
+        }
+        if let Some(value) = var("LOG_DIR") {
+            self.log.dir = Some(value.into());
+        }
+        if let Some(value) = var("LOG_FILE_NAME") {
+            self.log.file_name = value;
+        }
+        if let Some(value) = var("LOG_ROTATION") {
+            match value.as_str() {
+                "never" => self.log.rotation = LogRotation::Never,
+                "hourly" => self.log.rotation = LogRotation::Hourly,
+                "daily" => self.log.rotation = LogRotation::Daily,
+                _ => errors.push(format!("LOG_ROTATION 只能是 never、hourly 或 daily，当前为 {}", value)),
+            }
+        }
+        parsed!("LOG_MAX_FILE_BYTES", self.log.max_file_bytes);
+        parsed!("LOG_MAX_FILES", self.log.max_files);
+
+        if let Some(value) = var("METRICS_ENABLED") {
+            self.metrics.enabled = value != "false" && value != "0";
+        }
+        if let Some(value) = var("METRICS_LISTEN") {
+            match value.trim().parse() {
+                Ok(addr) => self.metrics.listen = Some(addr),
+                Err(e) => errors.push(format!("无法解析环境变量 METRICS_LISTEN={}: {}", value, e)),
+            }
+        }
+        if let Some(value) = var("METRICS_TOKEN") {
+            self.metrics.token = Some(value);
+        }
+
+        if let Some(value) = var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            self.tracing.otlp_endpoint = Some(value);
+        }
+        if let Some(value) = var("OTEL_SERVICE_NAME") {
+            self.tracing.service_name = value;
+        }
+        parsed!("OTEL_TRACES_SAMPLER_ARG", self.tracing.sample_ratio);
+    }
+
+    /// 命令行参数优先级最高。
+    fn apply_overrides(&mut self, overrides: &Overrides) {
+        if let Some(environment) = overrides.environment {
+            self.environment = environment;
+        }
+        if let Some(listen) = overrides.listen {
+            self.server.listen = listen;
+        }
+        if let Some(url) = &overrides.database_url {
+            self.database.url = url.clone();
+        }
+        if let Some(level) = &overrides.log_level {
+            self.log.level = level.clone();
+        }
+    }
+
+    /// 校验合并后的配置，错误追加到 `errors`，非致命问题记入 `warnings`。
+    fn validate(&mut self, errors: &mut Vec<String>) {
+        let production = self.environment == Environment::Production;
+
+        match self.auth.jwt_secret.as_deref() {
+            None if production => errors.push("生产模式必须设置 JWT_SECRET (auth.jwt_secret)".into()),
+            None => self.warnings.push(format!(
+                "未设置 JWT_SECRET，使用开发用默认密钥 {}，生产环境请务必设置！", DEV_JWT_SECRET
+            )),
+            Some(DEV_JWT_SECRET) if production => errors.push("生产模式不能使用默认的 JWT 密钥".into()),
+            Some(secret) if production && secret.len() < MIN_JWT_SECRET_LEN => errors.push(format!(
+                "生产模式的 JWT 密钥长度至少为 {} 字节", MIN_JWT_SECRET_LEN
+            )),
+            Some(_) => {}
+        }
+        if !(4..=31).contains(&self.auth.bcrypt_cost) {
+            errors.push(format!("bcrypt_cost 必须在 4 到 31 之间，当前为 {}", self.auth.bcrypt_cost));
+        }
+        if self.auth.token_ttl_hours == 0 {
+            errors.push("token_ttl_hours 必须大于 0".into());
+        }
+
+        if self.database.url.trim().is_empty() {
+            errors.push("数据库连接地址不能为空".into());
+        }
+        if self.database.max_connections == 0 {
+            errors.push("database.max_connections 必须大于 0".into());
+        }
+        if self.database.min_connections > self.database.max_connections {
+            errors.push(format!(
+                "database.min_connections ({}) 不能大于 max_connections ({})",
+                self.database.min_connections, self.database.max_connections
+            ));
+        }
+
+        let any_origin = self.server.cors_origins.iter().any(|o| o == "*");
+        if any_origin && self.server.cors_origins.len() > 1 {
+            errors.push("cors_origins 中的 \"*\" 不能与其他来源同时使用".into());
+        } else if any_origin && production {
+            self.warnings.push("生产模式允许任意来源跨域访问，建议在 server.cors_origins 中列出前端地址".into());
+        }
+        for origin in self.server.cors_origins.iter().filter(|o| *o != "*") {
+            if !(origin.starts_with("http://") || origin.starts_with("https://"))
+                || axum::http::HeaderValue::from_str(origin).is_err()
+            {
+                errors.push(format!("无效的跨域来源: {}", origin));
+            }
+        }
+
+        if !(self.site.url.starts_with("http://") || self.site.url.starts_with("https://")) {
+            errors.push(format!("site.url 必须以 http:// 或 https:// 开头，当前为 {}", self.site.url));
+        }
+
+        if self.media.upload_max_bytes == 0 {
+            errors.push("media.upload_max_bytes 必须大于 0".into());
+        }
+        if self.media.image_variant_widths.contains(&0) {
+            errors.push("media.image_variant_widths 中的宽度必须大于 0".into());
+        }
+        let storage = &self.media.storage;
+        if storage.backend == StorageKind::S3 {
+            for (name, value) in [
+                ("bucket", &storage.bucket),
+                ("access_key_id", &storage.access_key_id),
+                ("secret_access_key", &storage.secret_access_key),
+            ] {
+                if value.is_empty() {
+                    errors.push(format!("使用对象存储时必须设置 media.storage.{}", name));
+                }
+            }
+            if Endpoint::parse(&storage.endpoint()).is_none() {
+                errors.push(format!("无效的对象存储地址 media.storage.endpoint: {}", storage.endpoint()));
+            }
+            if storage.multipart_part_size < MIN_PART_SIZE {
+                errors.push(format!("media.storage.multipart_part_size 不能小于 {} 字节", MIN_PART_SIZE));
+            }
+        }
+
+        if self.metrics.listen.is_some_and(|addr| addr == self.server.listen) {
+            errors.push("metrics.listen 不能与 server.listen 相同".into());
+        }
+        if production && self.metrics.enabled && self.metrics.listen.is_none() && self.metrics.token.is_none() {
+            self.warnings.push("/metrics 在公开端口上且未设置访问令牌，建议设置 metrics.token 或 metrics.listen".into());
+        }
+
+        if let Some(endpoint) = &self.tracing.otlp_endpoint {
+            if !(endpoint.starts_with("http://") || endpoint.starts_with("https://")) {
+                errors.push(format!("tracing.otlp_endpoint 必须以 http:// 或 https:// 开头，当前为 {}", endpoint));
+            }
+        }
+        if !(0.0..=1.0).contains(&self.tracing.sample_ratio) {
+            errors.push(format!("tracing.sample_ratio 必须在 0 到 1 之间，当前为 {}", self.tracing.sample_ratio));
+        }
+
+        if let Err(e) = tracing_subscriber::EnvFilter::try_new(&self.log.level) {
+            errors.push(format!("无效的日志过滤规则 {}: {}", self.log.level, e));
+        }
+        if self.log.file_name.is_empty() || self.log.file_name.contains(['/', '\\']) {
+            errors.push(format!("log.file_name 必须是不含路径的文件名，当前为 {:?}", self.log.file_name));
+        }
+    }
+
+    /// 构建处理函数共享的应用配置（会读取自定义 robots.txt 文件）。
+    pub fn app_config(&self) -> Result<Config> {
+        let robots_txt = match &self.site.robots_txt_path {
+            Some(path) => Some(std::fs::read_to_string(path)
+                .map_err(|e| anyhow!("无法读取 robots.txt 文件 {}: {}", path.display(), e))?),
+            None => None,
+        };
+
+        Ok(Config {
+            jwt_secret: self.auth.jwt_secret.clone().unwrap_or_else(|| DEV_JWT_SECRET.into()),
+            bcrypt_cost: self.auth.bcrypt_cost,
+            token_ttl_hours: self.auth.token_ttl_hours,
+            site_url: self.site.url.trim_end_matches('/').to_string(),
+            site_title: self.site.title.clone(),
+            site_description: self.site.description.clone(),
+            robots_txt,
+            theme_dir: self.site.theme_dir.clone(),
+            static_dir: self.site.static_dir.clone(),
+            api_docs_bundle: self.site.api_docs_bundle.clone(),
+            upload_max_bytes: self.media.upload_max_bytes,
+            media_quota_bytes: self.media.quota_bytes,
+            image_variant_widths: self.media.image_variant_widths.clone(),
+            metrics_token: self.metrics.token.clone(),
+            trust_forwarded_for: self.server.trust_forwarded_for,
+        })
+    }
+}
+
+/// 读取配置文件内容。
+fn read_config_file(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| anyhow!("无法读取配置文件 {}: {}", path.display(), e))
+}
+
+/// 拆分逗号分隔的列表，忽略空白项。
+fn split_list(value: &str) -> Vec<String> {
+    value.split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect()
+}
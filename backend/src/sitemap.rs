@@ -0,0 +1,93 @@
+// src/sitemap.rs
+//! 站点地图与 robots.txt 生成。
+//! 按 sitemaps.org 协议输出 `<urlset>`，URL 数量超过单个文件上限时改为输出 `<sitemapindex>`。
+
+use chrono::NaiveDateTime;
+
+use crate::render::highlight::escape_html;
+
+/// 单个站点地图文件最多包含的 URL 数量（协议上限）。
+pub const SITEMAP_MAX_URLS: u64 = 50_000;
+
+/// 站点地图中的一个地址。
+#[derive(Debug, Clone)]
+pub struct SitemapUrl {
+    pub loc: String,
+    pub lastmod: Option<NaiveDateTime>,
+}
+
+/// 以 W3C Datetime 格式输出 `lastmod`（数据库时间均为 UTC）。
+fn format_lastmod(time: NaiveDateTime) -> String {
+    time.and_utc().format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// 输出 `<loc>` 和可选的 `<lastmod>`。
+fn push_entry(xml: &mut String, url: &SitemapUrl) {
+    xml.push_str("<loc>");
+    xml.push_str(&escape_html(&url.loc));
+    xml.push_str("</loc>");
+    if let Some(lastmod) = url.lastmod {
+        xml.push_str("<lastmod>");
+        xml.push_str(&format_lastmod(lastmod));
+        xml.push_str("</lastmod>");
+    }
+}
+
+/// 生成 `<urlset>` 站点地图。
+///
+/// # 参数
+/// * `urls` - 页面地址列表，调用方需保证不超过 `SITEMAP_MAX_URLS` 条。
+pub fn build_urlset(urls: &[SitemapUrl]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for url in urls {
+        xml.push_str("<url>");
+        push_entry(&mut xml, url);
+        xml.push_str("</url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// 生成 `<sitemapindex>` 站点地图索引。
+///
+/// # 参数
+/// * `sitemaps` - 子站点地图的地址列表。
+pub fn build_sitemap_index(sitemaps: &[SitemapUrl]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for sitemap in sitemaps {
+        xml.push_str("<sitemap>");
+        push_entry(&mut xml, sitemap);
+        xml.push_str("</sitemap>\n");
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
+
+/// 生成 robots.txt。
+///
+/// 未配置自定义内容时，默认允许抓取站点页面、禁止抓取 `/api/`；
+/// 无论是否自定义，都会确保包含指向站点地图的 `Sitemap:` 行。
+///
+/// # 参数
+/// * `custom` - 可选的自定义 robots.txt 内容。
+/// * `sitemap_url` - 站点地图的绝对地址。
+pub fn build_robots_txt(custom: Option<&str>, sitemap_url: &str) -> String {
+    let mut robots = match custom {
+        Some(custom) => custom.trim_end().to_string(),
+        None => "User-agent: *\nAllow: /\nDisallow: /api/".to_string(),
+    };
+
+    let has_sitemap = robots
+        .lines()
+        .any(|line| line.trim().to_ascii_lowercase().starts_with("sitemap:"));
+    if !has_sitemap {
+        robots.push_str("\n\nSitemap: ");
+        robots.push_str(sitemap_url);
+    }
+    robots.push('\n');
+    robots
+}
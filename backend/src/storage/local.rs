@@ -0,0 +1,113 @@
+// src/storage/local.rs
+//! 本地文件系统存储后端。
+
+use std::io::SeekFrom;
+use std::path::PathBuf;
+
+use axum::async_trait;
+use axum::body::Body;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+use super::{validate_key, ByteRange, StorageBackend};
+use crate::error::CustomError;
+
+/// 将文件保存在本地目录中，存储键即相对路径。
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// 创建一个新的 `LocalStorage`，目录在首次写入时自动创建。
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalStorage { root: root.into() }
+    }
+
+    /// 存储键对应的文件路径。
+    fn path_for(&self, key: &str) -> Result<PathBuf, CustomError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+
+    /// 打开文件，文件不存在时返回 `None`。
+    async fn open(&self, key: &str) -> Result<Option<tokio::fs::File>, CustomError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::File::open(&path).await {
+            Ok(file) => Ok(Some(file)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error("读取", key, e)),
+        }
+    }
+}
+
+/// 将 IO 错误转换为内部错误。
+fn io_error(action: &str, key: &str, e: std::io::Error) -> CustomError {
+    CustomError::InternalServerError(format!("{}文件 {} 失败: {}", action, key, e))
+}
+
+#[async_trait]
+impl StorageBackend for LocalStorage {
+    async fn put(&self, key: &str, data: &[u8], _content_type: &str) -> Result<(), CustomError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| io_error("创建目录", key, e))?;
+        }
+        // 先写临时文件再重命名，避免读取到写了一半的文件
+        let tmp_path = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&tmp_path, data).await.map_err(|e| io_error("写入", key, e))?;
+        tokio::fs::rename(&tmp_path, &path).await.map_err(|e| io_error("写入", key, e))?;
+        Ok(())
+    }
+
+    async fn size(&self, key: &str) -> Result<Option<u64>, CustomError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::metadata(&path).await {
+            Ok(metadata) if metadata.is_file() => Ok(Some(metadata.len())),
+            Ok(_) => Ok(None),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error("读取", key, e)),
+        }
+    }
+
+    async fn get(&self, key: &str, range: Option<ByteRange>) -> Result<Option<Vec<u8>>, CustomError> {
+        let Some(mut file) = self.open(key).await? else {
+            return Ok(None);
+        };
+
+        let mut data = Vec::new();
+        match range {
+            Some(range) => {
+                file.seek(SeekFrom::Start(range.start)).await.map_err(|e| io_error("读取", key, e))?;
+                file.take(range.byte_count()).read_to_end(&mut data).await.map_err(|e| io_error("读取", key, e))?;
+            }
+            None => {
+                file.read_to_end(&mut data).await.map_err(|e| io_error("读取", key, e))?;
+            }
+        }
+        Ok(Some(data))
+    }
+
+    async fn get_stream(&self, key: &str, range: Option<ByteRange>) -> Result<Option<Body>, CustomError> {
+        let Some(mut file) = self.open(key).await? else {
+            return Ok(None);
+        };
+        let body = match range {
+            Some(range) => {
+                file.seek(SeekFrom::Start(range.start)).await.map_err(|e| io_error("读取", key, e))?;
+                Body::from_stream(ReaderStream::new(file.take(range.byte_count())))
+            }
+            None => Body::from_stream(ReaderStream::new(file)),
+        };
+        Ok(Some(body))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CustomError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_error("删除", key, e)),
+        }
+    }
+}
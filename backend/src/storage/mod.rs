@@ -0,0 +1,127 @@
+// src/storage/mod.rs
+//! 媒体文件存储抽象。
+//! `StorageBackend` 屏蔽具体的存储介质（本地文件系统、对象存储等），按字符串键读写文件内容。
+
+pub mod local; // 本地文件系统实现
+pub mod s3;    // S3 兼容对象存储实现
+pub mod sigv4; // S3 请求签名
+
+use std::path::{Component, Path};
+
+use axum::async_trait;
+use axum::body::Body;
+
+use crate::error::CustomError;
+use crate::i18n::t_args;
+
+pub use local::LocalStorage;
+pub use s3::{S3Config, S3Storage};
+
+/// 闭区间字节范围 `[start, end]`，对应 HTTP `Range: bytes=start-end`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64, // 包含
+}
+
+impl ByteRange {
+    /// 范围内的字节数。
+    pub fn byte_count(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+/// 请求的范围超出文件大小，应返回 `416 Range Not Satisfiable`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeNotSatisfiable;
+
+/// 解析 `Range` 请求头。
+///
+/// 只支持单个 `bytes` 范围；无法识别的格式和多段范围按 RFC 9110 的建议忽略（返回完整内容）。
+///
+/// # 参数
+/// * `value` - `Range` 请求头的值。
+/// * `total` - 文件总大小。
+///
+/// # 返回
+/// `Ok(Some(range))` 表示返回部分内容，`Ok(None)` 表示忽略该请求头，`Err` 表示范围无法满足。
+pub fn parse_range_header(value: &str, total: u64) -> Result<Option<ByteRange>, RangeNotSatisfiable> {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return Ok(None);
+    };
+
+    let range = match (start.trim(), end.trim()) {
+        // bytes=-500：最后 500 个字节
+        ("", suffix) => {
+            let Ok(suffix) = suffix.parse::<u64>() else { return Ok(None) };
+            if suffix == 0 || total == 0 {
+                return Err(RangeNotSatisfiable);
+            }
+            ByteRange { start: total.saturating_sub(suffix), end: total - 1 }
+        }
+        // bytes=500- 或 bytes=500-999
+        (start, end) => {
+            let Ok(start) = start.parse::<u64>() else { return Ok(None) };
+            let end = if end.is_empty() {
+                total.saturating_sub(1)
+            } else {
+                let Ok(end) = end.parse::<u64>() else { return Ok(None) };
+                if end < start {
+                    return Ok(None);
+                }
+                end.min(total.saturating_sub(1))
+            };
+            if start >= total {
+                return Err(RangeNotSatisfiable);
+            }
+            ByteRange { start, end }
+        }
+    };
+    Ok(Some(range))
+}
+
+/// 校验存储键：只允许由普通路径段组成的相对路径，防止越出存储根目录。
+pub fn validate_key(key: &str) -> Result<(), CustomError> {
+    let valid = !key.is_empty()
+        && !key.contains('\\')
+        && Path::new(key).components().all(|c| matches!(c, Component::Normal(_)));
+    if valid {
+        Ok(())
+    } else {
+        Err(CustomError::BadRequest(t_args("storage-invalid-key", &[("key", &key)])))
+    }
+}
+
+/// 媒体文件存储后端。
+#[async_trait]
+pub trait StorageBackend: Send + Sync + std::fmt::Debug {
+    /// 写入文件，已存在时覆盖。
+    async fn put(&self, key: &str, data: &[u8], content_type: &str) -> Result<(), CustomError>;
+
+    /// 获取文件大小，文件不存在时返回 `None`。
+    async fn size(&self, key: &str) -> Result<Option<u64>, CustomError>;
+
+    /// 读取文件内容；指定 `range` 时只读取该范围。文件不存在时返回 `None`。
+    async fn get(&self, key: &str, range: Option<ByteRange>) -> Result<Option<Vec<u8>>, CustomError>;
+
+    /// 以流的形式读取文件内容，用于直接作为响应体返回，不把整个文件读入内存。
+    /// 默认实现读取完整内容后返回；文件不存在时返回 `None`。
+    async fn get_stream(&self, key: &str, range: Option<ByteRange>) -> Result<Option<Body>, CustomError> {
+        Ok(self.get(key, range).await?.map(Body::from))
+    }
+
+    /// 删除文件，文件不存在时视为成功。
+    async fn delete(&self, key: &str) -> Result<(), CustomError>;
+
+    /// 生成可由客户端直接下载文件的临时地址。
+    /// 不支持时返回 `None`，由应用读取文件内容后返回。
+    async fn presigned_url(&self, _key: &str) -> Result<Option<String>, CustomError> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,356 @@
+// src/storage/s3.rs
+//! S3 兼容对象存储后端（AWS S3、MinIO、Cloudflare R2 等）。
+//! 多个实例共享同一个存储桶，媒体文件不再依赖本地磁盘。
+
+use std::time::Duration;
+
+use axum::async_trait;
+use axum::body::{Body, Bytes};
+use chrono::Utc;
+use reqwest::{Method, Response, Url};
+use serde::Deserialize;
+
+use super::sigv4::{self, Credentials};
+use super::{validate_key, ByteRange, StorageBackend};
+use crate::error::CustomError;
+use crate::telemetry;
+
+/// S3 要求除最后一个分片外，每个分片至少 5 MiB。
+pub const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// 连接与读取超时。
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 解析后的服务地址，例如 `https://s3.us-east-1.amazonaws.com` 或 `http://[::1]:9000`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    pub https: bool,
+    pub host: String, // 不含端口，IPv6 地址带方括号
+    pub port: u16,
+}
+
+impl Endpoint {
+    /// 解析 `scheme://host[:port]` 形式的地址，忽略末尾的 `/`。
+    pub fn parse(url: &str) -> Option<Endpoint> {
+        let url = Url::parse(url).ok()?;
+        let https = match url.scheme() {
+            "https" => true,
+            "http" => false,
+            _ => return None,
+        };
+        if url.path() != "/" || url.query().is_some() || url.fragment().is_some() || !url.username().is_empty() {
+            return None;
+        }
+        Some(Endpoint { https, host: url.host_str()?.to_string(), port: url.port_or_known_default()? })
+    }
+
+    /// `Host` 请求头的值，非默认端口时带上端口号。
+    pub fn authority(&self) -> String {
+        let default_port = if self.https { 443 } else { 80 };
+        if self.port == default_port {
+            self.host.clone()
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+
+    /// 协议与主机部分，例如 `http://127.0.0.1:9000`。
+    pub fn origin(&self) -> String {
+        format!("{}://{}", if self.https { "https" } else { "http" }, self.authority())
+    }
+}
+
+/// S3 存储后端的配置。
+#[derive(Debug, Clone)]
+pub struct S3Config {
+    pub endpoint: String,             // 服务地址，例如 https://s3.us-east-1.amazonaws.com 或 http://127.0.0.1:9000
+    pub credentials: Credentials,     // 访问密钥与区域
+    pub bucket: String,
+    pub prefix: String,               // 对象键前缀，例如 "media/"
+    pub path_style: bool,             // 使用 `endpoint/bucket/key` 形式的地址 (MinIO 等通常需要)
+    pub presign_expires_secs: u64,    // 预签名 URL 的有效期，0 表示不使用预签名 URL
+    pub multipart_threshold: usize,   // 超过该大小的文件使用分片上传
+    pub multipart_part_size: usize,   // 分片大小
+}
+
+/// 将文件保存在 S3 兼容的对象存储中。
+#[derive(Debug, Clone)]
+pub struct S3Storage {
+    config: S3Config,
+    endpoint: Endpoint, // 实际请求的地址 (虚拟主机风格时包含存储桶名)
+    base_path: String,  // 路径风格时为 "/bucket"，否则为空
+    client: reqwest::Client,
+}
+
+impl S3Storage {
+    /// 根据配置创建 S3 存储后端。
+    ///
+    /// # 返回
+    /// 地址无法解析、存储桶为空或 HTTP 客户端初始化失败时返回 `InternalServerError`。
+    pub fn new(config: S3Config) -> Result<Self, CustomError> {
+        let mut endpoint = Endpoint::parse(&config.endpoint).ok_or_else(|| {
+            CustomError::InternalServerError(format!("无效的对象存储地址: {}", config.endpoint))
+        })?;
+        if config.bucket.is_empty() {
+            return Err(CustomError::InternalServerError("未配置对象存储桶".to_string()));
+        }
+        let base_path = if config.path_style {
+            format!("/{}", sigv4::uri_encode(&config.bucket, true))
+        } else {
+            endpoint.host = format!("{}.{}", config.bucket, endpoint.host);
+            String::new()
+        };
+        let client = reqwest::Client::builder()
+            .connect_timeout(TIMEOUT)
+            .read_timeout(TIMEOUT)
+            .build()
+            .map_err(|e| CustomError::InternalServerError(format!("初始化对象存储客户端失败: {}", e)))?;
+        Ok(S3Storage { config, endpoint, base_path, client })
+    }
+
+    /// 对象的请求路径（已编码）。
+    pub fn object_path(&self, key: &str) -> String {
+        format!("{}/{}", self.base_path, sigv4::uri_encode(&format!("{}{}", self.config.prefix, key), false))
+    }
+
+    /// 对象的完整地址（不含签名）。
+    pub fn object_url(&self, key: &str) -> String {
+        format!("{}{}", self.endpoint.origin(), self.object_path(key))
+    }
+
+    /// 发送一个签名请求。
+    async fn request(
+        &self,
+        method: Method,
+        key: &str,
+        query: Vec<(String, String)>,
+        extra_headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    ) -> Result<Response, CustomError> {
+        validate_key(key)?;
+        let path = self.object_path(key);
+        let time = Utc::now();
+        let payload_hash = sigv4::sha256_hex(&body);
+
+        let mut headers = vec![
+            ("host".to_string(), self.endpoint.authority()),
+            ("x-amz-content-sha256".to_string(), payload_hash.clone()),
+            ("x-amz-date".to_string(), time.format("%Y%m%dT%H%M%SZ").to_string()),
+        ];
+        headers.extend(extra_headers);
+        let authorization = sigv4::authorization(
+            &self.config.credentials, time, method.as_str(), &path, &query, &headers, &payload_hash,
+        );
+        headers.push(("authorization".to_string(), authorization));
+        // traceparent 不参与签名，只用于把对象存储请求关联到当前追踪
+        headers.extend(telemetry::trace_headers());
+
+        let mut url = format!("{}{}", self.endpoint.origin(), path);
+        if !query.is_empty() {
+            url = format!("{}?{}", url, sigv4::canonical_query(&query));
+        }
+        let mut request = self.client.request(method.clone(), url).body(body);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        request
+            .send()
+            .await
+            .map_err(|e| CustomError::InternalServerError(format!("对象存储请求失败 ({} {}): {}", method, key, e)))
+    }
+
+    /// 普通上传。
+    async fn put_single(&self, key: &str, data: &[u8], content_type: &str) -> Result<(), CustomError> {
+        let headers = vec![("content-type".to_string(), content_type.to_string())];
+        let response = self.request(Method::PUT, key, Vec::new(), headers, data.to_vec()).await?;
+        ensure_success(response, "上传", key).await?;
+        Ok(())
+    }
+
+    /// 分片上传：初始化、逐个上传分片、合并；任一步失败时中止上传，避免残留未完成的分片。
+    async fn put_multipart(&self, key: &str, data: &[u8], content_type: &str) -> Result<(), CustomError> {
+        let headers = vec![("content-type".to_string(), content_type.to_string())];
+        let response = self.request(Method::POST, key, vec![query("uploads", "")], headers, Vec::new()).await?;
+        let body = read_body(ensure_success(response, "初始化分片上传", key).await?, "初始化分片上传", key).await?;
+        let upload_id = parse_xml::<InitiateMultipartUploadResult>(&body)
+            .map(|result| result.upload_id)
+            .ok_or_else(|| {
+                CustomError::InternalServerError(format!("初始化分片上传 {} 失败: 响应中缺少 UploadId", key))
+            })?;
+
+        let result = self.upload_parts(key, &upload_id, data).await;
+        if result.is_err() {
+            let abort = self.request(Method::DELETE, key, vec![query("uploadId", &upload_id)], Vec::new(), Vec::new()).await;
+            if let Err(e) = abort {
+                tracing::warn!("中止分片上传 {} 失败: {}", key, e);
+            }
+        }
+        result
+    }
+
+    async fn upload_parts(&self, key: &str, upload_id: &str, data: &[u8]) -> Result<(), CustomError> {
+        let mut complete = String::from("<CompleteMultipartUpload>");
+        for (index, part) in data.chunks(self.config.multipart_part_size.max(1)).enumerate() {
+            let part_number = (index + 1).to_string();
+            let params = vec![query("partNumber", &part_number), query("uploadId", upload_id)];
+            let response = self.request(Method::PUT, key, params, Vec::new(), part.to_vec()).await?;
+            let response = ensure_success(response, "上传分片", key).await?;
+            let etag = response.headers().get("etag").and_then(|v| v.to_str().ok()).ok_or_else(|| {
+                CustomError::InternalServerError(format!("上传分片 {} 失败: 响应中缺少 ETag", key))
+            })?;
+            complete.push_str(&format!(
+                "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+                part_number,
+                crate::render::highlight::escape_html(etag)
+            ));
+        }
+        complete.push_str("</CompleteMultipartUpload>");
+
+        let headers = vec![("content-type".to_string(), "application/xml".to_string())];
+        let params = vec![query("uploadId", upload_id)];
+        let response = self.request(Method::POST, key, params, headers, complete.into_bytes()).await?;
+        let status = response.status();
+        let body = read_body(ensure_success(response, "合并分片", key).await?, "合并分片", key).await?;
+        // 合并失败时 S3 可能返回 200 并在响应体中给出错误
+        if let Some(CompleteMultipartUploadResponse::Error(error)) = parse_xml(&body) {
+            return Err(storage_error(status, &error, "合并分片", key));
+        }
+        Ok(())
+    }
+
+    /// 按 `range` 读取对象，对象不存在时返回 `None`。
+    async fn get_object(&self, key: &str, range: Option<ByteRange>) -> Result<Option<Response>, CustomError> {
+        let headers = match range {
+            Some(range) => vec![("range".to_string(), format!("bytes={}-{}", range.start, range.end))],
+            None => Vec::new(),
+        };
+        let response = self.request(Method::GET, key, Vec::new(), headers, Vec::new()).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        ensure_success(response, "读取", key).await.map(Some)
+    }
+}
+
+fn query(name: &str, value: &str) -> (String, String) {
+    (name.to_string(), value.to_string())
+}
+
+/// 初始化分片上传的响应。
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InitiateMultipartUploadResult {
+    upload_id: String,
+}
+
+/// 合并分片的响应，按根元素区分成功与失败。
+#[derive(Debug, Deserialize)]
+enum CompleteMultipartUploadResponse {
+    CompleteMultipartUploadResult(serde::de::IgnoredAny),
+    Error(ErrorResponse),
+}
+
+/// S3 的错误响应 (`<Error><Code>…</Code><Message>…</Message></Error>`)。
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ErrorResponse {
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    message: String,
+}
+
+/// 解析 XML 响应体，格式不符时返回 `None`。
+fn parse_xml<T: serde::de::DeserializeOwned>(body: &[u8]) -> Option<T> {
+    quick_xml::de::from_str(std::str::from_utf8(body).ok()?).ok()
+}
+
+/// 读取完整的响应体。
+async fn read_body(response: Response, action: &str, key: &str) -> Result<Bytes, CustomError> {
+    response
+        .bytes()
+        .await
+        .map_err(|e| CustomError::InternalServerError(format!("{}文件 {} 失败: 读取响应失败: {}", action, key, e)))
+}
+
+fn storage_error(status: reqwest::StatusCode, error: &ErrorResponse, action: &str, key: &str) -> CustomError {
+    CustomError::InternalServerError(format!(
+        "{}文件 {} 失败: HTTP {} {} {}",
+        action, key, status.as_u16(), error.code, error.message
+    ))
+}
+
+/// 检查响应状态，失败时读取响应体中的错误信息。
+async fn ensure_success(response: Response, action: &str, key: &str) -> Result<Response, CustomError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.bytes().await.unwrap_or_default();
+    let error = parse_xml::<ErrorResponse>(&body).unwrap_or_default();
+    Err(storage_error(status, &error, action, key))
+}
+
+#[async_trait]
+impl StorageBackend for S3Storage {
+    async fn put(&self, key: &str, data: &[u8], content_type: &str) -> Result<(), CustomError> {
+        if data.len() > self.config.multipart_threshold {
+            self.put_multipart(key, data, content_type).await
+        } else {
+            self.put_single(key, data, content_type).await
+        }
+    }
+
+    async fn size(&self, key: &str) -> Result<Option<u64>, CustomError> {
+        let response = self.request(Method::HEAD, key, Vec::new(), Vec::new(), Vec::new()).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = ensure_success(response, "读取", key).await?;
+        let size = response
+            .headers()
+            .get("content-length")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| {
+                CustomError::InternalServerError(format!("读取文件 {} 失败: 响应中缺少 Content-Length", key))
+            })?;
+        Ok(Some(size))
+    }
+
+    async fn get(&self, key: &str, range: Option<ByteRange>) -> Result<Option<Vec<u8>>, CustomError> {
+        match self.get_object(key, range).await? {
+            Some(response) => Ok(Some(read_body(response, "读取", key).await?.to_vec())),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_stream(&self, key: &str, range: Option<ByteRange>) -> Result<Option<Body>, CustomError> {
+        Ok(self.get_object(key, range).await?.map(|response| Body::from_stream(response.bytes_stream())))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CustomError> {
+        let response = self.request(Method::DELETE, key, Vec::new(), Vec::new(), Vec::new()).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        ensure_success(response, "删除", key).await?;
+        Ok(())
+    }
+
+    async fn presigned_url(&self, key: &str) -> Result<Option<String>, CustomError> {
+        if self.config.presign_expires_secs == 0 {
+            return Ok(None);
+        }
+        validate_key(key)?;
+        let path = self.object_path(key);
+        let query = sigv4::presigned_query(
+            &self.config.credentials,
+            Utc::now(),
+            &self.endpoint.authority(),
+            &path,
+            self.config.presign_expires_secs,
+        );
+        Ok(Some(format!("{}{}?{}", self.endpoint.origin(), path, query)))
+    }
+}
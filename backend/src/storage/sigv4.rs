@@ -0,0 +1,174 @@
+// src/storage/sigv4.rs
+//! AWS Signature Version 4 签名（仅实现 S3 需要的部分）。
+//! 支持请求头签名（`Authorization`）和预签名 URL（查询参数签名）。
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use sha2::{Digest, Sha256};
+
+/// 空请求体的 SHA-256。
+pub const EMPTY_PAYLOAD_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+/// 预签名 URL 不对请求体签名。
+pub const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// URI 编码时保留的字符：字母数字以及 `-._~`。
+const URI_UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+/// 签名所需的凭证和作用域。
+#[derive(Clone)]
+pub struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub region: String,
+}
+
+impl std::fmt::Debug for Credentials {
+    // 不输出密钥
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("region", &self.region)
+            .finish_non_exhaustive()
+    }
+}
+
+/// 按 SigV4 规则进行 URI 编码；`encode_slash` 为 false 时保留路径中的 `/`。
+pub fn uri_encode(value: &str, encode_slash: bool) -> String {
+    if encode_slash {
+        utf8_percent_encode(value, URI_UNRESERVED).to_string()
+    } else {
+        value.split('/').map(|segment| uri_encode(segment, true)).collect::<Vec<_>>().join("/")
+    }
+}
+
+/// 十六进制编码的 SHA-256。
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC 接受任意长度的密钥");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// 规范化查询字符串：参数名和值分别编码后按字典序排列。
+pub fn canonical_query(params: &[(String, String)]) -> String {
+    let mut encoded: Vec<(String, String)> = params
+        .iter()
+        .map(|(k, v)| (uri_encode(k, true), uri_encode(v, true)))
+        .collect();
+    encoded.sort();
+    encoded.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join("&")
+}
+
+/// 计算签名。
+///
+/// # 参数
+/// * `credentials` - 凭证。
+/// * `time` - 请求时间。
+/// * `method` - HTTP 方法。
+/// * `path` - 已编码的请求路径。
+/// * `query` - 查询参数（未编码）。
+/// * `headers` - 参与签名的请求头（名称需为小写）。
+/// * `payload_hash` - 请求体的 SHA-256，或 `UNSIGNED-PAYLOAD`。
+///
+/// # 返回
+/// `(签名, 参与签名的请求头列表)`。
+pub fn signature(
+    credentials: &Credentials,
+    time: DateTime<Utc>,
+    method: &str,
+    path: &str,
+    query: &[(String, String)],
+    headers: &[(String, String)],
+    payload_hash: &str,
+) -> (String, String) {
+    let mut headers: Vec<(String, String)> = headers
+        .iter()
+        .map(|(k, v)| (k.to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+    headers.sort();
+    let canonical_headers: String = headers.iter().map(|(k, v)| format!("{}:{}\n", k, v)).collect();
+    let signed_headers = headers.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>().join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method,
+        path,
+        canonical_query(query),
+        canonical_headers,
+        signed_headers,
+        payload_hash
+    );
+
+    let date = time.format("%Y%m%d").to_string();
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        time.format("%Y%m%dT%H%M%SZ"),
+        scope(credentials, time),
+        sha256_hex(canonical_request.as_bytes())
+    );
+
+    let key = hmac_sha256(format!("AWS4{}", credentials.secret_access_key).as_bytes(), &date);
+    let key = hmac_sha256(&key, &credentials.region);
+    let key = hmac_sha256(&key, "s3");
+    let key = hmac_sha256(&key, "aws4_request");
+    (hex(&hmac_sha256(&key, &string_to_sign)), signed_headers)
+}
+
+/// 凭证作用域，例如 `20130524/us-east-1/s3/aws4_request`。
+fn scope(credentials: &Credentials, time: DateTime<Utc>) -> String {
+    format!("{}/{}/s3/aws4_request", time.format("%Y%m%d"), credentials.region)
+}
+
+/// 生成 `Authorization` 请求头的值。
+pub fn authorization(
+    credentials: &Credentials,
+    time: DateTime<Utc>,
+    method: &str,
+    path: &str,
+    query: &[(String, String)],
+    headers: &[(String, String)],
+    payload_hash: &str,
+) -> String {
+    let (signature, signed_headers) = signature(credentials, time, method, path, query, headers, payload_hash);
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id,
+        scope(credentials, time),
+        signed_headers,
+        signature
+    )
+}
+
+/// 生成预签名 GET 请求的查询字符串（已编码，包含签名）。
+///
+/// # 参数
+/// * `host` - `Host` 请求头的值。
+/// * `path` - 已编码的请求路径。
+/// * `expires_secs` - 有效期（秒）。
+pub fn presigned_query(
+    credentials: &Credentials,
+    time: DateTime<Utc>,
+    host: &str,
+    path: &str,
+    expires_secs: u64,
+) -> String {
+    let mut query = vec![
+        ("X-Amz-Algorithm".to_string(), "AWS4-HMAC-SHA256".to_string()),
+        ("X-Amz-Credential".to_string(), format!("{}/{}", credentials.access_key_id, scope(credentials, time))),
+        ("X-Amz-Date".to_string(), time.format("%Y%m%dT%H%M%SZ").to_string()),
+        ("X-Amz-Expires".to_string(), expires_secs.to_string()),
+        ("X-Amz-SignedHeaders".to_string(), "host".to_string()),
+    ];
+    let headers = [("host".to_string(), host.to_string())];
+    let (signature, _) = signature(credentials, time, "GET", path, &query, &headers, UNSIGNED_PAYLOAD);
+    query.push(("X-Amz-Signature".to_string(), signature));
+    canonical_query(&query)
+}
@@ -0,0 +1,113 @@
+// src/telemetry.rs
+//! 分布式追踪：请求 ID、W3C `traceparent` 传播与 OpenTelemetry (OTLP) 导出。
+//!
+//! 每个请求有一个根 span (`http_request`)，携带 `request_id` 和 `trace_id` 字段，
+//! 处理函数与 Repository 中的 span 和日志都挂在它下面。请求带有 `traceparent` 头时，
+//! 根 span 成为上游调用方的子 span；配置了 `tracing.otlp_endpoint` 时 span 通过 OTLP/HTTP 导出。
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use anyhow::Result;
+use axum::http::{HeaderMap, Request};
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::trace::{TraceContextExt, TraceId};
+use opentelemetry::Context;
+use opentelemetry_http::HeaderExtractor;
+use opentelemetry_otlp::{Protocol, WithExportConfig};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::settings::TracingSettings;
+
+/// 请求 ID 使用的 HTTP 头。
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    /// 当前请求的 ID，错误响应体从这里读取。
+    static REQUEST_ID: String;
+}
+
+/// 在给定请求 ID 的上下文中运行 `future`。
+pub async fn with_request_id<F: Future>(request_id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// 当前请求的 ID；不在请求上下文中（例如后台任务、命令行工具）时为 `None`。
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// 创建 OpenTelemetry 的 `TracerProvider`。
+///
+/// 未配置 `otlp_endpoint` 时不导出任何数据，但仍会为每个请求生成（或沿用上游的）trace ID，
+/// 这样日志中的 `trace_id` 与下游请求中的 `traceparent` 始终可用。
+///
+/// # 参数
+/// * `settings` - `[tracing]` 配置，`otlp_endpoint` 为收集器的根地址（span 发送到 `<地址>/v1/traces`）。
+pub fn init_tracer_provider(settings: &TracingSettings) -> Result<SdkTracerProvider> {
+    let mut builder = SdkTracerProvider::builder()
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(settings.sample_ratio))))
+        .with_resource(Resource::builder().with_service_name(settings.service_name.clone()).build());
+
+    if let Some(endpoint) = &settings.otlp_endpoint {
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_protocol(Protocol::HttpBinary)
+            .with_endpoint(traces_endpoint(endpoint))
+            .build()
+            .map_err(|e| anyhow::anyhow!("无法创建 OTLP 导出器: {}", e))?;
+        builder = builder.with_batch_exporter(exporter);
+    }
+    Ok(builder.build())
+}
+
+/// 与 `OTEL_EXPORTER_OTLP_ENDPOINT` 的约定一致：根地址后追加 `/v1/traces`。
+fn traces_endpoint(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/traces", endpoint)
+    }
+}
+
+/// 从请求头中读取上游的 W3C Trace Context (`traceparent` / `tracestate`)。
+pub fn extract_context(headers: &HeaderMap) -> Context {
+    TraceContextPropagator::new().extract(&HeaderExtractor(headers))
+}
+
+/// 当前 span 的 Trace Context，作为发往下游服务（例如对象存储）的请求头。
+pub fn trace_headers() -> Vec<(String, String)> {
+    let mut headers = HashMap::new();
+    TraceContextPropagator::new().inject_context(&Span::current().context(), &mut headers);
+    headers.into_iter().collect()
+}
+
+/// 为请求创建根 span，供 `TraceLayer::make_span_with` 使用。
+/// 需要放在生成请求 ID 的中间件之内，此时 `X-Request-Id` 头一定存在。
+pub fn request_span<B>(request: &Request<B>) -> Span {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let span = tracing::info_span!(
+        "http_request",
+        method = %request.method(),
+        path = %request.uri().path(),
+        request_id = %request_id,
+        trace_id = tracing::field::Empty,
+    );
+
+    // 未安装 OpenTelemetry 层时设置父上下文没有效果，忽略错误即可
+    let _ = span.set_parent(extract_context(request.headers()));
+    let trace_id = span.context().span().span_context().trace_id();
+    if trace_id != TraceId::INVALID {
+        span.record("trace_id", tracing::field::display(trace_id));
+    }
+    span
+}
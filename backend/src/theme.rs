@@ -0,0 +1,80 @@
+// src/theme.rs
+//! 公开页面的模板主题。
+//! 内置默认主题编译进二进制；配置了主题目录时，目录中的同名模板会覆盖内置模板。
+
+use std::path::Path;
+
+use minijinja::{escape_formatter, AutoEscape, Environment, Error, ErrorKind};
+use serde::Serialize;
+
+use crate::error::CustomError;
+use crate::render::highlight::escape_html;
+
+/// 主题包含的模板及其内置的默认实现。
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    ("base.html", include_str!("../themes/default/base.html")),
+    ("post.html", include_str!("../themes/default/post.html")),
+    ("list.html", include_str!("../themes/default/list.html")),
+    ("not_found.html", include_str!("../themes/default/not_found.html")),
+];
+
+/// 已加载的模板主题。
+/// 模板在启动时全部加载并校验，渲染时不再访问文件系统。
+#[derive(Debug)]
+pub struct Theme {
+    env: Environment<'static>,
+}
+
+impl Theme {
+    /// 加载主题。
+    ///
+    /// # 参数
+    /// * `theme_dir` - 可选的主题目录，其中存在的模板文件会覆盖内置模板。
+    ///
+    /// # 返回
+    /// 包含 `Theme` 或 `CustomError` 的 `Result`；模板读取或语法错误时返回错误。
+    pub fn load(theme_dir: Option<&Path>) -> Result<Self, CustomError> {
+        let mut env = Environment::new();
+        // 默认的 HTML 转义会把 `/` 写成 `&#x2f;`，链接和 meta 标签难以阅读；
+        // 这里对字符串只转义必要的字符，其余类型交给默认格式化。
+        env.set_formatter(|out, state, value| match value.as_str() {
+            Some(text) if !value.is_safe() && matches!(state.auto_escape(), AutoEscape::Html) => out
+                .write_str(&escape_html(text))
+                .map_err(|_| Error::new(ErrorKind::WriteFailure, "写入模板输出失败")),
+            _ => escape_formatter(out, state, value),
+        });
+
+        for (name, builtin) in BUILTIN_TEMPLATES {
+            let source = match theme_dir.map(|dir| dir.join(name)) {
+                Some(path) if path.is_file() => std::fs::read_to_string(&path).map_err(|e| {
+                    CustomError::InternalServerError(format!("无法读取模板 {}: {}", path.display(), e))
+                })?,
+                _ => builtin.to_string(),
+            };
+            env.add_template_owned(name.to_string(), source).map_err(|e| {
+                CustomError::InternalServerError(format!("模板 {} 解析失败: {}", name, e))
+            })?;
+        }
+
+        Ok(Theme { env })
+    }
+
+    /// 渲染指定模板。
+    ///
+    /// # 参数
+    /// * `name` - 模板名，例如 `post.html`。
+    /// * `context` - 模板上下文。
+    pub fn render<S: Serialize>(&self, name: &str, context: S) -> Result<String, CustomError> {
+        self.env
+            .get_template(name)
+            .and_then(|template| template.render(context))
+            .map_err(|e| CustomError::InternalServerError(format!("模板 {} 渲染失败: {}", name, e)))
+    }
+}
+
+impl Default for Theme {
+    /// 仅使用内置模板的主题。
+    fn default() -> Self {
+        Theme::load(None).expect("内置模板必须能够正确解析")
+    }
+}
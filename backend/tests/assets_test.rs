@@ -0,0 +1,99 @@
+//! 前端静态资源测试用例
+//! 测试路径清洗、缓存策略、预压缩协商以及 SPA 回退
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use axum_blog_engine::assets::{
+        accepted_encodings, cache_control_for, is_hashed_asset, sanitize_path, ContentEncoding,
+        StaticAssets,
+    };
+
+    /// 创建一个临时的前端构建目录。
+    fn setup_static_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("blog-assets-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(dir.join("assets")).unwrap();
+        std::fs::write(dir.join("index.html"), "<div id=\"app\"></div>").unwrap();
+        std::fs::write(dir.join("assets/index-B3xk9_aZ.js"), "console.log(1)").unwrap();
+        std::fs::write(dir.join("assets/index-B3xk9_aZ.js.br"), "br-bytes").unwrap();
+        std::fs::write(dir.join("assets/index-B3xk9_aZ.js.gz"), "gz-bytes").unwrap();
+        std::fs::write(dir.join("favicon.ico"), "icon").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_sanitize_path() {
+        assert_eq!(sanitize_path("/").as_deref(), Some("index.html"));
+        assert_eq!(sanitize_path("/assets/app.js").as_deref(), Some("assets/app.js"));
+        assert_eq!(sanitize_path("/docs/").as_deref(), Some("docs/index.html"));
+        assert_eq!(sanitize_path("/%E4%B8%AD.png").as_deref(), Some("中.png"));
+        assert!(sanitize_path("/../secret").is_none());
+        assert!(sanitize_path("/assets/%2e%2e/%2e%2e/etc/passwd").is_none());
+        assert!(sanitize_path("/a\\b").is_none());
+    }
+
+    #[test]
+    fn test_cache_control() {
+        assert!(is_hashed_asset("assets/index-B3xk9_aZ.js"));
+        assert!(is_hashed_asset("assets/logo.4f2a9c1d.svg"));
+        assert!(!is_hashed_asset("my-long-filename.png"));
+        assert!(!is_hashed_asset("assets/app.js"));
+
+        assert_eq!(cache_control_for("assets/index-B3xk9_aZ.js"), "public, max-age=31536000, immutable");
+        assert_eq!(cache_control_for("index.html"), "no-cache");
+        assert_eq!(cache_control_for("favicon.ico"), "public, max-age=3600");
+    }
+
+    #[test]
+    fn test_accepted_encodings() {
+        assert_eq!(
+            accepted_encodings(Some("gzip, deflate, br")),
+            vec![ContentEncoding::Brotli, ContentEncoding::Gzip]
+        );
+        assert_eq!(accepted_encodings(Some("gzip, br;q=0")), vec![ContentEncoding::Gzip]);
+        assert!(accepted_encodings(Some("identity")).is_empty());
+        assert!(accepted_encodings(None).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_precompressed_variants() {
+        let dir = setup_static_dir("encoding");
+        let assets = StaticAssets::from_config(Some(&dir)).unwrap();
+
+        let asset = assets.resolve("/assets/index-B3xk9_aZ.js", Some("gzip, br")).await.unwrap();
+        assert_eq!(asset.content_encoding, Some(ContentEncoding::Brotli));
+        assert_eq!(&*asset.body, b"br-bytes");
+        assert_eq!(asset.content_type, "text/javascript");
+
+        let asset = assets.resolve("/assets/index-B3xk9_aZ.js", Some("gzip")).await.unwrap();
+        assert_eq!(asset.content_encoding, Some(ContentEncoding::Gzip));
+
+        let asset = assets.resolve("/assets/index-B3xk9_aZ.js", None).await.unwrap();
+        assert_eq!(asset.content_encoding, None);
+        assert_eq!(&*asset.body, b"console.log(1)");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_resolve_spa_fallback() {
+        let dir = setup_static_dir("fallback");
+        let assets = StaticAssets::from_config(Some(&dir)).unwrap();
+
+        // 前端路由回退到 index.html
+        let asset = assets.resolve("/drafts/edit", None).await.unwrap();
+        assert_eq!(&*asset.body, b"<div id=\"app\"></div>");
+        assert_eq!(asset.cache_control, "no-cache");
+        assert_eq!(asset.content_type, "text/html");
+
+        // 缺失的带扩展名文件不回退
+        assert!(assets.resolve("/assets/missing.js", None).await.is_none());
+        // 越界路径被拒绝
+        assert!(assets.resolve("/../Cargo.toml", None).await.is_none());
+
+        let asset = assets.resolve("/favicon.ico", None).await.unwrap();
+        assert_eq!(asset.cache_control, "public, max-age=3600");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,354 @@
+//! 审计日志测试用例
+//! 测试字段差异与敏感字段隐藏、审计记录的过滤与分页、CSV 转义，
+//! 以及管理员操作和登录事件经由路由写入审计日志（含客户端 IP 与请求 ID）
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use std::sync::Arc;
+
+    use axum::body::{to_bytes, Body};
+    use axum::http::{header, Request, StatusCode};
+    use axum::Router;
+    use chrono::{Duration, Utc};
+    use sea_orm::DatabaseConnection;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+    use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+
+    use axum_blog_engine::audit::{self, diff, AuditEvent};
+    use axum_blog_engine::database::audit::{AuditFilter, AuditRepository, Model as AuditModel};
+    use axum_blog_engine::database::user::Model as UserModel;
+    use axum_blog_engine::handler::audit::{audit_routes, csv_row, CSV_BATCH_SIZE};
+    use axum_blog_engine::handler::request_id::scope_request_id;
+    use axum_blog_engine::handler::user::{generate_jwt, user_routes};
+    use axum_blog_engine::logging::REDACTED;
+    use axum_blog_engine::{AppState, Config};
+
+    fn user(role: &str, password_hash: &str) -> UserModel {
+        let time = Utc::now().naive_utc();
+        UserModel {
+            id: 2,
+            username: "bob".to_string(),
+            email: "bob@example.com".to_string(),
+            password_hash: password_hash.to_string(),
+            role: role.to_string(),
+            display_name: None,
+            avatar_url: None,
+            created_at: time,
+            updated_at: time,
+        }
+    }
+
+    /// 与 server.rs 相同的请求 ID 中间件，挂载用户和审计日志路由；
+    /// 开启 `trust_forwarded_for`，以便通过请求头指定客户端 IP
+    fn app(db: DatabaseConnection) -> (Router, AppState) {
+        let config = Config { trust_forwarded_for: true, ..common::test_config() };
+        let state = AppState { config: Arc::new(config), ..common::test_app_state(db) };
+        let router = Router::new()
+            .nest("/api", Router::new()
+                .nest("/user", user_routes().into_router())
+                .nest("/admin", audit_routes().into_router()))
+            .layer(axum::middleware::from_fn(scope_request_id))
+            .layer(PropagateRequestIdLayer::x_request_id())
+            .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+            .with_state(state.clone());
+        (router, state)
+    }
+
+    fn token(user_id: i32, role: &str) -> String {
+        let exp = (Utc::now() + Duration::hours(1)).timestamp() as usize;
+        generate_jwt(&user_id.to_string(), "admin", role, exp, "test_secret").unwrap()
+    }
+
+    fn request(method: &str, uri: &str, token: Option<&str>, body: Option<Value>) -> Request<Body> {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("x-forwarded-for", "203.0.113.9, 198.51.100.7")
+            .header("x-request-id", "req-audit-1");
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+            None => builder.body(Body::empty()).unwrap(),
+        }
+    }
+
+    async fn body_text(response: axum::response::Response) -> String {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    /// 符合条件的全部审计记录（按时间倒序）
+    async fn matching_entries(repo: &AuditRepository, filter: &AuditFilter) -> Vec<AuditModel> {
+        repo.get_paginated_entries(filter, 1, 10_000).await.unwrap().0
+    }
+
+    async fn all_entries(repo: &AuditRepository) -> Vec<AuditModel> {
+        matching_entries(repo, &AuditFilter::default()).await
+    }
+
+    #[test]
+    fn test_diff_reports_changed_fields_only() {
+        let before = user("user", "$2b$04$old");
+        let after = UserModel { role: "admin".to_string(), password_hash: "$2b$04$new".to_string(), ..before.clone() };
+
+        let changes = diff(Some(&before), Some(&after));
+        assert_eq!(changes, json!({
+            "password_hash": {"before": REDACTED, "after": REDACTED},
+            "role": {"before": "user", "after": "admin"},
+        }));
+    }
+
+    #[test]
+    fn test_diff_snapshot_on_create_and_delete() {
+        let model = user("user", "$2b$04$hash");
+        let deleted = diff(Some(&model), None);
+        assert_eq!(deleted["username"], json!({"before": "bob", "after": null}));
+        assert_eq!(deleted["password_hash"], json!({"before": REDACTED, "after": null}));
+
+        let created = diff(None, Some(&model));
+        assert_eq!(created["role"], json!({"before": null, "after": "user"}));
+    }
+
+    #[test]
+    fn test_event_without_changes() {
+        let model = user("user", "hash");
+        let event = AuditEvent::new(audit::USER_UPDATE).changes(diff(Some(&model), Some(&model)));
+        assert!(event.changes.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_filter_and_paginate() {
+        let repo = AuditRepository::new(common::setup_test_db().await);
+        repo.record(AuditEvent::new(audit::AUTH_LOGIN).by_user(Some(1), "alice"), None, None).await.unwrap();
+        repo.record(AuditEvent::new(audit::USER_DELETE).by_user(Some(1), "alice").target("user", 2), None, None)
+            .await
+            .unwrap();
+        repo.record(AuditEvent::new(audit::AUTH_LOGIN).by_user(Some(3), "carol"), None, None).await.unwrap();
+
+        let logins = AuditFilter { action: Some(audit::AUTH_LOGIN.to_string()), ..AuditFilter::default() };
+        assert_eq!(matching_entries(&repo, &logins).await.len(), 2);
+
+        let by_alice = AuditFilter { actor_id: Some(1), ..AuditFilter::default() };
+        let (entries, total_pages, _, total) = repo.get_paginated_entries(&by_alice, 1, 1).await.unwrap();
+        assert_eq!((total_pages, total), (2, 2));
+        assert_eq!(entries[0].action, audit::USER_DELETE); // 最新的在前
+        assert_eq!(entries[0].target_id.as_deref(), Some("2"));
+
+        let now = Utc::now().naive_utc();
+        let future = AuditFilter { from: Some(now + Duration::hours(1)), ..AuditFilter::default() };
+        assert!(matching_entries(&repo, &future).await.is_empty());
+        let past = AuditFilter { to: Some(now - Duration::hours(1)), ..AuditFilter::default() };
+        assert!(matching_entries(&repo, &past).await.is_empty());
+        let window = AuditFilter {
+            from: Some(now - Duration::hours(1)),
+            to: Some(now + Duration::hours(1)),
+            ..AuditFilter::default()
+        };
+        assert_eq!(matching_entries(&repo, &window).await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_entries_after_cursor() {
+        let repo = AuditRepository::new(common::setup_test_db().await);
+        for _ in 0..5 {
+            repo.record(AuditEvent::new(audit::AUTH_LOGIN).by_user(Some(1), "alice"), None, None).await.unwrap();
+        }
+        // 同一秒内的记录同样按 (created_at, id) 游标分批，不重复也不遗漏
+        let filter = AuditFilter::default();
+        let mut ids = Vec::new();
+        let mut after = None;
+        loop {
+            let batch = repo.get_entries_after(&filter, after, 2).await.unwrap();
+            let Some(last) = batch.last() else { break };
+            after = Some((last.created_at, last.id));
+            ids.extend(batch.iter().map(|e| e.id));
+        }
+        let expected: Vec<i64> = all_entries(&repo).await.iter().map(|e| e.id).collect();
+        assert_eq!(ids, expected);
+        assert_eq!(ids.len(), 5);
+    }
+
+    #[test]
+    fn test_csv_escaping() {
+        let entry = AuditModel {
+            id: 1,
+            actor_id: None,
+            actor_name: Some("=HYPERLINK(\"x\")".to_string()),
+            action: audit::AUTH_LOGIN_FAILED.to_string(),
+            target_type: None,
+            target_id: None,
+            changes: Some(r#"{"role":{"after":"admin","before":"user"}}"#.to_string()),
+            ip: Some("203.0.113.9".to_string()),
+            request_id: None,
+            created_at: chrono::NaiveDateTime::parse_from_str("2026-10-18 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+        };
+        assert_eq!(
+            csv_row(&entry),
+            concat!(
+                r#"1,2026-10-18T08:00:00Z,,"'=HYPERLINK(""x"")",auth.login_failed,,,"#,
+                r#""{""role"":{""after"":""admin"",""before"":""user""}}",203.0.113.9,"#,
+                "\r\n",
+            )
+        );
+
+        // 以制表符或回车开头的值同样加前缀
+        let tab = AuditModel { actor_name: Some("\t=1+1".to_string()), changes: None, ..entry.clone() };
+        assert!(csv_row(&tab).contains(",'\t=1+1,"));
+        let cr = AuditModel { actor_name: Some("\r=1+1".to_string()), changes: None, ..entry };
+        assert!(csv_row(&cr).contains(",\"'\r=1+1\","));
+    }
+
+    #[tokio::test]
+    async fn test_admin_role_change_is_audited() {
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 2).await;
+        let (app, state) = app(db);
+
+        let admin = token(1, "admin");
+        let response = app.clone()
+            .oneshot(request("PUT", "/api/user/2", Some(&admin), Some(json!({"role": "admin"}))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let entries = all_entries(&state.audit_repo).await;
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.action, audit::USER_UPDATE);
+        assert_eq!(entry.actor_id, Some(1));
+        assert_eq!((entry.target_type.as_deref(), entry.target_id.as_deref()), (Some("user"), Some("2")));
+        assert_eq!(entry.ip.as_deref(), Some("198.51.100.7")); // 代理追加的最后一项
+        assert_eq!(entry.request_id.as_deref(), Some("req-audit-1"));
+        let changes: Value = serde_json::from_str(entry.changes.as_deref().unwrap()).unwrap();
+        assert_eq!(changes["role"], json!({"before": "user", "after": "admin"}));
+
+        // 不存在的用户返回 USER_NOT_FOUND，不写入审计记录
+        for method in ["PUT", "DELETE"] {
+            let response = app.clone()
+                .oneshot(request(method, "/api/user/99", Some(&admin), Some(json!({"role": "admin"}))))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            let body: Value = serde_json::from_str(&body_text(response).await).unwrap();
+            assert_eq!(body["code"], "USER_NOT_FOUND");
+        }
+        assert_eq!(all_entries(&state.audit_repo).await.len(), 1);
+
+        // 批量删除为每个用户各记录一条
+        let response = app
+            .oneshot(request("DELETE", "/api/user", Some(&admin), Some(json!({"user_ids": [1, 2, 99]}))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let deletes = AuditFilter { action: Some(audit::USER_DELETE.to_string()), ..AuditFilter::default() };
+        let deleted = matching_entries(&state.audit_repo, &deletes).await;
+        let mut targets: Vec<_> = deleted.iter().filter_map(|e| e.target_id.clone()).collect();
+        targets.sort();
+        assert_eq!(targets, vec!["1", "2"]);
+    }
+
+    #[tokio::test]
+    async fn test_login_events_are_audited() {
+        let (app, state) = app(common::setup_test_db().await);
+        let register = json!({"username": "alice", "email": "alice@example.com", "password": "password123"});
+        let response = app.clone().oneshot(request("POST", "/api/user/register", None, Some(register))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        for login in ["alice", "nobody"] {
+            let body = json!({"username_or_email": login, "password": "wrong_password"});
+            let response = app.clone().oneshot(request("POST", "/api/user/login", None, Some(body))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+        let body = json!({"username_or_email": "alice", "password": "password123"});
+        let response = app.oneshot(request("POST", "/api/user/login", None, Some(body))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let entries = all_entries(&state.audit_repo).await;
+        let summary: Vec<(&str, Option<i32>, Option<&str>)> = entries
+            .iter()
+            .rev()
+            .map(|e| (e.action.as_str(), e.actor_id, e.actor_name.as_deref()))
+            .collect();
+        assert_eq!(summary, vec![
+            (audit::USER_REGISTER, Some(1), Some("alice")),
+            (audit::AUTH_LOGIN_FAILED, Some(1), Some("alice")),
+            (audit::AUTH_LOGIN_FAILED, None, Some("nobody")),
+            (audit::AUTH_LOGIN, Some(1), Some("alice")),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_audit_endpoint_requires_admin_and_exports_csv() {
+        let (app, state) = app(common::setup_test_db().await);
+        state.audit_repo
+            .record(AuditEvent::new(audit::AUTH_LOGIN).by_user(Some(1), "alice"), None, None)
+            .await
+            .unwrap();
+        state.audit_repo
+            .record(AuditEvent::new(audit::USER_DELETE).by_user(Some(1), "alice").target("user", 5), None, None)
+            .await
+            .unwrap();
+
+        let response = app.clone().oneshot(request("GET", "/api/admin/audit", Some(&token(2, "user")), None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let admin = token(1, "admin");
+        let response = app.clone()
+            .oneshot(request("GET", "/api/admin/audit?action=user.delete&actor_id=1", Some(&admin), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = serde_json::from_str(&body_text(response).await).unwrap();
+        assert_eq!(body["total_entries"], 1);
+        assert_eq!(body["entries"][0]["target_id"], "5");
+
+        let response = app.clone()
+            .oneshot(request("GET", "/api/admin/audit?format=csv", Some(&admin), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv; charset=utf-8");
+        let csv = body_text(response).await;
+        assert_eq!(csv.lines().count(), 3); // 列名 + 2 条记录
+        assert_eq!(
+            csv.lines().next(),
+            Some("id,created_at,actor_id,actor_name,action,target_type,target_id,changes,ip,request_id")
+        );
+
+        let response = app.oneshot(request("GET", "/api/admin/audit?format=xml", Some(&admin), None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_csv_export_spans_multiple_batches() {
+        let (app, state) = app(common::setup_test_db().await);
+        // 两个完整批次再加一条，覆盖游标翻页和最后一个不满的批次
+        let total = CSV_BATCH_SIZE as usize * 2 + 1;
+        for i in 0..total {
+            state.audit_repo
+                .record(AuditEvent::new(audit::AUTH_LOGIN).by_user(Some(1), &format!("user{}", i)), None, None)
+                .await
+                .unwrap();
+        }
+
+        let response = app
+            .oneshot(request("GET", "/api/admin/audit?format=csv", Some(&token(1, "admin")), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let csv = body_text(response).await;
+        let ids: Vec<i64> = csv.lines().skip(1).map(|line| line.split(',').next().unwrap().parse().unwrap()).collect();
+        let expected: Vec<i64> = all_entries(&state.audit_repo).await.iter().map(|e| e.id).collect();
+        assert_eq!(ids.len(), total);
+        assert_eq!(ids, expected); // 按时间倒序，不重复也不遗漏
+    }
+}
@@ -0,0 +1,127 @@
+//! 数据导出 / 导入测试用例
+//! 测试 blogctl export / import 使用的 backup 模块
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use sea_orm::{ConnectionTrait, EntityTrait, PaginatorTrait};
+
+    use axum_blog_engine::backup::{self, BACKUP_VERSION};
+    use axum_blog_engine::database::{comment, favorite, post, user};
+    use axum_blog_engine::handler::idl::CreatePostRequest;
+    use axum_blog_engine::CustomError;
+
+    /// 创建带有用户、文章、嵌套评论和收藏的源数据库
+    async fn setup_source_db() -> sea_orm::DatabaseConnection {
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 3).await;
+        // 通过 Repository 创建文章，与线上数据一样带有写入时计算的派生列
+        let posts = post::PostRepository::new(db.clone());
+        for title in ["第一篇", "第二篇"] {
+            let req = CreatePostRequest {
+                title: title.to_string(),
+                content_markdown: "# 标题\n\n正文内容".to_string(),
+                category: "技术".to_string(),
+                is_published: Some(true),
+                cover_url: None,
+                excerpt: None,
+            };
+            posts.create_post(req, 1).await.expect("Failed to seed post");
+        }
+        for sql in [
+            "INSERT INTO comments (post_id, user_id, content) VALUES (1, 2, '第一条评论')",
+            "INSERT INTO comments (post_id, user_id, content, parent_id) VALUES (1, 3, '回复', 1)",
+            "INSERT INTO favorites (user_id, post_id) VALUES (2, 1)",
+        ] {
+            db.execute_unprepared(sql).await.expect("Failed to seed data");
+        }
+        db
+    }
+
+    #[tokio::test]
+    async fn test_export_import_roundtrip() {
+        let source = setup_source_db().await;
+        let exported = backup::export(&source).await.unwrap();
+        assert_eq!(exported.version, BACKUP_VERSION);
+        assert_eq!(exported.users.len(), 3);
+        assert_eq!(exported.posts.len(), 2);
+        assert_eq!(exported.comments.len(), 2);
+        assert_eq!(exported.favorites.len(), 1);
+
+        // 经过 JSON 序列化后导入到新的数据库
+        let json = serde_json::to_string(&exported).unwrap();
+        let target = common::setup_test_db().await;
+        backup::import(&target, serde_json::from_str(&json).unwrap()).await.unwrap();
+
+        let reimported = backup::export(&target).await.unwrap();
+        assert_eq!(reimported.users, exported.users);
+        assert_eq!(reimported.posts, exported.posts);
+        assert_eq!(reimported.comments, exported.comments);
+        assert_eq!(reimported.favorites, exported.favorites);
+
+        // 导入后新插入的记录不会与已有 ID 冲突
+        common::seed_posts(&target, 1, 2).await;
+        assert_eq!(post::Entity::find().count(&target).await.unwrap(), 3);
+        let newest = post::Entity::find_by_id(3).one(&target).await.unwrap();
+        assert!(newest.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_import_fills_missing_text_stats() {
+        // 旧版本导出的文章没有字数和自动摘要，导入时补齐
+        let source = setup_source_db().await;
+        let mut exported = backup::export(&source).await.unwrap();
+        for post in &mut exported.posts {
+            post.word_count = 0;
+            post.reading_time_minutes = 0;
+            post.auto_excerpt = None;
+        }
+
+        let target = common::setup_test_db().await;
+        backup::import(&target, exported).await.unwrap();
+        let imported = post::Entity::find_by_id(1).one(&target).await.unwrap().unwrap();
+        assert_eq!(imported.auto_excerpt.as_deref(), Some("标题 正文内容"));
+        assert_eq!(imported.word_count, 6);
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_non_empty_database() {
+        let source = setup_source_db().await;
+        let exported = backup::export(&source).await.unwrap();
+
+        let result = backup::import(&source, exported).await;
+        assert!(matches!(result, Err(CustomError::BadRequest(_))));
+        assert_eq!(user::Entity::find().count(&source).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_unknown_version() {
+        let source = setup_source_db().await;
+        let mut exported = backup::export(&source).await.unwrap();
+        exported.version = BACKUP_VERSION + 1;
+
+        let target = common::setup_test_db().await;
+        let result = backup::import(&target, exported).await;
+        assert!(matches!(result, Err(CustomError::BadRequest(_))));
+        assert_eq!(user::Entity::find().count(&target).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_import_is_atomic() {
+        let source = setup_source_db().await;
+        let mut exported = backup::export(&source).await.unwrap();
+        // 指向不存在文章的收藏会违反外键约束，整个导入应回滚
+        exported.favorites.push(favorite::Model {
+            user_id: 1,
+            post_id: 99,
+            created_at: exported.favorites[0].created_at,
+        });
+
+        let target = common::setup_test_db().await;
+        assert!(backup::import(&target, exported).await.is_err());
+        assert_eq!(user::Entity::find().count(&target).await.unwrap(), 0);
+        assert_eq!(comment::Entity::find().count(&target).await.unwrap(), 0);
+    }
+}
@@ -1,9 +1,12 @@
 //! 评论模块测试用例
 //! 测试 CommentRepository 的各种数据库操作功能
 
+mod common;
+
 #[cfg(test)]
 mod tests {
-    use sea_orm::{Database, DatabaseConnection, ConnectionTrait};
+    use crate::common;
+    use sea_orm::DatabaseConnection;
     use chrono::Utc;
     
     // 导入项目模块
@@ -11,28 +14,13 @@ mod tests {
     use axum_blog_engine::handler::idl::{
         CreateCommentRequest, UpdateCommentRequest
     };
-    use axum_blog_engine::error::CustomError;
+    use axum_blog_engine::error::{CustomError, ErrorCode};
 
-    /// 创建内存数据库连接用于测试
+    /// 创建测试数据库并执行全部迁移 (见 tests/common)
     async fn setup_test_db() -> DatabaseConnection {
-        let db = Database::connect("sqlite::memory:")
-            .await
-            .expect("Failed to connect to test database");
-        
-        // 创建评论表
-        let sql = r#"
-            CREATE TABLE IF NOT EXISTS comments (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                content TEXT NOT NULL,
-                post_id INTEGER NOT NULL,
-                user_id INTEGER NOT NULL,
-                parent_id INTEGER,
-                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now'))
-            );
-        "#;
-        
-        db.execute_unprepared(sql).await.expect("Failed to create comments table");
-        
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 10).await;
+        common::seed_posts(&db, 10, 1).await;
         db
     }
 
@@ -173,11 +161,11 @@ mod tests {
         let result = repo.update_comment(999, update_req).await;
         assert!(result.is_err());
         
-        if let Err(CustomError::NotFound(msg)) = result {
+        if let Err(CustomError::Coded { code: ErrorCode::CommentNotFound, message: msg }) = result {
             assert!(msg.contains("999"));
             assert!(msg.contains("未找到"));
         } else {
-            panic!("Expected NotFound error");
+            panic!("Expected CommentNotFound error");
         }
     }
 
@@ -207,11 +195,11 @@ mod tests {
         let result = repo.delete_comment(999).await;
         assert!(result.is_err());
         
-        if let Err(CustomError::NotFound(msg)) = result {
+        if let Err(CustomError::Coded { code: ErrorCode::CommentNotFound, message: msg }) = result {
             assert!(msg.contains("999"));
             assert!(msg.contains("未找到或已被删除"));
         } else {
-            panic!("Expected NotFound error");
+            panic!("Expected CommentNotFound error");
         }
     }
 
@@ -236,7 +224,7 @@ mod tests {
         
         // 根据数据库设计，子评论可能也会被级联删除（取决于外键约束）
         // 这里我们测试子评论的状态
-        let found_child = repo.get_comment_by_id(child_comment.id).await.unwrap();
+        let _found_child = repo.get_comment_by_id(child_comment.id).await.unwrap();
         // 如果设置了级联删除，子评论应该也被删除
         // 如果没有设置，子评论仍然存在但parent_id指向已删除的评论
         // 这里我们假设子评论仍然存在（具体行为取决于数据库约束设置）
@@ -385,7 +373,7 @@ mod tests {
             post_id,
             parent_id: None,
         };
-        let comment1 = repo.create_comment(create_req1, 1).await.unwrap();
+        let _comment1 = repo.create_comment(create_req1, 1).await.unwrap();
         
         tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
         
@@ -394,7 +382,7 @@ mod tests {
             post_id,
             parent_id: None,
         };
-        let comment2 = repo.create_comment(create_req2, 2).await.unwrap();
+        let _comment2 = repo.create_comment(create_req2, 2).await.unwrap();
         
         tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
         
@@ -403,7 +391,7 @@ mod tests {
             post_id,
             parent_id: None,
         };
-        let comment3 = repo.create_comment(create_req3, 3).await.unwrap();
+        let _comment3 = repo.create_comment(create_req3, 3).await.unwrap();
         
         // 获取评论列表（应该按创建时间倒序）
         let result = repo.get_paginated_comments_by_post_id(post_id, 1, 10).await;
@@ -0,0 +1,172 @@
+//! 集成测试共用的数据库初始化
+//! 默认使用 SQLite 内存数据库；设置 `TEST_DATABASE_URL` (postgres:// 或 mysql://) 后，
+//! 每个测试在该数据库服务器上创建一个独立的 `blog_test_*` 数据库，测试之间互不影响，
+//! 测试结束时删除。
+//! 所有后端都通过与服务启动时相同的迁移建表。
+//! 另外提供直接调用处理函数时使用的测试配置和 `AppState`。
+
+#![allow(dead_code)] // 各测试文件只用到其中一部分
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection, DbBackend};
+
+use axum_blog_engine::database::audit::AuditRepository;
+use axum_blog_engine::database::comment::CommentRepository;
+use axum_blog_engine::database::favorite::FavoriteRepository;
+use axum_blog_engine::database::media::MediaRepository;
+use axum_blog_engine::database::post::PostRepository;
+use axum_blog_engine::database::user::UserRepository;
+use axum_blog_engine::lifecycle::BackgroundTasks;
+use axum_blog_engine::metrics::Metrics;
+use axum_blog_engine::migration::Migrator;
+use axum_blog_engine::storage::LocalStorage;
+use axum_blog_engine::theme::Theme;
+use axum_blog_engine::{AppState, Config};
+
+/// 当前线程创建的测试数据库 (服务器连接地址, 数据库名)。
+/// libtest 在独立线程中运行每个测试并在报告结果前等待线程退出，
+/// 因此线程局部变量的 `Drop` 在测试结束、测试自身的运行时和连接都已释放后执行。
+struct CreatedDatabases(Vec<(String, String)>);
+
+impl Drop for CreatedDatabases {
+    fn drop(&mut self) {
+        let databases = std::mem::take(&mut self.0);
+        if databases.is_empty() {
+            return;
+        }
+        // 线程退出阶段不能再使用 tokio 的线程局部状态，在新线程的运行时中删除
+        let result = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+            runtime.block_on(async {
+                for (url, name) in databases {
+                    let server = Database::connect(&url).await?;
+                    // PostgreSQL 连接池中可能仍有空闲连接，需要 FORCE 断开
+                    let sql = match server.get_database_backend() {
+                        DbBackend::Postgres => format!("DROP DATABASE IF EXISTS {} WITH (FORCE)", name),
+                        _ => format!("DROP DATABASE IF EXISTS {}", name),
+                    };
+                    server.execute_unprepared(&sql).await?;
+                    server.close().await?;
+                }
+                Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+            })
+        })
+        .join();
+        if let Ok(Err(e)) = result {
+            eprintln!("Failed to drop test database: {}", e);
+        }
+    }
+}
+
+thread_local! {
+    static CREATED_DATABASES: RefCell<CreatedDatabases> = const { RefCell::new(CreatedDatabases(Vec::new())) };
+}
+
+/// 连接测试数据库（不执行迁移）
+pub async fn connect_test_db() -> DatabaseConnection {
+    let Ok(url) = std::env::var("TEST_DATABASE_URL") else {
+        return Database::connect("sqlite::memory:")
+            .await
+            .expect("Failed to connect to test database");
+    };
+
+    let server = Database::connect(&url)
+        .await
+        .expect("Failed to connect to TEST_DATABASE_URL");
+    let name = format!("blog_test_{}", uuid::Uuid::new_v4().simple());
+    server
+        .execute_unprepared(&format!("CREATE DATABASE {}", name))
+        .await
+        .expect("Failed to create test database");
+    server.close().await.ok();
+    CREATED_DATABASES.with(|created| created.borrow_mut().0.push((url.clone(), name.clone())));
+
+    Database::connect(with_database(&url, &name))
+        .await
+        .expect("Failed to connect to test database")
+}
+
+/// 连接测试数据库并执行全部迁移
+pub async fn setup_test_db() -> DatabaseConnection {
+    let db = connect_test_db().await;
+    Migrator::up(&db, None).await.expect("Failed to run migrations");
+    db
+}
+
+/// 插入 `count` 个测试用户 (id 从 1 开始)，满足外键约束
+pub async fn seed_users(db: &DatabaseConnection, count: i32) {
+    for id in 1..=count {
+        let sql = format!(
+            "INSERT INTO users (username, email, password_hash) VALUES ('user{0}', 'user{0}@example.com', 'hash')",
+            id
+        );
+        db.execute_unprepared(&sql).await.expect("Failed to create test user");
+    }
+}
+
+/// 插入 `count` 篇测试文章 (id 从 1 开始)，作者为 `author_id`
+pub async fn seed_posts(db: &DatabaseConnection, count: i32, author_id: i32) {
+    for _ in 0..count {
+        let sql = format!(
+            "INSERT INTO posts (title, content_markdown, author_id) VALUES ('测试文章', '测试内容', {})",
+            author_id
+        );
+        db.execute_unprepared(&sql).await.expect("Failed to create test post");
+    }
+}
+
+/// 测试用的应用配置
+pub fn test_config() -> Config {
+    Config {
+        jwt_secret: "test_secret".to_string(),
+        bcrypt_cost: 4,
+        token_ttl_hours: 720,
+        site_url: "https://blog.example.com".to_string(),
+        site_title: "测试博客".to_string(),
+        site_description: "一个测试博客".to_string(),
+        robots_txt: None,
+        theme_dir: None,
+        static_dir: None,
+        api_docs_bundle: None,
+        upload_max_bytes: 10 * 1024 * 1024,
+        media_quota_bytes: 100 * 1024 * 1024,
+        image_variant_widths: vec![320, 768, 1280],
+        metrics_token: None,
+        trust_forwarded_for: false,
+    }
+}
+
+/// 使用给定数据库和 `test_config()` 构建应用状态（内置主题、临时目录中的本地存储）
+pub fn test_app_state(db: DatabaseConnection) -> AppState {
+    AppState {
+        db: db.clone(),
+        config: Arc::new(test_config()),
+        user_repo: UserRepository::new(db.clone()),
+        post_repo: PostRepository::new(db.clone()),
+        comment_repo: CommentRepository::new(db.clone()),
+        favorite_repo: FavoriteRepository::new(db.clone()),
+        theme: Arc::new(Theme::load(None).expect("Failed to load built-in theme")),
+        assets: None,
+        media_repo: MediaRepository::new(db.clone()),
+        storage: Arc::new(LocalStorage::new(std::env::temp_dir().join("blog_test_media"))),
+        tasks: BackgroundTasks::new(),
+        metrics: Arc::new(Metrics::new()),
+        audit_repo: AuditRepository::new(db),
+    }
+}
+
+/// 将连接地址中的数据库名替换为 `name`，保留查询参数
+fn with_database(url: &str, name: &str) -> String {
+    let authority_start = url.find("://").map(|i| i + 3).unwrap_or(0);
+    let (base, query) = match url.find('?') {
+        Some(i) => (&url[..i], &url[i..]),
+        None => (url, ""),
+    };
+    let base = match base[authority_start..].find('/') {
+        Some(i) => &base[..authority_start + i],
+        None => base,
+    };
+    format!("{}/{}{}", base, name, query)
+}
@@ -0,0 +1,194 @@
+//! 错误响应测试用例
+//! 测试错误码、逐字段的验证错误详情、内部错误细节的隐藏，
+//! 以及按 Accept 头协商的 application/problem+json 格式
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+
+    use axum::body::{to_bytes, Body};
+    use axum::http::{header, HeaderValue, Request, StatusCode};
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use chrono::{Duration, Utc};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+    use validator::Validate;
+
+    use axum_blog_engine::error::{CustomError, ErrorCode, ErrorFormat, PROBLEM_JSON};
+    use axum_blog_engine::handler::audit::ClientIp;
+    use axum_blog_engine::handler::idl::RegisterRequest;
+    use axum_blog_engine::handler::problem::negotiate_error_format;
+    use axum_blog_engine::handler::user::{generate_jwt, register_user, AuthUser};
+
+    /// 与 server.rs 相同的错误格式协商中间件，路由总是返回文章不存在
+    fn app() -> Router {
+        Router::new()
+            .route("/missing", get(|| async {
+                CustomError::coded(ErrorCode::PostNotFound, "ID为 42 的文章未找到")
+            }))
+            .layer(axum::middleware::from_fn(negotiate_error_format))
+    }
+
+    async fn get_missing(accept: Option<&str>) -> axum::response::Response {
+        let mut request = Request::builder().uri("/missing");
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        app().oneshot(request.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    async fn json_body(response: axum::response::Response) -> Value {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn invalid_register_request() -> RegisterRequest {
+        RegisterRequest {
+            username: "ab".to_string(),
+            email: "not-an-email".to_string(),
+            password: "password123".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_error_code_serialization_and_status() {
+        assert_eq!(serde_json::to_value(ErrorCode::AuthTokenExpired).unwrap(), json!("AUTH_TOKEN_EXPIRED"));
+        assert_eq!(ErrorCode::PostNotFound.as_str(), "POST_NOT_FOUND");
+        assert_eq!(ErrorCode::UsernameTaken.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(ErrorCode::MediaQuotaExceeded.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        assert_eq!(CustomError::NotFound("x".to_string()).code(), ErrorCode::NotFound);
+        assert_eq!(CustomError::DbError(sea_orm::DbErr::Custom("x".to_string())).code(), ErrorCode::DatabaseError);
+        assert_eq!(CustomError::BcryptError("x".to_string()).code(), ErrorCode::InternalError);
+    }
+
+    #[tokio::test]
+    async fn test_json_error_body_has_code() {
+        let response = get_missing(None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response.headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("application/json"));
+
+        let body = json_body(response).await;
+        assert_eq!(body["success"], false);
+        assert_eq!(body["code"], "POST_NOT_FOUND");
+        assert_eq!(body["message"], "ID为 42 的文章未找到");
+    }
+
+    #[tokio::test]
+    async fn test_validation_error_field_details() {
+        let error = CustomError::from(invalid_register_request().validate().unwrap_err());
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = json_body(response).await;
+        assert_eq!(body["code"], "VALIDATION_FAILED");
+        assert_eq!(body["errors"], json!([
+            { "field": "email", "code": "email", "message": "邮箱格式无效" },
+            { "field": "username", "code": "length", "message": "用户名长度必须在3到20个字符之间" },
+        ]));
+        assert!(body["message"].as_str().unwrap().contains("邮箱格式无效"));
+    }
+
+    #[tokio::test]
+    async fn test_internal_error_details_hidden() {
+        let errors = [
+            CustomError::DbError(sea_orm::DbErr::Custom("relation \"users\" does not exist".to_string())),
+            CustomError::BcryptError("invalid cost".to_string()),
+        ];
+        for error in errors {
+            let response = error.into_response();
+            assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let text = String::from_utf8(bytes.to_vec()).unwrap();
+            assert!(!text.contains("users") && !text.contains("cost"), "{}", text);
+            assert!(text.contains("服务器内部错误"), "{}", text);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_problem_json_negotiation() {
+        let response = get_missing(Some("application/problem+json")).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+
+        let body = json_body(response).await;
+        assert_eq!(body["type"], "about:blank");
+        assert_eq!(body["title"], "Not Found");
+        assert_eq!(body["status"], 404);
+        assert_eq!(body["detail"], "ID为 42 的文章未找到");
+        assert_eq!(body["code"], "POST_NOT_FOUND");
+        assert!(body.get("success").is_none());
+
+        let response = get_missing(Some("application/json")).await;
+        assert_eq!(json_body(response).await["code"], "POST_NOT_FOUND");
+        assert!(get_missing(Some("application/problem+json;q=0")).await
+            .headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("application/json"));
+    }
+
+    #[test]
+    fn test_error_format_from_accept() {
+        let format = |accept: &'static str| ErrorFormat::from_accept(Some(&HeaderValue::from_static(accept)));
+        assert_eq!(ErrorFormat::from_accept(None), ErrorFormat::Json);
+        assert_eq!(format("*/*"), ErrorFormat::Json);
+        assert_eq!(format("application/problem+json"), ErrorFormat::Problem);
+        assert_eq!(format("Application/Problem+JSON"), ErrorFormat::Problem);
+        assert_eq!(format("application/json, application/problem+json"), ErrorFormat::Problem);
+        assert_eq!(format("application/json, application/problem+json;q=0.5"), ErrorFormat::Json);
+        assert_eq!(format("application/problem+json; q=0"), ErrorFormat::Json);
+    }
+
+    #[tokio::test]
+    async fn test_auth_error_codes() {
+        let state = common::test_app_state(common::setup_test_db().await);
+        let app = Router::new()
+            .route("/me", get(|AuthUser(_): AuthUser| async { "ok" }))
+            .with_state(state.clone());
+        let request = |token: Option<String>| {
+            let mut request = Request::builder().uri("/me");
+            if let Some(token) = token {
+                request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+            }
+            request.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(json_body(response).await["code"], "AUTH_TOKEN_MISSING");
+
+        let response = app.clone().oneshot(request(Some("garbage".to_string()))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(json_body(response).await["code"], "AUTH_TOKEN_INVALID");
+
+        let expired = (Utc::now() - Duration::hours(2)).timestamp() as usize;
+        let token = generate_jwt("1", "alice", "user", expired, &state.config.jwt_secret).unwrap();
+        let response = app.clone().oneshot(request(Some(token))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(json_body(response).await["code"], "AUTH_TOKEN_EXPIRED");
+
+        let valid = (Utc::now() + Duration::hours(1)).timestamp() as usize;
+        let token = generate_jwt("1", "alice", "user", valid, &state.config.jwt_secret).unwrap();
+        let response = app.oneshot(request(Some(token))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_register_duplicate_codes() {
+        let state = common::test_app_state(common::setup_test_db().await);
+        let request = |username: &str, email: &str| Json(RegisterRequest {
+            username: username.to_string(),
+            email: email.to_string(),
+            password: "password123".to_string(),
+        });
+        let _ = register_user(axum::extract::State(state.clone()), ClientIp(None), request("alice", "alice@example.com"))
+            .await.unwrap();
+
+        let result = register_user(axum::extract::State(state.clone()), ClientIp(None), request("alice", "other@example.com")).await;
+        assert!(matches!(result, Err(e) if e.code() == ErrorCode::UsernameTaken));
+        let result = register_user(axum::extract::State(state), ClientIp(None), request("bob", "alice@example.com")).await;
+        assert!(matches!(result, Err(e) if e.code() == ErrorCode::EmailTaken));
+    }
+}
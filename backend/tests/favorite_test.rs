@@ -1,57 +1,28 @@
 //! 收藏模块测试用例
 //! 测试 FavoriteRepository 的各种数据库操作功能
 
+mod common;
+
 #[cfg(test)]
 mod tests {
-    use sea_orm::{Database, DatabaseConnection, ConnectionTrait};
+    use crate::common;
+    use sea_orm::{DatabaseConnection, ConnectionTrait};
     use chrono::Utc;
     
     // 导入项目模块
     use axum_blog_engine::database::favorite::FavoriteRepository;
-    use axum_blog_engine::error::CustomError;
 
-    /// 创建内存数据库连接用于测试
+    /// 创建测试数据库并执行全部迁移 (见 tests/common)
     async fn setup_test_db() -> DatabaseConnection {
-        let db = Database::connect("sqlite::memory:")
-            .await
-            .expect("Failed to connect to test database");
-        
-        // 创建收藏表
-        let favorites_sql = r#"
-            CREATE TABLE IF NOT EXISTS favorites (
-                user_id INTEGER NOT NULL,
-                post_id INTEGER NOT NULL,
-                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now')),
-                PRIMARY KEY (user_id, post_id)
-            );
-        "#;
-        
-        // 创建文章表（用于联接查询）
-        let posts_sql = r#"
-            CREATE TABLE IF NOT EXISTS posts (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                title TEXT NOT NULL,
-                content_markdown TEXT NOT NULL,
-                category TEXT NOT NULL,
-                author_id INTEGER NOT NULL,
-                is_published INTEGER NOT NULL DEFAULT 0,
-                view_count INTEGER NOT NULL DEFAULT 0,
-                cover_url TEXT,
-                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now')),
-                updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now'))
-            );
-        "#;
-        
-        db.execute_unprepared(favorites_sql).await.expect("Failed to create favorites table");
-        db.execute_unprepared(posts_sql).await.expect("Failed to create posts table");
-        
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 10).await;
         db
     }
 
     /// 创建测试文章数据
     async fn create_test_post(db: &DatabaseConnection, id: i32, title: &str, author_id: i32) {
         let sql = format!(
-            "INSERT INTO posts (id, title, content_markdown, category, author_id, is_published) VALUES ({}, '{}', '测试内容', '技术', {}, 1)",
+            "INSERT INTO posts (id, title, content_markdown, category, author_id, is_published) VALUES ({}, '{}', '测试内容', '技术', {}, TRUE)",
             id, title, author_id
         );
         db.execute_unprepared(&sql).await.expect("Failed to create test post");
@@ -71,7 +42,7 @@ mod tests {
         // 第一次切换（添加收藏）
         let result = repo.toggle_favorite(user_id, post_id).await;
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true); // 返回true表示已收藏
+        assert!(result.unwrap()); // 返回true表示已收藏
         
         // 验证收藏状态
         let is_favorited = repo.is_favorited(user_id, post_id).await.unwrap();
@@ -96,7 +67,7 @@ mod tests {
         // 再次切换（取消收藏）
         let result = repo.toggle_favorite(user_id, post_id).await;
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), false); // 返回false表示已取消收藏
+        assert!(!result.unwrap()); // 返回false表示已取消收藏
         
         // 验证收藏状态
         let is_favorited = repo.is_favorited(user_id, post_id).await.unwrap();
@@ -116,16 +87,16 @@ mod tests {
         
         // 多次切换收藏状态
         let result1 = repo.toggle_favorite(user_id, post_id).await.unwrap();
-        assert_eq!(result1, true); // 第一次：添加收藏
+        assert!(result1); // 第一次：添加收藏
         
         let result2 = repo.toggle_favorite(user_id, post_id).await.unwrap();
-        assert_eq!(result2, false); // 第二次：取消收藏
+        assert!(!result2); // 第二次：取消收藏
         
         let result3 = repo.toggle_favorite(user_id, post_id).await.unwrap();
-        assert_eq!(result3, true); // 第三次：再次添加收藏
+        assert!(result3); // 第三次：再次添加收藏
         
         let result4 = repo.toggle_favorite(user_id, post_id).await.unwrap();
-        assert_eq!(result4, false); // 第四次：再次取消收藏
+        assert!(!result4); // 第四次：再次取消收藏
         
         // 最终状态应该是未收藏
         let is_favorited = repo.is_favorited(user_id, post_id).await.unwrap();
@@ -182,7 +153,7 @@ mod tests {
         // 多个用户收藏同一篇文章
         for user_id in 1..=3 {
             let result = repo.toggle_favorite(user_id, post_id).await.unwrap();
-            assert_eq!(result, true); // 都应该成功添加收藏
+            assert!(result); // 都应该成功添加收藏
             
             let is_favorited = repo.is_favorited(user_id, post_id).await.unwrap();
             assert!(is_favorited);
@@ -196,7 +167,7 @@ mod tests {
         
         // 用户1取消收藏，不应该影响其他用户
         let result = repo.toggle_favorite(1, post_id).await.unwrap();
-        assert_eq!(result, false);
+        assert!(!result);
         
         assert!(!repo.is_favorited(1, post_id).await.unwrap()); // 用户1已取消收藏
         assert!(repo.is_favorited(2, post_id).await.unwrap());  // 用户2仍然收藏
@@ -218,7 +189,7 @@ mod tests {
         // 用户收藏多篇文章
         for post_id in 1..=3 {
             let result = repo.toggle_favorite(user_id, post_id).await.unwrap();
-            assert_eq!(result, true);
+            assert!(result);
             
             let is_favorited = repo.is_favorited(user_id, post_id).await.unwrap();
             assert!(is_favorited);
@@ -232,7 +203,7 @@ mod tests {
         
         // 取消收藏文章2
         let result = repo.toggle_favorite(user_id, 2).await.unwrap();
-        assert_eq!(result, false);
+        assert!(!result);
         
         // 验证收藏状态
         assert!(repo.is_favorited(user_id, 1).await.unwrap());  // 文章1仍被收藏
@@ -0,0 +1,117 @@
+//! 订阅源模块测试用例
+//! 测试 RSS / Atom / JSON Feed 生成以及条件请求判断
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+    use axum_blog_engine::feed::{
+        build_feed, compute_etag, http_date, is_not_modified, FeedChannel, FeedFormat, FeedItem,
+        FeedMode,
+    };
+
+    fn time(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn sample_channel() -> FeedChannel {
+        FeedChannel {
+            title: "测试博客".to_string(),
+            description: "一个测试博客".to_string(),
+            home_page_url: "https://blog.example.com".to_string(),
+            feed_url: "https://blog.example.com/feed.xml".to_string(),
+            updated: time("2024-05-02 08:30:00"),
+        }
+    }
+
+    fn sample_item(content_html: Option<&str>) -> FeedItem {
+        FeedItem {
+            id: 1,
+            title: "Rust & Axum".to_string(),
+            url: "https://blog.example.com/post/1".to_string(),
+            author_name: "alice".to_string(),
+            category: "技术".to_string(),
+            summary: "文章摘要".to_string(),
+            content_html: content_html.map(str::to_string),
+            published: time("2024-05-01 10:00:00"),
+            updated: time("2024-05-02 08:30:00"),
+        }
+    }
+
+    #[test]
+    fn test_build_rss() {
+        let xml = build_feed(FeedFormat::Rss, &sample_channel(), &[sample_item(Some("<p>正文</p>"))]);
+
+        assert!(xml.contains("<rss version=\"2.0\""));
+        assert!(xml.contains("<title>Rust &amp; Axum</title>"));
+        assert!(xml.contains("<guid>https://blog.example.com/post/1</guid>"));
+        assert!(xml.contains("<pubDate>Wed, 1 May 2024 10:00:00 +0000</pubDate>"));
+        assert!(xml.contains("<dc:creator>alice</dc:creator>"));
+        assert!(xml.contains("<content:encoded><![CDATA[<p>正文</p>]]></content:encoded>"));
+    }
+
+    #[test]
+    fn test_build_rss_excerpt_mode_has_no_content() {
+        let xml = build_feed(FeedFormat::Rss, &sample_channel(), &[sample_item(None)]);
+
+        assert!(xml.contains("<description><![CDATA[文章摘要]]></description>"));
+        assert!(!xml.contains("content:encoded"));
+    }
+
+    #[test]
+    fn test_build_atom() {
+        let xml = build_feed(FeedFormat::Atom, &sample_channel(), &[sample_item(Some("<p>正文</p>"))]);
+
+        assert!(xml.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\""));
+        assert!(xml.contains("<updated>2024-05-02T08:30:00+00:00</updated>"));
+        assert!(xml.contains("<published>2024-05-01T10:00:00+00:00</published>"));
+        assert!(xml.contains("rel=\"self\""));
+        assert!(xml.contains("<content type=\"html\">&lt;p&gt;正文&lt;/p&gt;</content>"));
+    }
+
+    #[test]
+    fn test_build_json_feed() {
+        let json = build_feed(FeedFormat::Json, &sample_channel(), &[sample_item(None)]);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(value["items"][0]["id"], "https://blog.example.com/post/1");
+        assert_eq!(value["items"][0]["authors"][0]["name"], "alice");
+        assert_eq!(value["items"][0]["date_modified"], "2024-05-02T08:30:00Z");
+        // 摘要模式下没有 content_html，用摘要作为 content_text
+        assert!(value["items"][0].get("content_html").is_none());
+        assert_eq!(value["items"][0]["content_text"], "文章摘要");
+    }
+
+    #[test]
+    fn test_feed_mode_parse() {
+        assert_eq!(FeedMode::parse("full"), Some(FeedMode::Full));
+        assert_eq!(FeedMode::parse("excerpt"), Some(FeedMode::Excerpt));
+        assert_eq!(FeedMode::parse("summary"), None);
+        assert_eq!(FeedMode::default(), FeedMode::Full);
+    }
+
+    #[test]
+    fn test_conditional_request_with_etag() {
+        let etag = compute_etag("body");
+        let updated = time("2024-05-02 08:30:00");
+
+        assert!(is_not_modified(Some(&etag), None, &etag, updated));
+        assert!(is_not_modified(Some(&format!("W/{}, \"other\"", etag)), None, &etag, updated));
+        assert!(!is_not_modified(Some("\"other\""), None, &etag, updated));
+        // 存在 If-None-Match 时忽略 If-Modified-Since
+        assert!(!is_not_modified(Some("\"other\""), Some(&http_date(updated)), &etag, updated));
+        assert_ne!(etag, compute_etag("changed body"));
+    }
+
+    #[test]
+    fn test_conditional_request_with_last_modified() {
+        let etag = compute_etag("body");
+        let updated = time("2024-05-02 08:30:00");
+
+        assert_eq!(http_date(updated), "Thu, 02 May 2024 08:30:00 GMT");
+        assert!(is_not_modified(None, Some("Thu, 02 May 2024 08:30:00 GMT"), &etag, updated));
+        assert!(!is_not_modified(None, Some("Thu, 02 May 2024 08:29:59 GMT"), &etag, updated));
+        assert!(!is_not_modified(None, Some("not a date"), &etag, updated));
+        assert!(!is_not_modified(None, None, &etag, updated));
+    }
+}
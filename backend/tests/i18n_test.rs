@@ -0,0 +1,111 @@
+//! 国际化测试用例
+//! 测试消息目录的一致性、Accept-Language 协商、占位符替换，
+//! 以及成功消息、错误消息和验证消息按请求语言返回
+
+#[cfg(test)]
+mod tests {
+    use axum::body::{to_bytes, Body};
+    use axum::http::{header, Request};
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::Router;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+    use validator::Validate;
+
+    use axum_blog_engine::error::CustomError;
+    use axum_blog_engine::handler::idl::{BaseResponse, RegisterRequest};
+    use axum_blog_engine::handler::locale::negotiate_locale;
+    use axum_blog_engine::i18n::{current_locale, message_ids, t, t_args, translate, with_locale, Locale};
+
+    /// 与 server.rs 相同的语言协商中间件
+    fn app() -> Router {
+        Router::new()
+            .route("/ok", get(|| async {
+                BaseResponse { success: true, message: Some(t("post-created")) }
+            }))
+            .route("/missing", get(|| async {
+                CustomError::NotFound(t_args("post-not-found", &[("id", &42)]))
+            }))
+            .layer(axum::middleware::from_fn(negotiate_locale))
+    }
+
+    async fn message(uri: &str, headers: &[(header::HeaderName, &str)]) -> String {
+        let mut request = Request::builder().uri(uri);
+        for (name, value) in headers {
+            request = request.header(name, *value);
+        }
+        let response = app().oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        body["message"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_catalogues_have_same_messages() {
+        let zh = message_ids(Locale::ZhCn);
+        assert!(!zh.is_empty());
+        assert_eq!(zh, message_ids(Locale::En));
+    }
+
+    #[test]
+    fn test_parse_locale_and_accept_language() {
+        assert_eq!(Locale::parse("zh-Hans-CN"), Some(Locale::ZhCn));
+        assert_eq!(Locale::parse("EN_us"), Some(Locale::En));
+        assert_eq!(Locale::parse("fr"), None);
+
+        assert_eq!(Locale::from_accept_language("en-US,en;q=0.9"), Some(Locale::En));
+        assert_eq!(Locale::from_accept_language("fr-FR, en;q=0.8, zh-CN;q=0.9"), Some(Locale::ZhCn));
+        assert_eq!(Locale::from_accept_language("en;q=0, zh;q=0.1"), Some(Locale::ZhCn));
+        assert_eq!(Locale::from_accept_language("de, *;q=0.5"), None);
+    }
+
+    #[test]
+    fn test_translate_with_arguments_and_fallback() {
+        assert_eq!(translate(Locale::En, "users-deleted", &[("count", &3)]), "Deleted 3 user(s)");
+        assert_eq!(translate(Locale::ZhCn, "users-deleted", &[("count", &3)]), "成功删除 3 个用户");
+        // 缺少参数时保留占位符，未登记的 ID 原样返回
+        assert_eq!(translate(Locale::En, "post-not-found", &[]), "Post { $id } not found");
+        assert_eq!(translate(Locale::En, "没有登记的文本", &[]), "没有登记的文本");
+        // 不在请求上下文中时使用默认语言
+        assert_eq!(current_locale(), Locale::ZhCn);
+        assert_eq!(t("login-succeeded"), "登录成功");
+    }
+
+    #[tokio::test]
+    async fn test_locale_negotiation_order() {
+        assert_eq!(message("/ok", &[]).await, "文章创建成功");
+        assert_eq!(message("/ok", &[(header::ACCEPT_LANGUAGE, "en-GB,en;q=0.9")]).await, "Post created");
+        assert_eq!(message("/missing", &[(header::ACCEPT_LANGUAGE, "en")]).await, "Post 42 not found");
+        // Cookie 中的偏好优先于 Accept-Language，查询参数优先于 Cookie
+        assert_eq!(message("/ok", &[
+            (header::ACCEPT_LANGUAGE, "zh-CN"),
+            (header::COOKIE, "theme=dark; lang=en"),
+        ]).await, "Post created");
+        assert_eq!(message("/ok?lang=zh-CN", &[
+            (header::ACCEPT_LANGUAGE, "en"),
+            (header::COOKIE, "lang=en"),
+        ]).await, "文章创建成功");
+        // 不支持的语言回退到默认语言
+        assert_eq!(message("/ok?lang=fr", &[]).await, "文章创建成功");
+    }
+
+    #[tokio::test]
+    async fn test_validation_messages_localized() {
+        let request = RegisterRequest {
+            username: "ab".to_string(),
+            email: "not-an-email".to_string(),
+            password: "password123".to_string(),
+        };
+        let response = with_locale(Locale::En, async {
+            CustomError::from(request.validate().unwrap_err()).into_response()
+        }).await;
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["errors"], json!([
+            { "field": "email", "code": "email", "message": "Invalid email address" },
+            { "field": "username", "code": "length", "message": "Username must be 3 to 20 characters long" },
+        ]));
+        assert!(body["message"].as_str().unwrap().starts_with("Validation failed: "));
+    }
+}
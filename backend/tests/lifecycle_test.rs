@@ -0,0 +1,111 @@
+//! 停机流程与健康检查测试用例
+//! 测试后台任务跟踪、停机通知以及 /healthz、/readyz、/api/version 处理函数
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use axum::extract::State;
+    use axum::http::StatusCode;
+
+    use axum_blog_engine::handler::health::{get_version, healthz, readyz};
+    use axum_blog_engine::lifecycle::{close_database, BackgroundTasks};
+
+    #[tokio::test]
+    async fn test_wait_for_background_tasks() {
+        let tasks = BackgroundTasks::new();
+        let finished = Arc::new(AtomicBool::new(false));
+        let flag = finished.clone();
+        tasks.spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        assert!(tasks.wait(Duration::from_secs(5)).await);
+        assert!(finished.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_wait_times_out_for_stuck_task() {
+        let tasks = BackgroundTasks::new();
+        tasks.spawn(std::future::pending());
+        assert!(!tasks.wait(Duration::from_millis(50)).await);
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_shares_deadline() {
+        let tasks = BackgroundTasks::new();
+        tasks.spawn(std::future::pending());
+        // 截止时间已过（例如等待请求时已用完超时）时不再额外等待
+        let started = tokio::time::Instant::now();
+        assert!(!tasks.wait_until(started - Duration::from_millis(10)).await);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_notifies_tasks() {
+        let tasks = BackgroundTasks::new();
+        let token = tasks.shutdown_token();
+        tasks.spawn(async move { token.cancelled().await });
+
+        assert!(!tasks.is_shutting_down());
+        tasks.begin_shutdown();
+        assert!(tasks.is_shutting_down());
+        assert!(tasks.wait(Duration::from_secs(5)).await);
+    }
+
+    #[tokio::test]
+    async fn test_healthz_and_version() {
+        assert_eq!(healthz().await.0.status, "ok");
+
+        let version = get_version().await.0;
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+        assert!(!version.git_commit.is_empty());
+        assert!(!version.database_backends.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_readyz_when_migrated() {
+        let state = common::test_app_state(common::setup_test_db().await);
+        let (status, body) = readyz(State(state)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.ready);
+        assert_eq!(body.database, "ok");
+        assert_eq!(body.pending_migrations, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_readyz_with_pending_migrations() {
+        let state = common::test_app_state(common::connect_test_db().await);
+        let (status, body) = readyz(State(state)).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!body.ready);
+        assert!(body.pending_migrations.unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_readyz_while_shutting_down() {
+        let state = common::test_app_state(common::setup_test_db().await);
+        state.tasks.begin_shutdown();
+        let (status, body) = readyz(State(state)).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(body.shutting_down);
+    }
+
+    #[tokio::test]
+    async fn test_readyz_after_database_closed() {
+        let db = common::setup_test_db().await;
+        let state = common::test_app_state(db.clone());
+        close_database(db).await;
+
+        let (status, body) = readyz(State(state)).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        // 不向未认证的调用方暴露数据库错误详情
+        assert_eq!(body.database, "unavailable");
+    }
+}
@@ -0,0 +1,487 @@
+//! 媒体文件测试用例
+//! 测试 MediaRepository 的数据库操作、本地存储后端、Range 解析、文件类型识别、
+//! 元数据清除以及响应式变体的生成
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use std::io::Cursor;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use axum::body::to_bytes;
+    use sea_orm::DatabaseConnection;
+    use tokio_util::sync::CancellationToken;
+
+    // 导入项目模块
+    use axum_blog_engine::Config;
+    use axum_blog_engine::error::ErrorCode;
+    use axum_blog_engine::database::media::{media_variants, MediaRepository, NewMedia};
+    use axum_blog_engine::handler::media::build_cover_image;
+    use axum_blog_engine::media::{
+        encode_image, generate_variants, image_dimensions, process_variants, sniff_media_type,
+        strip_metadata, variant_key, MediaType, MediaVariant,
+    };
+    use axum_blog_engine::storage::{
+        parse_range_header, validate_key, ByteRange, LocalStorage, RangeNotSatisfiable, StorageBackend,
+    };
+
+    /// 创建测试数据库并执行全部迁移 (见 tests/common)
+    async fn setup_test_db() -> DatabaseConnection {
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 10).await;
+        db
+    }
+
+    fn new_media(owner_id: i32, key: &str, size_bytes: i64) -> NewMedia {
+        NewMedia {
+            owner_id,
+            storage_key: key.to_string(),
+            original_name: Some("photo.png".to_string()),
+            mime_type: "image/png".to_string(),
+            size_bytes,
+            checksum: "abc".to_string(),
+            width: Some(2),
+            height: Some(1),
+        }
+    }
+
+    /// 创建一个临时存储目录。
+    fn temp_storage_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("blog-media-{}-{}", name, std::process::id()))
+    }
+
+    fn test_config() -> Config {
+        Config {
+            jwt_secret: "test_secret".to_string(),
+            bcrypt_cost: 4,
+            token_ttl_hours: 720,
+            site_url: "https://blog.example.com".to_string(),
+            site_title: "测试博客".to_string(),
+            site_description: "一个测试博客".to_string(),
+            robots_txt: None,
+            theme_dir: None,
+            static_dir: None,
+            api_docs_bundle: None,
+            upload_max_bytes: 10 * 1024 * 1024,
+            media_quota_bytes: 100 * 1024 * 1024,
+            image_variant_widths: vec![320, 768, 1280],
+            metrics_token: None,
+            trust_forwarded_for: false,
+        }
+    }
+
+    const JPEG: MediaType = MediaType { mime_type: "image/jpeg", extension: "jpg" };
+    const PNG: MediaType = MediaType { mime_type: "image/png", extension: "png" };
+
+    /// 生成一张带渐变的图片，宽高不同以便检查方向。
+    fn gradient_image(width: u32, height: u32) -> image::DynamicImage {
+        image::DynamicImage::ImageRgb8(image::RgbImage::from_fn(width, height, |x, y| {
+            image::Rgb([(x * 255 / width) as u8, (y * 255 / height) as u8, 128])
+        }))
+    }
+
+    /// 构造一个 JPEG APP1 (EXIF) 段，包含方向和一个带 "GPS" 字样的 Make 字段。
+    fn exif_segment(orientation: u16) -> Vec<u8> {
+        let mut tiff = Vec::new();
+        tiff.extend_from_slice(b"II*\0");
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+        tiff.extend_from_slice(&2u16.to_le_bytes());
+        // Make (ASCII, 4 字节内联)
+        tiff.extend_from_slice(&0x010Fu16.to_le_bytes());
+        tiff.extend_from_slice(&2u16.to_le_bytes());
+        tiff.extend_from_slice(&4u32.to_le_bytes());
+        tiff.extend_from_slice(b"GPS\0");
+        // Orientation (SHORT)
+        tiff.extend_from_slice(&0x0112u16.to_le_bytes());
+        tiff.extend_from_slice(&3u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&orientation.to_le_bytes());
+        tiff.extend_from_slice(&[0, 0]);
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+
+        let mut payload = b"Exif\0\0".to_vec();
+        payload.extend_from_slice(&tiff);
+        let mut segment = vec![0xFF, 0xE1];
+        segment.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        segment.extend_from_slice(&payload);
+        segment
+    }
+
+    /// 在 JPEG 的 SOI 之后插入一个段。
+    fn insert_after_soi(jpeg: &[u8], segment: &[u8]) -> Vec<u8> {
+        let mut data = jpeg[..2].to_vec();
+        data.extend_from_slice(segment);
+        data.extend_from_slice(&jpeg[2..]);
+        data
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    /// 生成一张指定尺寸的 PNG 图片。
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let image = image::RgbImage::new(width, height);
+        let mut data = Vec::new();
+        image.write_to(&mut Cursor::new(&mut data), image::ImageFormat::Png).unwrap();
+        data
+    }
+
+    #[tokio::test]
+    async fn test_create_and_query_media() {
+        let db = setup_test_db().await;
+        let repo = MediaRepository::new(db);
+
+        let created = repo.create_media(new_media(1, "a.png", 100)).await.unwrap();
+        assert_eq!(created.storage_key, "a.png");
+        assert_eq!(created.width, Some(2));
+
+        let by_id = repo.get_media_by_id(created.id).await.unwrap().unwrap();
+        assert_eq!(by_id.owner_id, 1);
+        let by_key = repo.get_media_by_key("a.png").await.unwrap().unwrap();
+        assert_eq!(by_key.id, created.id);
+        assert!(repo.get_media_by_key("missing.png").await.unwrap().is_none());
+
+        // 存储键唯一
+        assert!(repo.create_media(new_media(2, "a.png", 1)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_used_bytes_and_delete() {
+        let db = setup_test_db().await;
+        let repo = MediaRepository::new(db);
+
+        // 没有上传过文件时用量为 0
+        assert_eq!(repo.get_used_bytes(1).await.unwrap(), 0);
+
+        let first = repo.create_media(new_media(1, "a.png", 100)).await.unwrap();
+        repo.create_media(new_media(1, "b.png", 250)).await.unwrap();
+        repo.create_media(new_media(2, "c.png", 999)).await.unwrap();
+
+        assert_eq!(repo.get_used_bytes(1).await.unwrap(), 350);
+        assert_eq!(repo.get_media_by_owner(1).await.unwrap().len(), 2);
+
+        repo.delete_media(first.id).await.unwrap();
+        assert_eq!(repo.get_used_bytes(1).await.unwrap(), 250);
+        assert!(repo.delete_media(first.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_used_bytes_counts_variants() {
+        let db = setup_test_db().await;
+        let repo = MediaRepository::new(db);
+
+        let media = repo.create_media(new_media(1, "a.png", 100)).await.unwrap();
+        let variant = MediaVariant {
+            width: 320,
+            height: 160,
+            mime_type: "image/webp".to_string(),
+            storage_key: "a-320w.webp".to_string(),
+            size_bytes: 40,
+        };
+        repo.set_media_variants(media.id, &[variant.clone(), MediaVariant { size_bytes: 10, ..variant }])
+            .await
+            .unwrap();
+        assert_eq!(repo.get_used_bytes(1).await.unwrap(), 150);
+    }
+
+    #[tokio::test]
+    async fn test_create_media_within_quota() {
+        let db = setup_test_db().await;
+        let repo = MediaRepository::new(db);
+
+        repo.create_media_within_quota(new_media(1, "a.png", 60), 100).await.unwrap();
+        let err = repo.create_media_within_quota(new_media(1, "b.png", 60), 100).await.unwrap_err();
+        assert_eq!(err.code(), ErrorCode::MediaQuotaExceeded);
+        // 超出配额的记录不会写入
+        assert!(repo.get_media_by_key("b.png").await.unwrap().is_none());
+        assert_eq!(repo.get_used_bytes(1).await.unwrap(), 60);
+        // 其他用户的配额不受影响
+        repo.create_media_within_quota(new_media(2, "c.png", 100), 100).await.unwrap();
+
+        // 并发上传合计不会超出配额
+        let uploads = (0..4).map(|i| {
+            let repo = repo.clone();
+            tokio::spawn(async move { repo.create_media_within_quota(new_media(3, &format!("d{}.png", i), 60), 100).await })
+        });
+        let mut created = 0;
+        for upload in uploads {
+            if upload.await.unwrap().is_ok() {
+                created += 1;
+            }
+        }
+        assert_eq!(created, 1);
+        assert_eq!(repo.get_used_bytes(3).await.unwrap(), 60);
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_roundtrip() {
+        let dir = temp_storage_dir("roundtrip");
+        let storage = LocalStorage::new(&dir);
+
+        storage.put("2024/file.txt", b"hello world", "text/plain").await.unwrap();
+        assert_eq!(storage.size("2024/file.txt").await.unwrap(), Some(11));
+        assert_eq!(storage.get("2024/file.txt", None).await.unwrap().unwrap(), b"hello world");
+
+        let range = ByteRange { start: 6, end: 10 };
+        assert_eq!(storage.get("2024/file.txt", Some(range)).await.unwrap().unwrap(), b"world");
+
+        // 流式读取与普通读取内容一致
+        let body = storage.get_stream("2024/file.txt", Some(range)).await.unwrap().unwrap();
+        assert_eq!(&to_bytes(body, usize::MAX).await.unwrap()[..], b"world");
+        let body = storage.get_stream("2024/file.txt", None).await.unwrap().unwrap();
+        assert_eq!(&to_bytes(body, usize::MAX).await.unwrap()[..], b"hello world");
+
+        storage.delete("2024/file.txt").await.unwrap();
+        assert_eq!(storage.size("2024/file.txt").await.unwrap(), None);
+        assert!(storage.get_stream("2024/file.txt", None).await.unwrap().is_none());
+        assert!(storage.get("2024/file.txt", None).await.unwrap().is_none());
+        // 重复删除视为成功
+        storage.delete("2024/file.txt").await.unwrap();
+
+        // 越界的键被拒绝
+        assert!(storage.put("../escape.txt", b"x", "text/plain").await.is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_parse_range_header() {
+        assert_eq!(parse_range_header("bytes=0-99", 1000), Ok(Some(ByteRange { start: 0, end: 99 })));
+        assert_eq!(parse_range_header("bytes=900-", 1000), Ok(Some(ByteRange { start: 900, end: 999 })));
+        assert_eq!(parse_range_header("bytes=-100", 1000), Ok(Some(ByteRange { start: 900, end: 999 })));
+        // 结束位置超出文件大小时截断
+        assert_eq!(parse_range_header("bytes=500-5000", 1000), Ok(Some(ByteRange { start: 500, end: 999 })));
+        // 后缀长度大于文件时返回整个文件
+        assert_eq!(parse_range_header("bytes=-5000", 1000), Ok(Some(ByteRange { start: 0, end: 999 })));
+
+        // 无法满足的范围
+        assert_eq!(parse_range_header("bytes=1000-", 1000), Err(RangeNotSatisfiable));
+        assert_eq!(parse_range_header("bytes=-0", 1000), Err(RangeNotSatisfiable));
+
+        // 无法识别或多段的范围被忽略
+        assert_eq!(parse_range_header("items=0-1", 1000), Ok(None));
+        assert_eq!(parse_range_header("bytes=0-1,5-6", 1000), Ok(None));
+        assert_eq!(parse_range_header("bytes=9-1", 1000), Ok(None));
+        assert_eq!(parse_range_header("bytes=abc", 1000), Ok(None));
+    }
+
+    #[test]
+    fn test_validate_key() {
+        assert!(validate_key("abc.png").is_ok());
+        assert!(validate_key("2024/05/abc.png").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("../abc.png").is_err());
+        assert!(validate_key("/etc/passwd").is_err());
+        assert!(validate_key("a/./b.png").is_ok()); // `.` 段会被 Path 规范化掉
+        assert!(validate_key("a\\b.png").is_err());
+    }
+
+    #[test]
+    fn test_sniff_media_type_and_dimensions() {
+        let png = png_bytes(3, 2);
+        let media_type = sniff_media_type(&png).unwrap();
+        assert_eq!(media_type.mime_type, "image/png");
+        assert_eq!(media_type.extension, "png");
+        assert_eq!(image_dimensions(&png), Some((3, 2)));
+
+        // 文件头决定类型，与内容声明无关
+        assert!(sniff_media_type(b"<html><script>alert(1)</script></html>").is_none());
+        assert!(sniff_media_type(b"%PDF-1.7\n").is_none());
+        assert!(image_dimensions(b"not an image").is_none());
+    }
+
+    #[test]
+    fn test_strip_jpeg_exif_losslessly() {
+        let jpeg = encode_image(&gradient_image(8, 4), JPEG).unwrap();
+        let with_exif = insert_after_soi(&jpeg, &exif_segment(1));
+        assert!(contains(&with_exif, b"Exif"));
+
+        let stripped = strip_metadata(&with_exif, JPEG).unwrap();
+        assert!(!contains(&stripped, b"Exif"));
+        assert!(!contains(&stripped, b"GPS"));
+        // 方向正常时只删除 EXIF 段，像素数据原样保留
+        assert_eq!(stripped, jpeg);
+    }
+
+    #[test]
+    fn test_strip_jpeg_applies_orientation() {
+        let jpeg = encode_image(&gradient_image(8, 4), JPEG).unwrap();
+        // 6 = 顺时针旋转 90 度显示
+        let with_exif = insert_after_soi(&jpeg, &exif_segment(6));
+
+        let stripped = strip_metadata(&with_exif, JPEG).unwrap();
+        assert!(!contains(&stripped, b"Exif"));
+        assert_eq!(image_dimensions(&stripped), Some((4, 8)));
+    }
+
+    #[test]
+    fn test_strip_png_text_chunks() {
+        let png = png_bytes(3, 2);
+        // 在 IHDR (8 字节签名 + 25 字节) 之后插入一个 tEXt 块
+        let text = b"Comment\0secret location";
+        let mut chunk = (text.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(b"tEXt");
+        chunk.extend_from_slice(text);
+        chunk.extend_from_slice(&[0, 0, 0, 0]); // CRC 不参与校验
+        let mut with_text = png[..33].to_vec();
+        with_text.extend_from_slice(&chunk);
+        with_text.extend_from_slice(&png[33..]);
+
+        let stripped = strip_metadata(&with_text, PNG).unwrap();
+        assert!(!contains(&stripped, b"secret location"));
+        assert_eq!(stripped, png);
+
+        // 结构损坏的文件被拒绝
+        assert!(strip_metadata(&png[..20], PNG).is_err());
+    }
+
+    #[test]
+    fn test_strip_webp_exif_chunk() {
+        let simple = encode_image(&gradient_image(4, 2), MediaType::WEBP).unwrap();
+        let vp8l = &simple[12..]; // 去掉 RIFF 头后的 VP8L 块
+
+        // 构造扩展格式：VP8X (带 EXIF 标志) + VP8L + EXIF
+        let mut body = b"WEBP".to_vec();
+        body.extend_from_slice(b"VP8X");
+        body.extend_from_slice(&10u32.to_le_bytes());
+        body.extend_from_slice(&[0x08, 0, 0, 0, 3, 0, 0, 1, 0, 0]); // 画布 4x2
+        body.extend_from_slice(vp8l);
+        body.extend_from_slice(b"EXIF");
+        body.extend_from_slice(&4u32.to_le_bytes());
+        body.extend_from_slice(b"GPS!");
+        let mut extended = b"RIFF".to_vec();
+        extended.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        extended.extend_from_slice(&body);
+
+        let stripped = strip_metadata(&extended, MediaType::WEBP).unwrap();
+        assert!(!contains(&stripped, b"GPS!"));
+        assert_eq!(stripped[20] & 0x08, 0); // EXIF 标志位已清除
+        assert_eq!(
+            u32::from_le_bytes(stripped[4..8].try_into().unwrap()) as usize,
+            stripped.len() - 8
+        );
+        assert_eq!(image_dimensions(&stripped), Some((4, 2)));
+    }
+
+    #[test]
+    fn test_generate_variants() {
+        let png = encode_image(&gradient_image(1000, 500), PNG).unwrap();
+        let variants = generate_variants(&png, PNG, &[320, 768, 1280, 768]).unwrap();
+
+        // 只生成小于原图宽度的尺寸，且去重
+        let png_variants: Vec<_> = variants.iter().filter(|v| v.media_type == PNG).collect();
+        assert_eq!(png_variants.len(), 2);
+        assert_eq!((png_variants[0].width, png_variants[0].height), (320, 160));
+        assert_eq!((png_variants[1].width, png_variants[1].height), (768, 384));
+        assert_eq!(image_dimensions(&png_variants[0].data), Some((320, 160)));
+
+        // 保留的 WebP 版本都比同尺寸的原格式更小
+        for webp in variants.iter().filter(|v| v.media_type == MediaType::WEBP) {
+            let original_size = png_variants.iter()
+                .find(|v| v.width == webp.width)
+                .map_or(png.len(), |v| v.data.len());
+            assert!(webp.data.len() < original_size);
+        }
+
+        // GIF 不生成变体
+        assert!(generate_variants(&png, MediaType { mime_type: "image/gif", extension: "gif" }, &[320])
+            .unwrap()
+            .is_empty());
+        assert_eq!(variant_key("abc.png", 320, "webp"), "abc-320w.webp");
+    }
+
+    #[tokio::test]
+    async fn test_process_variants() {
+        let db = setup_test_db().await;
+        let repo = MediaRepository::new(db);
+        let dir = temp_storage_dir("variants");
+        let storage = Arc::new(LocalStorage::new(&dir));
+
+        let png = encode_image(&gradient_image(800, 400), PNG).unwrap();
+        let media = repo.create_media(new_media(1, "cover.png", png.len() as i64)).await.unwrap();
+        assert!(media_variants(&media).is_none()); // 尚未处理
+
+        let variants = process_variants(
+            storage.clone(), repo.clone(), media.id, "cover.png".to_string(), png.clone(), PNG, vec![320],
+            CancellationToken::new(),
+        ).await.unwrap();
+        assert!(variants.iter().any(|v| v.storage_key == "cover-320w.png" && v.height == 160));
+        for variant in &variants {
+            assert_eq!(storage.size(&variant.storage_key).await.unwrap(), Some(variant.size_bytes));
+        }
+        let saved = repo.get_media_by_id(media.id).await.unwrap().unwrap();
+        assert_eq!(media_variants(&saved).unwrap(), variants);
+
+        // 处理期间记录被删除：返回错误并清理已写入的文件
+        let result = process_variants(
+            storage.clone(), repo.clone(), 999, "gone.png".to_string(), png.clone(), PNG, vec![320],
+            CancellationToken::new(),
+        ).await;
+        assert!(result.is_err());
+        assert_eq!(storage.size("gone-320w.png").await.unwrap(), None);
+
+        // 停机后中止处理：不写入变体，记录保持未处理状态
+        let pending = repo.create_media(new_media(1, "pending.png", png.len() as i64)).await.unwrap();
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+        let result = process_variants(
+            storage.clone(), repo.clone(), pending.id, "pending.png".to_string(), png, PNG, vec![320], shutdown,
+        ).await;
+        assert!(result.is_err());
+        assert_eq!(storage.size("pending-320w.png").await.unwrap(), None);
+        assert!(media_variants(&repo.get_media_by_id(pending.id).await.unwrap().unwrap()).is_none());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_build_cover_image() {
+        let config = test_config();
+        assert_eq!(config.media_key_from_url("https://blog.example.com/media/a.png"), Some("a.png"));
+        assert_eq!(config.media_key_from_url("/media/a.png"), Some("a.png"));
+        assert_eq!(config.media_key_from_url("https://cdn.example.com/media/a.png"), None);
+
+        // 外部图片原样返回
+        let external = build_cover_image(&config, "https://cdn.example.com/c.jpg", None);
+        assert_eq!(external.url, "https://cdn.example.com/c.jpg");
+        assert!(external.srcset.is_none());
+
+        let variant = |width: u32, mime_type: &str, key: &str| MediaVariant {
+            width,
+            height: width / 2,
+            mime_type: mime_type.to_string(),
+            storage_key: key.to_string(),
+            size_bytes: 1,
+        };
+        let variants = vec![
+            variant(768, "image/jpeg", "a-768w.jpg"),
+            variant(320, "image/webp", "a-320w.webp"),
+            variant(320, "image/jpeg", "a-320w.jpg"),
+        ];
+        let media = axum_blog_engine::database::media::Model {
+            id: 1,
+            owner_id: 1,
+            storage_key: "a.jpg".to_string(),
+            original_name: None,
+            mime_type: "image/jpeg".to_string(),
+            size_bytes: 1,
+            checksum: String::new(),
+            width: Some(1600),
+            height: Some(800),
+            variants: Some(serde_json::to_string(&variants).unwrap()),
+            created_at: chrono::Utc::now().naive_utc(),
+        };
+
+        let cover = build_cover_image(&config, "/media/a.jpg", Some(&media));
+        assert_eq!(cover.url, "https://blog.example.com/media/a.jpg");
+        assert_eq!(cover.width, Some(1600));
+        assert_eq!(
+            cover.srcset.as_deref(),
+            Some("https://blog.example.com/media/a-320w.jpg 320w, https://blog.example.com/media/a-768w.jpg 768w, https://blog.example.com/media/a.jpg 1600w")
+        );
+        assert_eq!(cover.webp_srcset.as_deref(), Some("https://blog.example.com/media/a-320w.webp 320w"));
+    }
+}
@@ -0,0 +1,178 @@
+//! Prometheus 指标测试用例
+//! 测试指标输出格式、数据库语句耗时与连接池指标、业务计数器、/metrics 令牌保护以及请求计时中间件
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use std::sync::Arc;
+
+    use axum::body::Body;
+    use axum::extract::State;
+    use axum::http::{header, HeaderMap, HeaderValue, Request, StatusCode};
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use sea_orm::{ConnectionTrait, DatabaseConnection, Statement};
+    use tower::ServiceExt;
+
+    use axum_blog_engine::error::{CustomError, ErrorCode};
+use axum_blog_engine::handler::audit::ClientIp;
+    use axum_blog_engine::handler::idl::{LoginRequest, RegisterRequest};
+    use axum_blog_engine::handler::metrics::{get_metrics, tag_matched_route, track_metrics};
+    use axum_blog_engine::handler::user::{login_user, register_user};
+    use axum_blog_engine::metrics::Metrics;
+    use axum_blog_engine::{AppState, Config};
+
+    /// 在输出中查找某个序列（指标名加标签）的值
+    fn sample(output: &str, series: &str) -> Option<f64> {
+        output
+            .lines()
+            .find_map(|line| line.strip_prefix(series)?.strip_prefix(' ')?.parse().ok())
+    }
+
+    /// 使用带语句耗时回调的数据库连接构建应用状态
+    async fn instrumented_state() -> AppState {
+        let metrics = Arc::new(Metrics::new());
+        let mut db: DatabaseConnection = common::setup_test_db().await;
+        metrics.instrument_database(&mut db);
+        AppState { metrics, ..common::test_app_state(db) }
+    }
+
+    #[tokio::test]
+    async fn test_render_lists_all_metrics() {
+        let db = common::setup_test_db().await;
+        let output = Metrics::new().render(&db);
+
+        for name in [
+            "blog_db_pool_connections",
+            "blog_db_pool_max_connections",
+            "blog_user_registrations_total",
+            "blog_user_logins_total",
+            "blog_posts_published_total",
+            "blog_comments_created_total",
+        ] {
+            assert!(output.contains(&format!("# TYPE {}", name)), "缺少指标 {}", name);
+        }
+        assert_eq!(sample(&output, r#"blog_user_logins_total{result="failure"}"#), Some(0.0));
+        assert!(sample(&output, "blog_db_pool_max_connections").unwrap() >= 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_db_query_duration_by_operation() {
+        let state = instrumented_state().await;
+        let backend = state.db.get_database_backend();
+        state.db.query_one(Statement::from_string(backend, "SELECT 1")).await.unwrap();
+        let _ = state.db.query_one(Statement::from_string(backend, "SELECT * FROM no_such_table")).await;
+
+        let output = state.metrics.render(&state.db);
+        assert!(sample(&output, r#"blog_db_query_duration_seconds_count{operation="SELECT",success="true"}"#).unwrap() >= 1.0);
+        assert_eq!(
+            sample(&output, r#"blog_db_query_duration_seconds_count{operation="SELECT",success="false"}"#),
+            Some(1.0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_registration_and_login_counters() {
+        let state = common::test_app_state(common::setup_test_db().await);
+
+        let _ = register_user(State(state.clone()), ClientIp(None), Json(RegisterRequest {
+            username: "metrics".to_string(),
+            email: "metrics@example.com".to_string(),
+            password: "password123".to_string(),
+        })).await.unwrap();
+        let _ = login_user(State(state.clone()), ClientIp(None), Json(LoginRequest {
+            username_or_email: "metrics".to_string(),
+            password: "password123".to_string(),
+        })).await.unwrap();
+        for username in ["metrics", "nobody"] {
+            let result = login_user(State(state.clone()), ClientIp(None), Json(LoginRequest {
+                username_or_email: username.to_string(),
+                password: "wrong_password".to_string(),
+            })).await;
+            assert!(matches!(result, Err(e) if e.code() == ErrorCode::AuthInvalidCredentials));
+        }
+
+        let output = state.metrics.render(&state.db);
+        assert_eq!(sample(&output, "blog_user_registrations_total"), Some(1.0));
+        assert_eq!(sample(&output, r#"blog_user_logins_total{result="success"}"#), Some(1.0));
+        assert_eq!(sample(&output, r#"blog_user_logins_total{result="failure"}"#), Some(2.0));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_without_token() {
+        let state = common::test_app_state(common::setup_test_db().await);
+        let response = get_metrics(State(state), HeaderMap::new()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("text/plain; version=0.0.4"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_requires_token() {
+        let state = AppState {
+            config: Arc::new(Config { metrics_token: Some("scrape-token".to_string()), ..common::test_config() }),
+            ..common::test_app_state(common::setup_test_db().await)
+        };
+
+        let result = get_metrics(State(state.clone()), HeaderMap::new()).await;
+        assert!(matches!(result, Err(CustomError::Unauthorized(_))));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer wrong"));
+        let result = get_metrics(State(state.clone()), headers).await;
+        assert!(matches!(result, Err(CustomError::Unauthorized(_))));
+
+        // 令牌的前缀同样被拒绝
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer scrape-toke"));
+        let result = get_metrics(State(state.clone()), headers).await;
+        assert!(matches!(result, Err(CustomError::Unauthorized(_))));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer scrape-token"));
+        let response = get_metrics(State(state), headers).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_http_metrics_use_route_template() {
+        let state = common::test_app_state(common::setup_test_db().await);
+        let app = Router::new()
+            .route("/api/post/:id", get(|| async { "ok" }))
+            .route_layer(axum::middleware::from_fn(tag_matched_route))
+            .fallback(|| async { StatusCode::NOT_FOUND })
+            .layer(axum::middleware::from_fn_with_state(state.clone(), track_metrics))
+            .with_state(state.clone());
+
+        for uri in ["/api/post/1", "/api/post/2", "/missing/page"] {
+            let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            app.clone().oneshot(request).await.unwrap();
+        }
+        // 非标准方法统一记为 OTHER
+        for method in ["PURGE", "X-RANDOM-1"] {
+            let request = Request::builder().method(method).uri("/missing/page").body(Body::empty()).unwrap();
+            app.clone().oneshot(request).await.unwrap();
+        }
+
+        let output = state.metrics.render(&state.db);
+        assert_eq!(
+            sample(&output, r#"blog_http_requests_total{method="GET",route="/api/post/:id",status="200"}"#),
+            Some(2.0)
+        );
+        assert_eq!(
+            sample(&output, r#"blog_http_requests_total{method="GET",route="<unmatched>",status="404"}"#),
+            Some(1.0)
+        );
+        assert_eq!(
+            sample(&output, r#"blog_http_request_duration_seconds_count{method="GET",route="/api/post/:id",status="200"}"#),
+            Some(2.0)
+        );
+        assert_eq!(
+            sample(&output, r#"blog_http_requests_total{method="OTHER",route="<unmatched>",status="404"}"#),
+            Some(2.0)
+        );
+        assert!(!output.contains("/api/post/1"));
+        assert!(!output.contains("PURGE"));
+    }
+}
@@ -0,0 +1,295 @@
+//! 数据库迁移测试用例
+//! 测试迁移的执行、回滚、状态查询以及与旧数据库的兼容
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend};
+
+    // 导入项目模块
+    use axum_blog_engine::database::comment::CommentRepository;
+    use axum_blog_engine::database::favorite::FavoriteRepository;
+    use axum_blog_engine::database::post::PostRepository;
+    use axum_blog_engine::handler::idl::PostListRequest;
+    use axum_blog_engine::migration::{migrations, Migrator};
+
+    const TABLES: [&str; 6] = ["audit_log", "comments", "favorites", "media", "posts", "users"];
+
+    /// 引入迁移之前 init.sql 中的建表语句（SQLite），不含初始数据
+    const INIT_SQL: &str = "
+        CREATE TABLE IF NOT EXISTS users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT UNIQUE NOT NULL CHECK(length(username) >= 3),
+            email TEXT UNIQUE NOT NULL CHECK(email LIKE '%@%.%'),
+            password_hash TEXT NOT NULL CHECK(length(password_hash) > 0),
+            role TEXT NOT NULL DEFAULT 'user' CHECK(role IN ('user', 'admin')),
+            created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now')),
+            updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now'))
+        );
+        CREATE TABLE IF NOT EXISTS posts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT NOT NULL,
+            content_markdown TEXT NOT NULL,
+            category TEXT NOT NULL DEFAULT '未分类',
+            author_id INTEGER NOT NULL,
+            is_published INTEGER NOT NULL DEFAULT 0,
+            view_count INTEGER NOT NULL DEFAULT 0,
+            cover_url TEXT,
+            created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now')),
+            updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now')),
+            FOREIGN KEY (author_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+        CREATE TABLE IF NOT EXISTS comments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            content TEXT NOT NULL,
+            post_id INTEGER NOT NULL,
+            user_id INTEGER NOT NULL,
+            parent_id INTEGER,
+            created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now')),
+            FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+            FOREIGN KEY (parent_id) REFERENCES comments(id) ON DELETE CASCADE
+        );
+        CREATE TABLE IF NOT EXISTS favorites (
+            user_id INTEGER NOT NULL,
+            post_id INTEGER NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now')),
+            PRIMARY KEY (user_id, post_id),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+            FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+        );
+        CREATE INDEX idx_posts_author ON posts(author_id);
+        CREATE INDEX idx_posts_category ON posts(category);
+        CREATE INDEX idx_comments_post ON comments(post_id);
+        CREATE INDEX idx_comments_user ON comments(user_id);
+        CREATE INDEX idx_favorites_user ON favorites(user_id);
+    ";
+
+    async fn connect() -> DatabaseConnection {
+        common::connect_test_db().await
+    }
+
+    /// 查询当前数据库中存在的业务表（通过查询各表判断，与具体数据库无关）
+    async fn table_names(db: &DatabaseConnection) -> Vec<String> {
+        let mut names = Vec::new();
+        for table in TABLES {
+            if db.execute_unprepared(&format!("SELECT 1 FROM {} WHERE 1 = 0", table)).await.is_ok() {
+                names.push(table.to_string());
+            }
+        }
+        names
+    }
+
+    #[tokio::test]
+    async fn test_up_creates_all_tables() {
+        let db = connect().await;
+
+        let executed = Migrator::up(&db, None).await.unwrap();
+        assert_eq!(executed.len(), migrations().len());
+        assert_eq!(table_names(&db).await, vec!["audit_log", "comments", "favorites", "media", "posts", "users"]);
+
+        // 再次执行不会重复应用
+        assert!(Migrator::up(&db, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_applied_and_pending() {
+        let db = connect().await;
+
+        let status = Migrator::status(&db).await.unwrap();
+        assert!(status.iter().all(|s| s.applied_at.is_none()));
+
+        let executed = Migrator::up(&db, Some(2)).await.unwrap();
+        assert_eq!(executed, vec!["m20240101_000001_create_users", "m20240101_000002_create_posts"]);
+
+        let status = Migrator::status(&db).await.unwrap();
+        assert_eq!(status.len(), migrations().len());
+        assert!(status[0].applied_at.is_some());
+        assert!(status[1].applied_at.is_some());
+        assert!(status[2..].iter().all(|s| s.applied_at.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_down_reverts_in_reverse_order() {
+        let db = connect().await;
+        Migrator::up(&db, None).await.unwrap();
+
+        // 最后四个迁移只修改了列，回滚后列被删除或恢复原类型，表仍然存在
+        let reverted = Migrator::down(&db, Some(4)).await.unwrap();
+        assert_eq!(
+            reverted,
+            vec![
+                "m20261018_000005_add_post_text_stats",
+                "m20261018_000004_post_is_published_boolean",
+                "m20261018_000003_add_post_content_columns",
+                "m20261018_000002_add_user_profile",
+            ]
+        );
+        assert!(db.execute_unprepared("SELECT content_html FROM posts WHERE 1 = 0").await.is_err());
+        assert!(db.execute_unprepared("SELECT display_name FROM users WHERE 1 = 0").await.is_err());
+        assert!(table_names(&db).await.contains(&"posts".to_string()));
+        assert!(table_names(&db).await.contains(&"users".to_string()));
+
+        let reverted = Migrator::down(&db, Some(1)).await.unwrap();
+        assert_eq!(reverted, vec!["m20261018_000001_create_audit_log"]);
+        assert!(!table_names(&db).await.contains(&"audit_log".to_string()));
+
+        Migrator::down(&db, None).await.unwrap();
+        assert!(table_names(&db).await.is_empty());
+        assert!(Migrator::status(&db).await.unwrap().iter().all(|s| s.applied_at.is_none()));
+
+        // 回滚后可以重新执行
+        Migrator::up(&db, None).await.unwrap();
+        assert_eq!(table_names(&db).await.len(), TABLES.len());
+    }
+
+    #[tokio::test]
+    async fn test_up_on_database_created_by_init_sql() {
+        // 之前通过 init.sql 手动建表的数据库：迁移使用 IF NOT EXISTS，已有数据保留
+        let db = connect().await;
+        if db.get_database_backend() != DbBackend::Sqlite {
+            return; // init.sql 只用于 SQLite
+        }
+        db.execute_unprepared(INIT_SQL).await.unwrap();
+        db.execute_unprepared(
+            "INSERT INTO users (username, email, password_hash) VALUES ('alice', 'alice@example.com', 'hash');
+            INSERT INTO posts (title, content_markdown, author_id, is_published) VALUES ('旧文章', '**旧内容**', 1, 1);
+            INSERT INTO comments (content, post_id, user_id) VALUES ('旧评论', 1, 1);
+            INSERT INTO favorites (user_id, post_id) VALUES (1, 1);",
+        )
+        .await
+        .unwrap();
+
+        Migrator::up(&db, None).await.unwrap();
+
+        for table in ["users", "posts", "comments", "favorites"] {
+            let row = db
+                .query_one(sea_orm::Statement::from_string(
+                    db.get_database_backend(),
+                    format!("SELECT COUNT(*) AS count FROM {}", table),
+                ))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(row.try_get::<i64>("", "count").unwrap(), 1, "{} 中的已有数据应保留", table);
+        }
+        // 后续迁移添加的列也补到了已有的表上
+        db.execute_unprepared("SELECT display_name, avatar_url FROM users").await.unwrap();
+        db.execute_unprepared("SELECT excerpt, content_html, content_hash, toc_json FROM posts").await.unwrap();
+
+        // 已有数据可以通过 Repository 正常读取
+        let posts = PostRepository::new(db.clone());
+        let post = posts.get_post_by_id(1).await.unwrap().unwrap();
+        assert!(post.is_published);
+        assert!(posts.get_rendered_content(&post).await.unwrap().html.contains("<strong>旧内容</strong>"));
+        let (summaries, _, _, total) = posts.get_paginated_posts(PostListRequest::default()).await.unwrap();
+        assert_eq!(total, 1);
+        // 字数和自动摘要在迁移时为已有文章补齐
+        assert_eq!(summaries[0].excerpt, "旧内容");
+        assert_eq!(summaries[0].word_count, 3);
+        assert_eq!(CommentRepository::new(db.clone()).count_comments_by_post_id(1).await.unwrap(), 1);
+        assert!(FavoriteRepository::new(db).is_favorited(1, 1).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_up_on_database_partially_migrated_by_old_schema() {
+        // 旧版建表迁移直接创建了完整的文章表：补列迁移应跳过已存在的列
+        let db = connect().await;
+        Migrator::up(&db, None).await.unwrap();
+        Migrator::down(&db, Some(3)).await.unwrap();
+        db.execute_unprepared("ALTER TABLE posts ADD COLUMN content_html TEXT").await.unwrap();
+
+        Migrator::up(&db, None).await.unwrap();
+        db.execute_unprepared("SELECT excerpt, content_html, content_hash, toc_json FROM posts").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_is_published_is_converted_to_boolean() {
+        // 发布状态改为布尔类型之前写入的整数数据在迁移后仍可读取
+        let db = connect().await;
+        Migrator::up(&db, Some(migrations().len() - 2)).await.unwrap();
+        db.execute_unprepared("INSERT INTO users (username, email, password_hash) VALUES ('alice', 'alice@example.com', 'hash')")
+            .await
+            .unwrap();
+        db.execute_unprepared(
+            "INSERT INTO posts (title, content_markdown, author_id, is_published) \
+             SELECT '已发布', 'c', id, 1 FROM users UNION ALL SELECT '草稿', 'c', id, 0 FROM users",
+        )
+        .await
+        .unwrap();
+
+        let executed = Migrator::up(&db, Some(1)).await.unwrap();
+        assert_eq!(executed, vec!["m20261018_000004_post_is_published_boolean"]);
+
+        let rows = db
+            .query_all(sea_orm::Statement::from_string(
+                db.get_database_backend(),
+                "SELECT title, is_published FROM posts WHERE is_published = TRUE",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].try_get::<String>("", "title").unwrap(), "已发布");
+        assert!(rows[0].try_get::<bool>("", "is_published").unwrap());
+
+        // 回滚后恢复为整数
+        Migrator::down(&db, Some(1)).await.unwrap();
+        db.execute_unprepared("UPDATE posts SET is_published = 1").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_constraints_are_enforced() {
+        let db = connect().await;
+        Migrator::up(&db, None).await.unwrap();
+
+        // 外键：作者不存在
+        let result = db
+            .execute_unprepared("INSERT INTO posts (title, content_markdown, author_id) VALUES ('t', 'c', 42)")
+            .await;
+        assert!(result.is_err());
+
+        // CHECK：角色只能是 user / admin
+        let result = db
+            .execute_unprepared(
+                "INSERT INTO users (username, email, password_hash, role) VALUES ('bob', 'bob@example.com', 'hash', 'root')",
+            )
+            .await;
+        assert!(result.is_err());
+
+        // 默认值
+        db.execute_unprepared("INSERT INTO users (username, email, password_hash) VALUES ('bob', 'bob@example.com', 'hash')")
+            .await
+            .unwrap();
+        // 失败的插入在 PostgreSQL 中也会消耗自增序列，因此按用户名查找 id
+        db.execute_unprepared(
+            "INSERT INTO posts (title, content_markdown, author_id) SELECT 't', 'c', id FROM users WHERE username = 'bob'",
+        )
+            .await
+            .unwrap();
+        let row = db
+            .query_one(sea_orm::Statement::from_string(
+                db.get_database_backend(),
+                "SELECT category, is_published, view_count FROM posts",
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.try_get::<String>("", "category").unwrap(), "未分类");
+        assert!(!row.try_get::<bool>("", "is_published").unwrap());
+        assert_eq!(row.try_get::<i32>("", "view_count").unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_applied_version_is_rejected() {
+        let db = connect().await;
+        Migrator::up(&db, None).await.unwrap();
+        db.execute_unprepared("INSERT INTO seaql_migrations (version, applied_at) VALUES ('m29990101_000001_future', 0)")
+            .await
+            .unwrap();
+
+        assert!(Migrator::up(&db, None).await.is_err());
+        assert!(Migrator::status(&db).await.is_err());
+    }
+}
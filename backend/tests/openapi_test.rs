@@ -0,0 +1,196 @@
+//! OpenAPI 文档测试用例
+//! 测试每条 `/api` 路由实际允许的方法都写进了文档、文档结构完整，
+//! 以及 `/api/openapi.json`、`/api/docs` 和本站提供的文档脚本可以访问
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use std::collections::{BTreeSet, HashSet};
+    use std::sync::Arc;
+
+    use axum::body::{to_bytes, Body};
+    use axum::http::{header, Request, StatusCode};
+    use axum::Router;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    use axum_blog_engine::handler::openapi::api_routes;
+    use axum_blog_engine::openapi::openapi_path;
+    use axum_blog_engine::{AppState, Config};
+
+    async fn app() -> Router {
+        let db = common::setup_test_db().await;
+        Router::new()
+            .nest("/api", api_routes().into_router())
+            .with_state(common::test_app_state(db))
+    }
+
+    /// 把路径参数替换成具体值，得到可以请求的地址
+    fn concrete_uri(path: &str) -> String {
+        let path = path
+            .split('/')
+            .map(|segment| {
+                if segment.starts_with(':') {
+                    "1"
+                } else if segment.starts_with('*') {
+                    "x"
+                } else {
+                    segment
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        format!("/api{}", path)
+    }
+
+    /// 收集文档中所有 `$ref` 的值
+    fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    match (key.as_str(), value) {
+                        ("$ref", Value::String(r)) => refs.push(r),
+                        _ => collect_refs(value, refs),
+                    }
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|item| collect_refs(item, refs)),
+            _ => {}
+        }
+    }
+
+    /// 用路由不支持的方法请求每条路由，`Allow` 头列出的方法必须与文档一致；
+    /// 新增路由或方法而没有写文档时这里会失败
+    #[tokio::test]
+    async fn test_every_route_method_is_documented() {
+        let api = api_routes();
+        let app = app().await;
+
+        let paths: BTreeSet<&str> = api.routes().iter().map(String::as_str).collect();
+        for path in paths {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().method("PATCH").uri(concrete_uri(path)).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED, "{}", path);
+
+            let allowed: BTreeSet<String> = response.headers()[header::ALLOW]
+                .to_str()
+                .unwrap()
+                .split(',')
+                .map(|m| m.trim().to_string())
+                .filter(|m| !m.is_empty() && m != "HEAD")
+                .collect();
+            let documented: BTreeSet<String> = api
+                .operations()
+                .iter()
+                .filter(|op| op.path() == path)
+                .map(|op| op.method().to_string())
+                .collect();
+            assert_eq!(allowed, documented, "{} 的方法与文档不一致", path);
+        }
+    }
+
+    #[test]
+    fn test_operations_complete() {
+        let api = api_routes();
+        let document = api.openapi("/api");
+
+        let mut ids = HashSet::new();
+        for op in api.operations() {
+            assert!(!op.summary().is_empty(), "{} {} 缺少摘要", op.method(), op.path());
+            assert!(!op.tag().is_empty(), "{} {} 缺少分组", op.method(), op.path());
+
+            let item = &document["paths"][openapi_path(op.path())][op.method().as_str().to_lowercase()];
+            let id = item["operationId"].as_str().unwrap();
+            assert!(ids.insert(id.to_string()), "operationId 重复: {}", id);
+            assert!(item["responses"]["default"].is_object());
+        }
+        assert_eq!(document["paths"]["/post/{id}"]["get"]["parameters"][0]["name"], "id");
+        assert_eq!(document["paths"]["/post/{id}"]["put"]["security"][0]["bearerAuth"], Value::Array(vec![]));
+        assert_eq!(document["paths"]["/user/{id}"]["delete"]["x-admin-only"], true);
+    }
+
+    #[test]
+    fn test_refs_resolve() {
+        let document = api_routes().openapi("/api");
+        let mut refs = Vec::new();
+        collect_refs(&document, &mut refs);
+        assert!(!refs.is_empty());
+
+        for r in refs {
+            let pointer = r.strip_prefix('#').unwrap();
+            assert!(document.pointer(pointer).is_some(), "无法解析 {}", r);
+        }
+        let post = &document["components"]["schemas"]["PostInfo"];
+        assert_eq!(post["type"], "object");
+        assert!(post["required"].as_array().unwrap().iter().any(|f| f == "title"));
+    }
+
+    #[tokio::test]
+    async fn test_serve_openapi_document_and_docs() {
+        let app = app().await;
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/openapi.json").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let document: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(document["openapi"], "3.1.0");
+        assert_eq!(document["servers"][0]["url"], "/api");
+        assert!(document["paths"]["/user/register"]["post"].is_object());
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/docs").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8(body.to_vec()).unwrap().contains("/api/openapi.json"));
+    }
+
+    async fn get(app: &Router, uri: &str) -> (StatusCode, Option<String>, String) {
+        let response = app.clone().oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+        let status = response.status();
+        let content_type = response.headers().get(header::CONTENT_TYPE).map(|v| v.to_str().unwrap().to_string());
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, content_type, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_docs_bundle_is_served_locally() {
+        // 未配置脚本时页面只提供 OpenAPI 链接，脚本返回 404
+        let app = app().await;
+        let (_, _, page) = get(&app, "/api/docs").await;
+        assert!(!page.contains("<script"));
+        let (status, _, _) = get(&app, "/api/docs/scalar.js").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let bundle = std::env::temp_dir().join(format!("blog-scalar-{}.js", std::process::id()));
+        std::fs::write(&bundle, "console.log('scalar');").unwrap();
+        let state = AppState {
+            config: Arc::new(Config { api_docs_bundle: Some(bundle.clone()), ..common::test_config() }),
+            ..common::test_app_state(common::setup_test_db().await)
+        };
+        let app = Router::new().nest("/api", api_routes().into_router()).with_state(state);
+
+        // 页面只加载本站的脚本
+        let (status, _, page) = get(&app, "/api/docs").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(page.contains(r#"<script src="/api/docs/scalar.js"></script>"#));
+        assert!(!page.contains("https://"));
+
+        let (status, content_type, script) = get(&app, "/api/docs/scalar.js").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some("text/javascript; charset=utf-8"));
+        assert_eq!(script, "console.log('scalar');");
+
+        std::fs::remove_file(&bundle).ok();
+    }
+}
@@ -0,0 +1,181 @@
+//! 公开页面测试用例
+//! 测试模板主题的加载与渲染，以及 OpenGraph / JSON-LD 元数据的生成
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+    use serde_json::json;
+    use axum_blog_engine::Config;
+    use axum_blog_engine::handler::idl::{CoverImage, PostInfo};
+    use axum_blog_engine::render::render_markdown;
+    use axum_blog_engine::seo::{list_page_meta, post_page_meta};
+    use axum_blog_engine::theme::Theme;
+
+    fn test_config() -> Config {
+        Config {
+            jwt_secret: "test_secret".to_string(),
+            bcrypt_cost: 4,
+            token_ttl_hours: 720,
+            site_url: "https://blog.example.com".to_string(),
+            site_title: "测试博客".to_string(),
+            site_description: "一个测试博客".to_string(),
+            robots_txt: None,
+            theme_dir: None,
+            static_dir: None,
+            api_docs_bundle: None,
+            upload_max_bytes: 10 * 1024 * 1024,
+            media_quota_bytes: 100 * 1024 * 1024,
+            image_variant_widths: vec![320, 768, 1280],
+            metrics_token: None,
+            trust_forwarded_for: false,
+        }
+    }
+
+    fn test_post(cover_url: Option<&str>) -> PostInfo {
+        let time = NaiveDateTime::parse_from_str("2024-05-01 10:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let rendered = render_markdown("## 第一节\n\n正文内容");
+        PostInfo {
+            id: 7,
+            title: "Hello </script> 世界".to_string(),
+            content_markdown: "## 第一节\n\n正文内容".to_string(),
+            category: "技术".to_string(),
+            author_id: 3,
+            is_published: true,
+            view_count: 0,
+            cover_url: cover_url.map(str::to_string),
+            cover: None,
+            excerpt: None,
+            content_html: Some(rendered.html),
+            toc: Some(rendered.toc),
+            created_at: time,
+            updated_at: time,
+            author: None,
+            comment_count: None,
+            favorite_count: None,
+        }
+    }
+
+    #[test]
+    fn test_post_page_meta_and_json_ld() {
+        let config = test_config();
+        let meta = post_page_meta(&config, &test_post(Some("https://cdn.example.com/c.jpg")), "摘要", "alice");
+
+        assert_eq!(meta.canonical_url, "https://blog.example.com/post/7");
+        assert_eq!(meta.og_type, "article");
+        assert_eq!(meta.twitter_card, "summary_large_image");
+        assert_eq!(meta.published_time.as_deref(), Some("2024-05-01T10:00:00+00:00"));
+        assert_eq!(meta.author_url.as_deref(), Some("https://blog.example.com/author/3"));
+
+        let json_ld = meta.json_ld.unwrap();
+        // 标题中的 </script> 被转义，不会提前结束脚本块
+        assert!(!json_ld.contains("</script>"));
+        let value: serde_json::Value = serde_json::from_str(&json_ld).unwrap();
+        assert_eq!(value["@type"], "BlogPosting");
+        assert_eq!(value["headline"], "Hello </script> 世界");
+        assert_eq!(value["author"]["name"], "alice");
+        assert_eq!(value["image"], "https://cdn.example.com/c.jpg");
+        assert_eq!(value["mainEntityOfPage"]["@id"], "https://blog.example.com/post/7");
+    }
+
+    #[test]
+    fn test_post_page_image_is_absolute() {
+        let config = test_config();
+        let meta = post_page_meta(&config, &test_post(Some("/media/1/cover.jpg")), "摘要", "alice");
+        assert_eq!(meta.image.as_deref(), Some("https://blog.example.com/media/1/cover.jpg"));
+        let value: serde_json::Value = serde_json::from_str(&meta.json_ld.unwrap()).unwrap();
+        assert_eq!(value["image"], "https://blog.example.com/media/1/cover.jpg");
+
+        // 本站上传的封面使用 CoverImage 中的地址
+        let mut post = test_post(Some("/media/1/cover.jpg"));
+        post.cover = Some(CoverImage {
+            url: config.media_url("1/cover.jpg"),
+            width: Some(1600),
+            height: Some(900),
+            srcset: None,
+            webp_srcset: None,
+        });
+        let meta = post_page_meta(&config, &post, "摘要", "alice");
+        assert_eq!(meta.image.as_deref(), Some("https://blog.example.com/media/1/cover.jpg"));
+        assert_eq!(meta.twitter_card, "summary_large_image");
+    }
+
+    #[test]
+    fn test_post_page_without_cover_uses_summary_card() {
+        let meta = post_page_meta(&test_config(), &test_post(None), "摘要", "alice");
+
+        assert_eq!(meta.twitter_card, "summary");
+        assert!(meta.image.is_none());
+    }
+
+    #[test]
+    fn test_render_post_template() {
+        let config = test_config();
+        let post = test_post(None);
+        let meta = post_page_meta(&config, &post, "摘要 & 描述", "alice");
+
+        let html = Theme::default().render("post.html", json!({
+            "site": { "title": config.site_title, "description": config.site_description, "url": config.site_url },
+            "meta": meta,
+            "post": post,
+            "author_name": "alice",
+            "category_url": config.category_url("技术"),
+            "published_display": "2024-05-01",
+            "reading_time_minutes": 1,
+            "highlight_theme": "inspired-github",
+        })).unwrap();
+
+        assert!(html.contains("<link rel=\"canonical\" href=\"https://blog.example.com/post/7\">"));
+        assert!(html.contains("<meta property=\"og:type\" content=\"article\">"));
+        assert!(html.contains("<meta name=\"description\" content=\"摘要 &amp; 描述\">"));
+        assert!(html.contains("<script type=\"application/ld+json\">"));
+        // 标题被转义，正文 HTML 原样输出
+        assert!(html.contains("<h1>Hello &lt;/script&gt; 世界</h1>"));
+        assert!(html.contains("<h2 id=\"h-第一节\">第一节</h2>"));
+        assert!(html.contains("<a href=\"#h-第一节\">第一节</a>"));
+        assert!(html.contains("href=\"https://blog.example.com/category/%E6%8A%80%E6%9C%AF\""));
+    }
+
+    #[test]
+    fn test_render_list_template() {
+        let config = test_config();
+        let meta = list_page_meta(
+            &config,
+            "分类: 技术",
+            "技术分类下的文章",
+            config.category_url("技术"),
+            format!("{}/feed.xml", config.category_url("技术")),
+        );
+
+        let html = Theme::default().render("list.html", json!({
+            "site": { "title": config.site_title, "description": config.site_description, "url": config.site_url },
+            "meta": meta,
+            "heading": "分类: 技术",
+            "posts": [],
+            "prev_url": null,
+            "next_url": "https://blog.example.com/category/x?page=2",
+            "highlight_theme": "inspired-github",
+        })).unwrap();
+
+        assert!(html.contains("<meta property=\"og:type\" content=\"website\">"));
+        assert!(html.contains("暂无文章"));
+        assert!(html.contains("下一页"));
+        assert!(!html.contains("上一页"));
+    }
+
+    #[test]
+    fn test_theme_dir_overrides_builtin_template() {
+        let dir = std::env::temp_dir().join(format!("blog-theme-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("not_found.html"), "自定义 404: {{ message }}").unwrap();
+
+        let theme = Theme::load(Some(&dir)).unwrap();
+        let html = theme.render("not_found.html", json!({ "message": "没有这篇文章" })).unwrap();
+        assert_eq!(html, "自定义 404: 没有这篇文章");
+
+        // 模板语法错误在加载时即报错
+        std::fs::write(dir.join("post.html"), "{% if %}").unwrap();
+        assert!(Theme::load(Some(&dir)).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,218 @@
+//! 游标分页测试用例
+//! 测试游标的编码与校验、文章 / 评论 / 收藏列表的前后翻页（含创建时间相同的数据），
+//! 以及列表接口的游标模式、`Link` 响应头和兼容的 page / limit 模式
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+
+    use axum::body::{to_bytes, Body};
+    use axum::http::{header, Request, StatusCode};
+    use axum::Router;
+    use chrono::NaiveDate;
+    use sea_orm::{ConnectionTrait, DatabaseConnection};
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    use axum_blog_engine::database::comment::CommentRepository;
+    use axum_blog_engine::database::favorite::FavoriteRepository;
+    use axum_blog_engine::database::post::PostRepository;
+    use axum_blog_engine::error::ErrorCode;
+    use axum_blog_engine::handler::idl::{CreateCommentRequest, PostListRequest};
+    use axum_blog_engine::handler::openapi::api_routes;
+    use axum_blog_engine::pagination::{Cursor, CursorPage, Direction};
+
+    fn all_posts() -> PostListRequest {
+        PostListRequest { published_only: Some(false), ..Default::default() }
+    }
+
+    fn cursor(value: &Option<String>) -> Cursor {
+        Cursor::decode(value.as_deref().unwrap()).unwrap()
+    }
+
+    async fn post_page(repo: &PostRepository, cursor: Option<&Cursor>, limit: u64) -> (Vec<i32>, CursorPage<()>) {
+        let page = repo.get_posts_by_cursor(&all_posts(), cursor, limit).await.unwrap();
+        let ids = page.items.iter().map(|p| p.id).collect();
+        (ids, page.map(|_| ()))
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let created_at = NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_nano_opt(8, 30, 15, 123_456_789)
+            .unwrap();
+        let cursor = Cursor::after(created_at, 42);
+        let encoded = cursor.encode();
+
+        assert!(encoded.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(Cursor::decode(&encoded).unwrap(), cursor);
+        assert_eq!(Cursor::decode(&Cursor::before(created_at, 42).encode()).unwrap().direction, Direction::Before);
+        assert_eq!(Cursor::from_param("").unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_cursor_rejected() {
+        for value in ["not-a-cursor", "YTox", "eDoxLjA6MQ", "%%%"] {
+            let err = Cursor::decode(value).unwrap_err();
+            assert_eq!(err.code(), ErrorCode::InvalidCursor, "{}", value);
+        }
+    }
+
+    /// 同一秒内插入的文章按 ID 排序，向后翻到最后一页再向前翻回第一页
+    #[tokio::test]
+    async fn test_post_cursor_walk() {
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 1).await;
+        common::seed_posts(&db, 7, 1).await;
+        let repo = PostRepository::new(db);
+
+        let (ids, first) = post_page(&repo, None, 3).await;
+        assert_eq!(ids, vec![7, 6, 5]);
+        assert!(first.prev_cursor.is_none());
+
+        let (ids, second) = post_page(&repo, Some(&cursor(&first.next_cursor)), 3).await;
+        assert_eq!(ids, vec![4, 3, 2]);
+
+        let (ids, last) = post_page(&repo, Some(&cursor(&second.next_cursor)), 3).await;
+        assert_eq!(ids, vec![1]);
+        assert!(last.next_cursor.is_none());
+
+        let (ids, back) = post_page(&repo, Some(&cursor(&last.prev_cursor)), 3).await;
+        assert_eq!(ids, vec![4, 3, 2]);
+        assert!(back.next_cursor.is_some());
+
+        let (ids, start) = post_page(&repo, Some(&cursor(&back.prev_cursor)), 3).await;
+        assert_eq!(ids, vec![7, 6, 5]);
+        assert!(start.prev_cursor.is_none());
+        assert!(start.next_cursor.is_some());
+    }
+
+    /// 翻页期间新增的文章不会让下一页重复或跳过数据
+    #[tokio::test]
+    async fn test_post_cursor_stable_on_insert() {
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 1).await;
+        common::seed_posts(&db, 4, 1).await;
+        let repo = PostRepository::new(db.clone());
+
+        let (_, first) = post_page(&repo, None, 2).await;
+        common::seed_posts(&db, 2, 1).await;
+        let (ids, _) = post_page(&repo, Some(&cursor(&first.next_cursor)), 2).await;
+        assert_eq!(ids, vec![2, 1]);
+        assert_eq!(repo.count_posts(&all_posts()).await.unwrap(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_comment_cursor_pages() {
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 1).await;
+        common::seed_posts(&db, 2, 1).await;
+        let repo = CommentRepository::new(db);
+        for post_id in [1, 1, 2, 1, 1, 1] {
+            let req = CreateCommentRequest { content: "评论".to_string(), post_id, parent_id: None };
+            repo.create_comment(req, 1).await.unwrap();
+        }
+
+        let first = repo.get_comments_by_cursor(1, None, 3).await.unwrap();
+        let ids: Vec<i32> = first.items.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![6, 5, 4]);
+
+        let second = repo.get_comments_by_cursor(1, Some(&cursor(&first.next_cursor)), 3).await.unwrap();
+        let ids: Vec<i32> = second.items.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![2, 1]);
+        assert!(second.next_cursor.is_none());
+        assert_eq!(repo.count_comments_by_post_id(1).await.unwrap(), 5);
+    }
+
+    /// 收藏时间相同时按文章 ID 排序，文章按收藏记录的顺序返回
+    #[tokio::test]
+    async fn test_favorite_cursor_pages() {
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 1).await;
+        common::seed_posts(&db, 5, 1).await;
+        for post_id in 1..=5 {
+            let sql = format!(
+                "INSERT INTO favorites (user_id, post_id, created_at) VALUES (1, {}, '2024-01-01 00:00:00')",
+                post_id
+            );
+            db.execute_unprepared(&sql).await.unwrap();
+        }
+        let repo = FavoriteRepository::new(db);
+
+        let first = repo.get_user_favorites_by_cursor(1, None, 2).await.unwrap();
+        assert_eq!(first.items.iter().map(|p| p.id).collect::<Vec<_>>(), vec![5, 4]);
+        let second = repo.get_user_favorites_by_cursor(1, Some(&cursor(&first.next_cursor)), 2).await.unwrap();
+        assert_eq!(second.items.iter().map(|p| p.id).collect::<Vec<_>>(), vec![3, 2]);
+        let back = repo.get_user_favorites_by_cursor(1, Some(&cursor(&second.prev_cursor)), 2).await.unwrap();
+        assert_eq!(back.items.iter().map(|p| p.id).collect::<Vec<_>>(), vec![5, 4]);
+        assert_eq!(repo.count_user_favorites(1).await.unwrap(), 5);
+    }
+
+    async fn app(db: DatabaseConnection) -> Router {
+        Router::new()
+            .nest("/api", api_routes().into_router())
+            .with_state(common::test_app_state(db))
+    }
+
+    async fn get(app: &Router, uri: &str) -> (StatusCode, Option<String>, Value) {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let link = response.headers().get(header::LINK).map(|v| v.to_str().unwrap().to_string());
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, link, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_list_endpoint_cursor_mode() {
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 1).await;
+        common::seed_posts(&db, 5, 1).await;
+        db.execute_unprepared("UPDATE posts SET is_published = TRUE").await.unwrap();
+        let app = app(db).await;
+
+        let (status, link, body) = get(&app, "/api/post?cursor=&limit=2&include_total=true").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["posts"].as_array().unwrap().len(), 2);
+        assert_eq!(body["total_posts"], 5);
+        assert!(body.get("total_pages").is_none());
+        assert!(body["prev_cursor"].is_null());
+        let next = body["next_cursor"].as_str().unwrap();
+        assert_eq!(
+            link.unwrap(),
+            format!("</api/post?limit=2&include_total=true&cursor={}>; rel=\"next\"", next)
+        );
+
+        let (_, link, body) = get(&app, &format!("/api/post?cursor={}&limit=2", next)).await;
+        assert_eq!(body["posts"][0]["id"], 3);
+        assert!(body.get("total_posts").is_none());
+        let link = link.unwrap();
+        assert!(link.contains("rel=\"next\"") && link.contains("rel=\"prev\""));
+
+        let (status, _, body) = get(&app, "/api/post?cursor=bogus").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "INVALID_CURSOR");
+    }
+
+    #[tokio::test]
+    async fn test_list_endpoint_legacy_mode() {
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 1).await;
+        common::seed_posts(&db, 3, 1).await;
+        let app = app(db).await;
+
+        let (status, link, body) = get(&app, "/api/post/1/comments?page=1&limit=2").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(link.is_none());
+        assert_eq!(body["total_pages"], 0);
+        assert_eq!(body["current_page"], 1);
+        assert_eq!(body["total_comments"], 0);
+        assert!(body["next_cursor"].is_null());
+    }
+}
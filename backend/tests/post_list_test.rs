@@ -0,0 +1,189 @@
+//! 文章列表查询参数测试用例
+//! 测试 `StrictQuery` 对未知参数和无效值的拒绝、列表接口的排序和过滤参数，
+//! 以及文章和评论接口通过 `include` 展开作者信息和计数
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use axum::Router;
+    use sea_orm::ConnectionTrait;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    use axum_blog_engine::error::{CustomError, ErrorCode};
+    use axum_blog_engine::handler::idl::{PostListRequest, PostSort};
+    use axum_blog_engine::handler::openapi::api_routes;
+    use axum_blog_engine::handler::query::parse_query;
+
+    fn field_errors(err: CustomError) -> Vec<(String, String)> {
+        assert_eq!(err.code(), ErrorCode::ValidationFailed);
+        match err {
+            CustomError::InvalidQuery(errors) => errors.into_iter().map(|e| (e.field, e.code)).collect(),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_known_fields() {
+        let req: PostListRequest =
+            parse_query("sort=most_viewed&category=a,b&has_cover=true&from=2024-01-01T00:00:00Z&lang=en").unwrap();
+        assert_eq!(req.sort, Some(PostSort::MostViewed));
+        assert_eq!(req.category.as_deref(), Some("a,b"));
+        assert_eq!(req.has_cover, Some(true));
+        assert!(req.from.is_some());
+        assert_eq!(req.page, Some(1)); // 未提供的参数仍使用默认值
+
+        let req: PostListRequest = parse_query("").unwrap();
+        assert_eq!(req.sort, None);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_fields() {
+        let err = parse_query::<PostListRequest>("catgory=rust&page=1&order=asc&order=desc").unwrap_err();
+        assert_eq!(
+            field_errors(err),
+            vec![("catgory".to_string(), "unknown_field".to_string()), ("order".to_string(), "unknown_field".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_parse_reports_invalid_field() {
+        let err = parse_query::<PostListRequest>("page=2&sort=popular&has_cover=yes").unwrap_err();
+        assert_eq!(
+            field_errors(err),
+            vec![("sort".to_string(), "invalid".to_string()), ("has_cover".to_string(), "invalid".to_string())]
+        );
+
+        let err = parse_query::<PostListRequest>("from=yesterday").unwrap_err();
+        assert_eq!(field_errors(err), vec![("from".to_string(), "invalid".to_string())]);
+    }
+
+    async fn app() -> Router {
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 1).await;
+        common::seed_posts(&db, 3, 1).await;
+        db.execute_unprepared("UPDATE posts SET is_published = TRUE").await.unwrap();
+        db.execute_unprepared("UPDATE posts SET view_count = 10 WHERE id = 2").await.unwrap();
+        db.execute_unprepared(
+            "INSERT INTO comments (content, post_id, user_id) VALUES ('评论', 1, 1), ('评论', 1, 1), ('评论', 2, 1)",
+        )
+        .await
+        .unwrap();
+        db.execute_unprepared("INSERT INTO favorites (user_id, post_id) VALUES (1, 1)").await.unwrap();
+        Router::new()
+            .nest("/api", api_routes().into_router())
+            .with_state(common::test_app_state(db))
+    }
+
+    async fn get(app: &Router, uri: &str) -> (StatusCode, Value) {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_list_endpoint_sort() {
+        let app = app().await;
+
+        let (status, body) = get(&app, "/api/post?sort=most_viewed&limit=1").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["posts"][0]["id"], 2);
+        assert_eq!(body["total_posts"], 3);
+
+        let (status, body) = get(&app, "/api/post?sort=oldest").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["posts"][0]["id"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_endpoint_rejects_unknown_param() {
+        let app = app().await;
+
+        let (status, body) = get(&app, "/api/post?catgory=rust&lang=en").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "VALIDATION_FAILED");
+        assert_eq!(body["errors"][0]["field"], "catgory");
+        assert_eq!(body["errors"][0]["code"], "unknown_field");
+        assert!(body["errors"][0]["message"].as_str().unwrap().contains("catgory"));
+
+        let (status, body) = get(&app, "/api/post?sort=popular").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["errors"][0]["field"], "sort");
+        assert_eq!(body["errors"][0]["code"], "invalid");
+    }
+
+    #[tokio::test]
+    async fn test_list_endpoint_cursor_requires_newest() {
+        let app = app().await;
+
+        let (status, body) = get(&app, "/api/post?cursor=&sort=most_viewed").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["errors"][0]["field"], "sort");
+        assert_eq!(body["errors"][0]["code"], "cursor_unsupported");
+
+        let (status, body) = get(&app, "/api/post?cursor=&sort=newest").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["posts"][0]["id"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_include_author_and_counts() {
+        let app = app().await;
+
+        // 默认不展开
+        let (_, body) = get(&app, "/api/post?limit=1").await;
+        assert!(body["posts"][0].get("author").is_none());
+        assert!(body["posts"][0].get("comment_count").is_none());
+
+        let (status, body) = get(&app, "/api/post?include=author,counts&sort=oldest").await;
+        assert_eq!(status, StatusCode::OK);
+        for post in body["posts"].as_array().unwrap() {
+            assert_eq!(post["author"]["id"], 1);
+            assert_eq!(post["author"]["username"], "user1");
+            assert_eq!(post["author"]["display_name"], "user1");
+            assert!(post["author"].get("email").is_none());
+        }
+        assert_eq!(body["posts"][0]["comment_count"], 2);
+        assert_eq!(body["posts"][0]["favorite_count"], 1);
+        assert_eq!(body["posts"][1]["comment_count"], 1);
+        assert_eq!(body["posts"][1]["favorite_count"], 0); // 没有收藏的文章计数为 0 而不是省略
+
+        let (status, body) = get(&app, "/api/post/1?include=counts").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["comment_count"], 2);
+        assert_eq!(body["favorite_count"], 1);
+        assert!(body.get("author").is_none());
+
+        let (status, body) = get(&app, "/api/post?include=comments").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["errors"][0]["field"], "include");
+    }
+
+    #[tokio::test]
+    async fn test_include_comment_author() {
+        let app = app().await;
+
+        let (status, body) = get(&app, "/api/post/1/comments?include=author").await;
+        assert_eq!(status, StatusCode::OK);
+        let comments = body["comments"].as_array().unwrap();
+        assert_eq!(comments.len(), 2);
+        assert!(comments.iter().all(|c| c["author"]["username"] == "user1"));
+
+        let (_, body) = get(&app, "/api/comment/1?include=author").await;
+        assert_eq!(body["author"]["id"], 1);
+
+        // 评论没有计数可以展开
+        let (status, _) = get(&app, "/api/post/1/comments?include=counts").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -1,42 +1,27 @@
 //! 文章模块测试用例
 //! 测试 PostRepository 的各种数据库操作功能
 
+mod common;
+
 #[cfg(test)]
 mod tests {
-    use sea_orm::{Database, DatabaseConnection, ConnectionTrait};
+    use crate::common;
+    use sea_orm::{DatabaseConnection, ConnectionTrait};
     use chrono::Utc;
     
     // 导入项目模块
-    use axum_blog_engine::database::post::{PostRepository, Model};
+    use axum_blog_engine::database::post::{post_excerpt, PostCounts, PostRepository, Model};
+    use axum_blog_engine::database::comment::CommentRepository;
     use axum_blog_engine::handler::idl::{
-        CreatePostRequest, UpdatePostRequest, PostListRequest
+        CreateCommentRequest, CreatePostRequest, UpdatePostRequest, PostListRequest, PostSort
     };
-    use axum_blog_engine::error::CustomError;
+    use axum_blog_engine::error::{CustomError, ErrorCode};
+    use axum_blog_engine::render::content_hash;
 
-    /// 创建内存数据库连接用于测试
+    /// 创建测试数据库并执行全部迁移 (见 tests/common)
     async fn setup_test_db() -> DatabaseConnection {
-        let db = Database::connect("sqlite::memory:")
-            .await
-            .expect("Failed to connect to test database");
-        
-        // 创建文章表
-        let sql = r#"
-            CREATE TABLE IF NOT EXISTS posts (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                title TEXT NOT NULL,
-                content_markdown TEXT NOT NULL,
-                category TEXT NOT NULL,
-                author_id INTEGER NOT NULL,
-                is_published INTEGER NOT NULL DEFAULT 0,
-                view_count INTEGER NOT NULL DEFAULT 0,
-                cover_url TEXT,
-                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now')),
-                updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now'))
-            );
-        "#;
-        
-        db.execute_unprepared(sql).await.expect("Failed to create posts table");
-        
+        let db = common::setup_test_db().await;
+        common::seed_users(&db, 10).await;
         db
     }
 
@@ -48,6 +33,7 @@ mod tests {
             category: "技术".to_string(),
             is_published: Some(true),
             cover_url: Some("https://example.com/cover.jpg".to_string()),
+            excerpt: None,
         };
         
         repo.create_post(create_req, author_id).await
@@ -64,6 +50,7 @@ mod tests {
             category: "生活".to_string(),
             is_published: Some(false), // 草稿状态
             cover_url: None,
+            excerpt: None,
         };
         
         let result = repo.create_post(create_req, 1).await;
@@ -73,7 +60,7 @@ mod tests {
         assert_eq!(post.title, "新文章标题");
         assert_eq!(post.category, "生活");
         assert_eq!(post.author_id, 1);
-        assert_eq!(post.is_published, 0); // 草稿状态
+        assert!(!post.is_published); // 草稿状态
         assert_eq!(post.view_count, 0); // 初始浏览量为0
         assert!(post.cover_url.is_none());
         assert!(post.id > 0);
@@ -90,13 +77,14 @@ mod tests {
             category: "技术".to_string(),
             is_published: Some(true), // 发布状态
             cover_url: Some("https://example.com/published.jpg".to_string()),
+            excerpt: None,
         };
         
         let result = repo.create_post(create_req, 2).await;
         
         assert!(result.is_ok());
         let post = result.unwrap();
-        assert_eq!(post.is_published, 1); // 发布状态
+        assert!(post.is_published); // 发布状态
         assert_eq!(post.cover_url, Some("https://example.com/published.jpg".to_string()));
     }
 
@@ -147,6 +135,7 @@ mod tests {
             category: Some("更新分类".to_string()),
             is_published: Some(false), // 改为草稿
             cover_url: Some("https://example.com/updated.jpg".to_string()),
+            excerpt: None,
         };
         
         let result = repo.update_post(created_post.id, update_req).await;
@@ -155,7 +144,7 @@ mod tests {
         let updated_post = result.unwrap();
         assert_eq!(updated_post.title, "更新后的标题");
         assert_eq!(updated_post.category, "更新分类");
-        assert_eq!(updated_post.is_published, 0); // 草稿状态
+        assert!(!updated_post.is_published); // 草稿状态
         assert_eq!(updated_post.cover_url, Some("https://example.com/updated.jpg".to_string()));
         assert!(updated_post.updated_at > created_post.updated_at); // 更新时间应该更新
     }
@@ -175,6 +164,7 @@ mod tests {
             category: None,
             is_published: None,
             cover_url: None,
+            excerpt: None,
         };
         
         let result = repo.update_post(created_post.id, update_req).await;
@@ -198,16 +188,17 @@ mod tests {
             category: None,
             is_published: None,
             cover_url: None,
+            excerpt: None,
         };
         
         let result = repo.update_post(999, update_req).await;
         assert!(result.is_err());
         
-        if let Err(CustomError::NotFound(msg)) = result {
+        if let Err(CustomError::Coded { code: ErrorCode::PostNotFound, message: msg }) = result {
             assert!(msg.contains("999"));
             assert!(msg.contains("未找到"));
         } else {
-            panic!("Expected NotFound error");
+            panic!("Expected PostNotFound error");
         }
     }
 
@@ -237,11 +228,11 @@ mod tests {
         let result = repo.delete_post(999).await;
         assert!(result.is_err());
         
-        if let Err(CustomError::NotFound(msg)) = result {
+        if let Err(CustomError::Coded { code: ErrorCode::PostNotFound, message: msg }) = result {
             assert!(msg.contains("999"));
             assert!(msg.contains("未找到或已被删除"));
         } else {
-            panic!("Expected NotFound error");
+            panic!("Expected PostNotFound error");
         }
     }
 
@@ -281,6 +272,7 @@ mod tests {
                 category: if i % 2 == 0 { "技术".to_string() } else { "生活".to_string() },
                 is_published: Some(true),
                 cover_url: None,
+                excerpt: None,
             };
             repo.create_post(create_req, i).await.unwrap();
         }
@@ -292,6 +284,8 @@ mod tests {
             category: None,
             query: None,
             published_only: Some(true),
+            author_id: None,
+            ..Default::default()
         };
         
         let result = repo.get_paginated_posts(list_req).await;
@@ -317,6 +311,7 @@ mod tests {
                 category: if i <= 2 { "技术".to_string() } else { "生活".to_string() },
                 is_published: Some(true),
                 cover_url: None,
+                excerpt: None,
             };
             repo.create_post(create_req, i).await.unwrap();
         }
@@ -328,6 +323,8 @@ mod tests {
             category: Some("技术".to_string()),
             query: None,
             published_only: Some(true),
+            author_id: None,
+            ..Default::default()
         };
         
         let result = repo.get_paginated_posts(list_req).await;
@@ -355,6 +352,7 @@ mod tests {
             category: "技术".to_string(),
             is_published: Some(true),
             cover_url: None,
+            excerpt: None,
         };
         repo.create_post(create_req1, 1).await.unwrap();
         
@@ -364,6 +362,7 @@ mod tests {
             category: "技术".to_string(),
             is_published: Some(true),
             cover_url: None,
+            excerpt: None,
         };
         repo.create_post(create_req2, 2).await.unwrap();
         
@@ -374,6 +373,8 @@ mod tests {
             category: None,
             query: Some("Rust".to_string()),
             published_only: Some(true),
+            author_id: None,
+            ..Default::default()
         };
         
         let result = repo.get_paginated_posts(list_req).await;
@@ -417,11 +418,11 @@ mod tests {
         let result = repo.increment_view_count(999).await;
         assert!(result.is_err());
         
-        if let Err(CustomError::NotFound(msg)) = result {
+        if let Err(CustomError::Coded { code: ErrorCode::PostNotFound, message: msg }) = result {
             assert!(msg.contains("999"));
             assert!(msg.contains("未找到"));
         } else {
-            panic!("Expected NotFound error");
+            panic!("Expected PostNotFound error");
         }
     }
 
@@ -452,6 +453,7 @@ mod tests {
             category: None,
             is_published: None,
             cover_url: None,
+            excerpt: None,
         };
         
         let before_update = Utc::now().naive_utc();
@@ -464,4 +466,355 @@ mod tests {
         assert!(updated_post.updated_at <= after_update);
         assert!(updated_post.updated_at > created_post.updated_at); // 更新时间应该晚于创建时间
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_create_post_renders_html() {
+        let db = setup_test_db().await;
+        let repo = PostRepository::new(db);
+
+        let created_post = create_test_post(&repo, 1).await.unwrap();
+
+        // 创建时应写入渲染后的 HTML 和内容哈希
+        let html = created_post.content_html.clone().expect("应缓存渲染后的 HTML");
+        assert!(html.contains("<h1 id=\"h-测试文章内容\">测试文章内容</h1>"));
+        assert_eq!(
+            created_post.content_hash,
+            Some(content_hash(&created_post.content_markdown))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_post_rerenders_html() {
+        let db = setup_test_db().await;
+        let repo = PostRepository::new(db);
+
+        let created_post = create_test_post(&repo, 1).await.unwrap();
+
+        let update_req = UpdatePostRequest {
+            title: None,
+            content_markdown: Some("~~旧观点~~ 新观点".to_string()),
+            category: None,
+            is_published: None,
+            cover_url: None,
+            excerpt: None,
+        };
+        let updated_post = repo.update_post(created_post.id, update_req).await.unwrap();
+
+        // 内容变化后缓存的 HTML 和哈希都应更新
+        assert!(updated_post.content_html.unwrap().contains("<del>旧观点</del>"));
+        assert_ne!(updated_post.content_hash, created_post.content_hash);
+    }
+
+    #[tokio::test]
+    async fn test_stale_cache_is_rendered_on_read_and_refreshed_on_update() {
+        let db = setup_test_db().await;
+
+        // 模拟旧数据：没有渲染缓存的文章
+        db.execute_unprepared(
+            "INSERT INTO posts (id, title, content_markdown, category, author_id, is_published) \
+             VALUES (1, '旧文章', '**加粗**', '技术', 1, TRUE)",
+        )
+        .await
+        .unwrap();
+
+        let repo = PostRepository::new(db);
+        let post = repo.get_post_by_id(1).await.unwrap().unwrap();
+        assert!(post.content_html.is_none());
+
+        let rendered = repo.get_rendered_content(&post).await.unwrap();
+        assert!(rendered.html.contains("<strong>加粗</strong>"));
+
+        // 读取路径不回写数据库
+        let unchanged = repo.get_post_by_id(1).await.unwrap().unwrap();
+        assert!(unchanged.content_html.is_none());
+
+        // 任意更新都会补齐缺失的缓存
+        let update_req = UpdatePostRequest {
+            title: Some("旧文章（修订）".to_string()),
+            content_markdown: None,
+            category: None,
+            is_published: None,
+            cover_url: None,
+            excerpt: None,
+        };
+        let refreshed = repo.update_post(1, update_req).await.unwrap();
+        assert_eq!(refreshed.content_html, Some(rendered.html));
+        assert_eq!(refreshed.content_hash, Some(content_hash("**加粗**")));
+        assert_eq!(refreshed.toc_json, Some("[]".to_string()));
+        assert_eq!(refreshed.word_count, 2);
+        assert_eq!(refreshed.auto_excerpt.as_deref(), Some("加粗"));
+    }
+
+    #[tokio::test]
+    async fn test_reindex_all_refreshes_derived_columns() {
+        let db = setup_test_db().await;
+
+        // 模拟旧数据：没有派生列的文章，以及缓存已过期的文章
+        db.execute_unprepared(
+            "INSERT INTO posts (id, title, content_markdown, category, author_id, is_published, updated_at) \
+             VALUES (1, '旧文章', '## 标题\n\n**加粗**', '技术', 1, TRUE, '2024-01-01 00:00:00'), \
+                    (2, '过期缓存', '正文', '技术', 1, FALSE, '2024-01-01 00:00:00')",
+        )
+        .await
+        .unwrap();
+        db.execute_unprepared("UPDATE posts SET content_html = '<p>旧</p>', content_hash = 'stale' WHERE id = 2")
+            .await
+            .unwrap();
+
+        let repo = PostRepository::new(db);
+        assert_eq!(repo.reindex_all().await.unwrap(), 2);
+
+        let first = repo.get_post_by_id(1).await.unwrap().unwrap();
+        assert!(first.content_html.as_deref().unwrap().contains("<strong>加粗</strong>"));
+        assert_eq!(first.content_hash, Some(content_hash("## 标题\n\n**加粗**")));
+        assert!(first.toc_json.as_deref().unwrap().contains("标题"));
+        assert_eq!(first.word_count, 4);
+        assert_eq!(first.auto_excerpt.as_deref(), Some("标题 加粗"));
+        // 重建不修改文章的更新时间
+        assert_eq!(first.updated_at.format("%Y-%m-%d").to_string(), "2024-01-01");
+
+        let second = repo.get_post_by_id(2).await.unwrap().unwrap();
+        assert_eq!(second.content_html.as_deref(), Some("<p>正文</p>\n"));
+        assert_eq!(second.content_hash, Some(content_hash("正文")));
+    }
+
+    #[tokio::test]
+    async fn test_list_returns_summary_with_auto_excerpt() {
+        let db = setup_test_db().await;
+        let repo = PostRepository::new(db);
+
+        // 字数、阅读时间和自动摘要在写入时计算并存储
+        let created = create_test_post(&repo, 1).await.unwrap();
+        assert_eq!(created.word_count, 17);
+        assert_eq!(created.reading_time_minutes, 1);
+        assert_eq!(created.auto_excerpt.as_deref(), Some("测试文章内容 这是一篇测试文章的内容。"));
+
+        let list_req = PostListRequest {
+            page: Some(1),
+            limit: Some(10),
+            category: None,
+            query: None,
+            published_only: None,
+            author_id: None,
+            ..Default::default()
+        };
+        let (posts, _, _, _) = repo.get_paginated_posts(list_req).await.unwrap();
+
+        // 未填写摘要时根据正文自动生成，不包含 Markdown 标记
+        assert_eq!(posts[0].excerpt, "测试文章内容 这是一篇测试文章的内容。");
+        assert_eq!(posts[0].word_count, 17);
+        assert_eq!(posts[0].reading_time_minutes, 1);
+    }
+
+    #[tokio::test]
+    async fn test_author_excerpt_overrides_and_clears() {
+        let db = setup_test_db().await;
+        let repo = PostRepository::new(db);
+
+        let create_req = CreatePostRequest {
+            title: "自定义摘要".to_string(),
+            content_markdown: "正文内容".to_string(),
+            category: "技术".to_string(),
+            is_published: Some(true),
+            cover_url: None,
+            excerpt: Some("作者写的摘要".to_string()),
+        };
+        let created_post = repo.create_post(create_req, 1).await.unwrap();
+        assert_eq!(post_excerpt(&created_post), "作者写的摘要");
+
+        // 传入空字符串清除自定义摘要，恢复自动摘要
+        let update_req = UpdatePostRequest {
+            title: None,
+            content_markdown: None,
+            category: None,
+            is_published: None,
+            cover_url: None,
+            excerpt: Some(String::new()),
+        };
+        let updated_post = repo.update_post(created_post.id, update_req).await.unwrap();
+        assert!(updated_post.excerpt.is_none());
+        assert_eq!(post_excerpt(&updated_post), "正文内容");
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_published_posts() {
+        let db = setup_test_db().await;
+        let repo = PostRepository::new(db);
+
+        for (title, category, author_id, published) in [
+            ("技术文章", "技术", 1, true),
+            ("生活文章", "生活", 1, true),
+            ("其他作者", "技术", 2, true),
+            ("草稿", "技术", 1, false),
+        ] {
+            let create_req = CreatePostRequest {
+                title: title.to_string(),
+                content_markdown: "内容".to_string(),
+                category: category.to_string(),
+                is_published: Some(published),
+                cover_url: None,
+                excerpt: None,
+            };
+            repo.create_post(create_req, author_id).await.unwrap();
+        }
+
+        // 草稿不会出现在订阅源中，结果按创建时间倒序
+        let posts = repo.get_recent_published_posts(None, None, 10).await.unwrap();
+        let titles: Vec<_> = posts.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, vec!["其他作者", "生活文章", "技术文章"]);
+
+        let posts = repo.get_recent_published_posts(Some("技术".to_string()), Some(1), 10).await.unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].title, "技术文章");
+
+        let posts = repo.get_recent_published_posts(None, None, 2).await.unwrap();
+        assert_eq!(posts.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sitemap_queries_exclude_drafts() {
+        let db = setup_test_db().await;
+        let repo = PostRepository::new(db);
+
+        for (category, author_id, published) in [("技术", 1, true), ("技术", 2, true), ("生活", 3, false)] {
+            let create_req = CreatePostRequest {
+                title: "站点地图".to_string(),
+                content_markdown: "内容".to_string(),
+                category: category.to_string(),
+                is_published: Some(published),
+                cover_url: None,
+                excerpt: None,
+            };
+            repo.create_post(create_req, author_id).await.unwrap();
+        }
+
+        assert_eq!(repo.count_published_posts().await.unwrap(), 2);
+
+        let posts = repo.get_published_post_lastmods(0, 10).await.unwrap();
+        assert_eq!(posts.len(), 2);
+        assert!(posts[0].0 < posts[1].0); // 按 ID 升序
+        assert_eq!(repo.get_published_post_lastmods(1, 10).await.unwrap().len(), 1);
+
+        // 只有草稿的分类和作者不出现
+        let categories = repo.get_published_category_lastmods().await.unwrap();
+        assert_eq!(categories.len(), 1);
+        assert_eq!(categories[0].0, "技术");
+        assert_eq!(categories[0].1, posts[1].1); // 取分类下最新的更新时间
+
+        let authors: Vec<i32> = repo.get_published_author_lastmods().await.unwrap()
+            .into_iter()
+            .map(|(author_id, _)| author_id)
+            .collect();
+        assert_eq!(authors, vec![1, 2]);
+    }
+
+    /// 插入四篇创建时间、浏览量、更新时间和封面各不相同的文章，并添加收藏和评论
+    async fn seed_sortable_posts(db: &DatabaseConnection) {
+        let rows = [
+            ("技术", "2024-01-01 00:00:00", "2024-03-01 00:00:00", 5, "'https://example.com/a.png'"),
+            ("生活", "2024-01-02 00:00:00", "2024-01-02 00:00:00", 30, "NULL"),
+            ("随笔", "2024-01-03 00:00:00", "2024-02-01 00:00:00", 10, "''"),
+            ("技术", "2024-01-04 00:00:00", "2024-01-04 00:00:00", 0, "'https://example.com/d.png'"),
+        ];
+        for (category, created_at, updated_at, view_count, cover_url) in rows {
+            let sql = format!(
+                "INSERT INTO posts (title, content_markdown, category, author_id, is_published, view_count, cover_url, created_at, updated_at) \
+                 VALUES ('排序', '内容', '{}', 1, TRUE, {}, {}, '{}', '{}')",
+                category, view_count, cover_url, created_at, updated_at
+            );
+            db.execute_unprepared(&sql).await.unwrap();
+        }
+        // 收藏：文章 2 三次、文章 3 一次；评论：文章 1 两条、文章 4 一条
+        for (user_id, post_id) in [(1, 2), (2, 2), (3, 2), (1, 3)] {
+            let sql = format!("INSERT INTO favorites (user_id, post_id) VALUES ({}, {})", user_id, post_id);
+            db.execute_unprepared(&sql).await.unwrap();
+        }
+        let comments = CommentRepository::new(db.clone());
+        for post_id in [1, 4, 1] {
+            let req = CreateCommentRequest { content: "评论".to_string(), post_id, parent_id: None };
+            comments.create_comment(req, 1).await.unwrap();
+        }
+    }
+
+    async fn list_ids(repo: &PostRepository, req: PostListRequest) -> Vec<i32> {
+        let (posts, _, _, _) = repo.get_paginated_posts(req).await.unwrap();
+        posts.into_iter().map(|p| p.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_get_paginated_posts_sort() {
+        let db = setup_test_db().await;
+        seed_sortable_posts(&db).await;
+        let repo = PostRepository::new(db);
+
+        let expected = [
+            (PostSort::Newest, vec![4, 3, 2, 1]),
+            (PostSort::Oldest, vec![1, 2, 3, 4]),
+            (PostSort::MostViewed, vec![2, 3, 1, 4]),
+            (PostSort::MostFavorited, vec![2, 3, 4, 1]), // 收藏数相同按创建时间倒序
+            (PostSort::MostCommented, vec![1, 4, 3, 2]),
+            (PostSort::RecentlyUpdated, vec![1, 3, 4, 2]),
+        ];
+        for (sort, ids) in expected {
+            let req = PostListRequest { sort: Some(sort), ..Default::default() };
+            assert_eq!(list_ids(&repo, req).await, ids, "sort={}", sort.as_str());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_paginated_posts_filters() {
+        let db = setup_test_db().await;
+        seed_sortable_posts(&db).await;
+        let repo = PostRepository::new(db);
+
+        // 多个分类
+        let req = PostListRequest { category: Some("生活, 随笔".to_string()), ..Default::default() };
+        assert_eq!(list_ids(&repo, req).await, vec![3, 2]);
+
+        // 创建时间范围：包含起点，不包含终点
+        let req = PostListRequest {
+            from: Some("2024-01-02T00:00:00Z".parse().unwrap()),
+            to: Some("2024-01-04T00:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(list_ids(&repo, req).await, vec![3, 2]);
+
+        // 封面为空字符串视为没有封面
+        let req = PostListRequest { has_cover: Some(true), ..Default::default() };
+        assert_eq!(list_ids(&repo, req).await, vec![4, 1]);
+        let req = PostListRequest { has_cover: Some(false), ..Default::default() };
+        assert_eq!(list_ids(&repo, req).await, vec![3, 2]);
+
+        // 条件组合
+        let req = PostListRequest {
+            category: Some("技术".to_string()),
+            has_cover: Some(true),
+            sort: Some(PostSort::Oldest),
+            ..Default::default()
+        };
+        assert_eq!(list_ids(&repo, req).await, vec![1, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_get_post_counts_and_authors() {
+        let db = setup_test_db().await;
+        seed_sortable_posts(&db).await;
+        db.execute_unprepared("UPDATE users SET display_name = '作者一', avatar_url = '/a.png' WHERE id = 1").await.unwrap();
+        let repo = PostRepository::new(db);
+
+        let counts = repo.get_post_counts(&[1, 2, 3, 4]).await.unwrap();
+        assert_eq!(counts[&1], PostCounts { comment_count: 2, favorite_count: 0 });
+        assert_eq!(counts[&2], PostCounts { comment_count: 0, favorite_count: 3 });
+        assert_eq!(counts[&3], PostCounts { comment_count: 0, favorite_count: 1 });
+        assert_eq!(counts[&4], PostCounts { comment_count: 1, favorite_count: 0 });
+        assert!(repo.get_post_counts(&[]).await.unwrap().is_empty());
+
+        // 重复的 ID 只查询一次；未设置显示名称时使用用户名
+        let authors = repo.get_authors(&[1, 2, 1, 99]).await.unwrap();
+        assert_eq!(authors.len(), 2);
+        assert_eq!(authors[&1].display_name, "作者一");
+        assert_eq!(authors[&1].avatar_url.as_deref(), Some("/a.png"));
+        assert_eq!(authors[&2].display_name, "user2");
+        assert_eq!(authors[&2].avatar_url, None);
+    }
+}
@@ -0,0 +1,220 @@
+//! 渲染模块测试用例
+//! 测试 Markdown 渲染、HTML 清洗和内容哈希
+
+#[cfg(test)]
+mod tests {
+    use axum_blog_engine::render::{content_hash, render_markdown};
+    use axum_blog_engine::render::highlight::{available_themes, default_theme_slug, theme_css};
+    use axum_blog_engine::render::summary::{auto_excerpt, text_stats, EXCERPT_MAX_CHARS};
+
+    #[test]
+    fn test_render_commonmark() {
+        let html = render_markdown("# 标题\n\n一段 *强调* 文本和 [链接](https://example.com)。").html;
+
+        assert!(html.contains("<h1 id=\"h-标题\">标题</h1>"));
+        assert!(html.contains("<em>强调</em>"));
+        assert!(html.contains("href=\"https://example.com\""));
+    }
+
+    #[test]
+    fn test_render_gfm_table() {
+        let markdown = "| 名称 | 数量 |\n|:----|----:|\n| 苹果 | 3 |";
+        let html = render_markdown(markdown).html;
+
+        assert!(html.contains("<table>"));
+        assert!(html.contains("<td style=\"text-align:left\">苹果</td>"));
+        assert!(html.contains("<td style=\"text-align:right\">3</td>"));
+    }
+
+    #[test]
+    fn test_render_gfm_task_list() {
+        let html = render_markdown("- [x] 已完成\n- [ ] 未完成").html;
+
+        assert!(html.contains("type=\"checkbox\""));
+        assert!(html.contains("checked=\"\""));
+        assert!(html.contains("disabled=\"\""));
+    }
+
+    #[test]
+    fn test_render_gfm_strikethrough() {
+        let html = render_markdown("~~删除~~").html;
+        assert!(html.contains("<del>删除</del>"));
+    }
+
+    #[test]
+    fn test_render_gfm_footnotes() {
+        let html = render_markdown("正文[^note]\n\n[^note]: 脚注内容").html;
+
+        // 脚注 id 带有固定前缀，引用与定义能够对应
+        assert!(html.contains("href=\"#fn-note\""));
+        assert!(html.contains("id=\"fn-note\""));
+        assert!(html.contains("class=\"footnote-definition\""));
+    }
+
+    #[test]
+    fn test_sanitize_strips_scripts_and_event_handlers() {
+        let markdown = "<script>alert(1)</script>\n\n<img src=\"x.png\" onerror=\"alert(1)\">\n\n[点我](javascript:alert(1))";
+        let html = render_markdown(markdown).html;
+
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("<img src=\"x.png\">"));
+    }
+
+    #[test]
+    fn test_sanitize_rejects_unsafe_inputs_and_styles() {
+        let markdown = "<input type=\"text\" value=\"x\">\n\n<p style=\"position:fixed\">x</p>";
+        let html = render_markdown(markdown).html;
+
+        assert!(!html.contains("type=\"text\""));
+        assert!(!html.contains("position"));
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let hash = content_hash("# 内容");
+
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, content_hash("# 内容")); // 相同内容哈希稳定
+        assert_ne!(hash, content_hash("# 其他内容"));
+    }
+
+    #[test]
+    fn test_highlight_fenced_code() {
+        let html = render_markdown("```rust\nfn main() {}\n```").html;
+
+        // 输出基于类名而非内联样式
+        assert!(html.contains("<pre class=\"hl-code\"><code class=\"language-rust\">"));
+        assert!(html.contains("hl-storage hl-type hl-function hl-rust"));
+        assert!(!html.contains("style="));
+    }
+
+    #[test]
+    fn test_highlight_unknown_language_is_escaped() {
+        let html = render_markdown("```no-such-lang\n<b>x</b>\n```").html;
+
+        assert!(html.contains("&lt;b&gt;x&lt;/b&gt;"));
+        assert!(!html.contains("<b>"));
+    }
+
+    #[test]
+    fn test_sanitize_drops_foreign_classes() {
+        let html = render_markdown("<span class=\"admin-badge hl-keyword\">x</span>").html;
+
+        // 只保留高亮前缀的类名，站点自身的样式类会被移除
+        assert!(html.contains("<span class=\"hl-keyword\">x</span>"));
+        assert!(!html.contains("admin-badge"));
+    }
+
+    #[test]
+    fn test_sanitize_drops_foreign_ids() {
+        let html = render_markdown("<div id=\"app\">x</div>\n\n<h2 id=\"login\">y</h2>\n\n<p id=\"fn-1\">z</p>").html;
+
+        // 只有脚注 div 和标题上的生成前缀 id 会被保留
+        assert!(!html.contains("id=\"app\""));
+        assert!(!html.contains("id=\"login\""));
+        assert!(!html.contains("id=\"fn-1\""));
+
+        let html = render_markdown("<div id=\"fn-note\">x</div>\n\n<h3 id=\"h-intro\">y</h3>").html;
+        assert!(html.contains("<div id=\"fn-note\">"));
+        assert!(html.contains("<h3 id=\"h-intro\">"));
+    }
+
+    #[test]
+    fn test_highlight_themes() {
+        let themes = available_themes();
+        assert!(themes.iter().any(|(slug, _)| *slug == default_theme_slug()));
+
+        let css = theme_css(&default_theme_slug()).expect("默认主题应存在");
+        assert!(css.contains(".hl-"));
+        assert!(theme_css("no-such-theme").is_none());
+    }
+
+    #[test]
+    fn test_render_math() {
+        let html = render_markdown("质能方程 $E=mc^2$\n\n$$\\frac{1}{2}$$").html;
+
+        assert!(html.contains("<math"));
+        assert!(html.contains("<mfrac>"));
+        assert!(html.contains("display=\"block\""));
+        assert!(!html.contains("$E=mc^2$"));
+    }
+
+    #[test]
+    fn test_render_math_error_falls_back_to_source() {
+        let html = render_markdown("$\\left( x$").html;
+
+        assert!(html.contains("<code class=\"math-error\">\\left( x</code>"));
+    }
+
+    #[test]
+    fn test_heading_anchors_are_stable_and_unique() {
+        let rendered = render_markdown("## 快速开始\n\n## 快速开始\n\n## Hello, World!");
+
+        assert!(rendered.html.contains("<h2 id=\"h-快速开始\">"));
+        assert!(rendered.html.contains("<h2 id=\"h-快速开始-1\">"));
+        assert!(rendered.html.contains("<h2 id=\"h-hello-world\">"));
+        // 相同内容多次渲染得到相同的锚点
+        assert_eq!(rendered.html, render_markdown("## 快速开始\n\n## 快速开始\n\n## Hello, World!").html);
+    }
+
+    #[test]
+    fn test_heading_anchors_skip_issued_ids() {
+        let html = render_markdown("## A\n\n## A-1\n\n## A\n\n## A-1").html;
+
+        assert!(html.contains("<h2 id=\"h-a\">A</h2>"));
+        assert!(html.contains("<h2 id=\"h-a-1\">A-1</h2>"));
+        assert!(html.contains("<h2 id=\"h-a-2\">A</h2>"));
+        assert!(html.contains("<h2 id=\"h-a-1-1\">A-1</h2>"));
+    }
+
+    #[test]
+    fn test_table_of_contents() {
+        let toc = render_markdown("# 简介\n\n## 安装\n\n### 依赖 `cargo`\n\n## 使用\n\n# 附录").toc;
+
+        assert_eq!(toc.len(), 2);
+        assert_eq!(toc[0].title, "简介");
+        assert_eq!(toc[0].children.len(), 2);
+        assert_eq!(toc[0].children[0].id, "h-安装");
+        assert_eq!(toc[0].children[0].children[0].title, "依赖 cargo");
+        assert_eq!(toc[0].children[1].title, "使用");
+        assert_eq!(toc[1].level, 1);
+        assert!(toc[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_word_count_is_cjk_aware() {
+        let stats = text_stats("# 你好世界\n\nHello, world! Don't panic.");
+
+        // 4 个汉字 + 4 个英文单词
+        assert_eq!(stats.word_count, 8);
+        assert_eq!(stats.reading_time_minutes, 1);
+        assert_eq!(text_stats("").reading_time_minutes, 0);
+    }
+
+    #[test]
+    fn test_reading_time_scales_with_length() {
+        let long_chinese = "字".repeat(1000);
+        let long_english = "word ".repeat(1000);
+
+        assert_eq!(text_stats(&long_chinese).reading_time_minutes, 3);
+        assert_eq!(text_stats(&long_english).reading_time_minutes, 4);
+    }
+
+    #[test]
+    fn test_auto_excerpt_strips_markup_and_code() {
+        let markdown = "## 简介\n\n这是 **重点** 内容。\n\n```rust\nfn main() {}\n```\n\n结尾。";
+
+        assert_eq!(auto_excerpt(markdown, 200), "简介 这是 重点 内容。 结尾。");
+    }
+
+    #[test]
+    fn test_auto_excerpt_truncates() {
+        let excerpt = auto_excerpt(&"长".repeat(300), EXCERPT_MAX_CHARS);
+
+        assert_eq!(excerpt.chars().count(), EXCERPT_MAX_CHARS + 1);
+        assert!(excerpt.ends_with('…'));
+    }
+}
+
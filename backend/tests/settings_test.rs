@@ -0,0 +1,202 @@
+//! 分层配置测试用例
+//! 测试配置文件、环境变量与命令行参数的合并顺序以及启动校验
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum_blog_engine::settings::{
+        Environment, LogFormat, LogRotation, Overrides, Settings, StorageKind, DEV_JWT_SECRET,
+    };
+
+    const STRONG_SECRET: &str = "0123456789abcdef0123456789abcdef";
+
+    /// 使用给定的环境变量加载配置
+    fn load(file: Option<&str>, env: &[(&str, &str)], overrides: &Overrides) -> anyhow::Result<Settings> {
+        let env: HashMap<String, String> = env.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Settings::from_sources(file, |name| env.get(name).cloned(), overrides)
+    }
+
+    #[test]
+    fn test_defaults_without_any_source() {
+        let settings = load(None, &[], &Overrides::default()).unwrap();
+        assert_eq!(settings.environment, Environment::Development);
+        assert_eq!(settings.server.listen.to_string(), "0.0.0.0:3000");
+        assert_eq!(settings.database.max_connections, 10);
+        assert_eq!(settings.auth.token_ttl_hours, 720);
+        assert_eq!(settings.server.cors_origins, vec!["*"]);
+
+        // 开发模式下未设置密钥只产生警告
+        assert_eq!(settings.app_config().unwrap().jwt_secret, DEV_JWT_SECRET);
+        assert!(settings.warnings.iter().any(|w| w.contains("JWT_SECRET")));
+    }
+
+    #[test]
+    fn test_file_then_env_then_cli() {
+        let file = r#"
+            [server]
+            listen = "127.0.0.1:8080"
+            cors_origins = ["https://blog.example.com"]
+
+            [database]
+            url = "sqlite:file.db"
+            max_connections = 20
+
+            [auth]
+            token_ttl_hours = 24
+        "#;
+        let env = [("DATABASE_URL", "sqlite:env.db"), ("DB_MAX_CONNECTIONS", "30")];
+        let overrides = Overrides { database_url: Some("sqlite:cli.db".into()), ..Default::default() };
+
+        let settings = load(Some(file), &env, &overrides).unwrap();
+        assert_eq!(settings.server.listen.to_string(), "127.0.0.1:8080"); // 只在文件中设置
+        assert_eq!(settings.server.cors_origins, vec!["https://blog.example.com"]);
+        assert_eq!(settings.auth.token_ttl_hours, 24);
+        assert_eq!(settings.database.max_connections, 30); // 环境变量覆盖文件
+        assert_eq!(settings.database.url, "sqlite:cli.db"); // 命令行覆盖环境变量
+        assert_eq!(settings.app_config().unwrap().token_ttl_hours, 24);
+    }
+
+    #[test]
+    fn test_site_url_trailing_slash_is_trimmed() {
+        let settings = load(None, &[("SITE_URL", "https://blog.example.com/")], &Overrides::default()).unwrap();
+        assert_eq!(settings.app_config().unwrap().site_url, "https://blog.example.com");
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        let err = load(Some("[server]\nport = 3000\n"), &[], &Overrides::default()).unwrap_err();
+        assert!(err.to_string().contains("配置文件格式错误"));
+    }
+
+    #[test]
+    fn test_invalid_env_value_is_an_error() {
+        let err = load(None, &[("BCRYPT_COST", "twelve")], &Overrides::default()).unwrap_err();
+        assert!(err.to_string().contains("BCRYPT_COST"));
+    }
+
+    #[test]
+    fn test_all_problems_reported_together() {
+        let file = r#"
+            [auth]
+            bcrypt_cost = 2
+
+            [database]
+            max_connections = 2
+            min_connections = 5
+        "#;
+        let err = load(Some(file), &[("CORS_ORIGINS", "*,https://a.example.com")], &Overrides::default())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("bcrypt_cost"));
+        assert!(err.contains("min_connections"));
+        assert!(err.contains("cors_origins"));
+    }
+
+    #[test]
+    fn test_production_requires_secure_secret() {
+        let production = Overrides { environment: Some(Environment::Production), ..Default::default() };
+
+        assert!(load(None, &[], &production).is_err());
+        assert!(load(None, &[("JWT_SECRET", DEV_JWT_SECRET)], &production).is_err());
+        assert!(load(None, &[("JWT_SECRET", "123456")], &production).is_err());
+
+        let settings = load(None, &[("JWT_SECRET", STRONG_SECRET)], &production).unwrap();
+        assert_eq!(settings.app_config().unwrap().jwt_secret, STRONG_SECRET);
+    }
+
+    #[test]
+    fn test_production_mode_from_env_and_file() {
+        assert!(load(None, &[("APP_ENV", "production")], &Overrides::default()).is_err());
+        assert!(load(Some("environment = \"production\""), &[], &Overrides::default()).is_err());
+        assert!(load(None, &[("APP_ENV", "staging")], &Overrides::default()).is_err());
+
+        // 命令行参数可以切回开发模式
+        let development = Overrides { environment: Some(Environment::Development), ..Default::default() };
+        assert!(load(None, &[("APP_ENV", "production")], &development).is_ok());
+    }
+
+    #[test]
+    fn test_example_config_is_valid() {
+        let file = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/config.example.toml")).unwrap();
+        let settings = load(Some(&file), &[], &Overrides::default()).unwrap();
+        let defaults = load(None, &[], &Overrides::default()).unwrap();
+        assert_eq!(settings.server.listen, defaults.server.listen);
+        assert_eq!(settings.media.image_variant_widths, defaults.media.image_variant_widths);
+        assert_eq!(settings.log.level, defaults.log.level);
+        assert_eq!(settings.tracing.service_name, defaults.tracing.service_name);
+    }
+
+    #[test]
+    fn test_log_output_from_env() {
+        let env = [
+            ("LOG_FORMAT", "json"),
+            ("LOG_DIR", "/var/log/blog"),
+            ("LOG_ROTATION", "hourly"),
+            ("LOG_MAX_FILE_BYTES", "1048576"),
+            ("LOG_MAX_FILES", "3"),
+        ];
+        let settings = load(None, &env, &Overrides::default()).unwrap();
+        assert_eq!(settings.log.format, LogFormat::Json);
+        assert_eq!(settings.log.dir.as_deref(), Some(std::path::Path::new("/var/log/blog")));
+        assert_eq!(settings.log.rotation, LogRotation::Hourly);
+        assert_eq!(settings.log.max_file_bytes, 1048576);
+        assert_eq!(settings.log.max_files, 3);
+
+        let env = [("LOG_FORMAT", "xml"), ("LOG_ROTATION", "weekly"), ("LOG_FILE_NAME", "../backend.log")];
+        let message = load(None, &env, &Overrides::default()).unwrap_err().to_string();
+        assert!(message.contains("LOG_FORMAT"));
+        assert!(message.contains("LOG_ROTATION"));
+        assert!(message.contains("log.file_name"));
+    }
+
+    #[test]
+    fn test_tracing_from_standard_otel_env() {
+        let env = [
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://127.0.0.1:4318"),
+            ("OTEL_SERVICE_NAME", "blog-staging"),
+            ("OTEL_TRACES_SAMPLER_ARG", "0.25"),
+        ];
+        let settings = load(None, &env, &Overrides::default()).unwrap();
+        assert_eq!(settings.tracing.otlp_endpoint.as_deref(), Some("http://127.0.0.1:4318"));
+        assert_eq!(settings.tracing.service_name, "blog-staging");
+        assert_eq!(settings.tracing.sample_ratio, 0.25);
+
+        let env = [("OTEL_EXPORTER_OTLP_ENDPOINT", "127.0.0.1:4318"), ("OTEL_TRACES_SAMPLER_ARG", "2")];
+        let message = load(None, &env, &Overrides::default()).unwrap_err().to_string();
+        assert!(message.contains("otlp_endpoint"));
+        assert!(message.contains("sample_ratio"));
+    }
+
+    #[test]
+    fn test_media_storage_from_file_and_env() {
+        let file = r#"
+            [media.storage]
+            backend = "s3"
+            bucket = "blog"
+            access_key_id = "file-key"
+            secret_access_key = "file-secret"
+            path_style = true
+        "#;
+        let env = [("S3_ENDPOINT", "http://127.0.0.1:9000"), ("S3_ACCESS_KEY_ID", "env-key")];
+        let settings = load(Some(file), &env, &Overrides::default()).unwrap();
+        let storage = &settings.media.storage;
+        assert_eq!(storage.backend, StorageKind::S3);
+        assert_eq!(storage.endpoint(), "http://127.0.0.1:9000");
+        assert_eq!(storage.access_key_id, "env-key");
+        assert_eq!(storage.secret_access_key, "file-secret");
+        assert!(storage.path_style);
+
+        let defaults = load(None, &[], &Overrides::default()).unwrap();
+        assert_eq!(defaults.media.storage.backend, StorageKind::Local);
+        assert_eq!(defaults.media.storage.endpoint(), "https://s3.us-east-1.amazonaws.com");
+
+        let env = [("MEDIA_STORAGE", "s3"), ("S3_MULTIPART_PART_SIZE", "1024")];
+        let message = load(None, &env, &Overrides::default()).unwrap_err().to_string();
+        assert!(message.contains("media.storage.bucket"));
+        assert!(message.contains("media.storage.secret_access_key"));
+        assert!(message.contains("multipart_part_size"));
+        assert!(load(None, &[("MEDIA_STORAGE", "ftp")], &Overrides::default()).is_err());
+        assert!(load(Some("[media.storage]\nkind = \"s3\"\n"), &[], &Overrides::default()).is_err());
+    }
+}
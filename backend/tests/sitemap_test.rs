@@ -0,0 +1,61 @@
+//! 站点地图模块测试用例
+//! 测试 urlset / sitemapindex 和 robots.txt 的生成
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+    use axum_blog_engine::sitemap::{build_robots_txt, build_sitemap_index, build_urlset, SitemapUrl};
+
+    fn time(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_build_urlset() {
+        let xml = build_urlset(&[
+            SitemapUrl { loc: "https://blog.example.com/".to_string(), lastmod: None },
+            SitemapUrl {
+                loc: "https://blog.example.com/post/1?a=1&b=2".to_string(),
+                lastmod: Some(time("2024-05-02 08:30:00")),
+            },
+        ]);
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.contains("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">"));
+        assert!(xml.contains("<url><loc>https://blog.example.com/</loc></url>"));
+        assert!(xml.contains("<loc>https://blog.example.com/post/1?a=1&amp;b=2</loc><lastmod>2024-05-02T08:30:00Z</lastmod>"));
+    }
+
+    #[test]
+    fn test_build_sitemap_index() {
+        let xml = build_sitemap_index(&[SitemapUrl {
+            loc: "https://blog.example.com/sitemap/posts-1.xml".to_string(),
+            lastmod: None,
+        }]);
+
+        assert!(xml.contains("<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">"));
+        assert!(xml.contains("<sitemap><loc>https://blog.example.com/sitemap/posts-1.xml</loc></sitemap>"));
+    }
+
+    #[test]
+    fn test_default_robots_txt() {
+        let robots = build_robots_txt(None, "https://blog.example.com/sitemap.xml");
+
+        assert!(robots.contains("User-agent: *"));
+        assert!(robots.contains("Disallow: /api/"));
+        assert!(robots.ends_with("Sitemap: https://blog.example.com/sitemap.xml\n"));
+    }
+
+    #[test]
+    fn test_custom_robots_txt() {
+        let robots = build_robots_txt(Some("User-agent: *\nDisallow: /private/\n"), "https://blog.example.com/sitemap.xml");
+        assert_eq!(
+            robots,
+            "User-agent: *\nDisallow: /private/\n\nSitemap: https://blog.example.com/sitemap.xml\n"
+        );
+
+        // 已包含 Sitemap 行时不重复添加
+        let robots = build_robots_txt(Some("User-agent: *\nSitemap: https://cdn.example.com/s.xml"), "https://blog.example.com/sitemap.xml");
+        assert_eq!(robots.matches("Sitemap:").count(), 1);
+    }
+}
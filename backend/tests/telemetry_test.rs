@@ -0,0 +1,199 @@
+//! 分布式追踪测试用例
+//! 测试 X-Request-Id 的生成与回传、错误响应体中的请求 ID、traceparent 传播，
+//! 以及通过 OTLP/HTTP 把请求与 Repository 的 span 导出到本地的收集器替身
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    use axum::body::{to_bytes, Body, Bytes};
+    use axum::http::{header, HeaderMap, Request, StatusCode};
+    use axum::response::IntoResponse;
+    use axum::routing::{get, post};
+    use axum::Router;
+    use opentelemetry::trace::{TraceContextExt, Tracer, TracerProvider as _};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use serde_json::Value;
+    use tokio::net::TcpListener;
+    use tower::ServiceExt;
+    use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use axum_blog_engine::database::post::PostRepository;
+    use axum_blog_engine::error::CustomError;
+    use axum_blog_engine::handler::request_id::scope_request_id;
+    use axum_blog_engine::settings::TracingSettings;
+    use axum_blog_engine::telemetry::{init_tracer_provider, request_span, trace_headers};
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    /// 与 server.rs 相同的请求 ID 中间件组合，路由总是返回 404 错误
+    fn app() -> Router {
+        Router::new()
+            .route("/missing", get(|| async { CustomError::NotFound("文章不存在".to_string()) }))
+            .layer(axum::middleware::from_fn(scope_request_id))
+            .layer(PropagateRequestIdLayer::x_request_id())
+            .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+    }
+
+    async fn json_body(response: axum::response::Response) -> Value {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    /// 安装了 OpenTelemetry 层的订阅者
+    fn otel_subscriber(provider: &SdkTracerProvider) -> impl tracing::Subscriber + Send + Sync {
+        tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("telemetry_test")))
+    }
+
+    /// 本地的 OTLP 收集器替身，记录收到的 (Content-Type, 请求体)
+    async fn start_collector() -> (SocketAddr, Arc<Mutex<Vec<(String, Bytes)>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let store = received.clone();
+        let app = Router::new().route("/v1/traces", post(move |headers: HeaderMap, body: Bytes| {
+            let store = store.clone();
+            async move {
+                let content_type = headers[header::CONTENT_TYPE].to_str().unwrap().to_string();
+                store.lock().unwrap().push((content_type, body));
+                StatusCode::OK
+            }
+        }));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (addr, received)
+    }
+
+    fn collector_settings(addr: SocketAddr) -> TracingSettings {
+        TracingSettings { otlp_endpoint: Some(format!("http://{}", addr)), ..TracingSettings::default() }
+    }
+
+    /// 导出器使用阻塞的 HTTP 客户端，需要在阻塞线程中刷新
+    async fn flush(provider: &SdkTracerProvider) {
+        let provider = provider.clone();
+        tokio::task::spawn_blocking(move || provider.force_flush()).await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_id_generated() {
+        let response = app()
+            .oneshot(Request::builder().uri("/missing").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let request_id = response.headers()["x-request-id"].to_str().unwrap().to_string();
+        assert!(uuid::Uuid::parse_str(&request_id).is_ok());
+        let body = json_body(response).await;
+        assert_eq!(body["success"], false);
+        assert_eq!(body["request_id"], request_id);
+    }
+
+    #[tokio::test]
+    async fn test_request_id_from_client() {
+        let request = Request::builder()
+            .uri("/missing")
+            .header("x-request-id", "client-req-42")
+            .body(Body::empty())
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+
+        assert_eq!(response.headers()["x-request-id"], "client-req-42");
+        assert_eq!(json_body(response).await["request_id"], "client-req-42");
+    }
+
+    #[tokio::test]
+    async fn test_error_body_outside_request() {
+        let response = CustomError::BadRequest("参数错误".to_string()).into_response();
+        let body = json_body(response).await;
+        assert_eq!(body["message"], "参数错误");
+        assert!(body.get("request_id").is_none());
+    }
+
+    #[test]
+    fn test_request_span_continues_traceparent() {
+        let provider = SdkTracerProvider::builder().build();
+        tracing::subscriber::with_default(otel_subscriber(&provider), || {
+            let request = Request::builder()
+                .uri("/api/post/1")
+                .header("x-request-id", "req-1")
+                .header("traceparent", TRACEPARENT)
+                .body(())
+                .unwrap();
+            let span = request_span(&request);
+            let context = span.context();
+            assert_eq!(context.span().span_context().trace_id().to_string(), TRACE_ID);
+
+            // 下游请求沿用同一个 trace，但父 span 换成当前请求的 span
+            let _entered = span.enter();
+            let headers = trace_headers();
+            let (_, traceparent) = headers.iter().find(|(name, _)| name == "traceparent").unwrap();
+            assert!(traceparent.contains(TRACE_ID));
+            assert_ne!(traceparent, TRACEPARENT);
+        });
+    }
+
+    #[test]
+    fn test_request_span_starts_new_trace() {
+        let provider = SdkTracerProvider::builder().build();
+        tracing::subscriber::with_default(otel_subscriber(&provider), || {
+            let request = Request::builder().uri("/").body(()).unwrap();
+            let span = request_span(&request);
+            let span_context = span.context().span().span_context().clone();
+            assert!(span_context.is_valid());
+            assert_ne!(span_context.trace_id().to_string(), TRACE_ID);
+        });
+    }
+
+    #[test]
+    fn test_trace_headers_outside_span() {
+        assert!(trace_headers().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_otlp_export_to_collector() {
+        let (addr, received) = start_collector().await;
+        let provider = init_tracer_provider(&collector_settings(addr)).unwrap();
+        provider.tracer("telemetry_test").in_span("collector_probe", |_| {});
+        flush(&provider).await;
+
+        let received = received.lock().unwrap();
+        assert!(!received.is_empty());
+        let (content_type, body) = &received[0];
+        assert_eq!(content_type, "application/x-protobuf");
+        let body = String::from_utf8_lossy(body);
+        assert!(body.contains("collector_probe"));
+        assert!(body.contains("axum-blog-engine"));
+    }
+
+    #[tokio::test]
+    async fn test_repository_spans_exported_under_request() {
+        let (addr, received) = start_collector().await;
+        let provider = init_tracer_provider(&collector_settings(addr)).unwrap();
+        let repo = PostRepository::new(common::setup_test_db().await);
+
+        {
+            let _guard = tracing::subscriber::set_default(otel_subscriber(&provider));
+            let request = Request::builder()
+                .uri("/api/post/1")
+                .header("traceparent", TRACEPARENT)
+                .body(())
+                .unwrap();
+            let span = request_span(&request);
+            tracing::Instrument::instrument(repo.get_post_by_id(1), span).await.unwrap();
+        }
+        flush(&provider).await;
+
+        let exported: Vec<u8> = received.lock().unwrap().iter().flat_map(|(_, body)| body.to_vec()).collect();
+        let exported = String::from_utf8_lossy(&exported);
+        assert!(exported.contains("http_request"));
+        assert!(exported.contains("get_post_by_id"));
+    }
+}
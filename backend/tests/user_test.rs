@@ -1,40 +1,26 @@
 //! 用户模块测试用例
 //! 测试 UserRepository 的各种数据库操作功能
 
+mod common;
+
 #[cfg(test)]
 mod tests {
-    use sea_orm::{Database, DatabaseConnection, ConnectionTrait};
+    use crate::common;
+    use sea_orm::DatabaseConnection;
     use chrono::Utc;
     use bcrypt::{hash, DEFAULT_COST};
+    use validator::Validate;
     
     // 导入项目模块
     use axum_blog_engine::database::user::{UserRepository, Model};
     use axum_blog_engine::handler::idl::{
         RegisterRequest, CreateUserByAdminRequest, UpdateMyProfileRequest, UpdateUserRequest
     };
-    use axum_blog_engine::error::CustomError;
+    use axum_blog_engine::error::{CustomError, ErrorCode};
 
-    /// 创建内存数据库连接用于测试
+    /// 创建测试数据库并执行全部迁移 (见 tests/common)
     async fn setup_test_db() -> DatabaseConnection {
-        let db = Database::connect("sqlite::memory:")
-            .await
-            .expect("Failed to connect to test database");
-        
-        // 创建用户表
-        let sql = r#"
-            CREATE TABLE IF NOT EXISTS users (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                username TEXT UNIQUE NOT NULL,
-                email TEXT UNIQUE NOT NULL,
-                password_hash TEXT NOT NULL,
-                role TEXT NOT NULL DEFAULT 'user',
-                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now')),
-                updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%S', 'now'))
-            );
-        "#;
-        
-        db.execute_unprepared(sql).await.expect("Failed to create users table");
-        
+        let db = common::setup_test_db().await;
         db
     }
 
@@ -165,6 +151,7 @@ mod tests {
             username: Some("updateduser".to_string()),
             email: Some("updated@example.com".to_string()),
             password: Some("newpassword123".to_string()),
+            ..Default::default()
         };
         
         let new_password_hash = Some(hash("newpassword123", DEFAULT_COST).unwrap());
@@ -190,6 +177,7 @@ mod tests {
             username: Some("partialupdateuser".to_string()),
             email: None,
             password: None,
+            ..Default::default()
         };
         
         let result = repo.update_my_profile(created_user.id, update_req, None).await;
@@ -263,16 +251,17 @@ mod tests {
             username: Some("nonexistentuser".to_string()),
             email: None,
             password: None,
+            ..Default::default()
         };
         
         let result = repo.update_my_profile(999, update_req, None).await;
         
         assert!(result.is_err());
         match result.unwrap_err() {
-            CustomError::NotFound(msg) => {
+            CustomError::Coded { code: ErrorCode::UserNotFound, message: msg } => {
                 assert!(msg.contains("用户未找到"));
             }
-            _ => panic!("Expected NotFound error"),
+            _ => panic!("Expected UserNotFound error"),
         }
     }
 
@@ -304,10 +293,10 @@ mod tests {
         
         assert!(result.is_err());
         match result.unwrap_err() {
-            CustomError::NotFound(msg) => {
+            CustomError::Coded { code: ErrorCode::UserNotFound, message: msg } => {
                 assert!(msg.contains("用户未找到或已被删除"));
             }
-            _ => panic!("Expected NotFound error"),
+            _ => panic!("Expected UserNotFound error"),
         }
     }
 
@@ -380,6 +369,7 @@ mod tests {
             username: Some("timestamptest".to_string()),
             email: None,
             password: None,
+            ..Default::default()
         };
         
         let before_update = Utc::now().naive_utc();
@@ -392,4 +382,31 @@ mod tests {
         assert!(updated_user.updated_at <= after_update);
         assert!(updated_user.updated_at > created_user.updated_at); // 更新时间应该晚于创建时间
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_update_profile_display_name_and_avatar() {
+        let db = setup_test_db().await;
+        let repo = UserRepository::new(db);
+        let created_user = create_test_user(&repo).await.unwrap();
+        assert_eq!(created_user.display_name, None);
+
+        let update_req = UpdateMyProfileRequest {
+            display_name: Some("测试用户".to_string()),
+            avatar_url: Some("/media/avatar.png".to_string()),
+            ..Default::default()
+        };
+        assert!(update_req.validate().is_ok());
+        let updated_user = repo.update_my_profile(created_user.id, update_req, None).await.unwrap();
+        assert_eq!(updated_user.display_name.as_deref(), Some("测试用户"));
+        assert_eq!(updated_user.avatar_url.as_deref(), Some("/media/avatar.png"));
+
+        // 空字符串清除，未提供的字段保持不变
+        let update_req = UpdateMyProfileRequest { display_name: Some(String::new()), ..Default::default() };
+        let updated_user = repo.update_my_profile(created_user.id, update_req, None).await.unwrap();
+        assert_eq!(updated_user.display_name, None);
+        assert_eq!(updated_user.avatar_url.as_deref(), Some("/media/avatar.png"));
+
+        let invalid = UpdateMyProfileRequest { avatar_url: Some("javascript:alert(1)".to_string()), ..Default::default() };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -23,17 +23,19 @@ function clearToken() {
 async function request(url, options = {}) {
   const token = getToken();
   const fullUrl = `${API_BASE_URL}${url}`;
+  // 上传文件时由浏览器自动设置 multipart 边界
+  const isFormData = options.body instanceof FormData;
   
   const requestConfig = {
     headers: {
-      'Content-Type': 'application/json',
+      ...(!isFormData && { 'Content-Type': 'application/json' }),
       ...(token && { 'Authorization': `Bearer ${token}` }),
       ...options.headers,
     },
     ...options,
   };
 
-  if (requestConfig.body && typeof requestConfig.body === 'object') {
+  if (requestConfig.body && typeof requestConfig.body === 'object' && !isFormData) {
     requestConfig.body = JSON.stringify(requestConfig.body);
   }
 
@@ -133,7 +135,8 @@ export const postAPI = {
       content_markdown: postData.content || postData.content_markdown,
       category: postData.category,
       is_published: postData.is_published,
-      cover_url: postData.cover_url
+      cover_url: postData.cover_url,
+      excerpt: postData.excerpt
     };
     return request('/post', {
       method: 'POST',
@@ -167,7 +170,8 @@ export const postAPI = {
       content_markdown: postData.content || postData.content_markdown,
       category: postData.category,
       is_published: postData.is_published,
-      cover_url: postData.cover_url
+      cover_url: postData.cover_url,
+      excerpt: postData.excerpt
     };
     return request(`/post/${id}`, {
       method: 'PUT',
@@ -225,6 +229,27 @@ export const favoriteAPI = {
   list: () => request('/post_fav/my/list'),
 };
 
+// 媒体文件相关 API
+export const mediaAPI = {
+  // 上传图片，返回的 media.url 可直接作为封面地址
+  upload: (file) => {
+    const formData = new FormData();
+    formData.append('file', file);
+    return request('/media', {
+      method: 'POST',
+      body: formData,
+    });
+  },
+
+  // 获取当前用户的媒体文件及存储用量
+  list: () => request('/media'),
+
+  // 删除媒体文件
+  delete: (mediaId) => request(`/media/${mediaId}`, {
+    method: 'DELETE',
+  }),
+};
+
 // 导出工具函数
 export { getToken, setToken, clearToken };
 
@@ -234,6 +259,7 @@ export default {
   postAPI,
   commentAPI,
   favoriteAPI,
+  mediaAPI,
   getToken,
   setToken,
   clearToken,
@@ -37,27 +37,23 @@ mkdir -p logs
 echo "🔧 启动后端服务 (端口 3000)..."
 cd backend
 
-# 检查数据库文件是否存在
-if [ ! -f "blogdb.db" ]; then
-    echo "📊 初始化数据库..."
-    if command -v sqlite3 &> /dev/null; then
-        sqlite3 blogdb.db ".read init.sql"
-        echo "✅ 数据库初始化完成"
-    else
-        echo "⚠️  未找到 sqlite3，请手动创建数据库:"
-        echo "   sqlite3 blogdb.db '.read init.sql'"
-    fi
-fi
+# 数据库由后端启动时的迁移自动创建和升级
 
 # 检查 .env 文件
 if [ ! -f ".env" ]; then
     echo "📝 创建 .env 文件..."
     cat > .env << EOF
-DATABASE_URL=sqlite:blogdb.db
+DATABASE_URL=sqlite:blogdb.db?mode=rwc
 JWT_SECRET=your-super-secret-jwt-key-here-$(date +%s)
 SERVER_HOST=127.0.0.1
 SERVER_PORT=3000
 BCRYPT_COST=12
+SITE_URL=http://127.0.0.1:3000
+SITE_TITLE=Axum Blog
+MEDIA_DIR=./media
+UPLOAD_MAX_BYTES=10485760
+MEDIA_QUOTA_BYTES=104857600
+IMAGE_VARIANT_WIDTHS=320,768,1280
 EOF
     echo "✅ .env 文件创建完成"
 fi